@@ -1,19 +1,39 @@
 use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::progress::{ProgressCallback, ProgressPhase};
+use crate::domain::cancel::{check_cancelled, CancellationToken};
 use crate::domain::types::{AssetRef, LimitsConfig};
-use super::content_detection::{detect_extension_from_bytes, extension_to_mime_type};
+use super::content_detection::{detect_extension_from_bytes, extension_to_mime_type, format_for_extension};
+use super::url_validation::fetch_remote_asset;
 
-/// Copy data from reader to writer with size limits to prevent memory exhaustion
+/// Copy `reader` into `writer`, failing with `AssetTooLarge` before more than
+/// `max_bytes` are written. Read timeouts surface as `StreamTimeout`; the writer is
+/// flushed on success. Returns the number of bytes copied.
 pub fn copy_with_limits<R: std::io::Read, W: std::io::Write>(
   reader: &mut R,
   writer: &mut W,
   max_bytes: usize,
+) -> EngineResult<u64> {
+  copy_with_progress(reader, writer, max_bytes, None, None)
+}
+
+/// `copy_with_limits`, reporting each chunk as `Staging` progress against `total`
+/// and stopping with `Cancelled` once `cancel` is set.
+pub fn copy_with_progress<R: std::io::Read, W: std::io::Write>(
+  reader: &mut R,
+  writer: &mut W,
+  max_bytes: usize,
+  progress: Option<(&ProgressCallback, Option<u64>)>,
+  cancel: Option<&CancellationToken>,
 ) -> EngineResult<u64> {
   let mut buffer = [0u8; 8192]; // 8KB chunks for efficient copying
   let mut total_bytes = 0u64;
 
   loop {
-    let bytes_read = reader.read(&mut buffer)
-      .map_err(|e| EngineError::Io(e))?;
+    check_cancelled(cancel)?;
+    let bytes_read = reader.read(&mut buffer).map_err(|e| match e.kind() {
+      std::io::ErrorKind::TimedOut => EngineError::StreamTimeout,
+      _ => EngineError::Io(e),
+    })?;
 
     if bytes_read == 0 {
       break; // EOF reached
@@ -22,30 +42,58 @@ pub fn copy_with_limits<R: std::io::Read, W: std::io::Write>(
     // Check if this chunk would exceed the limit
     let new_total = total_bytes as usize + bytes_read;
     if new_total > max_bytes {
-      return Err(EngineError::Config(
-        format!("Stream size limit exceeded: {} bytes (max: {})", new_total, max_bytes)
-      ));
+      return Err(EngineError::AssetTooLarge { size: new_total as u64, limit: max_bytes as u64 });
     }
 
     writer.write_all(&buffer[..bytes_read])
       .map_err(|e| EngineError::Io(e))?;
 
     total_bytes = new_total as u64;
+    if let Some((callback, total)) = progress {
+      callback.emit(ProgressPhase::Staging, total_bytes, total);
+    }
   }
 
   writer.flush().map_err(|e| EngineError::Io(e))?;
   Ok(total_bytes)
 }
 
+/// A fresh temporary directory under `temp_dir`, or the system temp directory when
+/// `None`. Failing to create one is reported as `TempDirUnavailable` with the directory tried.
+pub fn staging_dir(temp_dir: Option<&std::path::Path>) -> EngineResult<tempfile::TempDir> {
+  let parent = temp_dir.map(std::path::Path::to_path_buf).unwrap_or_else(std::env::temp_dir);
+  tempfile::tempdir_in(&parent).map_err(|source| EngineError::TempDirUnavailable { path: parent, source })
+}
+
 pub fn asset_to_temp_path(
   asset: &AssetRef,
   limits: LimitsConfig,
+  temp_dir: Option<&std::path::Path>,
+) -> EngineResult<(std::path::PathBuf, Option<tempfile::TempDir>)> {
+  asset_to_temp_path_with_progress(asset, limits, temp_dir, None, None)
+}
+
+/// `asset_to_temp_path`, reporting `Staging` progress. Stream copies report per
+/// chunk and honour `cancel`; other sources report once when staged.
+pub fn asset_to_temp_path_with_progress(
+  asset: &AssetRef,
+  limits: LimitsConfig,
+  temp_dir: Option<&std::path::Path>,
+  progress: Option<&ProgressCallback>,
+  cancel: Option<&CancellationToken>,
 ) -> EngineResult<(std::path::PathBuf, Option<tempfile::TempDir>)> {
+  let staged = |path: &std::path::Path| {
+    if let Some(callback) = progress {
+      let len = std::fs::metadata(path).map(|m| m.len()).ok();
+      callback.emit(ProgressPhase::Staging, len.unwrap_or(0), len);
+    }
+  };
   match asset {
     AssetRef::Path(p) => {
       // If the path has an extension, use it as-is. Otherwise, sniff and copy to a temp
       // file with an inferred extension so downstream file-based APIs can determine type.
       if p.extension().is_some() {
+        staged(p);
         return Ok((p.clone(), None));
       }
 
@@ -61,42 +109,26 @@ pub fn asset_to_temp_path(
       } else { None };
 
       // Create a temp file and copy the full content there, with inferred extension if any
-      let dir = tempfile::tempdir()?;
+      let dir = staging_dir(temp_dir)?;
       let filename = if let Some(ext) = maybe_ext { format!("asset.{ext}") } else { "asset".to_string() };
       let temp_path = dir.path().join(filename);
       std::fs::copy(p, &temp_path)
         .map_err(|e| EngineError::Io(e))?;
+      staged(&temp_path);
       Ok((temp_path, Some(dir)))
     },
     AssetRef::Bytes { data } => {
-      if data.len() > limits.max_in_memory_asset_size {
-        return Err(EngineError::Config("in-memory asset too large".into()));
-      }
-      let dir = tempfile::tempdir()?;
-      let filename = if let Some(ext) = detect_extension_from_bytes(data) {
-        format!("asset.{ext}")
-      } else {
-        "asset".to_string()
-      };
-      let path = dir.path().join(filename);
-      std::fs::write(&path, data)?;
+      check_in_memory_size(data, limits)?;
+      let (path, dir) = write_bytes_to_temp_in(data, None, temp_dir)?;
+      staged(&path);
       Ok((path, Some(dir)))
     }
     AssetRef::Stream { reader, content_type } => {
-      let dir = tempfile::tempdir()?;
+      let dir = staging_dir(temp_dir)?;
 
       // Determine filename based on content type hint or by sniffing the stream header
       let filename = if let Some(ct) = content_type {
-        match ct.as_str() {
-          "image/jpeg" => "asset.jpg".to_string(),
-          "image/png" => "asset.png".to_string(),
-          "image/gif" => "asset.gif".to_string(),
-          "image/webp" => "asset.webp".to_string(),
-          "video/mp4" => "asset.mp4".to_string(),
-          "audio/mpeg" => "asset.mp3".to_string(),
-          "application/pdf" => "asset.pdf".to_string(),
-          _ => "asset".to_string(),
-        }
+        filename_for_content_type(ct)
       } else {
         // Sniff a few bytes to infer an extension when no content type is provided
         let mut maybe_ext: Option<String> = None;
@@ -128,12 +160,69 @@ pub fn asset_to_temp_path(
       let mut reader_ref = reader.borrow_mut();
       // Note: max_stream_read_timeout_secs is currently not enforced at this layer.
       // It is included in LimitsConfig for future extension and parity with defaults.
-      let _bytes_copied = copy_with_limits(&mut *reader_ref, &mut file, limits.max_stream_copy_size)?;
+      let total = progress.and_then(|_| stream_len(&mut *reader_ref));
+      let _bytes_copied = copy_with_progress(
+        &mut *reader_ref,
+        &mut file,
+        limits.max_stream_copy_size,
+        progress.map(|callback| (callback, total)),
+        cancel,
+      )?;
+      Ok((path, Some(dir)))
+    }
+    AssetRef::Url(url) => {
+      let fetched = fetch_remote_asset(url, false, limits)?;
+      let (path, dir) = write_bytes_to_temp_in(&fetched.data, fetched.content_type.as_deref(), temp_dir)?;
+      staged(&path);
       Ok((path, Some(dir)))
     }
   }
 }
 
+/// Write `data` to a new file in a fresh directory under the system temp directory.
+/// The file is named `asset.<ext>` after the format sniffed from the bytes, or after
+/// `content_type` when sniffing fails, so c2pa's file-based APIs can tell the type.
+/// Dropping the returned directory deletes both.
+pub fn write_bytes_to_temp(
+  data: &[u8],
+  content_type: Option<&str>,
+) -> EngineResult<(std::path::PathBuf, tempfile::TempDir)> {
+  write_bytes_to_temp_in(data, content_type, None)
+}
+
+/// `write_bytes_to_temp`, staging under `temp_dir` when one is given.
+pub fn write_bytes_to_temp_in(
+  data: &[u8],
+  content_type: Option<&str>,
+  temp_dir: Option<&std::path::Path>,
+) -> EngineResult<(std::path::PathBuf, tempfile::TempDir)> {
+  let filename = match detect_extension_from_bytes(data) {
+    Some(ext) => format!("asset.{ext}"),
+    None => content_type.map(filename_for_content_type).unwrap_or_else(|| "asset".to_string()),
+  };
+  let dir = staging_dir(temp_dir)?;
+  let path = dir.path().join(filename);
+  std::fs::write(&path, data)?;
+  Ok((path, dir))
+}
+
+fn filename_for_content_type(content_type: &str) -> String {
+  match content_type {
+    "image/jpeg" => "asset.jpg".to_string(),
+    "image/png" => "asset.png".to_string(),
+    "image/gif" => "asset.gif".to_string(),
+    "image/webp" => "asset.webp".to_string(),
+    "video/mp4" => "asset.mp4".to_string(),
+    "audio/mpeg" => "asset.mp3".to_string(),
+    "application/pdf" => "asset.pdf".to_string(),
+    "image/tiff" => "asset.tiff".to_string(),
+    "image/x-adobe-dng" | "image/dng" => "asset.dng".to_string(),
+    "image/x-nikon-nef" => "asset.nef".to_string(),
+    "image/x-sony-arw" => "asset.arw".to_string(),
+    _ => "asset".to_string(),
+  }
+}
+
 /// Peek the first bytes from a Read+Seek stream and infer a MIME type.
 /// The stream position is restored to the start before returning.
 pub fn sniff_content_type_from_reader<R: std::io::Read + std::io::Seek>(reader: &mut R) -> Option<&'static str> {
@@ -142,6 +231,315 @@ pub fn sniff_content_type_from_reader<R: std::io::Read + std::io::Seek>(reader:
   let n = reader.read(&mut head).ok()?;
   let _ = reader.seek(SeekFrom::Start(0));
   if n == 0 { return None; }
-  let ext = detect_extension_from_bytes(&head[..n])?;
-  Some(extension_to_mime_type(ext))
+  detect_extension_from_bytes(&head[..n]).map(format_for_extension)
+}
+
+/// Compare a source's declared format with the format sniffed from its leading bytes.
+///
+/// The declared format is `declared` when given, else a stream's `content_type` or a
+/// path's extension. Sources with nothing declared, or whose bytes can't be sniffed,
+/// pass: the check only rejects a positive detection that conflicts.
+pub fn check_declared_format(asset: &AssetRef, declared: Option<&str>) -> EngineResult<()> {
+  let (declared, detected) = match asset {
+    AssetRef::Stream { reader, content_type } => {
+      let Some(declared) = declared.or(content_type.as_deref()) else { return Ok(()) };
+      (declared.to_string(), sniff_content_type_from_reader(&mut *reader.borrow_mut()))
+    }
+    AssetRef::Path(p) => {
+      let from_ext = p.extension().and_then(|e| e.to_str()).map(|e| extension_to_mime_type(&e.to_ascii_lowercase()));
+      let Some(declared) = declared.or(from_ext) else { return Ok(()) };
+      let mut head = [0u8; 512];
+      let n = std::io::Read::read(&mut std::fs::File::open(p)?, &mut head)?;
+      (declared.to_string(), sniff_content_type_from_bytes(&head[..n]))
+    }
+    AssetRef::Bytes { data } => match declared {
+      Some(declared) => (declared.to_string(), sniff_content_type_from_bytes(data)),
+      None => return Ok(()),
+    },
+    // Remote assets are checked against their bytes once fetched.
+    AssetRef::Url(_) => return Ok(()),
+  };
+  match detected {
+    // An extension from a custom detector has no MIME type to compare against.
+    Some(detected) if detected.contains('/') && !same_format_family(&declared, detected) => Err(EngineError::Config(format!(
+      "declared format {declared} does not match detected format {detected}"
+    ))),
+    _ => Ok(()),
+  }
+}
+
+/// MIME types that the same sniffed signature can legitimately stand for.
+/// ISO-BMFF brands vary too much to tell containers apart from the `ftyp` box alone,
+/// and TIFF-based raws are only told apart when their tags fall within the sniffed head.
+fn same_format_family(declared: &str, detected: &str) -> bool {
+  const BMFF: [&str; 8] = [
+    "video/mp4",
+    "video/quicktime",
+    "audio/mp4",
+    "image/heic",
+    "image/heif",
+    "image/avif",
+    "image/heic-sequence",
+    "image/heif-sequence",
+  ];
+  const TIFF: [&str; 5] = ["image/tiff", "image/x-adobe-dng", "image/dng", "image/x-nikon-nef", "image/x-sony-arw"];
+  let declared = canonical_format(declared);
+  declared == detected
+    || declared == "application/octet-stream"
+    || (BMFF.contains(&declared.as_str()) && BMFF.contains(&detected))
+    || (TIFF.contains(&declared.as_str()) && TIFF.contains(&detected))
+}
+
+/// Lower-cased MIME type with common aliases folded into the name the sniffer reports.
+/// A bare extension (`jpg`, `mp4`) becomes its MIME type when the engine knows one.
+fn canonical_format(format: &str) -> String {
+  let format = format.trim().trim_start_matches('.').to_ascii_lowercase();
+  match format.as_str() {
+    "image/jpg" | "image/pjpeg" => "image/jpeg".to_string(),
+    "image/tif" => "image/tiff".to_string(),
+    "audio/mp3" => "audio/mpeg".to_string(),
+    ext if !ext.contains('/') => match extension_to_mime_type(ext) {
+      "application/octet-stream" => format,
+      mime => mime.to_string(),
+    },
+    _ => format,
+  }
+}
+
+/// Reject a source unless both its declared format and the format sniffed from its
+/// bytes are in `allowed` (MIME types or extensions, case-insensitive). The declared
+/// format is `declared` when given, else a stream's `content_type` or a path's
+/// extension. A `Url` source is not fetched here, so its format is unknown and it is
+/// rejected; callers that fetch it check the bytes with [`check_allowed_content`].
+pub fn check_allowed_format(asset: &AssetRef, declared: Option<&str>, allowed: &[String]) -> EngineResult<()> {
+  let declared = declared.map(str::to_string).or_else(|| match asset {
+    AssetRef::Stream { content_type, .. } => content_type.clone(),
+    AssetRef::Path(p) => p.extension().and_then(|e| e.to_str()).map(str::to_string),
+    _ => None,
+  });
+  let detected = sniff_asset_extension(asset)?.map(format_for_extension);
+  check_allowed_content(declared.as_deref(), detected, allowed)
+}
+
+/// [`check_allowed_format`] for a `declared` and `detected` format already in hand,
+/// e.g. a fetched URL's `Content-Type` and the format sniffed from its body.
+pub fn check_allowed_content(declared: Option<&str>, detected: Option<&str>, allowed: &[String]) -> EngineResult<()> {
+  let declared = declared
+    .map(canonical_format)
+    // Says nothing about the content, as in `same_format_family`.
+    .filter(|format| format != "application/octet-stream");
+  let detected = detected.map(canonical_format);
+  if declared.is_none() && detected.is_none() {
+    return Err(EngineError::UnsupportedFormat(
+      "allowed_formats is set but the asset's format could not be determined".into(),
+    ));
+  }
+  let allowed: Vec<String> = allowed.iter().map(|format| canonical_format(format)).collect();
+  match declared.into_iter().chain(detected).find(|format| !allowed.contains(format)) {
+    Some(format) => Err(EngineError::UnsupportedFormat(format!("{format} is not in allowed_formats"))),
+    None => Ok(()),
+  }
+}
+
+/// Extension sniffed from the leading bytes of a local source. URLs are not fetched
+/// and give `None`, as do unrecognised formats.
+pub fn sniff_asset_extension(asset: &AssetRef) -> EngineResult<Option<&'static str>> {
+  let mut head = [0u8; 512];
+  let n = match asset {
+    AssetRef::Bytes { data } => return Ok(detect_extension_from_bytes(data)),
+    AssetRef::Path(p) => std::io::Read::read(&mut std::fs::File::open(p)?, &mut head)?,
+    AssetRef::Stream { reader, .. } => {
+      use std::io::{Read, Seek, SeekFrom};
+      let mut reader_ref = reader.borrow_mut();
+      let n = reader_ref.read(&mut head)?;
+      reader_ref.seek(SeekFrom::Start(0))?;
+      n
+    }
+    AssetRef::Url(_) => return Ok(None),
+  };
+  Ok(detect_extension_from_bytes(&head[..n]))
+}
+
+/// Enforce `max_in_memory_asset_size` on a `Bytes` source.
+pub fn check_in_memory_size(data: &[u8], limits: LimitsConfig) -> EngineResult<()> {
+  if data.len() > limits.max_in_memory_asset_size {
+    return Err(EngineError::AssetTooLarge {
+      size: data.len() as u64,
+      limit: limits.max_in_memory_asset_size as u64,
+    });
+  }
+  Ok(())
+}
+
+/// Enforce `max_in_memory_output_size` on a result returned in memory.
+pub fn check_output_size(len: u64, limits: LimitsConfig) -> EngineResult<()> {
+  if len > limits.max_in_memory_output_size as u64 {
+    return Err(EngineError::OutputTooLarge {
+      size: len,
+      limit: limits.max_in_memory_output_size as u64,
+    });
+  }
+  Ok(())
+}
+
+/// Enforce `max_manifest_size` on a manifest store that did not come from the asset itself.
+pub fn check_manifest_size(len: u64, limits: LimitsConfig) -> EngineResult<()> {
+  if len > limits.max_manifest_size as u64 {
+    return Err(EngineError::ManifestTooLarge {
+      size: len,
+      limit: limits.max_manifest_size as u64,
+    });
+  }
+  Ok(())
+}
+
+/// Infer a MIME type from an in-memory asset's leading bytes.
+pub fn sniff_content_type_from_bytes(data: &[u8]) -> Option<&'static str> {
+  detect_extension_from_bytes(data).map(format_for_extension)
+}
+
+/// Seek a stream source back to its start so it can be read again; no-op otherwise.
+pub fn rewind_stream(asset: &AssetRef) -> EngineResult<()> {
+  if let AssetRef::Stream { reader, .. } = asset {
+    std::io::Seek::seek(&mut *reader.borrow_mut(), std::io::SeekFrom::Start(0))?;
+  }
+  Ok(())
+}
+
+/// Read a whole asset into memory, enforcing the per-call limits.
+/// Bytes are bounded by `max_in_memory_asset_size`; paths, streams and URLs by `max_stream_copy_size`.
+pub fn read_asset_bytes(asset: &AssetRef, limits: LimitsConfig) -> EngineResult<Vec<u8>> {
+  match asset {
+    AssetRef::Bytes { data } => {
+      check_in_memory_size(data, limits)?;
+      Ok(data.clone())
+    }
+    AssetRef::Path(p) => {
+      let mut file = std::fs::File::open(p)?;
+      let mut buf = Vec::new();
+      copy_with_limits(&mut file, &mut buf, limits.max_stream_copy_size)?;
+      Ok(buf)
+    }
+    AssetRef::Stream { reader, .. } => {
+      let mut reader_ref = reader.borrow_mut();
+      let mut buf = Vec::new();
+      copy_with_limits(&mut *reader_ref, &mut buf, limits.max_stream_copy_size)?;
+      Ok(buf)
+    }
+    AssetRef::Url(url) => Ok(fetch_remote_asset(url, false, limits)?.data),
+  }
+}
+
+/// Bytes remaining from the current position of a seekable stream, which is restored.
+pub fn stream_len<R: std::io::Seek + ?Sized>(reader: &mut R) -> Option<u64> {
+  use std::io::SeekFrom;
+  let pos = reader.stream_position().ok()?;
+  let end = reader.seek(SeekFrom::End(0)).ok()?;
+  reader.seek(SeekFrom::Start(pos)).ok()?;
+  Some(end.saturating_sub(pos))
+}
+
+/// How much of an asset is searched for an XMP packet by `xmp_provenance`.
+pub const XMP_SCAN_LIMIT: u64 = 1024 * 1024;
+
+/// The `dcterms:provenance` URL recorded in the asset's XMP, which c2pa writes for a
+/// remote manifest reference. Only the first `XMP_SCAN_LIMIT` bytes are searched;
+/// the reader's position is not restored.
+pub fn xmp_provenance<R: std::io::Read + ?Sized>(reader: &mut R) -> Option<String> {
+  use std::io::Read;
+  let mut head = Vec::new();
+  reader.take(XMP_SCAN_LIMIT).read_to_end(&mut head).ok()?;
+  xmp_provenance_in(&head)
+}
+
+fn xmp_provenance_in(data: &[u8]) -> Option<String> {
+  let find = |haystack: &[u8], needle: &[u8]| haystack.windows(needle.len()).position(|w| w == needle);
+  let start = find(data, b"<x:xmpmeta")?;
+  let end = start + find(&data[start..], b"</x:xmpmeta>")?;
+  let xmp = std::str::from_utf8(&data[start..end]).ok()?;
+
+  const KEY: &str = "dcterms:provenance";
+  let (_, rest) = xmp.split_once(KEY)?;
+  let value = match rest.trim_start().strip_prefix('=') {
+    // Attribute: dcterms:provenance="url"
+    Some(attr) => {
+      let attr = attr.trim_start();
+      let quote = attr.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+      attr[1..].split(quote).next()?
+    }
+    // Element: <dcterms:provenance>url</dcterms:provenance>
+    None => rest.strip_prefix('>')?.split('<').next()?,
+  };
+  let value = value.trim();
+  (!value.is_empty()).then(|| value.replace("&amp;", "&"))
+}
+
+/// Bytes read between progress reports from a `ProgressReader`.
+const PROGRESS_STEP: u64 = 1024 * 1024;
+
+/// Read + Seek adapter that reports bytes read as progress, about once per MiB, and
+/// fails reads once its cancellation token is set. Without either it only forwards
+/// to the inner stream.
+pub struct ProgressReader<'a, R: ?Sized> {
+  inner: &'a mut R,
+  callback: Option<&'a ProgressCallback>,
+  cancel: Option<&'a CancellationToken>,
+  phase: ProgressPhase,
+  total: Option<u64>,
+  read: u64,
+  reported: u64,
+}
+
+impl<'a, R: std::io::Seek + ?Sized> ProgressReader<'a, R> {
+  pub fn new(inner: &'a mut R, callback: Option<&'a ProgressCallback>, phase: ProgressPhase) -> Self {
+    let total = callback.and_then(|_| stream_len(inner));
+    if let Some(callback) = callback {
+      callback.emit(phase, 0, total);
+    }
+    Self { inner, callback, cancel: None, phase, total, read: 0, reported: 0 }
+  }
+
+  /// Fail reads with an I/O error once `cancel` is set; callers map the resulting
+  /// SDK error back to `Cancelled`.
+  pub fn with_cancel(mut self, cancel: Option<&'a CancellationToken>) -> Self {
+    self.cancel = cancel;
+    self
+  }
+}
+
+impl<R: std::io::Read + ?Sized> std::io::Read for ProgressReader<'_, R> {
+  fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+    if self.cancel.is_some_and(CancellationToken::is_cancelled) {
+      return Err(std::io::Error::other(EngineError::Cancelled.to_string()));
+    }
+    let n = self.inner.read(buf)?;
+    if let Some(callback) = self.callback {
+      self.read += n as u64;
+      if self.read - self.reported >= PROGRESS_STEP || (n == 0 && self.read > self.reported) {
+        self.reported = self.read;
+        callback.emit(self.phase, self.read, self.total);
+      }
+    }
+    Ok(n)
+  }
+}
+
+impl<R: std::io::Seek + ?Sized> std::io::Seek for ProgressReader<'_, R> {
+  fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+    self.inner.seek(pos)
+  }
+}
+
+/// Progress for an SDK step that reads `path` itself: `0` before, the file size after.
+pub fn report_file_progress(
+  progress: Option<&ProgressCallback>,
+  phase: ProgressPhase,
+  path: &std::path::Path,
+  done: bool,
+) {
+  if let Some(callback) = progress {
+    let len = std::fs::metadata(path).map(|m| m.len()).ok();
+    let processed = if done { len.unwrap_or(0) } else { 0 };
+    callback.emit(phase, processed, len);
+  }
 }
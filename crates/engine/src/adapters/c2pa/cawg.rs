@@ -1,15 +1,24 @@
 #[cfg(feature = "cawg")]
 use c2pa::{
-    crypto::raw_signature,
+    crypto::{
+        cose::{sign_v2_embedded_async, CosePayload, TimeStampStorage},
+        raw_signature::{self, AsyncRawSigner},
+    },
     identity::{
-        builder::{AsyncIdentityAssertionBuilder, AsyncIdentityAssertionSigner},
+        builder::{
+            AsyncCredentialHolder, AsyncIdentityAssertionBuilder, AsyncIdentityAssertionSigner,
+            IdentityBuilderError,
+        },
         validator::CawgValidator,
         x509::AsyncX509CredentialHolder,
+        SignerPayload,
     },
 
 };
 use crate::domain::error::{EngineError, EngineResult};
 use crate::domain::cawg::{CawgIdentity, CawgVerifyOptions, CawgVerification, CawgSigner};
+use crate::domain::types::SigAlg;
+use crate::telemetry::trace_event;
 use zeroize::Zeroize;
 use std::path::Path;
 
@@ -31,6 +40,10 @@ fn check_private_key_permissions(path: &Path) -> EngineResult<()> {
 #[cfg(not(unix))]
 fn check_private_key_permissions(_path: &Path) -> EngineResult<()> { Ok(()) }
 
+/// Identity assertions (CAWG identity plus co-signers) the c2pa SDK can attach to one manifest.
+#[cfg(feature = "cawg")]
+const MAX_IDENTITY_ASSERTIONS: usize = 1;
+
 /// Creates a CAWG-enabled signer from CAWG identity configuration.
 /// This creates a dual-signer setup where the main C2PA signer is wrapped
 /// with CAWG identity assertion capabilities.
@@ -43,17 +56,27 @@ fn check_private_key_permissions(_path: &Path) -> EngineResult<()> { Ok(()) }
 /// * `main_signer` - The main C2PA signer (will be temporarily converted to raw form)
 /// * `main_alg` - Signing algorithm for the main signer
 /// * `main_timestamp` - Optional timestamp URL for the main signer
-/// * `cawg_config` - CAWG identity configuration
+/// * `cawg_config` - Optional CAWG identity configuration
+/// * `co_signers` - Additional X.509 signers, each attached as its own identity assertion
 ///
 /// # Returns
-/// A signer that includes both C2PA signing and CAWG identity assertion
+/// A signer that includes both C2PA signing and the CAWG identity assertions
 #[cfg(feature = "cawg")]
 pub async fn create_cawg_signer(
     main_signer: &crate::crypto::signer::Signer,
     main_alg: c2pa::SigningAlg,
     main_timestamp: Option<String>,
-    cawg_config: &CawgIdentity,
+    cawg_config: Option<&CawgIdentity>,
+    co_signers: &[(crate::crypto::signer::Signer, SigAlg)],
 ) -> EngineResult<Box<dyn c2pa::AsyncSigner>> {
+    // c2pa 0.59 fails (`NotFound`) filling in a second identity assertion placeholder
+    if usize::from(cawg_config.is_some()) + co_signers.len() > MAX_IDENTITY_ASSERTIONS {
+        return Err(EngineError::Config(format!(
+            "at most {MAX_IDENTITY_ASSERTIONS} CAWG identity assertion per manifest is supported: \
+             set either cawg_identity or a single co_signers entry"
+        )));
+    }
+
     // Temporarily extract raw cert/key data for async signer creation
     // This is scoped to minimize the exposure of raw key material
     let (mut main_cert, mut main_key) = {
@@ -75,6 +98,7 @@ pub async fn create_cawg_signer(
                     .into_bytes();
                 (cert, key)
             }
+            crate::crypto::signer::Signer::Pem { cert_pem, key_pem } => (cert_pem.clone(), key_pem.to_vec()),
         }
     };
 
@@ -91,24 +115,45 @@ pub async fn create_cawg_signer(
     main_cert.zeroize();
     main_key.zeroize();
 
-    // Create CAWG raw signer from the CAWG identity configuration
-    let cawg_raw_signer = create_cawg_raw_signer(cawg_config, main_signer, main_timestamp).await?;
-
     // Wrap the main signer with CAWG identity assertion signer
     let mut ia_signer = AsyncIdentityAssertionSigner::new(main_raw_signer);
 
-    // Create X.509 credential holder for CAWG
-    let x509_holder = AsyncX509CredentialHolder::from_async_raw_signer(cawg_raw_signer);
+    if let Some(cawg_config) = cawg_config {
+        // Create CAWG raw signer from the CAWG identity configuration
+        let cawg_raw_signer =
+            create_cawg_raw_signer(cawg_config, main_signer, main_timestamp.clone()).await?;
 
-    // Create identity assertion builder
-    let mut iab = AsyncIdentityAssertionBuilder::for_credential_holder(x509_holder);
+        // Create the credential holder for CAWG and the identity assertion builder
+        let mut iab = match &cawg_config.signer {
+            CawgSigner::Did { did, credential, .. } => {
+                let did_holder = DidCredentialHolder {
+                    did: did.clone(),
+                    credential: credential.clone(),
+                    signer: cawg_raw_signer,
+                };
+                AsyncIdentityAssertionBuilder::for_credential_holder(did_holder)
+            }
+            CawgSigner::UseMainSigner | CawgSigner::Separate(_) => {
+                let x509_holder = AsyncX509CredentialHolder::from_async_raw_signer(cawg_raw_signer);
+                AsyncIdentityAssertionBuilder::for_credential_holder(x509_holder)
+            }
+        };
 
-    // Convert Vec<String> to Vec<&str> for the API
-    let referenced_assertions: Vec<&str> = cawg_config.referenced_assertions.iter().map(|s| s.as_str()).collect();
-    iab.add_referenced_assertions(&referenced_assertions);
+        // Convert Vec<String> to Vec<&str> for the API
+        let referenced_assertions: Vec<&str> = cawg_config.referenced_assertions.iter().map(|s| s.as_str()).collect();
+        iab.add_referenced_assertions(&referenced_assertions);
 
-    // Add the identity assertion to the signer
-    ia_signer.add_identity_assertion(iab);
+        // Add the identity assertion to the signer
+        ia_signer.add_identity_assertion(iab);
+    }
+
+    // Each co-signer signs its own X.509 identity assertion over the same claim.
+    // The hard binding is always referenced, so the signature covers the content.
+    for (signer, alg) in co_signers {
+        let raw_signer = create_co_signer_raw_signer(signer, *alg, main_timestamp.clone())?;
+        let x509_holder = AsyncX509CredentialHolder::from_async_raw_signer(raw_signer);
+        ia_signer.add_identity_assertion(AsyncIdentityAssertionBuilder::for_credential_holder(x509_holder));
+    }
 
     Ok(Box::new(ia_signer))
 }
@@ -146,6 +191,7 @@ async fn create_cawg_raw_signer(
                         .into_bytes();
                     (c, k)
                 }
+                Signer::Pem { cert_pem, key_pem } => (cert_pem.clone(), key_pem.to_vec()),
             };
 
             let signer = raw_signature::async_signer_from_cert_chain_and_private_key(
@@ -160,7 +206,8 @@ async fn create_cawg_raw_signer(
             key_bytes.zeroize();
             Ok(signer)
         }
-        CawgSigner::Separate(Signer::Local { cert_path, key_path }) => {
+        CawgSigner::Separate(Signer::Local { cert_path, key_path })
+        | CawgSigner::Did { signer: Signer::Local { cert_path, key_path }, .. } => {
             check_private_key_permissions(key_path)?;
             let mut cert_bytes = std::fs::read(cert_path)
                 .map_err(|e| EngineError::Config(format!("Failed to read CAWG cert: {}", e)))?;
@@ -181,7 +228,18 @@ async fn create_cawg_raw_signer(
 
             Ok(signer)
         }
-        CawgSigner::Separate(Signer::Env { cert_var, key_var }) => {
+        CawgSigner::Separate(Signer::Pem { cert_pem, key_pem })
+        | CawgSigner::Did { signer: Signer::Pem { cert_pem, key_pem }, .. } => {
+            raw_signature::async_signer_from_cert_chain_and_private_key(
+                cert_pem,
+                key_pem,
+                cfg.signing_alg.to_c2pa(),
+                cfg.timestamper.as_ref().and_then(|t| t.resolve()),
+            )
+            .map_err(|e| EngineError::C2pa(c2pa::Error::OtherError(Box::new(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))))
+        }
+        CawgSigner::Separate(Signer::Env { cert_var, key_var })
+        | CawgSigner::Did { signer: Signer::Env { cert_var, key_var }, .. } => {
             let cert_pem = std::env::var(cert_var)
                 .map_err(|_| EngineError::Config(format!("CAWG cert env var not found: {}", cert_var)))?;
             let key_pem = std::env::var(key_var)
@@ -206,41 +264,289 @@ async fn create_cawg_raw_signer(
     }
 }
 
-/// Extracts signature information from CAWG identity assertions.
-/// Parses the CAWG identity assertion to extract signature metadata.
+/// Creates the raw signer for one `C2paConfig::co_signers` entry. Co-signatures use
+/// the main signer's time-stamp authority, if any.
 #[cfg(feature = "cawg")]
-fn extract_cawg_signature_info(reader: &c2pa::Reader) -> Option<serde_json::Value> {
-    if let Some(active_manifest) = reader.active_manifest() {
-        for assertion in active_manifest.assertions() {
-            if assertion.label() == "cawg.identity" {
-                if let Ok(assertion_data) = assertion.to_assertion::<serde_json::Value>() {
-                    return Some(assertion_data);
-                }
-            }
+fn create_co_signer_raw_signer(
+    signer: &crate::crypto::signer::Signer,
+    alg: SigAlg,
+    timestamp: Option<String>,
+) -> EngineResult<Box<dyn AsyncRawSigner + Send + Sync>> {
+    use crate::crypto::signer::Signer;
+    let (mut cert_bytes, mut key_bytes) = match signer {
+        Signer::Local { cert_path, key_path } => {
+            check_private_key_permissions(key_path)?;
+            let cert = std::fs::read(cert_path)
+                .map_err(|e| EngineError::Config(format!("Failed to read co-signer cert: {}", e)))?;
+            let key = std::fs::read(key_path)
+                .map_err(|e| EngineError::Config(format!("Failed to read co-signer key: {}", e)))?;
+            (cert, key)
+        }
+        Signer::Env { cert_var, key_var } => {
+            let cert = std::env::var(cert_var)
+                .map_err(|_| EngineError::Config(format!("Co-signer cert env var not found: {}", cert_var)))?
+                .into_bytes();
+            let key = std::env::var(key_var)
+                .map_err(|_| EngineError::Config(format!("Co-signer key env var not found: {}", key_var)))?
+                .into_bytes();
+            (cert, key)
+        }
+        Signer::Pem { cert_pem, key_pem } => (cert_pem.clone(), key_pem.to_vec()),
+    };
+
+    let signer = raw_signature::async_signer_from_cert_chain_and_private_key(
+        &cert_bytes,
+        &key_bytes,
+        alg.to_c2pa(),
+        timestamp,
+    )
+    .map_err(|e| EngineError::C2pa(c2pa::Error::OtherError(Box::new(std::io::Error::other(e.to_string())))))?;
+
+    cert_bytes.zeroize();
+    key_bytes.zeroize();
+
+    Ok(signer)
+}
+
+/// Credential holder for DID-based identities (CAWG identity claims aggregation).
+///
+/// Wraps the caller-supplied verified identities into a W3C verifiable credential
+/// issued by `did`, binds it to the C2PA asset via the signer payload, and signs
+/// it as a COSE_Sign1 structure with the key referenced by the DID document.
+#[cfg(feature = "cawg")]
+struct DidCredentialHolder {
+    did: String,
+    credential: serde_json::Value,
+    signer: Box<dyn AsyncRawSigner + Send + Sync>,
+}
+
+#[cfg(feature = "cawg")]
+#[async_trait::async_trait]
+impl AsyncCredentialHolder for DidCredentialHolder {
+    fn sig_type(&self) -> &'static str {
+        "cawg.identity_claims_aggregation"
+    }
+
+    fn reserve_size(&self) -> usize {
+        // Credential JSON plus COSE overhead; keep generous headroom for chains.
+        self.signer.reserve_size() + self.credential.to_string().len() + 4096
+    }
+
+    async fn sign(&self, signer_payload: &SignerPayload) -> Result<Vec<u8>, IdentityBuilderError> {
+        let c2pa_asset = c2pa_asset_json(signer_payload)?;
+
+        let mut subject = match &self.credential {
+            serde_json::Value::Object(obj) => obj.clone(),
+            _ => serde_json::Map::new(),
+        };
+        subject.insert("id".to_string(), serde_json::Value::String(self.did.clone()));
+        subject.insert("c2paAsset".to_string(), c2pa_asset);
+
+        let vc = serde_json::json!({
+            "@context": [
+                "https://www.w3.org/ns/credentials/v2",
+                "https://cawg.io/identity/1.1/ica/context/"
+            ],
+            "type": ["VerifiableCredential", "IdentityClaimsAggregationCredential"],
+            "issuer": self.did,
+            "validFrom": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            "credentialSubject": subject,
+        });
+        let vc_bytes = serde_json::to_vec(&vc)
+            .map_err(|e| IdentityBuilderError::SignerError(e.to_string()))?;
+
+        // The credential travels inside the COSE_Sign1 (VC-JOSE-COSE), so the
+        // payload is embedded and typed `application/vc`.
+        sign_v2_embedded_async(
+            &*self.signer,
+            &vc_bytes,
+            None,
+            CosePayload::Embedded,
+            Some(coset::ContentType::Text("application/vc".to_string())),
+            TimeStampStorage::V2_sigTst2_CTT,
+        )
+        .await
+        .map_err(|e| IdentityBuilderError::SignerError(e.to_string()))
+    }
+}
+
+/// JSON form of the signer payload for the credential's `c2paAsset`: CAWG
+/// requires every hash to be standard base64 rather than a byte array.
+#[cfg(feature = "cawg")]
+fn c2pa_asset_json(signer_payload: &SignerPayload) -> Result<serde_json::Value, IdentityBuilderError> {
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+
+    let mut c2pa_asset = serde_json::to_value(signer_payload)
+        .map_err(|e| IdentityBuilderError::SignerError(e.to_string()))?;
+    let assertions = c2pa_asset
+        .get_mut("referenced_assertions")
+        .and_then(|a| a.as_array_mut())
+        .into_iter()
+        .flatten();
+    for assertion in assertions {
+        if let Some(hash) = assertion.get_mut("hash") {
+            let bytes: Vec<u8> = serde_json::from_value(hash.take())
+                .map_err(|e| IdentityBuilderError::SignerError(e.to_string()))?;
+            *hash = serde_json::Value::String(BASE64.encode(bytes));
         }
     }
-    None
+    Ok(c2pa_asset)
+}
+
+/// Whether a validation status was logged by the CAWG identity validator. X.509
+/// identities report `cawg.identity.*` codes, identity claims aggregation
+/// credentials `cawg.ica.*`.
+#[cfg(feature = "cawg")]
+fn is_identity_status(code: &str) -> bool {
+    code == "cawg.identity" || code.starts_with("cawg.identity.") || code.starts_with("cawg.ica.")
+}
+
+/// Identity details extracted from a CAWG identity assertion.
+#[cfg(feature = "cawg")]
+#[derive(Default)]
+struct CawgIdentityDetails {
+    signature_info: Option<serde_json::Value>,
+    identity_name: Option<String>,
+    claims: Option<serde_json::Value>,
+    did: Option<String>,
+}
+
+/// Extracts signature information from CAWG identity assertions.
+/// Reads the `cawg.identity` entry of `reader.json()`, where post-validation has
+/// replaced the raw assertion with the validator's summary, and, when `validated`
+/// is true, pulls out the verified identity name, claims, and DID. Identity fields
+/// are always `None` for unvalidated assertions so that unverified strings never
+/// reach consumers.
+#[cfg(feature = "cawg")]
+fn extract_cawg_signature_info(reader: &c2pa::Reader, validated: bool) -> CawgIdentityDetails {
+    let Some(identity) = identity_assertion_json(reader) else {
+        return CawgIdentityDetails::default();
+    };
+    let mut details = CawgIdentityDetails::default();
+    if validated {
+        let (identity_name, claims) = extract_identity_claims(&identity);
+        details.identity_name = identity_name;
+        details.claims = claims;
+        details.did = extract_identity_did(&identity);
+    }
+    details.signature_info = Some(identity);
+    details
+}
+
+/// The `data` of the active manifest's `cawg.identity` assertion as reported by
+/// `reader.json()`. `Manifest::assertions()` only holds the raw assertion
+/// (`signer_payload`, `signature`, padding); the validated value is only merged
+/// into the JSON report.
+#[cfg(feature = "cawg")]
+fn identity_assertion_json(reader: &c2pa::Reader) -> Option<serde_json::Value> {
+    let mut report: serde_json::Value = serde_json::from_str(&reader.json()).ok()?;
+    let active = report.get("active_manifest")?.as_str()?.to_string();
+    let assertions = report.get_mut("manifests")?.get_mut(&active)?.get_mut("assertions")?.as_array_mut()?;
+    let identity = assertions
+        .iter_mut()
+        .find(|a| a.get("label").and_then(|l| l.as_str()) == Some("cawg.identity"))?;
+    identity.get_mut("data").map(serde_json::Value::take)
+}
+
+/// Returns the issuing DID of a validated identity claims aggregation credential.
+#[cfg(feature = "cawg")]
+fn extract_identity_did(assertion: &serde_json::Value) -> Option<String> {
+    let issuer = assertion.get("issuer")?;
+    let did = issuer
+        .as_str()
+        .or_else(|| issuer.get("id").and_then(|id| id.as_str()))?;
+    did.starts_with("did:").then(|| did.to_string())
+}
+
+/// Pulls the named actor and its claims out of a validated identity assertion.
+/// Handles both summaries produced by the CAWG validator: identity claims
+/// aggregation (the credential's `verifiedIdentities`) and X.509 (`signature_info`).
+#[cfg(feature = "cawg")]
+fn extract_identity_claims(
+    assertion: &serde_json::Value,
+) -> (Option<String>, Option<serde_json::Value>) {
+    let string_at = |v: &serde_json::Value, key: &str| {
+        v.get(key).and_then(|s| s.as_str()).map(|s| s.to_string())
+    };
+
+    // Identity claims aggregation: the verified identities of the credential subject
+    if let Some(identities) = assertion.get("verifiedIdentities") {
+        let name = identities
+            .as_array()
+            .and_then(|ids| ids.iter().find_map(|id| string_at(id, "name")));
+        return (name, Some(serde_json::json!({ "verifiedIdentities": identities })));
+    }
+
+    // X.509 identity: the signer's subject details. Only the common name names the
+    // signer; `issuer` is an organization, so without a common name there is no name.
+    if let Some(sig_info) = assertion.get("signature_info") {
+        let name = string_at(sig_info, "common_name");
+        return (name, Some(sig_info.clone()));
+    }
+
+    (None, None)
+}
+
+/// Returns true if the active manifest carries a CAWG identity assertion,
+/// regardless of whether it has been validated.
+#[cfg(feature = "cawg")]
+fn has_cawg_identity_assertion(reader: &c2pa::Reader) -> bool {
+    reader
+        .active_manifest()
+        .map(|m| m.assertions().iter().any(|a| a.label() == "cawg.identity"))
+        .unwrap_or(false)
 }
 
 /// Validates CAWG identity assertions in a C2PA reader.
 /// Runs the CAWG validator and extracts identity assertion information.
 ///
+/// The options control how the validator runs:
+/// * `validate == false` skips the CAWG validator entirely and returns early with
+///   `valid: false` and no signature info; `present` still reflects whether an
+///   identity assertion exists in the active manifest.
+/// * `require_valid_identity == true` selects strict handling: errors raised by the
+///   validator are propagated to the caller.
+/// * `require_valid_identity == false` selects lenient handling: validator errors are
+///   reported as an invalid identity instead of failing the whole verification.
+///
 /// # Arguments
 /// * `reader` - The C2PA reader containing the manifest to validate
-/// * `_opts` - CAWG verification options controlling validation behavior (unused for now)
+/// * `opts` - CAWG verification options controlling validation behavior
 ///
 /// # Returns
 /// CAWG verification results including presence, validity, and signature info
 #[cfg(feature = "cawg")]
 pub async fn validate_cawg(
     reader: &mut c2pa::Reader,
-    _opts: &CawgVerifyOptions,
+    opts: &CawgVerifyOptions,
 ) -> EngineResult<CawgVerification> {
+    if !opts.validate {
+        return Ok(CawgVerification {
+            present: has_cawg_identity_assertion(reader),
+            valid: false,
+            signature_info: None,
+            identity_name: None,
+            claims: None,
+            did: None,
+        });
+    }
+
     // Run CAWG validation
-    reader
-        .post_validate_async(&CawgValidator {})
-        .await
-        .map_err(|e| EngineError::C2pa(c2pa::Error::OtherError(Box::new(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))))?;
+    trace_event!(debug, require_valid_identity = opts.require_valid_identity, "validating CAWG identity");
+    if let Err(e) = reader.post_validate_async(&CawgValidator {}).await {
+        trace_event!(warn, error = %e, "CAWG validation failed to run");
+        if opts.require_valid_identity {
+            return Err(EngineError::C2pa(c2pa::Error::OtherError(Box::new(std::io::Error::other(e.to_string())))));
+        }
+        return Ok(CawgVerification {
+            present: has_cawg_identity_assertion(reader),
+            valid: false,
+            signature_info: None,
+            identity_name: None,
+            claims: None,
+            did: None,
+        });
+    }
 
     // Check if CAWG identity assertion is present and extract information
     let validation_results = reader
@@ -252,35 +558,31 @@ pub async fn validate_cawg(
 
     // Check for CAWG identity assertions in validation results
     if let Some(active_manifest) = validation_results.active_manifest() {
-        for status in active_manifest.success() {
-            let code = status.code();
-            if code == "cawg.identity" || code.starts_with("cawg.identity.") {
-                cawg_present = true;
-                break;
-            }
+        if active_manifest.success().iter().any(|status| is_identity_status(status.code())) {
+            cawg_present = true;
         }
 
         // Check for CAWG validation failures
-        for status in active_manifest.failure() {
-            let code = status.code();
-            if code == "cawg.identity" || code.starts_with("cawg.identity.") {
-                cawg_present = true;
-                cawg_valid = false;
-                break;
-            }
+        if active_manifest.failure().iter().any(|status| is_identity_status(status.code())) {
+            cawg_present = true;
+            cawg_valid = false;
         }
     }
 
     // Extract signature information from CAWG identity assertion if present
-    let signature_info = if cawg_present {
-        extract_cawg_signature_info(reader)
+    trace_event!(info, present = cawg_present, valid = cawg_valid, "CAWG identity evaluated");
+    let details = if cawg_present {
+        extract_cawg_signature_info(reader, cawg_valid)
     } else {
-        None
+        CawgIdentityDetails::default()
     };
 
     Ok(CawgVerification {
         present: cawg_present,
         valid: cawg_valid,
-        signature_info,
+        signature_info: details.signature_info,
+        identity_name: details.identity_name,
+        claims: details.claims,
+        did: details.did,
     })
-}
\ No newline at end of file
+}
@@ -1,4 +1,34 @@
+use std::sync::{PoisonError, RwLock};
+
+#[cfg(feature = "c2pa")]
+use crate::domain::error::{EngineError, EngineResult};
+
+/// Recognises a format from an asset's leading bytes, returning its file extension
+/// (e.g. `"qcf"`). Given at most the first 512 bytes when sniffing streams and files.
+pub type ContentDetector = fn(&[u8]) -> Option<&'static str>;
+
+/// Detectors added with `register_content_detector`, in registration order.
+static CUSTOM_DETECTORS: RwLock<Vec<ContentDetector>> = RwLock::new(Vec::new());
+
+/// Register a detector consulted before the built-in signatures.
+///
+/// The registry is process-wide and guarded by a lock, so detectors may be registered
+/// from any thread, but should be registered at startup: detection already in progress
+/// may not see a detector added concurrently. Detectors run in registration order and
+/// the first match wins, so detection stays deterministic. A detector must not call
+/// back into the engine, and cannot be removed once registered.
+pub fn register_content_detector(detector: ContentDetector) {
+  CUSTOM_DETECTORS.write().unwrap_or_else(PoisonError::into_inner).push(detector);
+}
+
+/// File extension for an asset's leading bytes: registered detectors first, then the
+/// built-in signatures.
 pub fn detect_extension_from_bytes(data: &[u8]) -> Option<&'static str> {
+  let custom = CUSTOM_DETECTORS.read().unwrap_or_else(PoisonError::into_inner);
+  custom.iter().find_map(|detect| detect(data)).or_else(|| detect_builtin_extension(data))
+}
+
+fn detect_builtin_extension(data: &[u8]) -> Option<&'static str> {
   // JPEG
   if data.len() >= 3 && data[0] == 0xFF && data[1] == 0xD8 && data[2] == 0xFF {
     return Some("jpg");
@@ -29,26 +59,62 @@ pub fn detect_extension_from_bytes(data: &[u8]) -> Option<&'static str> {
     return Some("avi");
   }
 
-  // TIFF
+  // Fujifilm RAF
+  if data.len() >= 15 && &data[..15] == b"FUJIFILMCCD-RAW" {
+    return Some("raf");
+  }
+
+  // Olympus ORF and Panasonic RW2 use their own TIFF-like magic
+  if data.len() >= 4 && (&data[..4] == b"IIRO" || &data[..4] == b"IIRS" || &data[..4] == b"MMOR") {
+    return Some("orf");
+  }
+  if data.len() >= 4 && &data[..4] == b"IIU\0" {
+    return Some("rw2");
+  }
+
+  // TIFF, and the camera raw formats built on it
   if data.len() >= 4 && ((&data[..4] == b"II*\0") || (&data[..4] == b"MM\0*")) {
-    return Some("tiff");
+    // Canon CR2
+    if data.len() >= 10 && &data[8..10] == b"CR" {
+      return Some("cr2");
+    }
+    return Some(tiff_raw_extension(data));
   }
 
   // MP4/MOV/ISO-BMFF (ftyp box) - check for supported brands
   if data.len() >= 12 && &data[4..8] == b"ftyp" {
     if data.len() >= 16 {
       let brand = &data[8..12];
+      // HEIF image sequences, checked before stills since their compatible brands
+      // usually include `mif1`/`heic` for the cover image
+      if brand == b"hevc" || brand == b"hevx" || (brand == b"msf1" && has_compatible_brand(data, b"hevc")) {
+        return Some("heics");
+      }
+      if brand == b"msf1" {
+        return Some("heifs");
+      }
       // HEIC/HEIF
-      if brand == b"heic" {
+      if brand == b"heic" || brand == b"heix" {
         return Some("heic");
       }
       if brand == b"heif" {
         return Some("heif");
       }
-      // AVIF
-      if brand == b"avif" {
+      // AVIF, including sequences (`avis`), which share the still image's MIME type
+      // and are signed like any other ISO-BMFF file
+      if brand == b"avif" || brand == b"avis" {
         return Some("avif");
       }
+      // Generic HEIF still image; the coding is named by a compatible brand
+      if brand == b"mif1" {
+        if has_compatible_brand(data, b"avif") {
+          return Some("avif");
+        }
+        if has_compatible_brand(data, b"heic") || has_compatible_brand(data, b"heix") {
+          return Some("heic");
+        }
+        return Some("heif");
+      }
       // MP4 variants
       if brand == b"mp42" || brand == b"isom" || brand == b"mp41" || brand == b"dash" {
         return Some("mp4");
@@ -57,6 +123,10 @@ pub fn detect_extension_from_bytes(data: &[u8]) -> Option<&'static str> {
       if brand == b"qt  " {
         return Some("mov");
       }
+      // Canon CR3
+      if brand == b"crx " {
+        return Some("cr3");
+      }
       // M4A (audio MP4)
       if brand == b"M4A " || brand == b"m4af" {
         return Some("m4a");
@@ -72,26 +142,157 @@ pub fn detect_extension_from_bytes(data: &[u8]) -> Option<&'static str> {
   }
 
   // SVG
-  if data.len() >= 5 && data[0] == b'<' {
-    let head = &data[..std::cmp::min(512, data.len())];
-    if let Ok(s) = std::str::from_utf8(head) {
-      if s.to_ascii_lowercase().contains("<svg") {
-        return Some("svg");
-      }
-    }
+  if is_svg(data) {
+    return Some("svg");
   }
 
   // MP3
   if data.len() >= 3 && &data[..3] == b"ID3" {
     return Some("mp3");
   }
-  if data.len() >= 2 && data[0] == 0xFF && (data[1] & 0xE0) == 0xE0 {
+  if is_mpeg_audio_frame(data) {
     return Some("mp3");
   }
 
   None
 }
 
+/// Whether the `ftyp` box at the start of `data` lists `brand` among its compatible
+/// brands. Brands past the end of `data` are not seen.
+fn has_compatible_brand(data: &[u8], brand: &[u8; 4]) -> bool {
+  let size = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize;
+  let end = size.min(data.len());
+  // Major brand at 8..12, minor version at 12..16, then compatible brands.
+  end > 16 && data[16..end].chunks_exact(4).any(|b| b == brand)
+}
+
+/// SVG markup within the first 512 bytes. A UTF-8 BOM and leading whitespace are
+/// skipped, so the root element may follow an XML declaration, doctype or comments.
+fn is_svg(data: &[u8]) -> bool {
+  let head = &data[..std::cmp::min(512, data.len())];
+  let head = head.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(head);
+  let Some(start) = head.iter().position(|b| !b.is_ascii_whitespace()) else { return false };
+  // Lossy, since the bound may split a multi-byte character.
+  head[start] == b'<' && String::from_utf8_lossy(&head[start..]).to_ascii_lowercase().contains("<svg")
+}
+
+/// An MPEG audio frame header without reserved or invalid fields. The 11-bit frame
+/// sync alone matches too much binary data (and ADTS AAC), so the version, layer,
+/// bitrate and sample-rate bits are checked as well.
+fn is_mpeg_audio_frame(data: &[u8]) -> bool {
+  if data.len() < 4 || data[0] != 0xFF || data[1] & 0xE0 != 0xE0 {
+    return false;
+  }
+  let version = (data[1] >> 3) & 0b11;
+  let layer = (data[1] >> 1) & 0b11;
+  let bitrate = data[2] >> 4;
+  let sample_rate = (data[2] >> 2) & 0b11;
+  let emphasis = data[3] & 0b11;
+  // 01 is a reserved version and 00 a reserved layer; bitrate 0000 is "free format",
+  // which real files almost never use, and 1111 is invalid.
+  version != 0b01 && layer != 0b00 && bitrate != 0b0000 && bitrate != 0b1111
+    && sample_rate != 0b11 && emphasis != 0b10
+}
+
+/// Classify a TIFF header by its first IFD: DNG by the `DNGVersion` tag, NEF and ARW
+/// by the `Make` string. Anything else, or an IFD outside `data`, is plain TIFF.
+fn tiff_raw_extension(data: &[u8]) -> &'static str {
+  let little_endian = data[0] == b'I';
+  let u16_at = |at: usize| {
+    let b: [u8; 2] = data.get(at..at.checked_add(2)?)?.try_into().ok()?;
+    Some(if little_endian { u16::from_le_bytes(b) } else { u16::from_be_bytes(b) })
+  };
+  let u32_at = |at: usize| {
+    let b: [u8; 4] = data.get(at..at.checked_add(4)?)?.try_into().ok()?;
+    Some(if little_endian { u32::from_le_bytes(b) } else { u32::from_be_bytes(b) })
+  };
+
+  let Some(ifd) = u32_at(4).map(|o| o as usize) else { return "tiff" };
+  let mut by_make = "tiff";
+  for i in 0..u16_at(ifd).unwrap_or(0) as usize {
+    let entry = ifd + 2 + i * 12;
+    let (Some(tag), Some(count)) = (u16_at(entry), u32_at(entry + 4)) else { break };
+    match tag {
+      // A DNG converted from another camera's raw keeps that camera's Make.
+      0xC612 => return "dng",
+      0x010F => {
+        // ASCII values of up to four bytes are stored inline.
+        let start = if count <= 4 { Some(entry + 8) } else { u32_at(entry + 8).map(|o| o as usize) };
+        let make = start
+          .and_then(|s| data.get(s..s.checked_add(count as usize)?))
+          .unwrap_or_default();
+        if make.starts_with(b"NIKON") {
+          by_make = "nef";
+        } else if make.starts_with(b"SONY") {
+          by_make = "arw";
+        }
+      }
+      _ => {}
+    }
+  }
+  by_make
+}
+
+/// Camera raw formats that are recognised but that c2pa cannot embed a manifest in,
+/// with a display name for error messages.
+pub fn unsupported_raw_format(extension: &str) -> Option<&'static str> {
+  match extension {
+    "cr2" => Some("Canon CR2"),
+    "cr3" => Some("Canon CR3"),
+    "orf" => Some("Olympus ORF"),
+    "rw2" => Some("Panasonic RW2"),
+    "raf" => Some("Fujifilm RAF"),
+    _ => None,
+  }
+}
+
+/// Rejects a sniffed HEIF image sequence when the c2pa SDK has no handler for its MIME
+/// type, with an `UnsupportedFormat` naming the brand instead of the SDK's bare
+/// `UnsupportedType`. Sequences are never read or signed as HEIF stills.
+#[cfg(feature = "c2pa")]
+pub fn check_heif_sequence_supported(extension: Option<&str>, write: bool) -> EngineResult<()> {
+  let (mime, name) = match extension {
+    Some("heics") => ("image/heic-sequence", "HEIC image sequences (brand hevc)"),
+    Some("heifs") => ("image/heif-sequence", "HEIF image sequences (brand msf1)"),
+    _ => return Ok(()),
+  };
+  let supported = if write { c2pa::Builder::supported_mime_types() } else { c2pa::Reader::supported_mime_types() };
+  if supported.iter().any(|t| t == mime) {
+    return Ok(());
+  }
+  let operation = if write { "signing" } else { "reading" };
+  Err(EngineError::UnsupportedFormat(format!("{operation} {name} is not supported by c2pa {}", c2pa::VERSION)))
+}
+
+/// Rejects a sniffed PDF when the c2pa SDK has no PDF handler for the operation, with
+/// an `UnsupportedFormat` naming PDF instead of the SDK's bare `UnsupportedType`.
+/// c2pa-rs 0.59 cannot write manifests into PDFs at all, and reads them only with its
+/// `pdf` feature, which this engine does not enable.
+#[cfg(feature = "c2pa")]
+pub fn check_pdf_supported(extension: Option<&str>, write: bool) -> EngineResult<()> {
+  if extension != Some("pdf") {
+    return Ok(());
+  }
+  let supported = if write { c2pa::Builder::supported_mime_types() } else { c2pa::Reader::supported_mime_types() };
+  if supported.iter().any(|t| t == "application/pdf") {
+    return Ok(());
+  }
+  Err(EngineError::UnsupportedFormat(if write {
+    format!("pdf: signing PDF files is not supported by c2pa {}", c2pa::VERSION)
+  } else {
+    "pdf: reading PDF manifests needs c2pa's `pdf` feature, which this build does not enable".into()
+  }))
+}
+
+/// The format c2pa should read a detected extension as: its MIME type, or the
+/// extension itself when it is outside the built-in table (c2pa accepts either).
+pub fn format_for_extension(extension: &'static str) -> &'static str {
+  match extension_to_mime_type(extension) {
+    "application/octet-stream" => extension,
+    mime => mime,
+  }
+}
+
 /// Convert file extension to MIME type
 /// This is used when we need MIME types but only have file extensions
 pub fn extension_to_mime_type(extension: &str) -> &'static str {
@@ -103,9 +304,19 @@ pub fn extension_to_mime_type(extension: &str) -> &'static str {
     "wav" => "audio/wav",
     "avi" => "video/msvideo",
     "tiff" | "tif" => "image/tiff",
+    "dng" => "image/x-adobe-dng",
+    "nef" => "image/x-nikon-nef",
+    "arw" => "image/x-sony-arw",
+    "cr2" => "image/x-canon-cr2",
+    "cr3" => "image/x-canon-cr3",
+    "orf" => "image/x-olympus-orf",
+    "rw2" => "image/x-panasonic-rw2",
+    "raf" => "image/x-fuji-raf",
     "heic" => "image/heic",
     "heif" => "image/heif",
     "avif" => "image/avif",
+    "heics" => "image/heic-sequence",
+    "heifs" => "image/heif-sequence",
     "mp4" => "video/mp4",
     "mov" => "video/quicktime",
     "m4a" => "audio/mp4",
@@ -0,0 +1,122 @@
+// adapters/c2pa/engine/batch.rs
+
+use std::path::PathBuf;
+
+use crate::crypto::signer::Signer;
+use crate::crypto::timestamper::Timestamper;
+use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::types::{
+  default_limits, AssetRef, EngineDefaults, LimitsConfig, OutputTarget, SigAlg, TrustPolicyConfig,
+};
+use super::super::settings::{prepare_manifest_json, with_c2pa_settings};
+use super::sign::{sign_settings, sign_with_builder};
+use super::tsa_signer::with_tsa_headers;
+#[cfg(feature = "tsa_client")]
+use super::tsa_signer::with_tsa_client;
+#[cfg(feature = "tsa_client")]
+use super::super::tsa_client::TsaClient;
+
+/// Signs many assets with the same manifest template and signer.
+///
+/// The manifest template is prepared and the signer resolved once in [`BatchSigner::new`].
+/// Each batch call applies the c2pa settings once for all of its assets, and returns
+/// one result per asset so a bad asset does not abort the rest of the batch.
+///
+/// CAWG identities, parent ingredients and remote manifests are not supported here;
+/// use `sign_c2pa` for those.
+pub struct BatchSigner {
+  manifest_json: String,
+  signer: Box<dyn c2pa::Signer>,
+  /// Optional trust policy to apply when verifying immediately after signing.
+  pub trust_policy: Option<TrustPolicyConfig>,
+  pub skip_post_sign_validation: bool,
+  /// Per-asset limits. Defaults are tuned for production safety.
+  pub limits: LimitsConfig,
+  /// Directory for temporary files; `None` uses the system temp directory.
+  pub temp_dir: Option<PathBuf>,
+  /// Shared client for the time-stamp requests of every asset, reusing connections
+  /// to the TSA and caching responses. Ignored without a timestamper.
+  #[cfg(feature = "tsa_client")]
+  pub tsa_client: Option<TsaClient>,
+  #[cfg(feature = "tsa_client")]
+  timestamper: Option<Timestamper>,
+}
+
+impl BatchSigner {
+  pub fn new(
+    template_manifest: Option<String>,
+    signer: Signer,
+    alg: SigAlg,
+    timestamper: Option<Timestamper>,
+  ) -> EngineResult<Self> {
+    let manifest_json = prepare_manifest_json(template_manifest, &timestamper)?;
+    // Fail early on a malformed template rather than once per asset.
+    c2pa::Builder::from_json(&manifest_json)?;
+    let signer = with_tsa_headers(signer.resolve(alg.to_c2pa())?, timestamper.as_ref());
+
+    Ok(Self {
+      manifest_json,
+      signer,
+      trust_policy: EngineDefaults::HAS_TRUST_POLICY,
+      skip_post_sign_validation: EngineDefaults::SKIP_POST_SIGN_VALIDATION,
+      limits: default_limits(),
+      temp_dir: None,
+      #[cfg(feature = "tsa_client")]
+      tsa_client: None,
+      #[cfg(feature = "tsa_client")]
+      timestamper,
+    })
+  }
+
+  /// Sign each source and return the signed bytes, in input order.
+  ///
+  /// The outer error covers batch-wide failures (invalid settings); per-asset
+  /// failures are reported in the corresponding entry.
+  pub fn sign_all(&self, sources: &[AssetRef]) -> EngineResult<Vec<EngineResult<Vec<u8>>>> {
+    self.with_batch_settings(|| {
+      sources
+        .iter()
+        .map(|source| {
+          self.sign_one(source, &OutputTarget::Memory).and_then(|out| {
+            out.ok_or_else(|| {
+              EngineError::Config("memory output expected but none produced".into())
+            })
+          })
+        })
+        .collect()
+    })
+  }
+
+  /// Sign each `(source, output)` pair, in input order. `Path` outputs yield `None`.
+  pub fn sign_all_to(
+    &self,
+    jobs: &[(AssetRef, OutputTarget)],
+  ) -> EngineResult<Vec<EngineResult<Option<Vec<u8>>>>> {
+    self.with_batch_settings(|| {
+      jobs
+        .iter()
+        .map(|(source, output)| self.sign_one(source, output))
+        .collect()
+    })
+  }
+
+  fn with_batch_settings<T>(&self, f: impl FnOnce() -> Vec<T>) -> EngineResult<Vec<T>> {
+    let settings = sign_settings(self.skip_post_sign_validation, self.trust_policy.as_ref())?;
+    with_c2pa_settings(&settings, || Ok(f()))
+  }
+
+  fn sign_one(&self, source: &AssetRef, output: &OutputTarget) -> EngineResult<Option<Vec<u8>>> {
+    // Builders accumulate per-asset state while signing, so each asset gets a fresh one
+    // from the already-prepared template.
+    let mut builder = c2pa::Builder::from_json(&self.manifest_json)?;
+    #[cfg(feature = "tsa_client")]
+    if let Some(signer) = self
+      .tsa_client
+      .as_ref()
+      .and_then(|client| with_tsa_client(&*self.signer, self.timestamper.as_ref(), client))
+    {
+      return sign_with_builder(&mut builder, &signer, source, output, self.limits, self.temp_dir.as_deref(), None, None);
+    }
+    sign_with_builder(&mut builder, &*self.signer, source, output, self.limits, self.temp_dir.as_deref(), None, None)
+  }
+}
@@ -1,68 +1,410 @@
 // adapters/c2pa/engine/bmff.rs
 
-#[cfg(all(feature = "c2pa", feature = "bmff"))]
-use c2pa::Builder;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 
-use crate::domain::error::{EngineError, EngineResult};
-use super::super::settings::{with_c2pa_settings, prepare_manifest_json};
+use crate::domain::error::{EngineError, EngineResult, SegmentFailure};
+use crate::domain::types::{
+  AssetRef, BmffLayout, C2paVerificationConfig, FragmentedBmffConfig, FragmentedBmffVerifyConfig,
+};
+use crate::domain::verify::FragmentedBmffVerification;
+use super::super::settings::{apply_settings, with_c2pa_settings, prepare_manifest_json};
 use super::super::url_validation::validate_external_http_url;
+use super::soft_binding::collect_soft_bindings;
+use super::verify::{build_verification_result, build_verify_settings};
 
-pub fn generate_fragmented_bmff(
-  cfg: crate::domain::types::FragmentedBmffConfig,
-) -> EngineResult<()> {
+/// An init segment together with its fragments and output directory.
+/// For `BmffLayout::SingleFile`, `init_path` is the whole fragmented file and
+/// `fragments` is empty.
+struct SegmentGroup {
+  init_path: PathBuf,
+  fragments: Vec<PathBuf>,
+  output_dir: PathBuf,
+  layout: BmffLayout,
+}
+
+pub fn generate_fragmented_bmff(cfg: FragmentedBmffConfig) -> EngineResult<()> {
   let settings = vec![serde_json::json!({
     "verify": { "verify_after_sign": !cfg.skip_post_sign_validation }
   })];
 
+  // c2pa settings are per thread: the calling thread applies them here, and each
+  // parallel worker applies them again on its own thread.
   with_c2pa_settings(&settings, || {
     let manifest_json =
-      prepare_manifest_json(cfg.manifest_definition, &cfg.timestamper)?;
-    let mut builder = c2pa::Builder::from_json(&manifest_json)?;
+      prepare_manifest_json(cfg.manifest_definition.clone(), &cfg.timestamper)?;
     let alg = cfg.signing_alg.to_c2pa();
-    let signer = cfg.signer.resolve(alg)?;
 
-    if let Some(remote_url) = cfg.remote_manifest_url {
+    if let Some(remote_url) = &cfg.remote_manifest_url {
       let allow_http = cfg.allow_insecure_remote_http.unwrap_or(false);
-      validate_external_http_url(&remote_url, allow_http)?;
-      builder.set_remote_url(remote_url);
-    }
-    if !cfg.embed {
-      builder.set_no_embed(true);
+      validate_external_http_url(remote_url, allow_http)?;
     }
 
     std::fs::create_dir_all(&cfg.output_dir)?;
 
-    let init_glob_str = cfg
+    let groups = collect_segment_groups(&cfg)?;
+    let workers = cfg.parallelism.unwrap_or(1).clamp(1, groups.len().max(1));
+
+    let failures = if workers == 1 {
+      let mut builder = new_builder(&cfg, &manifest_json)?;
+      let signer = cfg.signer.resolve(alg)?;
+      groups
+        .iter()
+        .filter_map(|group| {
+          sign_group(&mut builder, &*signer, group)
+            .err()
+            .map(|e| segment_failure(group, e))
+        })
+        .collect()
+    } else {
+      sign_groups_parallel(&cfg, &settings, &manifest_json, alg, &groups, workers)
+    };
+
+    if failures.is_empty() {
+      Ok(())
+    } else {
+      Err(EngineError::FragmentedBmff { failures })
+    }
+  })
+}
+
+fn collect_segment_groups(cfg: &FragmentedBmffConfig) -> EngineResult<Vec<SegmentGroup>> {
+  if cfg.layout == BmffLayout::SingleFile {
+    let glob_str = cfg
       .init_glob
       .to_str()
       .ok_or_else(|| EngineError::Config("init_glob is not valid UTF-8".into()))?;
+    let mut groups = Vec::new();
+    for entry in glob::glob(glob_str)? {
+      groups.push(SegmentGroup {
+        init_path: entry?,
+        fragments: Vec::new(),
+        output_dir: cfg.output_dir.clone(),
+        layout: BmffLayout::SingleFile,
+      });
+    }
+    return Ok(groups);
+  }
 
-    for init_entry in glob::glob(init_glob_str)? {
-      let init_path = init_entry?;
-      let init_dir = init_path
+  find_segment_groups(&cfg.init_glob, &cfg.fragments_glob)?
+    .into_iter()
+    .map(|(init_path, fragments)| {
+      let init_dir_name = init_path
         .parent()
-        .ok_or_else(|| EngineError::Config("init segment has no parent".into()))?;
+        .and_then(|dir| dir.file_name())
+        .ok_or_else(|| EngineError::Config("invalid init dir name".into()))?;
+      let output_dir = cfg.output_dir.join(init_dir_name);
+      Ok(SegmentGroup { init_path, fragments, output_dir, layout: BmffLayout::Segmented })
+    })
+    .collect()
+}
 
-      let frag_glob_path = init_dir.join(&cfg.fragments_glob);
-      let frag_glob_str = frag_glob_path.to_str().ok_or_else(|| {
-        EngineError::Config("fragments_glob is not valid UTF-8".into())
-      })?;
+/// Init segments matching `init_glob`, each with the fragments matching
+/// `fragments_glob` in its directory. Fragments are ordered by name with digit runs
+/// compared numerically, so `seg2` precedes `seg10`.
+fn find_segment_groups(
+  init_glob: &Path,
+  fragments_glob: &Path,
+) -> EngineResult<Vec<(PathBuf, Vec<PathBuf>)>> {
+  let init_glob_str = init_glob
+    .to_str()
+    .ok_or_else(|| EngineError::Config("init_glob is not valid UTF-8".into()))?;
 
-      let mut fragments = Vec::new();
-      for frag_entry in glob::glob(frag_glob_str)? {
-        fragments.push(frag_entry?);
-      }
+  let mut groups = Vec::new();
+  for init_entry in glob::glob(init_glob_str)? {
+    let init_path = init_entry?;
+    let init_dir = init_path
+      .parent()
+      .ok_or_else(|| EngineError::Config("init segment has no parent".into()))?;
+
+    let frag_glob_path = init_dir.join(fragments_glob);
+    let frag_glob_str = frag_glob_path.to_str().ok_or_else(|| {
+      EngineError::Config("fragments_glob is not valid UTF-8".into())
+    })?;
+
+    let mut fragments = Vec::new();
+    for frag_entry in glob::glob(frag_glob_str)? {
+      fragments.push(frag_entry?);
+    }
+    fragments.sort_by(|a, b| natural_cmp(&a.to_string_lossy(), &b.to_string_lossy()));
+
+    groups.push((init_path, fragments));
+  }
+
+  Ok(groups)
+}
+
+fn new_builder(cfg: &FragmentedBmffConfig, manifest_json: &str) -> EngineResult<c2pa::Builder> {
+  let mut builder = c2pa::Builder::from_json(manifest_json)?;
+  if let Some(remote_url) = &cfg.remote_manifest_url {
+    builder.set_remote_url(remote_url.clone());
+  }
+  if !cfg.embed {
+    builder.set_no_embed(true);
+  }
+  Ok(builder)
+}
+
+fn sign_group(
+  builder: &mut c2pa::Builder,
+  signer: &dyn c2pa::Signer,
+  group: &SegmentGroup,
+) -> EngineResult<()> {
+  std::fs::create_dir_all(&group.output_dir)?;
+  match group.layout {
+    BmffLayout::Segmented => {
+      builder.sign_fragmented_files(signer, &group.init_path, &group.fragments, &group.output_dir)?;
+    }
+    BmffLayout::SingleFile => {
+      ensure_fragmented_file(&group.init_path)?;
+      let file_name = group
+        .init_path
+        .file_name()
+        .ok_or_else(|| EngineError::Config("invalid input file name".into()))?;
+      // BMFF hashing covers each moof/mdat pair, so the file is signed whole.
+      builder.sign_file(signer, &group.init_path, group.output_dir.join(file_name))?;
+    }
+  }
+  Ok(())
+}
 
-      let sub_output_dir = cfg.output_dir.join(
-        init_dir
-          .file_name()
-          .ok_or_else(|| EngineError::Config("invalid init dir name".into()))?,
-      );
-      std::fs::create_dir_all(&sub_output_dir)?;
+/// Require a fragmented MP4: a `moov` carrying `mvex` followed by `moof` boxes.
+fn ensure_fragmented_file(path: &Path) -> EngineResult<()> {
+  let mut file = std::fs::File::open(path)?;
+  let file_len = file.metadata()?.len();
 
-      builder.sign_fragmented_files(&*signer, &init_path, &fragments, &sub_output_dir)?;
+  let has_mvex = match find_box(&mut file, 0, file_len, b"moov")? {
+    Some((moov_start, moov_end)) => find_box(&mut file, moov_start, moov_end, b"mvex")?.is_some(),
+    None => {
+      return Err(EngineError::Config(format!(
+        "{} is not an MP4 file (no moov box)",
+        path.display()
+      )));
     }
+  };
+  let has_moof = find_box(&mut file, 0, file_len, b"moof")?.is_some();
 
+  if has_mvex && has_moof {
     Ok(())
+  } else {
+    Err(EngineError::Config(format!(
+      "{} is a progressive MP4, not a fragmented one; sign it with sign_c2pa instead",
+      path.display()
+    )))
+  }
+}
+
+fn sign_groups_parallel(
+  cfg: &FragmentedBmffConfig,
+  settings: &[serde_json::Value],
+  manifest_json: &str,
+  alg: c2pa::SigningAlg,
+  groups: &[SegmentGroup],
+  workers: usize,
+) -> Vec<SegmentFailure> {
+  let next = AtomicUsize::new(0);
+  let failures = Mutex::new(Vec::new());
+
+  std::thread::scope(|scope| {
+    for _ in 0..workers {
+      scope.spawn(|| {
+        // A new thread starts from c2pa's defaults, so the run's settings are applied
+        // before it signs anything. Each worker owns its builder and signer; neither
+        // is shared across threads.
+        let mut setup = apply_settings(settings)
+          .and_then(|()| new_builder(cfg, manifest_json))
+          .and_then(|builder| Ok((builder, cfg.signer.resolve(alg)?)));
+
+        loop {
+          let index = next.fetch_add(1, Ordering::Relaxed);
+          let Some(group) = groups.get(index) else { break };
+
+          let result = match &mut setup {
+            Ok((builder, signer)) => sign_group(builder, &**signer, group),
+            Err(e) => Err(EngineError::Config(format!("worker setup failed: {e}"))),
+          };
+          if let Err(e) = result {
+            if let Ok(mut failures) = failures.lock() {
+              failures.push((index, segment_failure(group, e)));
+            }
+          }
+        }
+      });
+    }
+  });
+
+  // Report failures in glob order regardless of which worker hit them.
+  let mut failures = failures.into_inner().unwrap_or_else(|e| e.into_inner());
+  failures.sort_by_key(|(index, _)| *index);
+  failures.into_iter().map(|(_, failure)| failure).collect()
+}
+
+fn segment_failure(group: &SegmentGroup, error: EngineError) -> SegmentFailure {
+  SegmentFailure {
+    init_path: group.init_path.clone(),
+    message: error.to_string(),
+  }
+}
+
+/// Compare strings with runs of ASCII digits ordered by numeric value.
+fn natural_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+  let (mut a, mut b) = (a.as_bytes(), b.as_bytes());
+  loop {
+    match (a.first(), b.first()) {
+      (None, None) => return std::cmp::Ordering::Equal,
+      (None, Some(_)) => return std::cmp::Ordering::Less,
+      (Some(_), None) => return std::cmp::Ordering::Greater,
+      (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+        let a_len = a.iter().take_while(|c| c.is_ascii_digit()).count();
+        let b_len = b.iter().take_while(|c| c.is_ascii_digit()).count();
+        let (a_num, b_num) = (trim_zeros(&a[..a_len]), trim_zeros(&b[..b_len]));
+        let ord = a_num.len().cmp(&b_num.len()).then_with(|| a_num.cmp(b_num));
+        if ord != std::cmp::Ordering::Equal {
+          return ord;
+        }
+        a = &a[a_len..];
+        b = &b[b_len..];
+      }
+      (Some(x), Some(y)) => {
+        if x != y {
+          return x.cmp(y);
+        }
+        a = &a[1..];
+        b = &b[1..];
+      }
+    }
+  }
+}
+
+fn trim_zeros(digits: &[u8]) -> &[u8] {
+  let zeros = digits.iter().take_while(|c| **c == b'0').count();
+  &digits[zeros..]
+}
+
+/// Verify each init segment matched by `init_glob` together with its fragments.
+///
+/// Groups are verified independently: a group whose fragments are missing or out of
+/// order, or whose manifest fails to load, reports an error without affecting the rest.
+pub fn verify_fragmented_bmff(
+  cfg: FragmentedBmffVerifyConfig,
+) -> EngineResult<Vec<FragmentedBmffVerification>> {
+  let mut template = C2paVerificationConfig::secure_default(AssetRef::Path(PathBuf::new()));
+  template.mode = cfg.mode;
+  template.policy = cfg.policy.clone();
+  template.include_certificates = cfg.include_certificates;
+  let settings = build_verify_settings(&template)?;
+
+  let groups = find_segment_groups(&cfg.init_glob, &cfg.fragments_glob)?;
+  if groups.is_empty() {
+    return Err(EngineError::Config(format!(
+      "no init segments match {}",
+      cfg.init_glob.display()
+    )));
+  }
+
+  with_c2pa_settings(&settings, || {
+    Ok(
+      groups
+        .into_iter()
+        .map(|(init_path, fragments)| {
+          let result = check_fragment_order(&init_path, &fragments).and_then(|()| {
+            let reader = c2pa::Reader::from_fragmented_files(&init_path, &fragments)?;
+            let mut result = build_verification_result(&template, &reader);
+            result.soft_bindings = collect_soft_bindings(&template, &reader)?;
+            Ok(result)
+          });
+          FragmentedBmffVerification { init_path, fragments, result }
+        })
+        .collect(),
+    )
   })
-}
\ No newline at end of file
+}
+
+/// Require at least one fragment and `mfhd` sequence numbers that increase by one in
+/// glob order, so a missing or misnamed fragment is reported before hash validation
+/// fails on it.
+fn check_fragment_order(init_path: &Path, fragments: &[PathBuf]) -> EngineResult<()> {
+  if fragments.is_empty() {
+    return Err(EngineError::Config(format!(
+      "no fragments found for init segment {}",
+      init_path.display()
+    )));
+  }
+
+  let mut previous: Option<(u32, &Path)> = None;
+  for fragment in fragments {
+    let sequence = fragment_sequence_number(fragment)?;
+    if let Some((prev_seq, prev_path)) = previous {
+      if sequence <= prev_seq {
+        return Err(EngineError::Config(format!(
+          "fragment {} (sequence {sequence}) is out of order after {} (sequence {prev_seq}); \
+           fragment names must sort in playback order",
+          fragment.display(),
+          prev_path.display()
+        )));
+      }
+      if sequence != prev_seq + 1 {
+        return Err(EngineError::Config(format!(
+          "missing fragment(s): sequence jumps from {prev_seq} ({}) to {sequence} ({})",
+          prev_path.display(),
+          fragment.display()
+        )));
+      }
+    }
+    previous = Some((sequence, fragment));
+  }
+  Ok(())
+}
+
+/// Sequence number from the first `moof/mfhd` box of a fragment.
+fn fragment_sequence_number(path: &Path) -> EngineResult<u32> {
+  let missing = || {
+    EngineError::Config(format!("{} is not a BMFF fragment (no moof/mfhd box)", path.display()))
+  };
+
+  let mut file = std::fs::File::open(path)?;
+  let file_len = file.metadata()?.len();
+  let (moof_start, moof_end) = find_box(&mut file, 0, file_len, b"moof")?.ok_or_else(missing)?;
+  let (mfhd_start, _) = find_box(&mut file, moof_start, moof_end, b"mfhd")?.ok_or_else(missing)?;
+
+  // FullBox: version (1) + flags (3), then sequence_number.
+  let mut sequence = [0u8; 4];
+  file.seek(SeekFrom::Start(mfhd_start + 4))?;
+  file.read_exact(&mut sequence)?;
+  Ok(u32::from_be_bytes(sequence))
+}
+
+/// Find the first box of `box_type` between `start` and `end`, returning its payload range.
+fn find_box(
+  file: &mut std::fs::File,
+  start: u64,
+  end: u64,
+  box_type: &[u8; 4],
+) -> EngineResult<Option<(u64, u64)>> {
+  let mut pos = start;
+  while pos + 8 <= end {
+    let mut header = [0u8; 8];
+    file.seek(SeekFrom::Start(pos))?;
+    file.read_exact(&mut header)?;
+    let size32 = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as u64;
+    let (header_len, size) = match size32 {
+      0 => (8, end - pos),
+      1 => {
+        let mut large = [0u8; 8];
+        file.read_exact(&mut large)?;
+        (16, u64::from_be_bytes(large))
+      }
+      n => (8, n),
+    };
+    if size < header_len || pos + size > end {
+      return Ok(None);
+    }
+    if &header[4..8] == box_type {
+      return Ok(Some((pos + header_len, pos + size)));
+    }
+    pos += size;
+  }
+  Ok(None)
+}
@@ -4,8 +4,90 @@
 use c2pa::Ingredient;
 
 use crate::domain::error::{EngineError, EngineResult};
-use crate::domain::types::{AssetRef, C2paConfig, TrustPolicyConfig};
+use crate::domain::types::{AssetRef, C2paConfig, ClaimGeneratorInfo, TrustPolicyConfig};
+use super::super::system_trust::load_system_trust_anchors;
 use super::super::url_validation::validate_external_http_url;
+use crate::telemetry::trace_event;
+#[cfg(feature = "c2pa")]
+use super::super::asset_utils::{asset_to_temp_path, read_asset_bytes};
+#[cfg(feature = "c2pa")]
+use super::thumbnail::apply_auto_thumbnail;
+#[cfg(feature = "c2pa")]
+use super::ingredient::ingredient_from_json;
+
+/// The policy's `anchors`, with the system root certificates appended when
+/// `use_system_trust` is set, followed by the policy's `intermediates` that chain to
+/// them. `None` when neither supplies anchors.
+pub fn policy_trust_anchors(policy: &TrustPolicyConfig) -> EngineResult<Option<String>> {
+  let mut pem = match &policy.anchors {
+    Some(anchors) => Some(
+      std::str::from_utf8(anchors)
+        .map_err(|_| EngineError::Config("trust anchors must be valid UTF-8".into()))?
+        .to_owned(),
+    ),
+    None => None,
+  };
+  if policy.use_system_trust == Some(true) {
+    let system = load_system_trust_anchors()?;
+    let pem = pem.get_or_insert_with(String::new);
+    if !pem.is_empty() && !pem.ends_with('\n') {
+      pem.push('\n');
+    }
+    pem.push_str(&system);
+  }
+  if let (Some(pem), Some(intermediates)) = (pem.as_mut(), &policy.intermediates) {
+    let chained = chained_intermediates(intermediates, pem)?;
+    if !pem.is_empty() && !pem.ends_with('\n') {
+      pem.push('\n');
+    }
+    pem.push_str(&chained);
+  }
+  Ok(pem)
+}
+
+/// The certificates of `intermediates` that chain to `anchors`, as PEM.
+///
+/// c2pa builds chains only from the certificates in the manifest and treats every
+/// entry of its anchor list as a trust point, so a missing intermediate can only be
+/// supplied as an anchor. Checking the chain first keeps that from trusting an
+/// intermediate the anchors never issued.
+#[cfg(feature = "openssl")]
+fn chained_intermediates(intermediates: &[u8], anchors: &str) -> EngineResult<String> {
+  use openssl::stack::Stack;
+  use openssl::x509::store::X509StoreBuilder;
+  use openssl::x509::{X509StoreContext, X509};
+
+  let invalid = |e: openssl::error::ErrorStack| EngineError::Config(format!("invalid intermediates: {e}"));
+  let certs = X509::stack_from_pem(intermediates).map_err(invalid)?;
+  if certs.is_empty() {
+    return Err(EngineError::Config("intermediates must contain PEM certificates".into()));
+  }
+
+  let mut store = X509StoreBuilder::new().map_err(invalid)?;
+  for anchor in X509::stack_from_pem(anchors.as_bytes()).map_err(invalid)? {
+    store.add_cert(anchor).map_err(invalid)?;
+  }
+  let store = store.build();
+  let mut untrusted = Stack::new().map_err(invalid)?;
+  for cert in &certs {
+    untrusted.push(cert.clone()).map_err(invalid)?;
+  }
+
+  let mut pem = String::new();
+  for cert in &certs {
+    let mut ctx = X509StoreContext::new().map_err(invalid)?;
+    if ctx.init(&store, cert, &untrusted, |c| c.verify_cert()).map_err(invalid)? {
+      pem.push_str(&String::from_utf8_lossy(&cert.to_pem().map_err(invalid)?));
+    }
+  }
+  trace_event!(debug, supplied = certs.len(), chained = pem.matches("BEGIN CERTIFICATE").count(), "intermediates checked");
+  Ok(pem)
+}
+
+#[cfg(not(feature = "openssl"))]
+fn chained_intermediates(_intermediates: &[u8], _anchors: &str) -> EngineResult<String> {
+  Err(EngineError::Feature("openssl"))
+}
 
 pub fn build_trust_settings(
   policy: &TrustPolicyConfig,
@@ -13,10 +95,7 @@ pub fn build_trust_settings(
   let mut settings = Vec::new();
   let mut enable_trust = false;
 
-  if let Some(anchors) = &policy.anchors {
-    let pem = std::str::from_utf8(anchors)
-      .map_err(|_| EngineError::Config("trust anchors must be valid UTF-8".into()))?
-      .to_owned();
+  if let Some(pem) = policy_trust_anchors(policy)? {
     settings.push(serde_json::json!({
       "trust": { "trust_anchors": pem, "trust_anchors_path": null }
     }));
@@ -48,6 +127,15 @@ pub fn build_trust_settings(
     }));
   }
 
+  trace_event!(
+    debug,
+    anchors = policy.anchors.is_some(),
+    system_trust = policy.use_system_trust == Some(true),
+    allowed_list = policy.allowed_list.is_some(),
+    ekus = policy.allowed_ekus.as_ref().map_or(0, Vec::len),
+    enable_trust,
+    "trust policy applied",
+  );
   Ok((settings, enable_trust))
 }
 
@@ -69,6 +157,29 @@ pub fn ensure_claim_version_2(
     .map_err(|e| EngineError::Config(format!("Failed to serialize manifest: {}", e)))
 }
 
+/// Append an assertion to the manifest definition's `assertions` array.
+pub fn push_assertion(
+  manifest_json: String,
+  label: &str,
+  data: serde_json::Value,
+) -> EngineResult<String> {
+  let mut manifest: serde_json::Value = serde_json::from_str(&manifest_json)
+    .map_err(|e| EngineError::Config(format!("Invalid manifest JSON: {}", e)))?;
+
+  let obj = manifest
+    .as_object_mut()
+    .ok_or_else(|| EngineError::Config("manifest definition must be a JSON object".into()))?;
+  let assertions = obj
+    .entry("assertions")
+    .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+  let list = assertions
+    .as_array_mut()
+    .ok_or_else(|| EngineError::Config("manifest `assertions` must be an array".into()))?;
+  list.push(serde_json::json!({ "label": label, "data": data }));
+
+  Ok(serde_json::to_string(&manifest)?)
+}
+
 #[cfg(feature = "c2pa")]
 pub fn setup_builder(
   builder: &mut c2pa::Builder,
@@ -77,21 +188,29 @@ pub fn setup_builder(
   if let Some(parent) = &config.parent {
     let mut parent_ingredient = match parent {
       AssetRef::Path(p) => Ingredient::from_file(p)?,
-      AssetRef::Bytes { data } => {
-        let mut ing: Ingredient = serde_json::from_slice(data)?;
-        if let Some(base) = &config.parent_base_dir {
-          ing.resources_mut().set_base_path(base.clone());
-        }
-        ing
+      AssetRef::Bytes { data } => parent_from_json(data, config)?,
+      // A JSON stream is an `ingredient.json`, like parent bytes; any other stream is
+      // the parent asset itself and is staged like a source stream.
+      AssetRef::Stream { content_type: Some(ct), .. } if is_json_content_type(ct) => {
+        parent_from_json(&read_asset_bytes(parent, config.limits)?, config)?
       }
-      AssetRef::Stream { .. } => {
-        return Err(EngineError::Config(
-          "parent ingredients from streams are not currently supported".into(),
-        ));
+      AssetRef::Url(_) | AssetRef::Stream { .. } => {
+        let (path, _tmp_dir) = asset_to_temp_path(parent, config.limits, config.temp_dir.as_deref())?;
+        Ingredient::from_file(&path)?
       }
     };
     parent_ingredient.set_is_parent();
+
+    if let Some(redactions) = &config.redactions {
+      let uris = redaction_uris(redactions, parent_ingredient.active_manifest())?;
+      builder.definition.redactions = Some(uris);
+    }
+
     builder.add_ingredient(parent_ingredient);
+  } else if config.redactions.as_ref().is_some_and(|r| !r.is_empty()) {
+    return Err(EngineError::Config(
+      "redactions require a parent ingredient".into(),
+    ));
   }
 
   if let Some(ref remote_url) = config.remote_manifest_url {
@@ -103,9 +222,103 @@ pub fn setup_builder(
     builder.set_no_embed(true);
   }
 
+  if let Some(generator) = &config.claim_generator {
+    apply_claim_generator(builder, generator)?;
+  }
+
+  apply_auto_thumbnail(builder, config)?;
+
   Ok(())
 }
 
+/// Resource identifier for the claim generator icon.
+#[cfg(feature = "c2pa")]
+const CLAIM_GENERATOR_ICON_ID: &str = "claim_generator_icon";
+
+/// Replace the definition's claim generator info; an icon is added as a builder resource.
+#[cfg(feature = "c2pa")]
+fn apply_claim_generator(
+  builder: &mut c2pa::Builder,
+  generator: &ClaimGeneratorInfo,
+) -> EngineResult<()> {
+  if generator.name.trim().is_empty() {
+    return Err(EngineError::Config("claim generator name must not be empty".into()));
+  }
+  let mut info = serde_json::json!({ "name": generator.name });
+  if let Some(version) = &generator.version {
+    info["version"] = version.as_str().into();
+  }
+  if let Some(icon) = &generator.icon {
+    builder.add_resource(CLAIM_GENERATOR_ICON_ID, std::io::Cursor::new(icon.data.as_slice()))?;
+    // c2pa's `UriOrResource` is private, so the icon reference goes through serde.
+    info["icon"] = serde_json::to_value(c2pa::ResourceRef::new(&icon.format, CLAIM_GENERATOR_ICON_ID))?;
+  }
+  builder.set_claim_generator_info(serde_json::from_value::<c2pa::ClaimGeneratorInfo>(info)?);
+  Ok(())
+}
+
+/// An `ingredient.json` parent, resolving its resources against `parent_base_dir`.
+#[cfg(feature = "c2pa")]
+fn parent_from_json(data: &[u8], config: &C2paConfig) -> EngineResult<Ingredient> {
+  let mut ingredient = ingredient_from_json(data)?;
+  if let Some(base) = &config.parent_base_dir {
+    ingredient.resources_mut().set_base_path(base.clone());
+  }
+  Ok(ingredient)
+}
+
+#[cfg(feature = "c2pa")]
+fn is_json_content_type(content_type: &str) -> bool {
+  content_type
+    .split(';')
+    .next()
+    .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"))
+}
+
+/// Resolve redaction entries to JUMBF URIs in the parent's active manifest.
+/// Full `self#jumbf=` URIs are passed through; bare labels are scoped to the parent.
+#[cfg(feature = "c2pa")]
+fn redaction_uris(
+  redactions: &[String],
+  parent_manifest_label: Option<&str>,
+) -> EngineResult<Vec<String>> {
+  redactions
+    .iter()
+    .map(|entry| {
+      if entry.starts_with("self#jumbf=") {
+        return Ok(entry.clone());
+      }
+      let manifest_label = parent_manifest_label.ok_or_else(|| {
+        EngineError::Config(format!(
+          "cannot redact '{entry}': parent ingredient has no C2PA manifest"
+        ))
+      })?;
+      Ok(format!("self#jumbf=/c2pa/{manifest_label}/c2pa.assertions/{entry}"))
+    })
+    .collect()
+}
+
+/// Runs the synchronous `f` on tokio's blocking pool and awaits it, so async callers
+/// never stall a runtime worker. `f` applies c2pa's thread-local settings on the pool
+/// thread, and nothing thread-bound is held across the await, so the returned future
+/// is `Send`. Outside a runtime `f` simply runs on the calling thread.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn run_blocking<F, T>(f: F) -> EngineResult<T>
+where
+  F: FnOnce() -> EngineResult<T> + Send + 'static,
+  T: Send + 'static,
+{
+  let Ok(handle) = tokio::runtime::Handle::try_current() else {
+    return f();
+  };
+  match handle.spawn_blocking(f).await {
+    Ok(result) => result,
+    Err(e) if e.is_panic() => Err(EngineError::Panic("c2pa adapter panicked".into())),
+    Err(e) => Err(EngineError::Config(format!("blocking task failed: {}", e))),
+  }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
 pub fn run_on_current_thread<F, T>(fut: F) -> EngineResult<T>
 where
   F: std::future::Future<Output = EngineResult<T>>,
@@ -123,3 +336,22 @@ where
     .map_err(|e| EngineError::Config(format!("Failed to create tokio runtime: {}", e)))?;
   rt.block_on(fut)
 }
+
+#[cfg(target_arch = "wasm32")]
+pub fn run_on_current_thread<F, T>(fut: F) -> EngineResult<T>
+where
+  F: std::future::Future<Output = EngineResult<T>>,
+{
+  // There is no other thread to hand the runtime to, so blocking inside one would
+  // deadlock the event loop. Callers already in async code use the `_async` API.
+  if tokio::runtime::Handle::try_current().is_ok() {
+    return Err(EngineError::Config(
+      "blocking call inside an async runtime on WASM; use the async API".into(),
+    ));
+  }
+  let rt = tokio::runtime::Builder::new_current_thread()
+    .enable_all()
+    .build()
+    .map_err(|e| EngineError::Config(format!("Failed to create tokio runtime: {}", e)))?;
+  rt.block_on(fut)
+}
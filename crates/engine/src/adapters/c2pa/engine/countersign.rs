@@ -0,0 +1,111 @@
+// adapters/c2pa/engine/countersign.rs
+
+use std::path::Path;
+
+use c2pa::{Builder, Ingredient};
+
+use crate::crypto::signer::Signer;
+use crate::crypto::timestamper::Timestamper;
+use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::types::{default_limits, AssetRef, OutputTarget, SigAlg};
+use super::super::asset_utils::asset_to_temp_path;
+use super::super::content_detection::extension_to_mime_type;
+use super::super::settings::{prepare_manifest_json, with_c2pa_settings};
+use super::sign::{sign_settings, sign_with_builder};
+use super::tsa_signer::with_tsa_headers;
+
+/// Formats the SDK can write a second manifest into. PDF is read-only in c2pa-rs.
+pub const COUNTERSIGN_FORMATS: &[&str] = &[
+  "image/jpeg",
+  "image/png",
+  "image/gif",
+  "image/webp",
+  "image/tiff",
+  "image/x-adobe-dng",
+  "image/x-nikon-nef",
+  "image/x-sony-arw",
+  "image/heic",
+  "image/heif",
+  "image/avif",
+  "image/svg+xml",
+  "video/mp4",
+  "video/quicktime",
+  "audio/mp4",
+  "audio/mpeg",
+  "audio/wav",
+];
+
+/// Add a second signature to an already-signed asset.
+///
+/// The existing manifest store is kept: a new manifest is appended whose `parentOf`
+/// ingredient is the current active manifest, so verification reports both.
+pub fn countersign(
+  source: AssetRef,
+  signer: Signer,
+  alg: SigAlg,
+  timestamper: Option<Timestamper>,
+  output: OutputTarget,
+) -> EngineResult<Option<Vec<u8>>> {
+  let limits = default_limits();
+  let (src_path, _tmp_src_dir) = asset_to_temp_path(&source, limits, None)?;
+  let format = appendable_format(&src_path)?;
+  let parent = signed_parent(&src_path)?;
+  let manifest_json = prepare_manifest_json(
+    Some(appended_definition(&src_path, format).to_string()),
+    &timestamper,
+  )?;
+
+  let settings = sign_settings(false, None)?;
+  with_c2pa_settings(&settings, || {
+    let mut builder = Builder::from_json(&manifest_json)?;
+    builder.add_ingredient(parent);
+    let signer = with_tsa_headers(signer.resolve(alg.to_c2pa())?, timestamper.as_ref());
+    sign_with_builder(
+      &mut builder,
+      &*signer,
+      &AssetRef::Path(src_path.clone()),
+      &output,
+      limits,
+      None,
+      None,
+      None,
+    )
+  })
+}
+
+/// The MIME type of `path`, if a manifest can be appended to that format.
+pub(super) fn appendable_format(path: &Path) -> EngineResult<&'static str> {
+  let ext = path
+    .extension()
+    .and_then(|e| e.to_str())
+    .map(|e| e.to_ascii_lowercase())
+    .unwrap_or_default();
+  let format = extension_to_mime_type(&ext);
+  if !COUNTERSIGN_FORMATS.contains(&format) {
+    return Err(EngineError::UnsupportedFormat(format!(
+      "appending a manifest is not supported for format '{format}'"
+    )));
+  }
+  Ok(format)
+}
+
+/// The asset as a `parentOf` ingredient; errors if it carries no manifest.
+pub(super) fn signed_parent(path: &Path) -> EngineResult<Ingredient> {
+  let mut parent = Ingredient::from_file(path)?;
+  if parent.manifest_data().is_none() {
+    return Err(EngineError::Config(
+      "asset does not carry a C2PA manifest; sign it with sign_c2pa first".into(),
+    ));
+  }
+  parent.set_is_parent();
+  Ok(parent)
+}
+
+/// Minimal definition for a manifest appended to an existing store.
+pub(super) fn appended_definition(path: &Path, format: &str) -> serde_json::Value {
+  let title = path
+    .file_name()
+    .and_then(|n| n.to_str())
+    .unwrap_or("asset");
+  serde_json::json!({ "title": title, "format": format })
+}
@@ -0,0 +1,103 @@
+// adapters/c2pa/engine/deterministic.rs
+
+use serde_json::Value;
+
+use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::types::C2paConfig;
+
+/// Assertion labels whose `actions` get the fixed `when`.
+const ACTION_LABELS: [&str; 2] = ["c2pa.actions", "c2pa.actions.v2"];
+
+/// Drop the definition's time-stamp authority and stamp `fixed_time` on actions
+/// without a `when`. `config.timestamper` is cleared by the caller before the TSA URL
+/// is validated.
+pub fn apply_deterministic_manifest(
+  config: &C2paConfig,
+  manifest_json: String,
+) -> EngineResult<String> {
+  let Some(deterministic) = &config.deterministic else {
+    return Ok(manifest_json);
+  };
+
+  let mut manifest: Value = serde_json::from_str(&manifest_json)?;
+  let obj = manifest
+    .as_object_mut()
+    .ok_or_else(|| EngineError::Config("manifest definition must be a JSON object".into()))?;
+  obj.remove("ta_url");
+
+  if let Some(time) = &deterministic.fixed_time {
+    chrono::DateTime::parse_from_rfc3339(time)
+      .map_err(|e| EngineError::Config(format!("fixed_time must be an RFC 3339 time: {e}")))?;
+    let assertions = obj.get_mut("assertions").and_then(Value::as_array_mut);
+    for assertion in assertions.into_iter().flatten() {
+      let is_actions = assertion
+        .get("label")
+        .and_then(Value::as_str)
+        .is_some_and(|label| ACTION_LABELS.contains(&label));
+      if !is_actions {
+        continue;
+      }
+      let actions = assertion.pointer_mut("/data/actions").and_then(Value::as_array_mut);
+      for action in actions.into_iter().flatten().filter_map(Value::as_object_mut) {
+        action.entry("when").or_insert_with(|| time.as_str().into());
+      }
+    }
+  }
+
+  Ok(serde_json::to_string(&manifest)?)
+}
+
+/// Replace the random manifest label and ingredient instance IDs with UUIDs derived
+/// from the manifest definition and the parent's manifest label, so chained signings
+/// stay distinct. A label or instance ID given in the definition is kept.
+pub fn apply_deterministic_ids(
+  builder: &mut c2pa::Builder,
+  config: &C2paConfig,
+  manifest_json: &str,
+) -> EngineResult<()> {
+  if !config.deterministic.as_ref().is_some_and(|d| d.fixed_instance_ids) {
+    return Ok(());
+  }
+  let definition: Value = serde_json::from_str(manifest_json)?;
+  let parent_label = builder
+    .definition
+    .ingredients
+    .iter()
+    .find(|ingredient| ingredient.is_parent())
+    .and_then(|ingredient| ingredient.active_manifest())
+    .unwrap_or_default();
+  let seed = format!("{manifest_json}\0{parent_label}");
+
+  for (i, ingredient) in builder.definition.ingredients.iter_mut().enumerate() {
+    let given = definition
+      .pointer(&format!("/ingredients/{i}/instance_id"))
+      .is_some();
+    if !given {
+      ingredient.set_instance_id(format!("xmp:iid:{}", derived_uuid(&seed, &format!("ingredient {i}"))?));
+    }
+  }
+
+  if builder.definition.label.is_none() {
+    let uuid = derived_uuid(&seed, "manifest")?;
+    let vendor = builder.definition.vendor.as_deref();
+    let label = match (builder.claim_version(), vendor) {
+      (1, Some(vendor)) => format!("{vendor}:urn:uuid:{uuid}"),
+      (1, None) => format!("urn:uuid:{uuid}"),
+      (_, Some(vendor)) => format!("urn:c2pa:{uuid}:{vendor}"),
+      (_, None) => format!("urn:c2pa:{uuid}"),
+    };
+    builder.definition.label = Some(label);
+  }
+  Ok(())
+}
+
+/// A UUID shaped as version 4, which c2pa requires of manifest labels, taken from the
+/// SHA-256 of `purpose` and `seed`.
+fn derived_uuid(seed: &str, purpose: &str) -> EngineResult<String> {
+  let input = format!("{purpose}\0{seed}");
+  let mut b = c2pa::hash_stream_by_alg("sha256", &mut std::io::Cursor::new(input), None, true)?;
+  b[6] = (b[6] & 0x0f) | 0x40;
+  b[8] = (b[8] & 0x3f) | 0x80;
+  let hex: String = b[..16].iter().map(|byte| format!("{byte:02x}")).collect();
+  Ok(format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..]))
+}
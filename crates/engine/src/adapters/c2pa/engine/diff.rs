@@ -0,0 +1,35 @@
+// adapters/c2pa/engine/diff.rs
+
+use c2pa::Reader;
+
+use crate::domain::diff::ManifestDiff;
+use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::types::{AssetRef, C2paVerificationConfig};
+use super::super::settings::with_c2pa_settings;
+use super::verify::{build_verify_settings, open_reader};
+
+/// Compare the active manifests of `a` and `b`.
+///
+/// Both are read with the default verification settings and compared as the
+/// reader's parsed manifest JSON.
+pub fn diff_manifests(a: AssetRef, b: AssetRef) -> EngineResult<ManifestDiff> {
+  let before = active_manifest_json(a)?;
+  let after = active_manifest_json(b)?;
+  Ok(ManifestDiff::between(&before, &after))
+}
+
+fn active_manifest_json(source: AssetRef) -> EngineResult<serde_json::Value> {
+  let config = C2paVerificationConfig::secure_default(source);
+  let settings = build_verify_settings(&config)?;
+  let (reader, _): (Reader, _) = with_c2pa_settings(&settings, || open_reader(&config))?;
+
+  let label = reader
+    .active_label()
+    .ok_or_else(|| EngineError::Config("asset has no active manifest".into()))?;
+  let mut store: serde_json::Value = serde_json::from_str(&reader.json())?;
+  store
+    .get_mut("manifests")
+    .and_then(|m| m.get_mut(label))
+    .map(serde_json::Value::take)
+    .ok_or_else(|| EngineError::Config("active manifest missing from manifest store".into()))
+}
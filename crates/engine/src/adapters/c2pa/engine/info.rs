@@ -0,0 +1,29 @@
+// adapters/c2pa/engine/info.rs
+
+use crate::domain::info::{compiled_features, EngineInfo};
+
+pub fn engine_info() -> EngineInfo {
+  #[cfg(feature = "c2pa")]
+  let (c2pa_version, supported_read_formats, supported_write_formats) = (
+    Some(c2pa::VERSION.to_string()),
+    sorted(c2pa::Reader::supported_mime_types()),
+    sorted(c2pa::Builder::supported_mime_types()),
+  );
+  #[cfg(not(feature = "c2pa"))]
+  let (c2pa_version, supported_read_formats, supported_write_formats) = (None, Vec::new(), Vec::new());
+
+  EngineInfo {
+    engine_version: env!("CARGO_PKG_VERSION").to_string(),
+    c2pa_version,
+    supported_read_formats,
+    supported_write_formats,
+    features: compiled_features(),
+  }
+}
+
+#[cfg(feature = "c2pa")]
+fn sorted(mut formats: Vec<String>) -> Vec<String> {
+  formats.sort();
+  formats.dedup();
+  formats
+}
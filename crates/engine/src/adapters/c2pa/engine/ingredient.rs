@@ -3,49 +3,172 @@
 #[cfg(feature = "c2pa")]
 use c2pa::Ingredient;
 
-use crate::domain::error::EngineResult;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use serde_json::{json, Map, Value};
+
+use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::manifest_builder::IngredientRelationship;
 use crate::domain::types::{AssetRef, IngredientConfig, OutputTarget};
+use crate::domain::verify::IngredientReport;
 use super::super::asset_utils::asset_to_temp_path;
+use super::verify::collect_validation_statuses;
+
+/// Key under which parent-ready ingredients carry their resources (manifest store,
+/// thumbnail), base64-encoded by resource identifier. c2pa does not serialize them.
+const EMBEDDED_RESOURCES_KEY: &str = "que.resources";
 
 pub fn create_ingredient(
   config: IngredientConfig,
 ) -> EngineResult<Option<Vec<u8>>> {
-  match &config.source {
-    AssetRef::Stream { reader, content_type } => {
+  let to_memory = matches!(config.output, OutputTarget::Memory);
+  let report = create_ingredient_report(config)?;
+  Ok(to_memory.then_some(report.json))
+}
+
+/// Like `create_ingredient`, but also returns the validation state computed while
+/// reading the ingredient. `json` is populated for both output targets.
+pub fn create_ingredient_report(
+  config: IngredientConfig,
+) -> EngineResult<IngredientReport> {
+  if matches!(&config.metadata, Some(m) if !m.is_object()) {
+    return Err(EngineError::Config("ingredient metadata must be a JSON object".into()));
+  }
+  if config.parent_ready
+    && config.relationship.is_some_and(|r| r != IngredientRelationship::ParentOf)
+  {
+    return Err(EngineError::Config(
+      "parent-ready ingredients must use the parentOf relationship".into(),
+    ));
+  }
+
+  let ingredient = read_ingredient(&config)?;
+  if config.parent_ready && ingredient.active_manifest().is_none() {
+    return Err(EngineError::Config(
+      "parent-ready ingredient requires an asset with an active C2PA manifest".into(),
+    ));
+  }
+  let validation_status = ingredient
+    .validation_results()
+    .map(|results| collect_validation_statuses(results, ingredient.active_manifest(), &[]))
+    .unwrap_or_default();
+  let is_signed = ingredient.manifest_data().is_some();
+
+  let json = apply_ingredient_overrides(&ingredient, &config)?.into_bytes();
+  if let OutputTarget::Path(dir) = &config.output {
+    std::fs::write(dir.join("ingredient.json"), &json)?;
+  }
+
+  Ok(IngredientReport { json, validation_status, is_signed })
+}
+
+fn read_ingredient(config: &IngredientConfig) -> EngineResult<Ingredient> {
+  match (&config.source, &config.output) {
+    (AssetRef::Stream { reader, content_type }, OutputTarget::Memory) => {
       let format = content_type
         .as_deref()
         .unwrap_or("application/octet-stream");
       let mut stream = reader.borrow_mut();
+      Ok(Ingredient::from_stream(format, &mut *stream)?)
+    }
+    (_, OutputTarget::Path(dir)) => {
+      std::fs::create_dir_all(dir)?;
+      // There is no from_stream_with_folder; streams go through a temp file.
+      let (source_path, _temp_dir) = asset_to_temp_path(&config.source, config.limits, config.temp_dir.as_deref())?;
+      Ok(Ingredient::from_file_with_folder(&source_path, dir)?)
+    }
+    (_, OutputTarget::Memory) => {
+      let (source_path, _temp_dir) = asset_to_temp_path(&config.source, config.limits, config.temp_dir.as_deref())?;
+      Ok(Ingredient::from_file(&source_path)?)
+    }
+  }
+}
 
-      match config.output {
-        OutputTarget::Path(dir) => {
-          std::fs::create_dir_all(&dir)?;
-          // There is no from_stream_with_folder; use a temp file.
-          let (temp_path, _temp_dir) = asset_to_temp_path(&config.source, config.limits)?;
-          let report = Ingredient::from_file_with_folder(&temp_path, &dir)?;
-          std::fs::write(dir.join("ingredient.json"), report.to_string())?;
-          Ok(None)
-        }
-        OutputTarget::Memory => {
-          let ingredient = Ingredient::from_stream(format, &mut *stream)?;
-          Ok(Some(ingredient.to_string().into_bytes()))
-        }
-      }
+/// Apply the caller's overrides to the serialized ingredient.
+///
+/// Ingredients fetched from a URL are titled after the URL's file name (rather than
+/// the temp file they were downloaded to) and keep the URL as their data source.
+/// An explicit `title`, `relationship` or `metadata` in the config takes precedence;
+/// `metadata` keys are merged over any recorded data source. Parent-ready ingredients
+/// are marked `parentOf` and carry their resources inline.
+fn apply_ingredient_overrides(ingredient: &Ingredient, config: &IngredientConfig) -> EngineResult<String> {
+  let report = ingredient.to_string();
+  let source_url = match &config.source {
+    AssetRef::Url(url) => Some(url.as_str()),
+    _ => None,
+  };
+  let relationship = match config.parent_ready {
+    true => Some(IngredientRelationship::ParentOf),
+    false => config.relationship,
+  };
+  if source_url.is_none()
+    && config.title.is_none()
+    && relationship.is_none()
+    && config.metadata.is_none()
+  {
+    return Ok(report);
+  }
+
+  let mut json: Value = serde_json::from_str(&report)?;
+  let Some(obj) = json.as_object_mut() else {
+    return Ok(report);
+  };
+
+  if let Some(url) = source_url {
+    let title = url::Url::parse(url)
+      .ok()
+      .and_then(|u| u.path_segments()?.next_back().filter(|s| !s.is_empty()).map(str::to_string));
+    if let Some(title) = title {
+      obj.insert("title".into(), Value::String(title));
     }
-    _ => {
-      let (source_path, _temp_dir) = asset_to_temp_path(&config.source, config.limits)?;
-      match config.output {
-        OutputTarget::Path(dir) => {
-          std::fs::create_dir_all(&dir)?;
-          let report = Ingredient::from_file_with_folder(&source_path, &dir)?;
-          std::fs::write(dir.join("ingredient.json"), report.to_string())?;
-          Ok(None)
-        }
-        OutputTarget::Memory => {
-          let report = Ingredient::from_file(&source_path)?.to_string();
-          Ok(Some(report.into_bytes()))
-        }
+    obj.insert(
+      "metadata".into(),
+      json!({ "dataSource": { "type": "remoteProvider.3rdParty", "details": url } }),
+    );
+  }
+  if let Some(title) = &config.title {
+    obj.insert("title".into(), Value::String(title.clone()));
+  }
+  if let Some(relationship) = relationship {
+    obj.insert("relationship".into(), Value::String(relationship.as_str().into()));
+  }
+  if let Some(Value::Object(extra)) = &config.metadata {
+    let metadata = obj.entry("metadata").or_insert_with(|| json!({}));
+    if let Some(metadata) = metadata.as_object_mut() {
+      for (key, value) in extra {
+        metadata.insert(key.clone(), value.clone());
       }
     }
   }
-}
\ No newline at end of file
+  if config.parent_ready {
+    let resources: Map<String, Value> = ingredient
+      .resources()
+      .resources()
+      .iter()
+      .map(|(id, data)| (id.clone(), Value::String(BASE64.encode(data))))
+      .collect();
+    obj.insert(EMBEDDED_RESOURCES_KEY.into(), Value::Object(resources));
+  }
+  Ok(serde_json::to_string(&json)?)
+}
+
+/// Deserialize an `ingredient.json` given as `C2paConfig.parent` bytes, restoring the
+/// resources a parent-ready ingredient carries inline.
+pub fn ingredient_from_json(data: &[u8]) -> EngineResult<Ingredient> {
+  let mut json: Value = serde_json::from_slice(data)?;
+  let embedded = json.as_object_mut().and_then(|obj| obj.remove(EMBEDDED_RESOURCES_KEY));
+  let mut ingredient: Ingredient = serde_json::from_value(json)?;
+  if let Some(embedded) = embedded {
+    let Value::Object(embedded) = embedded else {
+      return Err(EngineError::Config(format!("ingredient `{EMBEDDED_RESOURCES_KEY}` must be an object")));
+    };
+    for (id, data) in embedded {
+      let data = data
+        .as_str()
+        .and_then(|d| BASE64.decode(d).ok())
+        .ok_or_else(|| EngineError::Config(format!("ingredient resource `{id}` is not valid base64")))?;
+      ingredient.resources_mut().add(id, data)?;
+    }
+  }
+  Ok(ingredient)
+}
@@ -0,0 +1,83 @@
+// adapters/c2pa/engine/manifest_store.rs
+
+#[cfg(feature = "c2pa")]
+use c2pa::{Ingredient, Reader};
+
+use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::types::{AssetRef, LimitsConfig};
+use super::super::asset_utils::{
+  asset_to_temp_path, check_in_memory_size, check_manifest_size, sniff_content_type_from_bytes,
+  sniff_content_type_from_reader,
+};
+use super::super::settings::with_c2pa_settings;
+
+/// Return the raw JUMBF manifest store embedded in `source`.
+///
+/// The store is read through an `Ingredient`, which keeps the original manifest
+/// bytes rather than a re-serialized copy, so two extractions can be compared
+/// byte-for-byte.
+pub fn extract_manifest_store(source: AssetRef, limits: LimitsConfig) -> EngineResult<Vec<u8>> {
+  let ingredient = match &source {
+    AssetRef::Stream { reader, content_type } => {
+      let mut stream = reader.borrow_mut();
+      let sniffed = sniff_content_type_from_reader(&mut *stream);
+      let format = content_type
+        .as_deref()
+        .or(sniffed)
+        .unwrap_or("application/octet-stream");
+      Ingredient::from_stream(format, &mut *stream)?
+    }
+    _ => {
+      let (src_path, _tmp_dir) = asset_to_temp_path(&source, limits, None)?;
+      Ingredient::from_file(&src_path)?
+    }
+  };
+
+  let data = ingredient
+    .manifest_data()
+    .ok_or_else(|| EngineError::Config("asset has no embedded C2PA manifest store".into()))?;
+  check_manifest_size(data.len() as u64, limits)?;
+  Ok(data.into_owned())
+}
+
+/// Return the manifest store of `source` as the SDK's JSON, or `None` when the asset
+/// has no manifest.
+///
+/// Display-only fast path: validation after reading, trust checks and remote manifest
+/// fetches are switched off, so the JSON carries no validation results.
+pub fn read_manifest_json(source: AssetRef, limits: LimitsConfig) -> EngineResult<Option<String>> {
+  let settings = [serde_json::json!({
+    "verify": {
+      "verify_after_reading": false,
+      "verify_trust": false,
+      "remote_manifest_fetch": false,
+    }
+  })];
+  with_c2pa_settings(&settings, || {
+    let read = match &source {
+      AssetRef::Stream { reader, content_type } => {
+        let mut stream = reader.borrow_mut();
+        let sniffed = sniff_content_type_from_reader(&mut *stream);
+        let format = content_type
+          .as_deref()
+          .or(sniffed)
+          .unwrap_or("application/octet-stream");
+        Reader::from_stream(format, &mut *stream)
+      }
+      AssetRef::Bytes { data } if sniff_content_type_from_bytes(data).is_some() => {
+        check_in_memory_size(data, limits)?;
+        let format = sniff_content_type_from_bytes(data).unwrap_or_default();
+        Reader::from_stream(format, std::io::Cursor::new(data.as_slice()))
+      }
+      _ => {
+        let (src_path, _tmp_dir) = asset_to_temp_path(&source, limits, None)?;
+        Reader::from_file(&src_path)
+      }
+    };
+    match read {
+      Ok(reader) => Ok(Some(reader.json())),
+      Err(c2pa::Error::JumbfNotFound | c2pa::Error::ProvenanceMissing) => Ok(None),
+      Err(e) => Err(e.into()),
+    }
+  })
+}
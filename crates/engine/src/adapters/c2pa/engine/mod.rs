@@ -2,17 +2,47 @@
 
 use crate::domain::manifest_engine::ManifestEngine;
 use crate::domain::types::{
-  C2paConfig, C2paVerificationConfig, IngredientConfig,
+  AssetRef, C2paConfig, C2paVerificationConfig, DetachedManifest, IngredientConfig, LimitsConfig,
+  OutputTarget, RemoveManifestConfig, SignOutcome, TrustPolicyConfig,
 };
-use crate::domain::verify::VerificationResult;
+use crate::domain::verify::{IngredientReport, VerificationResult, Verdict};
 use crate::domain::error::EngineResult;
+use crate::telemetry::{metrics_finish, metrics_start};
 
 mod common;
 mod sign;
 mod verify;
 mod ingredient;
+mod remove;
+mod soft_binding;
+#[cfg(feature = "c2pa")]
+mod reserve;
+#[cfg(feature = "c2pa")]
+mod tsa_signer;
+#[cfg(feature = "c2pa")]
+mod thumbnail;
+#[cfg(feature = "c2pa")]
+mod deterministic;
+#[cfg(feature = "c2pa")]
+mod manifest_store;
+#[cfg(feature = "c2pa")]
+mod sidecar;
+#[cfg(feature = "c2pa")]
+mod batch;
+#[cfg(feature = "c2pa")]
+mod countersign;
+#[cfg(feature = "c2pa")]
+mod timestamp;
+#[cfg(feature = "c2pa")]
+mod diff;
 #[cfg(feature = "bmff")]
 mod bmff;
+mod info;
+
+#[cfg(feature = "c2pa")]
+pub use batch::BatchSigner;
+#[cfg(feature = "c2pa")]
+pub use countersign::COUNTERSIGN_FORMATS;
 
 pub struct C2pa;
 
@@ -22,15 +52,73 @@ impl ManifestEngine for C2pa {
   type Artifact = Option<Vec<u8>>;
 
   fn generate(cfg: Self::Config) -> EngineResult<Self::Artifact> {
-    sign::sign_c2pa(cfg)
+    let measurement = metrics_start!(Sign, &cfg.source, Some(&cfg.output));
+    let signed = sign::sign_c2pa(cfg);
+    metrics_finish!(measurement, &signed);
+    signed
   }
 
   fn verify(cfg: Self::VerificationConfig) -> EngineResult<VerificationResult> {
-    verify::verify_c2pa(cfg)
+    let measurement = metrics_start!(Verify, &cfg.source, None);
+    let verified = verify::verify_c2pa(cfg);
+    metrics_finish!(measurement, &verified);
+    verified
   }
 }
 
 impl C2pa {
+  pub async fn generate_async(cfg: C2paConfig) -> EngineResult<Option<Vec<u8>>> {
+    let measurement = metrics_start!(Sign, &cfg.source, Some(&cfg.output));
+    let signed = sign::sign_c2pa_async(cfg).await;
+    metrics_finish!(measurement, &signed);
+    signed
+  }
+
+  pub fn generate_with_report(cfg: C2paConfig) -> EngineResult<SignOutcome> {
+    let measurement = metrics_start!(Sign, &cfg.source, Some(&cfg.output));
+    let signed = sign::sign_c2pa_with_report(cfg);
+    metrics_finish!(measurement, &signed);
+    signed
+  }
+
+  pub async fn generate_with_report_async(cfg: C2paConfig) -> EngineResult<SignOutcome> {
+    let measurement = metrics_start!(Sign, &cfg.source, Some(&cfg.output));
+    let signed = sign::sign_c2pa_with_report_async(cfg).await;
+    metrics_finish!(measurement, &signed);
+    signed
+  }
+
+  pub fn generate_detached(cfg: C2paConfig) -> EngineResult<DetachedManifest> {
+    let measurement = metrics_start!(Sign, &cfg.source, None);
+    let signed = sign::sign_c2pa_detached(cfg);
+    metrics_finish!(measurement, &signed);
+    signed
+  }
+
+  pub fn preview_manifest(cfg: &C2paConfig) -> EngineResult<String> {
+    sign::preview_manifest(cfg)
+  }
+
+  pub async fn verify_async(
+    cfg: C2paVerificationConfig,
+  ) -> EngineResult<VerificationResult> {
+    let measurement = metrics_start!(Verify, &cfg.source, None);
+    let verified = verify::verify_c2pa_async(cfg).await;
+    metrics_finish!(measurement, &verified);
+    verified
+  }
+
+  pub fn verify_verdict(source: AssetRef, policy: Option<TrustPolicyConfig>) -> EngineResult<Verdict> {
+    let measurement = metrics_start!(Verify, &source, None);
+    let verdict = verify::verify_verdict(source, policy);
+    metrics_finish!(measurement, &verdict);
+    verdict
+  }
+
+  pub fn verify_batch(cfgs: Vec<C2paVerificationConfig>) -> Vec<EngineResult<VerificationResult>> {
+    verify::verify_c2pa_batch(cfgs)
+  }
+
   #[cfg(all(feature = "c2pa", feature = "bmff"))]
   pub fn generate_fragmented_bmff(
     cfg: crate::domain::types::FragmentedBmffConfig,
@@ -38,10 +126,91 @@ impl C2pa {
     bmff::generate_fragmented_bmff(cfg)
   }
 
+  #[cfg(all(feature = "c2pa", feature = "bmff"))]
+  pub fn verify_fragmented_bmff(
+    cfg: crate::domain::types::FragmentedBmffVerifyConfig,
+  ) -> EngineResult<Vec<crate::domain::verify::FragmentedBmffVerification>> {
+    bmff::verify_fragmented_bmff(cfg)
+  }
+
   #[cfg(feature = "c2pa")]
   pub fn create_ingredient(
     cfg: IngredientConfig,
   ) -> EngineResult<Option<Vec<u8>>> {
     ingredient::create_ingredient(cfg)
   }
-}
\ No newline at end of file
+
+  #[cfg(feature = "c2pa")]
+  pub fn create_ingredient_report(
+    cfg: IngredientConfig,
+  ) -> EngineResult<IngredientReport> {
+    ingredient::create_ingredient_report(cfg)
+  }
+
+  pub fn remove_manifest(
+    cfg: RemoveManifestConfig,
+  ) -> EngineResult<Option<Vec<u8>>> {
+    remove::remove_manifest(cfg)
+  }
+
+  #[cfg(feature = "c2pa")]
+  pub fn countersign(
+    source: AssetRef,
+    signer: crate::crypto::signer::Signer,
+    alg: crate::domain::types::SigAlg,
+    timestamper: Option<crate::crypto::timestamper::Timestamper>,
+    output: OutputTarget,
+  ) -> EngineResult<Option<Vec<u8>>> {
+    countersign::countersign(source, signer, alg, timestamper, output)
+  }
+
+  #[cfg(feature = "c2pa")]
+  pub fn add_timestamp(
+    source: AssetRef,
+    signer: crate::crypto::signer::Signer,
+    alg: crate::domain::types::SigAlg,
+    timestamper: crate::crypto::timestamper::Timestamper,
+    force: bool,
+    output: OutputTarget,
+  ) -> EngineResult<Option<Vec<u8>>> {
+    timestamp::add_timestamp(source, signer, alg, timestamper, force, output)
+  }
+
+  #[cfg(feature = "c2pa")]
+  pub fn diff_manifests(
+    a: AssetRef,
+    b: AssetRef,
+  ) -> EngineResult<crate::domain::diff::ManifestDiff> {
+    diff::diff_manifests(a, b)
+  }
+
+  #[cfg(feature = "c2pa")]
+  pub fn extract_manifest_store(
+    source: AssetRef,
+    limits: LimitsConfig,
+  ) -> EngineResult<Vec<u8>> {
+    manifest_store::extract_manifest_store(source, limits)
+  }
+
+  #[cfg(feature = "c2pa")]
+  pub fn read_manifest_json(
+    source: AssetRef,
+    limits: LimitsConfig,
+  ) -> EngineResult<Option<String>> {
+    manifest_store::read_manifest_json(source, limits)
+  }
+
+  pub fn engine_info() -> crate::domain::info::EngineInfo {
+    info::engine_info()
+  }
+
+  #[cfg(feature = "c2pa")]
+  pub fn apply_sidecar_manifest(
+    asset: AssetRef,
+    manifest_bytes: Vec<u8>,
+    output: OutputTarget,
+    embed: bool,
+  ) -> EngineResult<Option<Vec<u8>>> {
+    sidecar::apply_sidecar_manifest(asset, manifest_bytes, output, embed)
+  }
+}
@@ -0,0 +1,48 @@
+// adapters/c2pa/engine/remove.rs
+
+use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::types::{AssetRef, OutputTarget, RemoveManifestConfig};
+use super::super::asset_utils::{check_output_size, read_asset_bytes, write_bytes_to_temp_in};
+
+pub fn remove_manifest(cfg: RemoveManifestConfig) -> EngineResult<Option<Vec<u8>>> {
+  #[cfg(not(feature = "c2pa"))]
+  {
+    return Err(EngineError::Feature("c2pa"));
+  }
+  #[cfg(feature = "c2pa")]
+  {
+    let data = read_asset_bytes(&cfg.source, cfg.limits)?;
+    let content_type = match &cfg.source {
+      AssetRef::Stream { content_type, .. } => content_type.as_deref(),
+      _ => None,
+    };
+
+    // c2pa strips the store in place, picking the format handler by file extension.
+    let (path, _staging) = write_bytes_to_temp_in(&data, content_type, cfg.temp_dir.as_deref())?;
+    c2pa::jumbf_io::remove_jumbf_from_file(&path).map_err(|e| match e {
+      // PDF and a few other handlers read stores but cannot write them.
+      c2pa::Error::UnsupportedType | c2pa::Error::NotImplemented(_) => unsupported(&path),
+      e => EngineError::C2pa(e),
+    })?;
+    let stripped = std::fs::read(&path)?;
+
+    match cfg.output {
+      OutputTarget::Path(dest) => {
+        std::fs::write(dest, &stripped)?;
+        Ok(None)
+      }
+      OutputTarget::Memory => {
+        check_output_size(stripped.len() as u64, cfg.limits)?;
+        Ok(Some(stripped))
+      }
+    }
+  }
+}
+
+#[cfg(feature = "c2pa")]
+fn unsupported(path: &std::path::Path) -> EngineError {
+  match path.extension().and_then(|ext| ext.to_str()) {
+    Some(ext) => EngineError::UnsupportedFormat(format!("manifest removal is not supported for {ext} assets")),
+    None => EngineError::UnsupportedFormat("manifest removal is not supported for unrecognized asset formats".into()),
+  }
+}
@@ -0,0 +1,144 @@
+// adapters/c2pa/engine/reserve.rs
+
+//! Signer wrappers that replace the signer's own reserve-size estimate with
+//! `C2paConfig::reserve_size`. Everything else is forwarded unchanged.
+
+use c2pa::crypto::raw_signature::RawSigner;
+use c2pa::dynamic_assertion::DynamicAssertion;
+use c2pa::{Signer, SigningAlg};
+#[cfg(feature = "cawg")]
+use c2pa::crypto::raw_signature::AsyncRawSigner;
+#[cfg(feature = "cawg")]
+use c2pa::dynamic_assertion::AsyncDynamicAssertion;
+#[cfg(feature = "cawg")]
+use c2pa::AsyncSigner;
+
+/// Wraps `signer` so c2pa reserves `reserve_size` bytes for its signature box.
+/// `None` returns the signer untouched.
+pub fn with_reserve_size(signer: Box<dyn Signer>, reserve_size: Option<usize>) -> Box<dyn Signer> {
+  match reserve_size {
+    Some(reserve_size) => Box::new(ReservedSigner { inner: signer, reserve_size }),
+    None => signer,
+  }
+}
+
+/// Async counterpart of `with_reserve_size`, used on the CAWG path.
+#[cfg(feature = "cawg")]
+pub fn with_reserve_size_async(
+  signer: Box<dyn AsyncSigner>,
+  reserve_size: Option<usize>,
+) -> Box<dyn AsyncSigner> {
+  match reserve_size {
+    Some(reserve_size) => Box::new(ReservedSigner { inner: signer, reserve_size }),
+    None => signer,
+  }
+}
+
+struct ReservedSigner<S> {
+  inner: S,
+  reserve_size: usize,
+}
+
+impl Signer for ReservedSigner<Box<dyn Signer>> {
+  // `Box<dyn Signer>` is also a `RawSigner`; name the trait to pick its methods.
+  fn sign(&self, data: &[u8]) -> c2pa::Result<Vec<u8>> {
+    Signer::sign(&*self.inner, data)
+  }
+
+  fn alg(&self) -> SigningAlg {
+    Signer::alg(&*self.inner)
+  }
+
+  fn certs(&self) -> c2pa::Result<Vec<Vec<u8>>> {
+    self.inner.certs()
+  }
+
+  fn reserve_size(&self) -> usize {
+    self.reserve_size
+  }
+
+  fn time_authority_url(&self) -> Option<String> {
+    self.inner.time_authority_url()
+  }
+
+  fn timestamp_request_headers(&self) -> Option<Vec<(String, String)>> {
+    self.inner.timestamp_request_headers()
+  }
+
+  fn timestamp_request_body(&self, message: &[u8]) -> c2pa::Result<Vec<u8>> {
+    self.inner.timestamp_request_body(message)
+  }
+
+  fn send_timestamp_request(&self, message: &[u8]) -> Option<c2pa::Result<Vec<u8>>> {
+    self.inner.send_timestamp_request(message)
+  }
+
+  fn ocsp_val(&self) -> Option<Vec<u8>> {
+    self.inner.ocsp_val()
+  }
+
+  fn direct_cose_handling(&self) -> bool {
+    self.inner.direct_cose_handling()
+  }
+
+  fn dynamic_assertions(&self) -> Vec<Box<dyn DynamicAssertion>> {
+    self.inner.dynamic_assertions()
+  }
+
+  fn raw_signer(&self) -> Option<Box<&dyn RawSigner>> {
+    self.inner.raw_signer()
+  }
+}
+
+#[cfg(feature = "cawg")]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl AsyncSigner for ReservedSigner<Box<dyn AsyncSigner>> {
+  async fn sign(&self, data: Vec<u8>) -> c2pa::Result<Vec<u8>> {
+    self.inner.sign(data).await
+  }
+
+  fn alg(&self) -> SigningAlg {
+    self.inner.alg()
+  }
+
+  fn certs(&self) -> c2pa::Result<Vec<Vec<u8>>> {
+    self.inner.certs()
+  }
+
+  fn reserve_size(&self) -> usize {
+    self.reserve_size
+  }
+
+  fn time_authority_url(&self) -> Option<String> {
+    self.inner.time_authority_url()
+  }
+
+  fn timestamp_request_headers(&self) -> Option<Vec<(String, String)>> {
+    self.inner.timestamp_request_headers()
+  }
+
+  fn timestamp_request_body(&self, message: &[u8]) -> c2pa::Result<Vec<u8>> {
+    self.inner.timestamp_request_body(message)
+  }
+
+  async fn send_timestamp_request(&self, message: &[u8]) -> Option<c2pa::Result<Vec<u8>>> {
+    self.inner.send_timestamp_request(message).await
+  }
+
+  async fn ocsp_val(&self) -> Option<Vec<u8>> {
+    self.inner.ocsp_val().await
+  }
+
+  fn direct_cose_handling(&self) -> bool {
+    self.inner.direct_cose_handling()
+  }
+
+  fn dynamic_assertions(&self) -> Vec<Box<dyn AsyncDynamicAssertion>> {
+    self.inner.dynamic_assertions()
+  }
+
+  fn async_raw_signer(&self) -> Option<Box<&dyn AsyncRawSigner>> {
+    self.inner.async_raw_signer()
+  }
+}
@@ -0,0 +1,124 @@
+// adapters/c2pa/engine/sidecar.rs
+
+#[cfg(feature = "c2pa")]
+use c2pa::Reader;
+
+use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::types::{default_limits, AssetRef, OutputTarget};
+use crate::domain::validation_code::ValidationCode;
+use super::super::asset_utils::{check_manifest_size, check_output_size, read_asset_bytes};
+use super::super::content_detection::{detect_extension_from_bytes, format_for_extension};
+use super::super::jumbf::is_c2pa_store;
+use super::super::settings::with_c2pa_settings;
+use super::verify::collect_validation_statuses;
+
+/// Attach a pre-built manifest store to an asset without re-signing.
+///
+/// With `embed`, the store is written into the asset, replacing any existing store, in
+/// any format c2pa can write. Otherwise the asset is copied to the `Path` output
+/// unchanged and the store is written next to it with a `.c2pa` extension.
+///
+/// An embedded store must still hash-bind to the asset it now sits in: the output is
+/// rejected unless its data hash is reported as matching.
+pub fn apply_sidecar_manifest(
+  asset: AssetRef,
+  manifest_bytes: Vec<u8>,
+  output: OutputTarget,
+  embed: bool,
+) -> EngineResult<Option<Vec<u8>>> {
+  let limits = default_limits();
+
+  check_manifest_size(manifest_bytes.len() as u64, limits)?;
+  if !is_c2pa_store(&manifest_bytes) {
+    return Err(EngineError::Config(
+      "manifest bytes are not a C2PA manifest store".into(),
+    ));
+  }
+
+  let data = read_asset_bytes(&asset, limits)?;
+  let content_type = match &asset {
+    AssetRef::Stream { content_type, .. } => content_type.clone(),
+    _ => None,
+  };
+  let format = content_type
+    .as_deref()
+    .or_else(|| detect_extension_from_bytes(&data).map(format_for_extension))
+    .ok_or_else(|| EngineError::UnsupportedFormat("could not determine asset format".into()))?
+    .to_string();
+
+  // Parsing the store against the asset rejects malformed stores and formats the
+  // SDK cannot bind a manifest to, before anything is written.
+  with_c2pa_settings(&[], || {
+    Reader::from_manifest_data_and_stream(
+      &manifest_bytes,
+      &format,
+      std::io::Cursor::new(&data),
+    )?;
+    Ok(())
+  })?;
+
+  if !embed {
+    return match output {
+      OutputTarget::Path(dest) => {
+        std::fs::write(&dest, &data)?;
+        std::fs::write(dest.with_extension("c2pa"), &manifest_bytes)?;
+        Ok(None)
+      }
+      OutputTarget::Memory => Err(EngineError::Config(
+        "detached sidecar output requires a Path target".into(),
+      )),
+    };
+  }
+
+  let embedded = c2pa::jumbf_io::save_jumbf_to_memory(&format, &data, &manifest_bytes).map_err(|e| match e {
+    c2pa::Error::UnsupportedType | c2pa::Error::NotImplemented(_) => EngineError::UnsupportedFormat(format!(
+      "embedding a pre-built manifest is not supported for {format} assets"
+    )),
+    e => EngineError::C2pa(e),
+  })?;
+  check_data_hash(&format, &embedded)?;
+
+  match output {
+    OutputTarget::Path(dest) => {
+      std::fs::write(dest, &embedded)?;
+      Ok(None)
+    }
+    OutputTarget::Memory => {
+      check_output_size(embedded.len() as u64, limits)?;
+      Ok(Some(embedded))
+    }
+  }
+}
+
+/// Fail unless the active manifest's data hash validates against `embedded`.
+fn check_data_hash(format: &str, embedded: &[u8]) -> EngineResult<()> {
+  let statuses = with_c2pa_settings(&[], || {
+    let reader = Reader::from_stream(format, std::io::Cursor::new(embedded))?;
+    Ok(
+      reader
+        .validation_results()
+        .map(|results| collect_validation_statuses(results, reader.active_label(), &[]))
+        .unwrap_or_default(),
+    )
+  })?;
+
+  let active = statuses.iter().filter(|s| s.ingredient_uri.is_none());
+  let mut matched = false;
+  for status in active {
+    match status.code_enum {
+      ValidationCode::AssertionDataHashMatch if status.passed => matched = true,
+      ValidationCode::AssertionDataHashMismatch | ValidationCode::AssertionDataHashMalformed => {
+        return Err(EngineError::C2pa(c2pa::Error::HashMismatch(
+          "manifest store does not match the asset it was embedded into".into(),
+        )));
+      }
+      _ => {}
+    }
+  }
+  if !matched {
+    return Err(EngineError::C2pa(c2pa::Error::HashMismatch(
+      "embedded manifest store has no data hash that validates against the asset".into(),
+    )));
+  }
+  Ok(())
+}
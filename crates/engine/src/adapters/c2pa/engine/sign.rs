@@ -1,194 +1,878 @@
 // adapters/c2pa/engine/sign.rs
 
 use crate::domain::error::{EngineError, EngineResult};
-use crate::domain::types::{AssetRef, C2paConfig, OutputTarget};
-use super::super::settings::{with_c2pa_settings, prepare_manifest_json};
-use super::super::asset_utils::{asset_to_temp_path, sniff_content_type_from_reader};
+use crate::domain::types::{
+  AssetRef, C2paConfig, C2paVerificationConfig, DetachedManifest, HashAlg, LimitsConfig, OutputTarget,
+  PostSignMode, SignOutcome, TrustPolicyConfig,
+};
+use super::super::settings::{with_c2pa_settings, prepare_manifest_source, push_raw_settings};
+#[cfg(target_arch = "wasm32")]
+use super::super::settings::with_c2pa_settings_async;
+use crate::domain::manifest_builder::ManifestSource;
+use crate::domain::manifest_validation::{validate_manifest_definition, validate_manifest_value};
+use crate::domain::progress::{ProgressCallback, ProgressPhase};
+use crate::domain::cancel::{check_cancelled, or_cancelled, CancellationToken};
+use super::super::asset_utils::{
+  asset_to_temp_path_with_progress, check_allowed_content, check_allowed_format, check_declared_format,
+  check_in_memory_size, check_manifest_size, check_output_size, read_asset_bytes, report_file_progress,
+  sniff_asset_extension, sniff_content_type_from_bytes, sniff_content_type_from_reader, staging_dir, ProgressReader,
+};
+use super::super::content_detection::unsupported_raw_format;
+#[cfg(feature = "c2pa")]
+use super::super::content_detection::{check_heif_sequence_supported, check_pdf_supported};
+use super::super::system_trust::to_pem;
+use super::super::url_validation::{check_remote_url_reachable, fetch_remote_asset};
+use crate::crypto::signer::Signer;
+use std::borrow::Cow;
+use x509_parser::pem::Pem;
 
 #[cfg(feature = "cawg")]
 use super::super::cawg;
 #[cfg(feature = "cawg")]
-use super::common::ensure_claim_version_2;
+use super::common::{ensure_claim_version_2, run_on_current_thread};
+#[cfg(feature = "cawg")]
+use super::reserve::with_reserve_size_async;
+#[cfg(feature = "c2pa")]
+use super::reserve::with_reserve_size;
+#[cfg(feature = "cawg")]
+use super::tsa_signer::with_tsa_headers_async;
+#[cfg(feature = "c2pa")]
+use super::tsa_signer::with_tsa_headers;
 
-use super::common::{build_trust_settings, run_on_current_thread, setup_builder};
+use super::common::{build_trust_settings, setup_builder};
+#[cfg(not(target_arch = "wasm32"))]
+use super::common::run_blocking;
+use crate::telemetry::{trace_event, trace_span};
+use super::soft_binding::{apply_fingerprint, apply_soft_binding};
+#[cfg(feature = "c2pa")]
+use super::deterministic::{apply_deterministic_ids, apply_deterministic_manifest};
 
 
 pub fn sign_c2pa(config: C2paConfig) -> EngineResult<Option<Vec<u8>>> {
+  sign_with_outcome(config, false).map(|outcome| outcome.artifact)
+}
+
+/// [`sign_c2pa`], also returning the engine's verification of the signed asset unless
+/// post-sign validation is skipped.
+pub fn sign_c2pa_with_report(config: C2paConfig) -> EngineResult<SignOutcome> {
+  sign_with_outcome(config, true)
+}
+
+/// Signs `config`, then verifies the output in `ValidateReport` mode, or with `report`
+/// unless validation is skipped.
+fn sign_with_outcome(config: C2paConfig, report: bool) -> EngineResult<SignOutcome> {
   #[cfg(not(feature = "c2pa"))]
   {
     return Err(EngineError::Feature("c2pa"));
   }
   #[cfg(feature = "c2pa")]
   {
-    let mut settings = vec![serde_json::json!({
-      "verify": { "verify_after_sign": !config.skip_post_sign_validation }
-    })];
-
-    if let Some(policy) = &config.trust_policy {
-      let (trust_settings, enable_trust) = build_trust_settings(policy)?;
-      settings.extend(trust_settings);
-      settings.push(serde_json::json!({
-        "verify": { "verify_trust": enable_trust }
-      }));
+    let mut config = config;
+    let _span = trace_span!(
+      "sign_c2pa",
+      alg = %config.signing_alg.to_c2pa(),
+      hash_alg = config.hash_alg.map(HashAlg::as_str),
+      embed = config.embed,
+    );
+    let (manifest_json, settings) = prepare_sign_call(&mut config)?;
+
+    let signed = with_c2pa_settings(&settings, || {
+      let alg = config.signing_alg.to_c2pa();
+
+      // CAWG path (async): identity assertions, including any co-signers
+      #[cfg(feature = "cawg")]
+      if config.cawg_identity.is_some() || !config.co_signers.is_empty() {
+        let manifest_json = ensure_claim_version_2(manifest_json)?;
+        return run_on_current_thread(sign_with_cawg(&config, &manifest_json, alg));
+      }
+
+      // Non-CAWG sync path
+      sign_with_signer(&config, &manifest_json, alg)
+    });
+    let artifact = or_cancelled(config.cancel.as_ref(), signed)?;
+    if !runs_post_sign_verify(&config, report) {
+      return Ok(SignOutcome { artifact, verification: None, verification_error: None });
     }
+    let verified = post_sign_verify_config(&config, artifact.as_deref()).and_then(super::verify::verify_c2pa);
+    Ok(post_sign_outcome(artifact, verified))
+  }
+}
 
-    with_c2pa_settings(&settings, || {
-      let manifest_json =
-        prepare_manifest_json(config.manifest_definition.clone(), &config.timestamper)?;
+/// Whether signing `config` verifies its output: always in `ValidateReport` mode, and
+/// in strict mode only when the caller asked for the report.
+#[cfg(feature = "c2pa")]
+fn runs_post_sign_verify(config: &C2paConfig, report: bool) -> bool {
+  match post_sign_mode(config) {
+    PostSignMode::Skip => false,
+    PostSignMode::ValidateStrict => report,
+    PostSignMode::ValidateReport => true,
+  }
+}
+
+/// The outcome of a signing whose output was verified. The asset is already signed
+/// (and written, for a `Path` output), so a verification that fails to run is
+/// reported alongside it rather than failing the call.
+#[cfg(feature = "c2pa")]
+fn post_sign_outcome(
+  artifact: Option<Vec<u8>>,
+  verified: EngineResult<crate::domain::verify::VerificationResult>,
+) -> SignOutcome {
+  match verified {
+    Ok(verification) => {
+      let failures = verification.status_summary.as_ref().map_or(0, |summary| summary.failure);
+      if failures > 0 {
+        trace_event!(warn, failures, "post-sign verification reported failures");
+      }
+      SignOutcome { artifact, verification: Some(verification), verification_error: None }
+    }
+    Err(e) => {
+      trace_event!(warn, error = %e, "post-sign verification failed to run");
+      SignOutcome { artifact, verification: None, verification_error: Some(e.to_string()) }
+    }
+  }
+}
+
+/// `config.post_sign`, unless `skip_post_sign_validation` overrides it.
+#[cfg(feature = "c2pa")]
+fn post_sign_mode(config: &C2paConfig) -> PostSignMode {
+  if config.skip_post_sign_validation {
+    PostSignMode::Skip
+  } else {
+    config.post_sign
+  }
+}
+
+/// Verification of the signed output of `config` with its trust policy, limits and
+/// raw settings, for `SignOutcome::verification`.
+#[cfg(feature = "c2pa")]
+fn post_sign_verify_config(config: &C2paConfig, artifact: Option<&[u8]>) -> EngineResult<C2paVerificationConfig> {
+  let source = match (artifact, &config.output) {
+    (Some(signed), _) => AssetRef::Bytes { data: signed.to_vec() },
+    (None, OutputTarget::Path(path)) => AssetRef::Path(path.clone()),
+    (None, OutputTarget::Memory) => return Err(EngineError::Config("no signed output to verify".into())),
+  };
+  let mut verify_config = C2paVerificationConfig::secure_default(source);
+  verify_config.policy = config.trust_policy.clone();
+  verify_config.limits = config.limits;
+  verify_config.raw_settings = config.raw_settings.clone();
+  verify_config.cancel = config.cancel.clone();
+  Ok(verify_config)
+}
+
+/// Async variant of [`sign_c2pa`] for callers already running inside an async runtime.
+///
+/// Inside a tokio runtime the call runs on the blocking pool, so it never stalls a
+/// runtime worker, and the returned future is `Send`: the config moves to the pool
+/// thread whole, and c2pa's thread-local settings are applied there, never across an
+/// await point. For the same reason the call holds that pool thread until it
+/// finishes: a CAWG signer is blocked on there rather than awaited, since the work
+/// can't resume on another thread with the settings in place. On wasm32, which has
+/// no blocking pool, the CAWG signer is awaited on the polling thread and the future
+/// is not `Send`.
+///
+/// Dropping the future does not stop a call already running on the pool; to stop it,
+/// keep a [`CancellationToken::drop_guard`] in the awaiting future.
+pub async fn sign_c2pa_async(config: C2paConfig) -> EngineResult<Option<Vec<u8>>> {
+  sign_with_outcome_async(config, false).await.map(|outcome| outcome.artifact)
+}
+
+/// Async variant of [`sign_c2pa_with_report`].
+pub async fn sign_c2pa_with_report_async(config: C2paConfig) -> EngineResult<SignOutcome> {
+  sign_with_outcome_async(config, true).await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn sign_with_outcome_async(config: C2paConfig, report: bool) -> EngineResult<SignOutcome> {
+  run_blocking(move || sign_with_outcome(config, report)).await
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn sign_with_outcome_async(config: C2paConfig, report: bool) -> EngineResult<SignOutcome> {
+  #[cfg(not(feature = "c2pa"))]
+  {
+    return Err(EngineError::Feature("c2pa"));
+  }
+  #[cfg(feature = "c2pa")]
+  {
+    let mut config = config;
+    trace_event!(
+      info,
+      alg = %config.signing_alg.to_c2pa(),
+      hash_alg = config.hash_alg.map(HashAlg::as_str),
+      embed = config.embed,
+      "sign_c2pa_async",
+    );
+    let (manifest_json, settings) = prepare_sign_call(&mut config)?;
 
+    let signed = with_c2pa_settings_async(&settings, || async {
       let alg = config.signing_alg.to_c2pa();
 
-      // CAWG path (async)
       #[cfg(feature = "cawg")]
-      if let Some(cawg_identity) = &config.cawg_identity {
+      if config.cawg_identity.is_some() || !config.co_signers.is_empty() {
+        let manifest_json = ensure_claim_version_2(manifest_json)?;
+        return sign_with_cawg(&config, &manifest_json, alg).await;
+      }
+
+      sign_with_signer(&config, &manifest_json, alg)
+    })
+    .await;
+    let artifact = or_cancelled(config.cancel.as_ref(), signed)?;
+    if !runs_post_sign_verify(&config, report) {
+      return Ok(SignOutcome { artifact, verification: None, verification_error: None });
+    }
+    let verified = match post_sign_verify_config(&config, artifact.as_deref()) {
+      Ok(verify_config) => super::verify::verify_c2pa_async(verify_config).await,
+      Err(e) => Err(e),
+    };
+    Ok(post_sign_outcome(artifact, verified))
+  }
+}
+
+/// Sign `config.source` with a detached manifest, returning the asset and the manifest
+/// store instead of writing them to `config.output`. `config.embed` is overridden.
+pub fn sign_c2pa_detached(config: C2paConfig) -> EngineResult<DetachedManifest> {
+  #[cfg(not(feature = "c2pa"))]
+  {
+    return Err(EngineError::Feature("c2pa"));
+  }
+  #[cfg(feature = "c2pa")]
+  {
+    let mut config = config;
+    let _span = trace_span!(
+      "sign_c2pa_detached",
+      alg = %config.signing_alg.to_c2pa(),
+      hash_alg = config.hash_alg.map(HashAlg::as_str),
+    );
+    config.embed = false;
+    if post_sign_mode(&config) == PostSignMode::ValidateReport {
+      // The detached asset can't be verified without its manifest, and there is no
+      // report to return it in.
+      return Err(EngineError::Config(
+        "post_sign ValidateReport is not supported for detached signing; use ValidateStrict or Skip".into(),
+      ));
+    }
+    let (manifest_json, settings) = prepare_sign_call(&mut config)?;
+
+    let signed = with_c2pa_settings(&settings, || {
+      let alg = config.signing_alg.to_c2pa();
+      let (data, format) = detached_source(&config)?;
+      let progress = config.progress.as_ref();
+      let cancel = config.cancel.as_ref();
+      let mut source = std::io::Cursor::new(data.as_slice());
+      let mut source = ProgressReader::new(&mut source, progress, ProgressPhase::Signing).with_cancel(cancel);
+      let mut asset = Vec::new();
+
+      #[cfg(feature = "cawg")]
+      if config.cawg_identity.is_some() || !config.co_signers.is_empty() {
         let manifest_json = ensure_claim_version_2(manifest_json)?;
         return run_on_current_thread(async {
-          let mut builder = c2pa::Builder::from_json(&manifest_json)?;
-          super::common::setup_builder(&mut builder, &config)?;
-
-          let timestamp_url = config.timestamper.as_ref().and_then(|t| t.resolve());
-
-          let signer = cawg::create_cawg_signer(
-            &config.signer,
-            alg,
-            timestamp_url,
-            cawg_identity,
-          )
-          .await?;
-
-          // Prefer stream-based async signing for streams to avoid relying on file extensions
-          match (&config.source, &config.output) {
-            (AssetRef::Stream { reader, content_type }, OutputTarget::Memory) => {
-              let mut source_reader = reader.borrow_mut();
-              let sniffed = sniff_content_type_from_reader(&mut *source_reader);
-              let format = content_type
-                .as_deref()
-                .or(sniffed)
-                .unwrap_or("application/octet-stream");
-
-              let mut output_buf = Vec::new();
-              let mut output_cursor = std::io::Cursor::new(&mut output_buf);
-
-              builder.sign_async(
-                &*signer,
-                format,
-                &mut *source_reader,
-                &mut output_cursor,
-              ).await?;
-              Ok(Some(output_buf))
-            }
-
-            (AssetRef::Stream { reader, content_type }, OutputTarget::Path(dest)) => {
-              let mut source_reader = reader.borrow_mut();
-              let sniffed = sniff_content_type_from_reader(&mut *source_reader);
-              let format = content_type
-                .as_deref()
-                .or(sniffed)
-                .unwrap_or("application/octet-stream");
-
-              let mut output_file = std::fs::File::create(dest)?;
-              builder.sign_async(
-                &*signer,
-                format,
-                &mut *source_reader,
-                &mut output_file,
-              ).await?;
-              Ok(None)
-            }
-
-            // Path/Bytes: keep file-based async signing
-            (AssetRef::Path(_) | AssetRef::Bytes { .. }, _) => {
-              let (src_path, _tmp_src_dir) = asset_to_temp_path(&config.source, config.limits)?;
-              match &config.output {
-                OutputTarget::Path(dest) => {
-                  builder.sign_file_async(&*signer, &src_path, dest).await?;
-                  Ok(None)
-                }
-                OutputTarget::Memory => {
-                  let temp_dir = tempfile::tempdir()?;
-                  let temp_path = temp_dir.path().join("signed_asset");
-                  builder.sign_file_async(&*signer, &src_path, &temp_path).await?;
-                  let buf = std::fs::read(&temp_path)?;
-                  if buf.len() > config.limits.max_in_memory_output_size {
-                    return Err(EngineError::Config(
-                      "signed output too large to return in memory".into(),
-                    ));
-                  }
-                  Ok(Some(buf))
-                }
-              }
-            }
-          }
+          let (mut builder, signer) = cawg_builder_and_signer(&config, &manifest_json, alg).await?;
+          let manifest = builder
+            .sign_async(&*signer, &format, &mut source, &mut std::io::Cursor::new(&mut asset))
+            .await?;
+          finish_detached(asset, manifest, data.len(), config.limits, progress)
         });
       }
 
-      // Non-CAWG sync path
+      let (mut builder, signer) = builder_and_signer(&config, &manifest_json, alg)?;
+      let manifest = builder.sign(&*signer, &format, &mut source, &mut std::io::Cursor::new(&mut asset))?;
+      finish_detached(asset, manifest, data.len(), config.limits, progress)
+    });
+    or_cancelled(config.cancel.as_ref(), signed)
+  }
+}
+
+/// The source bytes and format for a detached signing. The asset is read into memory,
+/// since it is returned alongside the manifest anyway.
+#[cfg(feature = "c2pa")]
+fn detached_source(config: &C2paConfig) -> EngineResult<(Vec<u8>, String)> {
+  let declared = match &config.source {
+    AssetRef::Stream { content_type, .. } => content_type.clone(),
+    AssetRef::Path(path) => c2pa::format_from_path(path),
+    _ => None,
+  };
+  let data = read_asset_bytes(&config.source, config.limits)?;
+  let format = declared
+    .or_else(|| sniff_content_type_from_bytes(&data).map(str::to_string))
+    .or_else(|| config.default_format.clone())
+    .ok_or_else(|| EngineError::UnsupportedFormat("could not determine asset format".into()))?;
+  if let Some(callback) = config.progress.as_ref() {
+    let len = data.len() as u64;
+    callback.emit(ProgressPhase::Staging, len, Some(len));
+  }
+  Ok((data, format))
+}
+
+/// Completes a detached signing: final progress, `max_manifest_size` on the manifest
+/// and the output size limit on the asset.
+#[cfg(feature = "c2pa")]
+fn finish_detached(
+  asset: Vec<u8>,
+  manifest: Vec<u8>,
+  source_len: usize,
+  limits: LimitsConfig,
+  progress: Option<&ProgressCallback>,
+) -> EngineResult<DetachedManifest> {
+  check_manifest_size(manifest.len() as u64, limits)?;
+  let asset = finish_in_memory(asset, source_len, limits, progress)?.unwrap_or_default();
+  trace_event!(debug, asset_bytes = asset.len(), manifest_bytes = manifest.len(), "signed detached");
+  Ok(DetachedManifest { asset, manifest })
+}
+
+/// The manifest `sign_c2pa` would sign for `config`, as JSON, without resolving the
+/// signer or signing. Manifest preparation and builder setup run as for signing
+/// (parent ingredient, remote URL, `ta_url`, thumbnail, deterministic IDs), but
+/// soft-binding and fingerprint assertions are left out: they are computed from the
+/// asset content at signing time.
+pub fn preview_manifest(config: &C2paConfig) -> EngineResult<String> {
+  #[cfg(not(feature = "c2pa"))]
+  {
+    let _ = config;
+    return Err(EngineError::Feature("c2pa"));
+  }
+  #[cfg(feature = "c2pa")]
+  {
+    let _span = trace_span!("preview_manifest");
+    let source = manifest_source(config)?;
+    if config.validate_manifest {
+      check_manifest_source(source.as_ref())?;
+    }
+    // As in `prepare_sign_manifest`: a deterministic signing drops the TSA.
+    let timestamper = if config.deterministic.is_some() { &None } else { &config.timestamper };
+    let manifest_json = prepare_manifest_source(source, timestamper)?;
+    let manifest_json = apply_deterministic_manifest(config, manifest_json)?;
+    #[cfg(feature = "cawg")]
+    let manifest_json = if config.cawg_identity.is_some() || !config.co_signers.is_empty() {
+      ensure_claim_version_2(manifest_json)?
+    } else {
+      manifest_json
+    };
+    let settings = build_sign_settings(config)?;
+
+    with_c2pa_settings(&settings, || {
       let mut builder = c2pa::Builder::from_json(&manifest_json)?;
-      setup_builder(&mut builder, &config)?;
-
-      let signer = config.signer.resolve(alg)?;
-
-      match (&config.source, &config.output) {
-        (AssetRef::Stream { reader, content_type }, OutputTarget::Memory) => {
-          let mut source_reader = reader.borrow_mut();
-          let sniffed = sniff_content_type_from_reader(&mut *source_reader);
-          let format = content_type
-            .as_deref()
-            .or(sniffed)
-            .unwrap_or("application/octet-stream");
-          let mut output_buf = Vec::new();
-          let mut output_cursor = std::io::Cursor::new(&mut output_buf);
-
-          let _manifest_bytes = builder.sign(
-            &*signer,
-            format,
-            &mut *source_reader,
-            &mut output_cursor,
-          )?;
-          Ok(Some(output_buf))
-        }
+      setup_builder(&mut builder, config)?;
+      apply_deterministic_ids(&mut builder, config, &manifest_json)?;
+
+      let mut preview = serde_json::to_value(&builder)?;
+      // c2pa takes `ta_url` from the signer, so the builder does not keep it.
+      let definition: serde_json::Value = serde_json::from_str(&manifest_json)?;
+      if let (Some(ta_url), Some(obj)) = (definition.get("ta_url"), preview.as_object_mut()) {
+        obj.insert("ta_url".into(), ta_url.clone());
+      }
+      Ok(serde_json::to_string_pretty(&preview)?)
+    })
+  }
+}
 
-        (AssetRef::Path(_) | AssetRef::Bytes { .. }, _) => {
-          let (src_path, _tmp_src_dir) = asset_to_temp_path(&config.source, config.limits)?;
-          match &config.output {
-            OutputTarget::Path(dest) => {
-              builder.sign_file(&*signer, &src_path, dest)?;
-              Ok(None)
-            }
-            OutputTarget::Memory => {
-              let dir = tempfile::tempdir()?;
-              let out_path = dir.path().join("output_asset");
-              builder.sign_file(&*signer, &src_path, &out_path)?;
-              let meta = std::fs::metadata(&out_path)?;
-              if meta.len() as usize > config.limits.max_in_memory_output_size {
-                return Err(EngineError::Config(
-                  "signed output too large to return in memory".into(),
-                ));
-              }
-              let buf = std::fs::read(&out_path)?;
-              Ok(Some(buf))
-            }
-          }
+/// Checks shared by every sign entry point, then the final manifest JSON and the
+/// c2pa settings to sign under.
+#[cfg(feature = "c2pa")]
+fn prepare_sign_call(config: &mut C2paConfig) -> EngineResult<(String, Vec<serde_json::Value>)> {
+  #[cfg(not(feature = "cawg"))]
+  if !config.co_signers.is_empty() {
+    return Err(EngineError::Feature("cawg"));
+  }
+  if let Some(allowed) = &config.allowed_formats {
+    if let AssetRef::Url(url) = &config.source {
+      // Checked against the response `Content-Type` and body, as verification does. The
+      // fetched bytes replace the source so it isn't downloaded a second time.
+      let fetched = fetch_remote_asset(url, false, config.limits)?;
+      check_allowed_content(fetched.content_type.as_deref(), sniff_content_type_from_bytes(&fetched.data), allowed)?;
+      config.source = AssetRef::Stream {
+        reader: std::cell::RefCell::new(Box::new(std::io::Cursor::new(fetched.data))),
+        content_type: fetched.content_type,
+      };
+    }
+  }
+  check_signable_format(&config.source)?;
+  apply_default_format(&mut config.source, config.default_format.as_deref())?;
+  if let Some(allowed) = &config.allowed_formats {
+    check_allowed_format(&config.source, None, allowed)?;
+  }
+  if config.check_declared_format {
+    check_declared_format(&config.source, None)?;
+  }
+  if let (true, Some(url)) = (config.verify_remote_url_reachable, &config.remote_manifest_url) {
+    check_remote_url_reachable(url, config.allow_insecure_remote_http.unwrap_or(false), config.limits)?;
+  }
+  let manifest_json = prepare_sign_manifest(config)?;
+  check_cancelled(config.cancel.as_ref())?;
+  let settings = build_sign_settings(config)?;
+  Ok((manifest_json, settings))
+}
+
+/// Build the final manifest JSON, applying any soft binding to the source first and
+/// fingerprinting the result.
+#[cfg(feature = "c2pa")]
+fn prepare_sign_manifest(config: &mut C2paConfig) -> EngineResult<String> {
+  let _span = trace_span!("prepare_manifest");
+  if config.deterministic.is_some() {
+    // A time-stamp differs on every call.
+    config.timestamper = None;
+  }
+  let source = manifest_source(config)?;
+  if config.validate_manifest {
+    check_manifest_source(source.as_ref())?;
+  }
+  let manifest_json = prepare_manifest_source(source, &config.timestamper)?;
+  let manifest_json = apply_soft_binding(config, manifest_json)?;
+  let manifest_json = apply_fingerprint(config, manifest_json)?;
+  let manifest_json = apply_deterministic_manifest(config, manifest_json)?;
+  trace_event!(debug, manifest_bytes = manifest_json.len(), "manifest prepared");
+  Ok(manifest_json)
+}
+
+/// Camera raws, PDFs and HEIF sequences that are recognised but that c2pa has no writer for would
+/// otherwise fail with a generic unsupported-type error, or be signed as the wrong format.
+#[cfg(feature = "c2pa")]
+fn check_signable_format(source: &AssetRef) -> EngineResult<()> {
+  let extension = sniff_asset_extension(source)?;
+  if let Some(name) = extension.and_then(unsupported_raw_format) {
+    return Err(EngineError::UnsupportedFormat(format!(
+      "signing {name} camera raw files is not supported; convert to DNG first"
+    )));
+  }
+  check_pdf_supported(extension, true)?;
+  check_heif_sequence_supported(extension, true)
+}
+
+/// Declares `default_format` on a stream source that has no `content_type` and can't be
+/// sniffed. Without either, c2pa would be handed `application/octet-stream` and fail
+/// deep in the SDK with a generic unsupported-type error.
+#[cfg(feature = "c2pa")]
+fn apply_default_format(source: &mut AssetRef, default_format: Option<&str>) -> EngineResult<()> {
+  let AssetRef::Stream { reader, content_type: content_type @ None } = source else {
+    return Ok(());
+  };
+  if sniff_content_type_from_reader(&mut *reader.borrow_mut()).is_some() {
+    return Ok(());
+  }
+  *content_type = Some(default_format.ok_or_else(stream_format_required)?.to_string());
+  Ok(())
+}
+
+#[cfg(feature = "c2pa")]
+fn stream_format_required() -> EngineError {
+  EngineError::UnsupportedFormat(
+    "could not determine the stream's format: set its content_type or C2paConfig::default_format".into(),
+  )
+}
+
+/// The manifest definition from either `manifest_definition` or `manifest_source`.
+#[cfg(feature = "c2pa")]
+fn manifest_source(config: &C2paConfig) -> EngineResult<Option<ManifestSource>> {
+  match (&config.manifest_definition, &config.manifest_source) {
+    (Some(_), Some(_)) => Err(EngineError::Config(
+      "set either manifest_definition or manifest_source, not both".into(),
+    )),
+    (Some(json), None) => Ok(Some(ManifestSource::Json(json.clone()))),
+    (None, source) => Ok(source.clone()),
+  }
+}
+
+/// Pre-sign validation: warnings are treated as errors since the caller opted in.
+#[cfg(feature = "c2pa")]
+fn check_manifest_source(source: Option<&ManifestSource>) -> EngineResult<()> {
+  let warnings = match source {
+    Some(ManifestSource::Json(json)) => validate_manifest_definition(json)?,
+    Some(ManifestSource::Value(value)) => validate_manifest_value(value)?,
+    None => Vec::new(),
+  };
+  if warnings.is_empty() {
+    Ok(())
+  } else {
+    Err(EngineError::Config(format!(
+      "manifest definition failed validation: {}",
+      warnings.join("; ")
+    )))
+  }
+}
+
+#[cfg(feature = "c2pa")]
+fn build_sign_settings(config: &C2paConfig) -> EngineResult<Vec<serde_json::Value>> {
+  // Only strict mode lets c2pa validate (and fail) the signed asset itself.
+  let skip_validation = post_sign_mode(config) != PostSignMode::ValidateStrict;
+  let mut settings = sign_settings(skip_validation, config.trust_policy.as_ref())?;
+  if let Some(hash_alg) = config.hash_alg {
+    check_hash_alg_conflict(hash_alg, config.raw_settings.as_ref())?;
+    settings.push(serde_json::json!({
+      "core": { "hash_alg": hash_alg.as_str() }
+    }));
+  }
+  push_raw_settings(&mut settings, config.raw_settings.as_ref())?;
+  Ok(settings)
+}
+
+/// `raw_settings` is applied last, so a different `core.hash_alg` there would
+/// silently replace the requested digest.
+#[cfg(feature = "c2pa")]
+fn check_hash_alg_conflict(hash_alg: HashAlg, raw_settings: Option<&serde_json::Value>) -> EngineResult<()> {
+  let raw_alg = raw_settings
+    .and_then(|raw| raw.pointer("/core/hash_alg"))
+    .and_then(|v| v.as_str());
+  match raw_alg {
+    Some(raw_alg) if !raw_alg.eq_ignore_ascii_case(hash_alg.as_str()) => Err(EngineError::Config(format!(
+      "hash_alg {} conflicts with raw_settings core.hash_alg {raw_alg}",
+      hash_alg.as_str()
+    ))),
+    _ => Ok(()),
+  }
+}
+
+#[cfg(feature = "c2pa")]
+pub(super) fn sign_settings(
+  skip_post_sign_validation: bool,
+  trust_policy: Option<&TrustPolicyConfig>,
+) -> EngineResult<Vec<serde_json::Value>> {
+  let mut settings = vec![serde_json::json!({
+    "verify": { "verify_after_sign": !skip_post_sign_validation }
+  })];
+
+  if let Some(policy) = trust_policy {
+    let (trust_settings, enable_trust) = build_trust_settings(policy)?;
+    settings.extend(trust_settings);
+    settings.push(serde_json::json!({
+      "verify": { "verify_trust": enable_trust }
+    }));
+  }
+
+  Ok(settings)
+}
+
+/// The builder for `manifest_json` and the CAWG signer for `config`, each set up as
+/// every CAWG signing path needs them.
+#[cfg(feature = "cawg")]
+async fn cawg_builder_and_signer(
+  config: &C2paConfig,
+  manifest_json: &str,
+  alg: c2pa::SigningAlg,
+) -> EngineResult<(c2pa::Builder, Box<dyn c2pa::AsyncSigner>)> {
+  let mut builder = c2pa::Builder::from_json(manifest_json)?;
+  setup_builder(&mut builder, config)?;
+  apply_deterministic_ids(&mut builder, config, manifest_json)?;
+
+  let timestamp_url = config.timestamper.as_ref().and_then(|t| t.resolve());
+
+  let signer = cawg::create_cawg_signer(
+    &*chain_signer(config)?,
+    alg,
+    timestamp_url,
+    config.cawg_identity.as_ref(),
+    &config.co_signers,
+  )
+  .await?;
+  let signer = with_tsa_headers_async(signer, config.timestamper.as_ref());
+  Ok((builder, with_reserve_size_async(signer, config.reserve_size)))
+}
+
+#[cfg(feature = "cawg")]
+async fn sign_with_cawg(
+  config: &C2paConfig,
+  manifest_json: &str,
+  alg: c2pa::SigningAlg,
+) -> EngineResult<Option<Vec<u8>>> {
+  let (mut builder, signer) = cawg_builder_and_signer(config, manifest_json, alg).await?;
+
+  let progress = config.progress.as_ref();
+  let cancel = config.cancel.as_ref();
+  check_cancelled(cancel)?;
+
+  if let Some((data, format)) = in_memory_source(&config.source, &config.output, config.limits, progress)? {
+    let mut output_buf = Vec::new();
+    builder.sign_async(
+      &*signer,
+      format,
+      &mut ProgressReader::new(&mut std::io::Cursor::new(data), progress, ProgressPhase::Signing).with_cancel(cancel),
+      &mut std::io::Cursor::new(&mut output_buf),
+    ).await?;
+    return finish_in_memory(output_buf, data.len(), config.limits, progress);
+  }
+
+  // Prefer stream-based async signing for streams to avoid relying on file extensions
+  match (&config.source, &config.output) {
+    (AssetRef::Stream { reader, content_type }, OutputTarget::Memory) => {
+      let mut source_reader = reader.borrow_mut();
+      let sniffed = sniff_content_type_from_reader(&mut *source_reader);
+      let format = content_type
+        .as_deref()
+        .or(sniffed)
+        .ok_or_else(stream_format_required)?;
+
+      let mut output_buf = Vec::new();
+      let mut output_cursor = std::io::Cursor::new(&mut output_buf);
+
+      builder.sign_async(
+        &*signer,
+        format,
+        &mut ProgressReader::new(&mut *source_reader, progress, ProgressPhase::Signing).with_cancel(cancel),
+        &mut output_cursor,
+      ).await?;
+      Ok(Some(output_buf))
+    }
+
+    (AssetRef::Stream { reader, content_type }, OutputTarget::Path(dest)) => {
+      let mut source_reader = reader.borrow_mut();
+      let sniffed = sniff_content_type_from_reader(&mut *source_reader);
+      let format = content_type
+        .as_deref()
+        .or(sniffed)
+        .ok_or_else(stream_format_required)?;
+
+      let mut output_file = std::fs::File::create(dest)?;
+      builder.sign_async(
+        &*signer,
+        format,
+        &mut ProgressReader::new(&mut *source_reader, progress, ProgressPhase::Signing).with_cancel(cancel),
+        &mut output_file,
+      ).await?;
+      Ok(None)
+    }
+
+    // Path/Bytes/Url: keep file-based async signing
+    (AssetRef::Path(_) | AssetRef::Bytes { .. } | AssetRef::Url(_), _) => {
+      let (src_path, _tmp_src_dir) =
+        asset_to_temp_path_with_progress(&config.source, config.limits, config.temp_dir.as_deref(), progress, cancel)?;
+      check_cancelled(cancel)?;
+      report_file_progress(progress, ProgressPhase::Signing, &src_path, false);
+      match &config.output {
+        OutputTarget::Path(dest) => {
+          builder.sign_file_async(&*signer, &src_path, dest).await?;
+          report_file_progress(progress, ProgressPhase::Signing, &src_path, true);
+          Ok(None)
         }
+        OutputTarget::Memory => {
+          let temp_dir = staging_dir(config.temp_dir.as_deref())?;
+          // c2pa picks the asset writer from the output extension.
+          let temp_path = temp_dir
+            .path()
+            .join("signed_asset")
+            .with_extension(src_path.extension().unwrap_or_default());
+          builder.sign_file_async(&*signer, &src_path, &temp_path).await?;
+          report_file_progress(progress, ProgressPhase::Signing, &src_path, true);
+          let buf = std::fs::read(&temp_path)?;
+          check_output_size(buf.len() as u64, config.limits)?;
+          Ok(Some(buf))
+        }
+      }
+    }
+  }
+}
+
+/// Sync counterpart of `cawg_builder_and_signer`.
+#[cfg(feature = "c2pa")]
+fn builder_and_signer(
+  config: &C2paConfig,
+  manifest_json: &str,
+  alg: c2pa::SigningAlg,
+) -> EngineResult<(c2pa::Builder, Box<dyn c2pa::Signer>)> {
+  let mut builder = c2pa::Builder::from_json(manifest_json)?;
+  setup_builder(&mut builder, config)?;
+  apply_deterministic_ids(&mut builder, config, manifest_json)?;
 
-        (AssetRef::Stream { reader, content_type }, OutputTarget::Path(dest)) => {
-          let mut source_reader = reader.borrow_mut();
-          let sniffed = sniff_content_type_from_reader(&mut *source_reader);
-          let format = content_type
-            .as_deref()
-            .or(sniffed)
-            .unwrap_or("application/octet-stream");
-          let mut output_file = std::fs::File::create(dest)?;
-
-          let _manifest_bytes = builder.sign(
-            &*signer,
-            format,
-            &mut *source_reader,
-            &mut output_file,
-          )?;
+  let signer = with_tsa_headers(chain_signer(config)?.resolve(alg)?, config.timestamper.as_ref());
+  let signer = with_reserve_size(signer, config.reserve_size);
+  Ok((builder, signer))
+}
+
+#[cfg(feature = "c2pa")]
+fn sign_with_signer(
+  config: &C2paConfig,
+  manifest_json: &str,
+  alg: c2pa::SigningAlg,
+) -> EngineResult<Option<Vec<u8>>> {
+  let (mut builder, signer) = builder_and_signer(config, manifest_json, alg)?;
+  sign_with_builder(
+    &mut builder,
+    &*signer,
+    &config.source,
+    &config.output,
+    config.limits,
+    config.temp_dir.as_deref(),
+    config.progress.as_ref(),
+    config.cancel.as_ref(),
+  )
+}
+
+/// `config.signer`, as an in-memory signer with `config.intermediates` appended to
+/// its certificate chain when any are set.
+#[cfg(feature = "c2pa")]
+fn chain_signer(config: &C2paConfig) -> EngineResult<Cow<'_, Signer>> {
+  let Some(intermediates) = &config.intermediates else {
+    return Ok(Cow::Borrowed(&config.signer));
+  };
+  let (cert_pem, key_pem) = config.signer.load_pem()?;
+  let cert_pem = append_intermediates(&cert_pem, intermediates)?;
+  Ok(Cow::Owned(Signer::Pem { cert_pem, key_pem }))
+}
+
+/// `chain` followed by each certificate of `intermediates` it does not already hold.
+/// c2pa expects the leaf first and each issuer after it, so intermediates should be
+/// given leaf-side first.
+#[cfg(feature = "c2pa")]
+fn append_intermediates(chain: &[u8], intermediates: &[u8]) -> EngineResult<Vec<u8>> {
+  let certificates = |pem: &[u8]| -> Vec<Vec<u8>> {
+    Pem::iter_from_buffer(pem)
+      .filter_map(Result::ok)
+      .filter(|p| p.label == "CERTIFICATE")
+      .map(|p| p.contents)
+      .collect()
+  };
+  let held = certificates(chain);
+  let extra = certificates(intermediates);
+  if extra.is_empty() {
+    return Err(EngineError::Config("intermediates must contain PEM certificates".into()));
+  }
+  let mut out = chain.to_vec();
+  if !out.is_empty() && !out.ends_with(b"\n") {
+    out.push(b'\n');
+  }
+  for der in extra.iter().filter(|der| !held.contains(der)) {
+    out.extend_from_slice(to_pem(der).as_bytes());
+  }
+  Ok(out)
+}
+
+/// Signs a single asset with an already-configured builder and resolved signer.
+/// Shared by the per-call path and `BatchSigner`.
+#[cfg(feature = "c2pa")]
+#[allow(clippy::too_many_arguments)]
+pub(super) fn sign_with_builder(
+  builder: &mut c2pa::Builder,
+  signer: &dyn c2pa::Signer,
+  source: &AssetRef,
+  output: &OutputTarget,
+  limits: LimitsConfig,
+  temp_dir: Option<&std::path::Path>,
+  progress: Option<&ProgressCallback>,
+  cancel: Option<&CancellationToken>,
+) -> EngineResult<Option<Vec<u8>>> {
+  let _span = trace_span!(
+    "sign_asset",
+    source = crate::telemetry::asset_kind(source),
+    output = crate::telemetry::output_kind(output),
+  );
+  check_cancelled(cancel)?;
+  if let Some((data, format)) = in_memory_source(source, output, limits, progress)? {
+    trace_event!(debug, format, "signing bytes in memory");
+    let mut output_buf = Vec::new();
+    builder.sign(
+      signer,
+      format,
+      &mut ProgressReader::new(&mut std::io::Cursor::new(data), progress, ProgressPhase::Signing).with_cancel(cancel),
+      &mut std::io::Cursor::new(&mut output_buf),
+    )?;
+    trace_event!(debug, output_bytes = output_buf.len(), "signed");
+    return finish_in_memory(output_buf, data.len(), limits, progress);
+  }
+  match (source, output) {
+    (AssetRef::Stream { reader, content_type }, OutputTarget::Memory) => {
+      let mut source_reader = reader.borrow_mut();
+      let sniffed = sniff_content_type_from_reader(&mut *source_reader);
+      let format = content_type
+        .as_deref()
+        .or(sniffed)
+        .ok_or_else(stream_format_required)?;
+      trace_event!(debug, format, "signing stream");
+      let mut output_buf = Vec::new();
+      let mut output_cursor = std::io::Cursor::new(&mut output_buf);
+
+      let _manifest_bytes = builder.sign(
+        signer,
+        format,
+        &mut ProgressReader::new(&mut *source_reader, progress, ProgressPhase::Signing).with_cancel(cancel),
+        &mut output_cursor,
+      )?;
+      trace_event!(debug, output_bytes = output_buf.len(), "signed");
+      Ok(Some(output_buf))
+    }
+
+    (AssetRef::Path(_) | AssetRef::Bytes { .. } | AssetRef::Url(_), _) => {
+      let (src_path, _tmp_src_dir) = asset_to_temp_path_with_progress(source, limits, temp_dir, progress, cancel)?;
+      check_cancelled(cancel)?;
+      report_file_progress(progress, ProgressPhase::Signing, &src_path, false);
+      match output {
+        OutputTarget::Path(dest) => {
+          builder.sign_file(signer, &src_path, dest)?;
+          report_file_progress(progress, ProgressPhase::Signing, &src_path, true);
           Ok(None)
         }
+        OutputTarget::Memory => {
+          let dir = staging_dir(temp_dir)?;
+          // c2pa picks the asset writer from the output extension.
+          let out_path = dir
+            .path()
+            .join("output_asset")
+            .with_extension(src_path.extension().unwrap_or_default());
+          builder.sign_file(signer, &src_path, &out_path)?;
+          report_file_progress(progress, ProgressPhase::Signing, &src_path, true);
+          let meta = std::fs::metadata(&out_path)?;
+          check_output_size(meta.len(), limits)?;
+          let buf = std::fs::read(&out_path)?;
+          trace_event!(debug, output_bytes = buf.len(), "signed");
+          Ok(Some(buf))
+        }
       }
-    })
+    }
+
+    (AssetRef::Stream { reader, content_type }, OutputTarget::Path(dest)) => {
+      let mut source_reader = reader.borrow_mut();
+      let sniffed = sniff_content_type_from_reader(&mut *source_reader);
+      let format = content_type
+        .as_deref()
+        .or(sniffed)
+        .ok_or_else(stream_format_required)?;
+      trace_event!(debug, format, "signing stream");
+      let mut output_file = std::fs::File::create(dest)?;
+
+      let _manifest_bytes = builder.sign(
+        signer,
+        format,
+        &mut ProgressReader::new(&mut *source_reader, progress, ProgressPhase::Signing).with_cancel(cancel),
+        &mut output_file,
+      )?;
+      Ok(None)
+    }
+  }
+}
+
+/// A `Bytes` source bound for a `Memory` output is signed straight from memory, without
+/// a temp-file round trip. Returns the data and its sniffed format, or `None` for other
+/// combinations and for formats that can't be sniffed (the file-based path reports
+/// those as before).
+#[cfg(feature = "c2pa")]
+fn in_memory_source<'a>(
+  source: &'a AssetRef,
+  output: &OutputTarget,
+  limits: LimitsConfig,
+  progress: Option<&ProgressCallback>,
+) -> EngineResult<Option<(&'a [u8], &'static str)>> {
+  let (AssetRef::Bytes { data }, OutputTarget::Memory) = (source, output) else {
+    return Ok(None);
+  };
+  let Some(format) = sniff_content_type_from_bytes(data) else {
+    return Ok(None);
+  };
+  check_in_memory_size(data, limits)?;
+  if let Some(callback) = progress {
+    let len = data.len() as u64;
+    callback.emit(ProgressPhase::Staging, len, Some(len));
+  }
+  Ok(Some((data, format)))
+}
+
+/// Completes an in-memory signing: final progress and the output size limit.
+#[cfg(feature = "c2pa")]
+fn finish_in_memory(
+  output: Vec<u8>,
+  source_len: usize,
+  limits: LimitsConfig,
+  progress: Option<&ProgressCallback>,
+) -> EngineResult<Option<Vec<u8>>> {
+  if let Some(callback) = progress {
+    let len = source_len as u64;
+    callback.emit(ProgressPhase::Signing, len, Some(len));
   }
-}
\ No newline at end of file
+  check_output_size(output.len() as u64, limits)?;
+  Ok(Some(output))
+}
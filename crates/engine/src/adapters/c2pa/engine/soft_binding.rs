@@ -0,0 +1,137 @@
+// adapters/c2pa/engine/soft_binding.rs
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+
+use crate::domain::error::EngineResult;
+use crate::domain::soft_binding::{SoftBindingInfo, SOFT_BINDING_LABEL};
+use crate::domain::types::{AssetRef, C2paConfig, C2paVerificationConfig};
+use super::super::asset_utils::{read_asset_bytes, rewind_stream};
+use super::super::content_detection::{detect_extension_from_bytes, format_for_extension};
+use super::common::push_assertion;
+
+/// Run the configured soft-binding algorithm over the source, replacing the source with
+/// the (possibly watermarked) bytes and recording the binding in the manifest.
+pub fn apply_soft_binding(
+  config: &mut C2paConfig,
+  manifest_json: String,
+) -> EngineResult<String> {
+  let Some(soft_binding) = config.soft_binding.clone() else {
+    return Ok(manifest_json);
+  };
+
+  let data = read_asset_bytes(&config.source, config.limits)?;
+  let format = asset_format(&config.source, &data);
+  let binding = soft_binding.algorithm.embed(&data, &format)?;
+  config.source = AssetRef::Bytes { data: binding.asset };
+
+  push_assertion(
+    manifest_json,
+    SOFT_BINDING_LABEL,
+    serde_json::json!({
+      "alg": soft_binding.algorithm.alg(),
+      "blocks": [ { "scope": {}, "value": BASE64.encode(&binding.value) } ],
+    }),
+  )
+}
+
+/// Compute the configured perceptual fingerprint over the source as it will be signed
+/// and record it in the manifest. The source itself is left untouched.
+pub fn apply_fingerprint(config: &C2paConfig, manifest_json: String) -> EngineResult<String> {
+  let Some(fingerprint) = &config.fingerprint else {
+    return Ok(manifest_json);
+  };
+
+  let data = read_asset_bytes(&config.source, config.limits)?;
+  // Streams are read again by the signer.
+  rewind_stream(&config.source)?;
+  let format = asset_format(&config.source, &data);
+  let value = fingerprint.fingerprinter.fingerprint(&data, &format)?;
+
+  push_assertion(
+    manifest_json,
+    SOFT_BINDING_LABEL,
+    serde_json::json!({
+      "alg": fingerprint.fingerprinter.alg(),
+      "blocks": [ { "scope": {}, "value": BASE64.encode(&value) } ],
+    }),
+  )
+}
+
+/// Collect soft bindings recorded in the active manifest. When the verification config
+/// carries an algorithm, bindings it produced are checked against the asset content.
+#[cfg(feature = "c2pa")]
+pub fn collect_soft_bindings(
+  config: &C2paVerificationConfig,
+  reader: &c2pa::Reader,
+) -> EngineResult<Option<Vec<SoftBindingInfo>>> {
+  let store: serde_json::Value = serde_json::from_str(&reader.json())?;
+  let assertions = store["active_manifest"]
+    .as_str()
+    .and_then(|label| store["manifests"][label]["assertions"].as_array())
+    .cloned()
+    .unwrap_or_default();
+
+  let mut infos: Vec<SoftBindingInfo> = assertions
+    .iter()
+    .filter(|a| {
+      a["label"]
+        .as_str()
+        .is_some_and(|label| label.starts_with(SOFT_BINDING_LABEL))
+    })
+    .flat_map(|a| {
+      let alg = a["data"]["alg"].as_str().unwrap_or_default().to_string();
+      a["data"]["blocks"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(move |block| {
+          block["value"].as_str().map(|value| SoftBindingInfo {
+            alg: alg.clone(),
+            value: value.to_string(),
+            detected: None,
+          })
+        })
+    })
+    .collect();
+
+  if infos.is_empty() {
+    return Ok(None);
+  }
+
+  if let Some(soft_binding) = &config.soft_binding {
+    let alg = soft_binding.algorithm.alg();
+    if infos.iter().any(|info| info.alg == alg) {
+      rewind_stream(&config.source)?;
+      let data = read_asset_bytes(&config.source, config.limits)?;
+      let format = asset_format(&config.source, &data);
+      // Detection is advisory: content the algorithm cannot decode counts as not detected.
+      let detected = soft_binding
+        .algorithm
+        .detect(&data, &format)
+        .ok()
+        .flatten()
+        .map(|value| BASE64.encode(value));
+      for info in infos.iter_mut().filter(|info| info.alg == alg) {
+        info.detected = Some(detected.as_deref() == Some(info.value.as_str()));
+      }
+    }
+  }
+
+  Ok(Some(infos))
+}
+
+/// MIME type of an asset: the caller's hint for streams, otherwise sniffed from the bytes.
+fn asset_format(source: &AssetRef, data: &[u8]) -> String {
+  let hinted = match source {
+    AssetRef::Stream { content_type, .. } => content_type.clone(),
+    _ => None,
+  };
+  hinted.unwrap_or_else(|| {
+    detect_extension_from_bytes(data)
+      .map(format_for_extension)
+      .unwrap_or("application/octet-stream")
+      .to_string()
+  })
+}
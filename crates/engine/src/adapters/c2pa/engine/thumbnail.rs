@@ -0,0 +1,70 @@
+// adapters/c2pa/engine/thumbnail.rs
+
+use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::types::C2paConfig;
+
+/// Formats the `image` crate is built to decode.
+#[cfg(feature = "thumbnail")]
+const RASTER_EXTENSIONS: &[&str] = &["jpg", "png", "gif", "webp", "tiff"];
+#[cfg(feature = "thumbnail")]
+const THUMBNAIL_JPEG_QUALITY: u8 = 80;
+
+/// Generate the claim thumbnail from the source when `auto_thumbnail` is set.
+///
+/// Non-raster sources (PDF, BMFF, audio, SVG) and images that fail to decode are left
+/// without a generated thumbnail rather than failing the signing operation.
+pub fn apply_auto_thumbnail(builder: &mut c2pa::Builder, config: &C2paConfig) -> EngineResult<()> {
+  let Some(thumbnail) = config.auto_thumbnail else {
+    return Ok(());
+  };
+
+  #[cfg(not(feature = "thumbnail"))]
+  {
+    let _ = (builder, thumbnail);
+    Err(EngineError::Feature("thumbnail"))
+  }
+
+  #[cfg(feature = "thumbnail")]
+  {
+    use super::super::asset_utils::{read_asset_bytes, rewind_stream};
+    use super::super::content_detection::detect_extension_from_bytes;
+    use crate::domain::types::ThumbnailFormat;
+
+    let data = read_asset_bytes(&config.source, config.limits)?;
+    // Streams are read again by the signer.
+    rewind_stream(&config.source)?;
+
+    let is_raster = detect_extension_from_bytes(&data)
+      .is_some_and(|ext| RASTER_EXTENSIONS.contains(&ext));
+    if !is_raster {
+      return Ok(());
+    }
+    let Ok(img) = image::load_from_memory(&data) else {
+      return Ok(());
+    };
+
+    let max = thumbnail.max_dimension.max(1);
+    let img = if img.width() > max || img.height() > max {
+      img.thumbnail(max, max)
+    } else {
+      img
+    };
+
+    let (img, output_format) = match thumbnail.format {
+      // JPEG has no alpha channel; flatten first.
+      ThumbnailFormat::Jpeg => (
+        image::DynamicImage::ImageRgb8(img.to_rgb8()),
+        image::ImageOutputFormat::Jpeg(THUMBNAIL_JPEG_QUALITY),
+      ),
+      ThumbnailFormat::Png => (img, image::ImageOutputFormat::Png),
+    };
+    let mut encoded = std::io::Cursor::new(Vec::new());
+    img
+      .write_to(&mut encoded, output_format)
+      .map_err(|e| EngineError::Config(format!("failed to encode thumbnail: {e}")))?;
+    encoded.set_position(0);
+
+    builder.set_thumbnail(thumbnail.format.mime_type(), &mut encoded)?;
+    Ok(())
+  }
+}
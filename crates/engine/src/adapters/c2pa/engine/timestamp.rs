@@ -0,0 +1,252 @@
+// adapters/c2pa/engine/timestamp.rs
+
+use std::collections::{BTreeMap, HashMap};
+
+use c2pa::crypto::time_stamp::{default_rfc3161_message, default_rfc3161_request};
+use c2pa::{Builder, Reader};
+use serde::{Serialize, Serializer};
+
+use crate::crypto::signer::Signer;
+use crate::crypto::timestamper::Timestamper;
+use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::types::{default_limits, AssetRef, OutputTarget, SigAlg};
+use crate::domain::validation_code::ValidationCode;
+use crate::domain::verify::{TimestampInfo, ValidationStatus};
+use super::super::asset_utils::asset_to_temp_path;
+use super::super::jumbf::claim_signature;
+use super::super::settings::{prepare_manifest_json, with_c2pa_settings};
+use super::super::url_validation::{validate_external_http_url, validate_request_headers};
+use super::countersign::{appendable_format, appended_definition, signed_parent};
+use super::sign::{sign_settings, sign_with_builder};
+
+/// Label of the C2PA assertion that carries time-stamps for earlier manifests.
+const TIME_STAMP_ASSERTION: &str = "c2pa.time-stamp";
+
+/// Time-stamp the active manifest of an already-signed asset.
+///
+/// An RFC 3161 token is requested over the active manifest's existing claim
+/// signature and recorded in a `c2pa.time-stamp` assertion of a new manifest that
+/// takes the asset as its `parentOf` ingredient. The original claim is not re-signed;
+/// `signer` only signs the new time-stamp manifest.
+pub fn add_timestamp(
+  source: AssetRef,
+  signer: Signer,
+  alg: SigAlg,
+  timestamper: Timestamper,
+  force: bool,
+  output: OutputTarget,
+) -> EngineResult<Option<Vec<u8>>> {
+  let limits = default_limits();
+  let tsa_url = timestamper
+    .resolve()
+    .ok_or_else(|| EngineError::Config("timestamper has no URL".into()))?;
+  validate_external_http_url(&tsa_url, false)?;
+  let headers = timestamper.headers().map(<[_]>::to_vec);
+  if let Some(headers) = &headers {
+    validate_request_headers(headers)?;
+  }
+
+  let (src_path, _tmp_src_dir) = asset_to_temp_path(&source, limits, None)?;
+  let format = appendable_format(&src_path)?;
+  let parent = signed_parent(&src_path)?;
+  let label = parent
+    .active_manifest()
+    .ok_or_else(|| EngineError::Config("asset has no active manifest".into()))?
+    .to_string();
+
+  if !force {
+    let reader = with_c2pa_settings(&[], || Ok(Reader::from_file(&src_path)?))?;
+    if collect_timestamps(&reader).iter().any(|t| t.manifest_label == label) {
+      return Err(EngineError::Config(
+        "active manifest is already time-stamped; set force to add another".into(),
+      ));
+    }
+  }
+
+  let signature = parent
+    .manifest_data()
+    .and_then(|store| claim_signature(&store, &label))
+    .ok_or_else(|| EngineError::Config("active manifest has no claim signature".into()))?;
+  let request = default_rfc3161_message(&signature).map_err(c2pa::Error::from)?;
+  let token = default_rfc3161_request(&tsa_url, headers, &request, &signature)
+    .map_err(c2pa::Error::from)?;
+
+  let manifest_json = prepare_manifest_json(
+    Some(appended_definition(&src_path, format).to_string()),
+    &Some(timestamper),
+  )?;
+  let time_stamps = BTreeMap::from([(label.as_str(), TimeStampToken(&token))]);
+
+  let settings = sign_settings(false, None)?;
+  with_c2pa_settings(&settings, || {
+    let mut builder = Builder::from_json(&manifest_json)?;
+    builder.add_ingredient(parent);
+    builder.add_assertion(TIME_STAMP_ASSERTION, &time_stamps)?;
+    let signer = signer.resolve(alg.to_c2pa())?;
+    sign_with_builder(
+      &mut builder,
+      &*signer,
+      &AssetRef::Path(src_path.clone()),
+      &output,
+      limits,
+      None,
+      None,
+      None,
+    )
+  })
+}
+
+/// A time-stamp response, serialized as a CBOR byte string as the assertion requires.
+struct TimeStampToken<'a>(&'a [u8]);
+
+impl Serialize for TimeStampToken<'_> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(self.0)
+  }
+}
+
+/// Time-stamps of every manifest in the store: those embedded in a signature, then
+/// those added by `c2pa.time-stamp` assertions. The reader has already validated the
+/// latter against the signatures they cover.
+pub(super) fn collect_timestamps(reader: &Reader) -> Vec<TimestampInfo> {
+  let mut timestamps = Vec::new();
+
+  for manifest in reader.iter_manifests() {
+    let Some(label) = manifest.label() else { continue };
+    if let Some(time) = manifest.signature_info().and_then(|si| si.time.clone()) {
+      timestamps.push(TimestampInfo {
+        manifest_label: label.to_string(),
+        time,
+        added_by: None,
+        valid: None,
+      });
+    }
+  }
+
+  for manifest in reader.iter_manifests() {
+    let Some(label) = manifest.label() else { continue };
+    let tokens = manifest
+      .assertions()
+      .iter()
+      .filter(|a| a.label() == TIME_STAMP_ASSERTION)
+      .filter_map(|a| a.to_assertion::<HashMap<String, Vec<u8>>>().ok());
+    for (stamped, token) in tokens.flatten() {
+      if let Some(time) = tst_gen_time(&token) {
+        timestamps.push(TimestampInfo {
+          manifest_label: stamped,
+          time,
+          added_by: Some(label.to_string()),
+          valid: None,
+        });
+      }
+    }
+  }
+
+  timestamps
+}
+
+/// Sets `valid` on each time-stamp from the statuses c2pa reported for its manifest.
+///
+/// A signature time-stamp is untrusted when its TSA chain failed c2pa's trust check
+/// (or the token did not validate at all), and trusted on `timeStamp.trusted`. c2pa
+/// drops an untrusted `c2pa.time-stamp` token with `assertion.timestamp.malformed`
+/// against the manifest it stamps, so one without that status was trusted.
+pub(super) fn apply_timestamp_trust(timestamps: &mut [TimestampInfo], statuses: &[ValidationStatus]) {
+  for timestamp in timestamps {
+    let reported = |codes: &[ValidationCode]| {
+      statuses.iter().any(|s| {
+        s.manifest_label.as_deref() == Some(timestamp.manifest_label.as_str()) && codes.contains(&s.code_enum)
+      })
+    };
+    timestamp.valid = if timestamp.added_by.is_some() {
+      Some(!reported(&[ValidationCode::AssertionTimestampMalformed]))
+    } else if reported(&[
+      ValidationCode::TimeStampUntrusted,
+      ValidationCode::TimeStampMismatch,
+      ValidationCode::TimeStampMalformed,
+      ValidationCode::TimeStampOutsideValidity,
+    ]) {
+      Some(false)
+    } else if reported(&[ValidationCode::TimeStampTrusted]) {
+      Some(true)
+    } else {
+      None
+    };
+  }
+}
+
+/// `genTime` of an RFC 3161 `TimeStampResp`, formatted as RFC 3339.
+fn tst_gen_time(response: &[u8]) -> Option<String> {
+  // TimeStampResp ::= SEQUENCE { status, timeStampToken ContentInfo }
+  let (resp, _) = der_take(response, 0x30)?;
+  let (_status, rest) = der_skip(resp)?;
+  // ContentInfo ::= SEQUENCE { contentType, [0] EXPLICIT SignedData }
+  let (content_info, _) = der_take(rest, 0x30)?;
+  let (_, rest) = der_skip(content_info)?;
+  let (explicit, _) = der_take(rest, 0xA0)?;
+  // SignedData ::= SEQUENCE { version, digestAlgorithms, encapContentInfo, ... }
+  let (signed_data, _) = der_take(explicit, 0x30)?;
+  let (_, rest) = der_skip(signed_data)?;
+  let (_, rest) = der_skip(rest)?;
+  let (encap, _) = der_take(rest, 0x30)?;
+  let (_, rest) = der_skip(encap)?;
+  let (explicit, _) = der_take(rest, 0xA0)?;
+  let (octets, _) = der_take(explicit, 0x04)?;
+  // TSTInfo ::= SEQUENCE { version, policy, messageImprint, serialNumber, genTime, ... }
+  let (tst_info, _) = der_take(octets, 0x30)?;
+  let mut rest = tst_info;
+  for _ in 0..4 {
+    rest = der_skip(rest)?.1;
+  }
+  let (gen_time, _) = der_take(rest, 0x18)?;
+  format_generalized_time(std::str::from_utf8(gen_time).ok()?)
+}
+
+/// `YYYYMMDDhhmmss[.f]Z` as `YYYY-MM-DDThh:mm:ss[.f]Z`.
+fn format_generalized_time(t: &str) -> Option<String> {
+  let digits = t.strip_suffix('Z')?;
+  if digits.len() < 14 || !digits.is_char_boundary(14) {
+    return None;
+  }
+  let (date, fraction) = digits.split_at(14);
+  if !date.bytes().all(|b| b.is_ascii_digit()) {
+    return None;
+  }
+  Some(format!(
+    "{}-{}-{}T{}:{}:{}{fraction}Z",
+    &date[0..4],
+    &date[4..6],
+    &date[6..8],
+    &date[8..10],
+    &date[10..12],
+    &date[12..14],
+  ))
+}
+
+/// The contents of the next DER element if it has tag `tag`, and the bytes after it.
+fn der_take(data: &[u8], tag: u8) -> Option<(&[u8], &[u8])> {
+  if *data.first()? != tag {
+    return None;
+  }
+  der_skip(data)
+}
+
+/// The contents of the next DER element, and the bytes after it.
+fn der_skip(data: &[u8]) -> Option<(&[u8], &[u8])> {
+  let rest = data.get(1..)?;
+  let (&first, rest) = rest.split_first()?;
+  let (len, rest) = if first & 0x80 == 0 {
+    (first as usize, rest)
+  } else {
+    let n = (first & 0x7F) as usize;
+    if n == 0 || n > 4 || rest.len() < n {
+      return None;
+    }
+    let len = rest[..n].iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+    (len, &rest[n..])
+  };
+  if rest.len() < len {
+    return None;
+  }
+  Some(rest.split_at(len))
+}
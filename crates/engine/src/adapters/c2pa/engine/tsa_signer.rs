@@ -0,0 +1,203 @@
+// adapters/c2pa/engine/tsa_signer.rs
+
+//! Signer wrappers that send the claim's timestamp request to a
+//! `Timestamper::CustomWithHeaders` TSA with its extra headers attached, or through
+//! a shared `TsaClient`. Signing itself is forwarded to the wrapped signer.
+
+use c2pa::dynamic_assertion::DynamicAssertion;
+use c2pa::{Signer, SigningAlg};
+#[cfg(feature = "cawg")]
+use c2pa::dynamic_assertion::AsyncDynamicAssertion;
+#[cfg(feature = "cawg")]
+use c2pa::AsyncSigner;
+
+use crate::crypto::timestamper::Timestamper;
+#[cfg(feature = "tsa_client")]
+use super::super::tsa_client::TsaClient;
+
+/// Wraps `signer` so c2pa requests its timestamp from `timestamper` with the
+/// timestamper's headers. Timestampers without headers return the signer untouched.
+pub fn with_tsa_headers(signer: Box<dyn Signer>, timestamper: Option<&Timestamper>) -> Box<dyn Signer> {
+  match tsa_request(timestamper) {
+    Some((url, headers)) => Box::new(TsaSigner { inner: signer, url, headers }),
+    None => signer,
+  }
+}
+
+/// Async counterpart of `with_tsa_headers`, used on the CAWG path.
+#[cfg(feature = "cawg")]
+pub fn with_tsa_headers_async(
+  signer: Box<dyn AsyncSigner>,
+  timestamper: Option<&Timestamper>,
+) -> Box<dyn AsyncSigner> {
+  match tsa_request(timestamper) {
+    Some((url, headers)) => Box::new(TsaSigner { inner: signer, url, headers }),
+    None => signer,
+  }
+}
+
+fn tsa_request(timestamper: Option<&Timestamper>) -> Option<(String, Vec<(String, String)>)> {
+  let timestamper = timestamper?;
+  Some((timestamper.resolve()?, timestamper.headers()?.to_vec()))
+}
+
+struct TsaSigner<S> {
+  inner: S,
+  url: String,
+  headers: Vec<(String, String)>,
+}
+
+// `raw_signer` is left as `None`: c2pa would otherwise take the time-stamp URL
+// and headers from the raw signer, which has neither.
+impl Signer for TsaSigner<Box<dyn Signer>> {
+  fn sign(&self, data: &[u8]) -> c2pa::Result<Vec<u8>> {
+    Signer::sign(&*self.inner, data)
+  }
+
+  fn alg(&self) -> SigningAlg {
+    Signer::alg(&*self.inner)
+  }
+
+  fn certs(&self) -> c2pa::Result<Vec<Vec<u8>>> {
+    self.inner.certs()
+  }
+
+  fn reserve_size(&self) -> usize {
+    Signer::reserve_size(&*self.inner)
+  }
+
+  fn time_authority_url(&self) -> Option<String> {
+    Some(self.url.clone())
+  }
+
+  fn timestamp_request_headers(&self) -> Option<Vec<(String, String)>> {
+    Some(self.headers.clone())
+  }
+
+  fn ocsp_val(&self) -> Option<Vec<u8>> {
+    self.inner.ocsp_val()
+  }
+
+  fn direct_cose_handling(&self) -> bool {
+    self.inner.direct_cose_handling()
+  }
+
+  fn dynamic_assertions(&self) -> Vec<Box<dyn DynamicAssertion>> {
+    self.inner.dynamic_assertions()
+  }
+}
+
+#[cfg(feature = "cawg")]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl AsyncSigner for TsaSigner<Box<dyn AsyncSigner>> {
+  async fn sign(&self, data: Vec<u8>) -> c2pa::Result<Vec<u8>> {
+    self.inner.sign(data).await
+  }
+
+  fn alg(&self) -> SigningAlg {
+    self.inner.alg()
+  }
+
+  fn certs(&self) -> c2pa::Result<Vec<Vec<u8>>> {
+    self.inner.certs()
+  }
+
+  fn reserve_size(&self) -> usize {
+    self.inner.reserve_size()
+  }
+
+  fn time_authority_url(&self) -> Option<String> {
+    Some(self.url.clone())
+  }
+
+  fn timestamp_request_headers(&self) -> Option<Vec<(String, String)>> {
+    Some(self.headers.clone())
+  }
+
+  async fn ocsp_val(&self) -> Option<Vec<u8>> {
+    self.inner.ocsp_val().await
+  }
+
+  fn direct_cose_handling(&self) -> bool {
+    self.inner.direct_cose_handling()
+  }
+
+  fn dynamic_assertions(&self) -> Vec<Box<dyn AsyncDynamicAssertion>> {
+    self.inner.dynamic_assertions()
+  }
+}
+
+/// Wraps `signer` so its time-stamp requests to `timestamper` go through `client`,
+/// with the timestamper's headers. `None` without a timestamper.
+#[cfg(feature = "tsa_client")]
+pub fn with_tsa_client<'a>(
+  signer: &'a dyn Signer,
+  timestamper: Option<&Timestamper>,
+  client: &'a TsaClient,
+) -> Option<ClientTsaSigner<'a>> {
+  let timestamper = timestamper?;
+  Some(ClientTsaSigner {
+    inner: signer,
+    url: timestamper.resolve()?,
+    headers: timestamper.headers().map(<[_]>::to_vec).unwrap_or_default(),
+    client,
+  })
+}
+
+/// Like `TsaSigner`, `raw_signer` stays `None` so c2pa calls `send_timestamp_request`.
+#[cfg(feature = "tsa_client")]
+pub struct ClientTsaSigner<'a> {
+  inner: &'a dyn Signer,
+  url: String,
+  headers: Vec<(String, String)>,
+  client: &'a TsaClient,
+}
+
+#[cfg(feature = "tsa_client")]
+impl Signer for ClientTsaSigner<'_> {
+  fn sign(&self, data: &[u8]) -> c2pa::Result<Vec<u8>> {
+    Signer::sign(self.inner, data)
+  }
+
+  fn alg(&self) -> SigningAlg {
+    Signer::alg(self.inner)
+  }
+
+  fn certs(&self) -> c2pa::Result<Vec<Vec<u8>>> {
+    self.inner.certs()
+  }
+
+  fn reserve_size(&self) -> usize {
+    Signer::reserve_size(self.inner)
+  }
+
+  fn time_authority_url(&self) -> Option<String> {
+    Some(self.url.clone())
+  }
+
+  fn timestamp_request_headers(&self) -> Option<Vec<(String, String)>> {
+    Some(self.headers.clone())
+  }
+
+  fn send_timestamp_request(&self, message: &[u8]) -> Option<c2pa::Result<Vec<u8>>> {
+    Some(
+      self
+        .client
+        .timestamp(&self.url, &self.headers, message)
+        .map_err(|e| c2pa::Error::OtherError(Box::new(e))),
+    )
+  }
+
+  fn ocsp_val(&self) -> Option<Vec<u8>> {
+    self.inner.ocsp_val()
+  }
+
+  fn direct_cose_handling(&self) -> bool {
+    self.inner.direct_cose_handling()
+  }
+
+  fn dynamic_assertions(&self) -> Vec<Box<dyn DynamicAssertion>> {
+    self.inner.dynamic_assertions()
+  }
+}
@@ -1,19 +1,45 @@
 // adapters/c2pa/engine/verify.rs
 
 #[cfg(feature = "c2pa")]
-use c2pa::Reader;
+use c2pa::{Reader, Relationship};
 
 use crate::domain::error::{EngineError, EngineResult};
-use crate::domain::types::{AssetRef, C2paVerificationConfig, VerifyMode};
+use std::collections::HashSet;
+
+use crate::domain::types::{
+  AssetRef, C2paVerificationConfig, EngineDefaults, LimitsConfig, StreamReader, TrustPolicyConfig, VerifyMode,
+};
+use crate::domain::validation_code::ValidationCode;
+use crate::domain::verdict::{compute_verdict, status_severity, Severity};
 use crate::domain::verify::{
-  CertInfo, ValidationStatus, VerificationResult, Verdict,
+  check_signer_name, match_trust_anchor, normalize_fingerprint, signer_certificate_der, CertInfo, ManifestLocation, ProvenanceSummary, StatusSummary, TrustAnchorInfo,
+  ValidationStatus, VerificationResult, Verdict,
+};
+use crate::domain::progress::ProgressPhase;
+use crate::domain::cancel::{check_cancelled, or_cancelled};
+use super::super::asset_utils::{
+  check_allowed_content, check_allowed_format, check_declared_format, check_in_memory_size, report_file_progress,
+  sniff_asset_extension, sniff_content_type_from_bytes, sniff_content_type_from_reader, xmp_provenance, ProgressReader,
 };
-use super::super::asset_utils::{asset_to_temp_path, sniff_content_type_from_reader};
-use super::super::settings::with_c2pa_settings;
+#[cfg(feature = "c2pa")]
+use super::super::content_detection::{check_heif_sequence_supported, check_pdf_supported};
+use super::super::settings::{apply_settings, push_raw_settings, with_c2pa_settings};
+#[cfg(target_arch = "wasm32")]
+use super::super::settings::with_c2pa_settings_async;
+use super::super::url_validation::{fetch_remote_asset, fetch_remote_manifest};
+use super::super::system_trust::to_pem;
 
 #[cfg(feature = "cawg")]
 use super::super::cawg;
-use super::common::{build_trust_settings, run_on_current_thread};
+#[cfg(feature = "cawg")]
+use super::common::run_on_current_thread;
+use super::common::{build_trust_settings, policy_trust_anchors};
+#[cfg(not(target_arch = "wasm32"))]
+use super::common::run_blocking;
+use super::soft_binding::collect_soft_bindings;
+use crate::telemetry::{metrics_finish, metrics_start, trace_event, trace_span};
+#[cfg(feature = "c2pa")]
+use super::timestamp::{apply_timestamp_trust, collect_timestamps};
 
 pub fn verify_c2pa(
   config: C2paVerificationConfig,
@@ -24,187 +50,910 @@ pub fn verify_c2pa(
   }
   #[cfg(feature = "c2pa")]
   {
-    let mut settings = Vec::<serde_json::Value>::new();
+    let _span = trace_span!("verify_c2pa", mode = ?config.mode);
+    let settings = build_verify_settings(&config)?;
+
+    let verified = with_c2pa_settings(&settings, || verify_in_scope(&config));
+    or_cancelled(config.cancel.as_ref(), verified)
+  }
+}
+
+/// Verdict of `source` under `policy` without building a [`VerificationResult`].
+///
+/// Validates like [`verify_c2pa`] in `Summary` mode with the secure defaults, then
+/// computes only the statuses the verdict needs: the report string, certificates,
+/// timestamps, soft bindings and provenance summary are never extracted. A store the
+/// SDK did not validate has no statuses to judge and yields `Rejected`.
+pub fn verify_verdict(source: AssetRef, policy: Option<TrustPolicyConfig>) -> EngineResult<Verdict> {
+  #[cfg(not(feature = "c2pa"))]
+  {
+    let _ = (source, policy);
+    return Err(EngineError::Feature("c2pa"));
+  }
+  #[cfg(feature = "c2pa")]
+  {
+    let _span = trace_span!("verify_verdict");
+    let mut config = C2paVerificationConfig::secure_default(source);
+    config.mode = VerifyMode::Summary;
+    config.policy = policy;
+    let settings = build_verify_settings(&config)?;
+
+    with_c2pa_settings(&settings, || {
+      let (reader, _) = open_reader(&config)?;
+      let (statuses, _) = evaluate_statuses(&config, &reader);
+      let verdict = statuses
+        .map_or(Verdict::Rejected, |statuses| compute_verdict(&statuses, config.unknown_status_severity));
+      trace_event!(info, verdict = ?verdict, "validation evaluated");
+      Ok(verdict)
+    })
+  }
+}
+
+/// Upper bound on worker threads used by [`verify_c2pa_batch`].
+#[cfg(all(feature = "c2pa", not(target_arch = "wasm32")))]
+const BATCH_VERIFY_WORKERS: usize = 4;
+
+/// Verifies each config, returning one result per config in input order.
+///
+/// Assets are verified on up to `BATCH_VERIFY_WORKERS` threads (sequentially on
+/// wasm32). Each worker opens one c2pa settings scope for all of its assets and only
+/// re-applies settings when an asset's differ from the previous one's, so a batch of
+/// identically configured assets applies them once per worker.
+pub fn verify_c2pa_batch(configs: Vec<C2paVerificationConfig>) -> Vec<EngineResult<VerificationResult>> {
+  #[cfg(not(feature = "c2pa"))]
+  {
+    return configs.into_iter().map(|_| Err(EngineError::Feature("c2pa"))).collect();
+  }
+  #[cfg(feature = "c2pa")]
+  {
+    let _span = trace_span!("verify_c2pa_batch", assets = configs.len());
+    let len = configs.len();
+    let queue = std::sync::Mutex::new(
+      configs
+        .into_iter()
+        .enumerate()
+        .map(|(index, config)| {
+          let settings = build_verify_settings(&config);
+          (index, config, settings)
+        })
+        .collect::<std::collections::VecDeque<_>>(),
+    );
+    let results = std::sync::Mutex::new((0..len).map(|_| None).collect::<Vec<_>>());
+
+    #[cfg(not(target_arch = "wasm32"))]
+    match BATCH_VERIFY_WORKERS.min(len) {
+      0 | 1 => run_batch_worker(&queue, &results),
+      workers => std::thread::scope(|scope| {
+        for _ in 0..workers {
+          scope.spawn(|| run_batch_worker(&queue, &results));
+        }
+      }),
+    }
+    #[cfg(target_arch = "wasm32")]
+    run_batch_worker(&queue, &results);
+
+    results
+      .into_inner()
+      .unwrap_or_else(std::sync::PoisonError::into_inner)
+      .into_iter()
+      .map(|slot| {
+        // Only left empty when the calling thread was already inside a c2pa call.
+        slot.unwrap_or_else(|| Err(EngineError::Config("a c2pa call is already in progress on this thread".into())))
+      })
+      .collect()
+  }
+}
+
+#[cfg(feature = "c2pa")]
+type BatchQueue = std::sync::Mutex<
+  std::collections::VecDeque<(usize, C2paVerificationConfig, EngineResult<Vec<serde_json::Value>>)>,
+>;
+
+#[cfg(feature = "c2pa")]
+type BatchResults = std::sync::Mutex<Vec<Option<EngineResult<VerificationResult>>>>;
+
+/// Verifies queued configs until the queue is empty, inside one settings scope.
+#[cfg(feature = "c2pa")]
+fn run_batch_worker(queue: &BatchQueue, results: &BatchResults) {
+  let next = || queue.lock().unwrap_or_else(std::sync::PoisonError::into_inner).pop_front();
+  let _ = with_c2pa_settings(&[], || {
+    let mut applied: Option<Vec<serde_json::Value>> = None;
+    while let Some((index, config, settings)) = next() {
+      let measurement = metrics_start!(Verify, &config.source, None);
+      let verified = settings.and_then(|settings| {
+        if applied.as_ref() != Some(&settings) {
+          // Cleared first so a failed apply is retried for the next asset.
+          applied = None;
+          apply_settings(&settings)?;
+          applied = Some(settings);
+        }
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| verify_in_scope(&config)))
+          .unwrap_or_else(|_| Err(EngineError::Panic("c2pa adapter panicked".into())))
+      });
+      let verified = or_cancelled(config.cancel.as_ref(), verified);
+      metrics_finish!(measurement, &verified);
+      results.lock().unwrap_or_else(std::sync::PoisonError::into_inner)[index] = Some(verified);
+    }
+    Ok(())
+  });
+}
+
+/// The body of [`verify_c2pa`], run once the config's settings are applied.
+#[cfg(feature = "c2pa")]
+fn verify_in_scope(config: &C2paVerificationConfig) -> EngineResult<VerificationResult> {
+  #[allow(unused_mut)]
+  let (mut reader, location) = open_reader(config)?;
+  check_cancelled(config.cancel.as_ref())?;
+  let mut result = build_verification_result(config, &reader);
+  apply_manifest_location(&mut result, location);
+  result.soft_bindings = collect_soft_bindings(config, &reader)?;
+
+  #[cfg(feature = "cawg")]
+  if let Some(cawg_opts) = &config.cawg {
+    check_cancelled(config.cancel.as_ref())?;
+    let cawg_result = run_on_current_thread(cawg::validate_cawg(&mut reader, cawg_opts))?;
+    apply_cawg_result(&mut result, cawg_opts, cawg_result)?;
+  }
+
+  Ok(result)
+}
+
+/// Records where the store was read from, with the fetched URL as `remote_url`.
+#[cfg(feature = "c2pa")]
+fn apply_manifest_location(result: &mut VerificationResult, location: ManifestLocation) {
+  if let ManifestLocation::Remote(url) = &location {
+    result.remote_url = Some(url.clone());
+  }
+  result.manifest_location = Some(location);
+}
+
+/// Async variant of [`verify_c2pa`] for callers already running inside an async runtime.
+///
+/// Like `sign_c2pa_async`, the call runs on tokio's blocking pool and the returned
+/// future is `Send`, except on wasm32, where CAWG validation is awaited on the polling
+/// thread.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn verify_c2pa_async(
+  config: C2paVerificationConfig,
+) -> EngineResult<VerificationResult> {
+  run_blocking(move || verify_c2pa(config)).await
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn verify_c2pa_async(
+  config: C2paVerificationConfig,
+) -> EngineResult<VerificationResult> {
+  #[cfg(not(feature = "c2pa"))]
+  {
+    return Err(EngineError::Feature("c2pa"));
+  }
+  #[cfg(feature = "c2pa")]
+  {
+    trace_event!(info, mode = ?config.mode, "verify_c2pa_async");
+    let settings = build_verify_settings(&config)?;
+
+    let verified = with_c2pa_settings_async(&settings, || async {
+      #[allow(unused_mut)]
+      let (mut reader, location) = open_reader(&config)?;
+      check_cancelled(config.cancel.as_ref())?;
+      let mut result = build_verification_result(&config, &reader);
+      apply_manifest_location(&mut result, location);
+      result.soft_bindings = collect_soft_bindings(&config, &reader)?;
 
-    #[cfg(not(feature = "remote_manifests"))]
-    {
-      if config.allow_remote_manifests {
-        return Err(EngineError::Feature("remote_manifests"));
+      #[cfg(feature = "cawg")]
+      if let Some(cawg_opts) = &config.cawg {
+        check_cancelled(config.cancel.as_ref())?;
+        let cawg_result = cawg::validate_cawg(&mut reader, cawg_opts).await?;
+        apply_cawg_result(&mut result, cawg_opts, cawg_result)?;
       }
+
+      Ok(result)
+    })
+    .await;
+    or_cancelled(config.cancel.as_ref(), verified)
+  }
+}
+
+#[cfg(feature = "c2pa")]
+pub(super) fn build_verify_settings(
+  config: &C2paVerificationConfig,
+) -> EngineResult<Vec<serde_json::Value>> {
+  let mut settings = Vec::<serde_json::Value>::new();
+
+  #[cfg(not(feature = "remote_manifests"))]
+  {
+    if config.allow_remote_manifests {
+      return Err(EngineError::Feature("remote_manifests"));
     }
+  }
+
+  // The SDK never fetches remote manifests itself: its fetch has no size cap or
+  // SSRF checks. `open_reader` fetches them instead when they are allowed.
+  settings.push(serde_json::json!({
+    "verify": { "remote_manifest_fetch": false }
+  }));
 
+  if let Some(policy) = &config.policy {
+    let (trust_settings, enable_trust) = build_trust_settings(policy)?;
+    settings.extend(trust_settings);
     settings.push(serde_json::json!({
-      "verify": { "fetch_remote_manifests": config.allow_remote_manifests }
+      "verify": { "verify_trust": enable_trust }
     }));
+  }
 
-    if let Some(policy) = &config.policy {
-      let (trust_settings, enable_trust) = build_trust_settings(policy)?;
-      settings.extend(trust_settings);
-      settings.push(serde_json::json!({
-        "verify": { "verify_trust": enable_trust }
-      }));
-    }
+  push_raw_settings(&mut settings, config.raw_settings.as_ref())?;
+  Ok(settings)
+}
 
-    with_c2pa_settings(&settings, || {
-      let mut reader = match &config.source {
-        AssetRef::Stream { reader, content_type } => {
-          let mut stream = reader.borrow_mut();
-          let sniffed = sniff_content_type_from_reader(&mut *stream);
-          let format = content_type
-            .as_deref()
-            .or(sniffed)
-            .unwrap_or("application/octet-stream");
-          Reader::from_stream(format, &mut *stream)?
-        }
-        _ => {
-          let (src_path, _tmp_dir) = asset_to_temp_path(&config.source, config.limits)?;
-          Reader::from_file(&src_path)?
+/// Reads the manifest store of `config.source`, returning the reader and, when the
+/// manifest was fetched from a remote URL (`config.remote_manifest`, or one the asset
+/// points to), that URL.
+#[cfg(feature = "c2pa")]
+pub(super) fn open_reader(config: &C2paVerificationConfig) -> EngineResult<(Reader, ManifestLocation)> {
+  let _span = trace_span!("read_manifest", source = crate::telemetry::asset_kind(&config.source));
+  let progress = config.progress.as_ref();
+  let format_override = config.format_override.as_deref().map(check_format_override).transpose()?;
+  if config.check_declared_format {
+    check_declared_format(&config.source, format_override)?;
+  }
+  if let Some(allowed) = &config.allowed_formats {
+    // A URL's format is only known once it is fetched, and is checked then.
+    if !matches!(config.source, AssetRef::Url(_)) {
+      check_allowed_format(&config.source, format_override, allowed)?;
+    }
+  }
+  if let Some(fingerprint) = &config.expected_signer_fingerprint {
+    normalize_fingerprint(fingerprint)?;
+  }
+  if format_override.is_none() {
+    let extension = sniff_asset_extension(&config.source)?;
+    check_pdf_supported(extension, false)?;
+    check_heif_sequence_supported(extension, false)?;
+  }
+  let (reader, location) = match &config.source {
+    AssetRef::Stream { reader, content_type } => {
+      let mut stream = reader.borrow_mut();
+      let sniffed = sniff_content_type_from_reader(&mut *stream);
+      let format = format_override
+        .or(content_type.as_deref())
+        .or(sniffed)
+        .unwrap_or("application/octet-stream");
+      let (reader, fetched_from) = read_stream(config, format, &mut *stream)?;
+      let location = manifest_location(&reader, fetched_from, || {
+        stream.seek(std::io::SeekFrom::Start(0)).ok()?;
+        let url = xmp_provenance(&mut *stream);
+        stream.seek(std::io::SeekFrom::Start(0)).ok();
+        url
+      });
+      (reader, location)
+    }
+    // In-memory sources are read in place, without a temp file.
+    AssetRef::Bytes { data } => {
+      check_in_memory_size(data, config.limits)?;
+      read_in_memory(config, format_override, None, data)?
+    }
+    AssetRef::Url(url) => {
+      let fetched = fetch_remote_asset(url, false, config.limits)?;
+      if let Some(allowed) = &config.allowed_formats {
+        let declared = format_override.or(fetched.content_type.as_deref());
+        check_allowed_content(declared, sniff_content_type_from_bytes(&fetched.data), allowed)?;
+      }
+      read_in_memory(config, format_override, fetched.content_type.as_deref(), &fetched.data)?
+    }
+    // Without an extension c2pa can't name the format, so the file is read as a
+    // stream in the sniffed format rather than copied under a sniffed name.
+    AssetRef::Path(path) if path.extension().is_none() => {
+      let mut file = std::fs::File::open(path)?;
+      report_file_progress(progress, ProgressPhase::Staging, path, true);
+      let format = format_override
+        .or_else(|| sniff_content_type_from_reader(&mut file))
+        .unwrap_or("application/octet-stream");
+      let (reader, fetched_from) = read_stream(config, format, &mut file)?;
+      let location = manifest_location(&reader, fetched_from, || {
+        xmp_provenance(&mut std::fs::File::open(path).ok()?)
+      });
+      (reader, location)
+    }
+    AssetRef::Path(src_path) => {
+      report_file_progress(progress, ProgressPhase::Staging, src_path, true);
+      check_cancelled(config.cancel.as_ref())?;
+      report_file_progress(progress, ProgressPhase::Verifying, src_path, false);
+      let remote_format = || {
+        format_override
+          .map(str::to_string)
+          .or_else(|| c2pa::format_from_path(src_path))
+          .unwrap_or_else(|| "application/octet-stream".into())
+      };
+      let (reader, fetched_from) = if let Some(url) = &config.remote_manifest {
+        let file = std::fs::File::open(src_path)?;
+        (read_remote_manifest(url, config.limits, &remote_format(), file)?, Some(url.clone()))
+      } else {
+        let read = match format_override {
+          // The extension may name another format; read the file with the caller's format instead.
+          Some(format) => Reader::from_stream(format, std::fs::File::open(src_path)?),
+          None => Reader::from_file(src_path),
+        };
+        match read {
+          Err(c2pa::Error::RemoteManifestUrl(url)) if config.allow_remote_manifests => {
+            let file = std::fs::File::open(src_path)?;
+            (read_remote_manifest(&url, config.limits, &remote_format(), file)?, Some(url))
+          }
+          read => (read?, None),
         }
       };
+      report_file_progress(progress, ProgressPhase::Verifying, src_path, true);
+      let location = manifest_location(&reader, fetched_from, || {
+        xmp_provenance(&mut std::fs::File::open(src_path).ok()?)
+      });
+      (reader, location)
+    }
+  };
+  trace_event!(
+    debug,
+    active_manifest = reader.active_label().is_some(),
+    embedded = reader.is_embedded(),
+    "manifest store read",
+  );
+  check_ingredient_limits(&reader, config.limits)?;
+  Ok((reader, location))
+}
 
-      let report_str = match config.mode {
-        VerifyMode::Detailed | VerifyMode::Tree => format!("{:?}", reader),
-        VerifyMode::Info | VerifyMode::Summary => format!("{}", reader),
-      };
+/// Reads the manifest store from an asset held in memory. The format is
+/// `format_override`, else sniffed from `data`, else the `content_type` it was served with.
+#[cfg(feature = "c2pa")]
+fn read_in_memory(
+  config: &C2paVerificationConfig,
+  format_override: Option<&str>,
+  content_type: Option<&str>,
+  data: &[u8],
+) -> EngineResult<(Reader, ManifestLocation)> {
+  if let Some(callback) = config.progress.as_ref() {
+    let len = data.len() as u64;
+    callback.emit(ProgressPhase::Staging, len, Some(len));
+  }
+  let format = format_override
+    .or_else(|| sniff_content_type_from_bytes(data))
+    .or(content_type)
+    .unwrap_or("application/octet-stream");
+  let (reader, fetched_from) = read_stream(config, format, &mut std::io::Cursor::new(data))?;
+  let location = manifest_location(&reader, fetched_from, || xmp_provenance(&mut &data[..]));
+  Ok((reader, location))
+}
 
-      let (is_embedded_opt, remote_url_opt) = {
-        let is_embedded = reader.is_embedded();
-        let remote_url = reader.remote_url();
-        (Some(is_embedded), remote_url.map(|u| u.to_string()))
-      };
+/// Where `reader`'s store came from. `fetched_from` is the URL the engine fetched it
+/// from, if any; `xmp_reference` looks up a remote reference next to an embedded store.
+#[cfg(feature = "c2pa")]
+fn manifest_location(
+  reader: &Reader,
+  fetched_from: Option<String>,
+  xmp_reference: impl FnOnce() -> Option<String>,
+) -> ManifestLocation {
+  match fetched_from.or_else(|| reader.remote_url().map(str::to_string)) {
+    Some(url) => ManifestLocation::Remote(url),
+    None if !reader.is_embedded() => ManifestLocation::Sidecar,
+    None => match xmp_reference() {
+      Some(url) => ManifestLocation::EmbeddedWithRemoteRef(url),
+      None => ManifestLocation::Embedded,
+    },
+  }
+}
 
-      let certificates = if config.include_certificates.unwrap_or(false) {
-        reader
-          .active_manifest()
-          .and_then(|m| m.signature_info())
-          .map(|ci| {
-            vec![CertInfo {
-              alg: ci.alg.map(|a| a.to_string()),
-              issuer: ci.issuer.clone(),
-              cert_serial_number: ci.cert_serial_number.clone(),
-              time: ci.time.clone(),
-              revocation_status: ci.revocation_status,
-              chain_pem: (!ci.cert_chain.is_empty()).then(|| ci.cert_chain.clone()),
-            }]
-          })
-      } else {
-        None
-      };
+/// Reads the manifest store from `stream` as `format`, following a remote manifest
+/// reference when the config allows it.
+#[cfg(feature = "c2pa")]
+fn read_stream<S: StreamReader + ?Sized>(
+  config: &C2paVerificationConfig,
+  format: &str,
+  stream: &mut S,
+) -> EngineResult<(Reader, Option<String>)> {
+  trace_event!(debug, format, "reading stream");
+  let asset = ProgressReader::new(stream, config.progress.as_ref(), ProgressPhase::Verifying)
+    .with_cancel(config.cancel.as_ref());
+  if let Some(url) = &config.remote_manifest {
+    return Ok((read_remote_manifest(url, config.limits, format, asset)?, Some(url.clone())));
+  }
+  match Reader::from_stream(format, asset) {
+    Err(c2pa::Error::RemoteManifestUrl(url)) if config.allow_remote_manifests => {
+      stream.seek(std::io::SeekFrom::Start(0))?;
+      Ok((read_remote_manifest(&url, config.limits, format, &mut *stream)?, Some(url)))
+    }
+    read => Ok((read?, None)),
+  }
+}
 
-      let status_vec = reader.validation_results().map(|results| {
-        let mut all_statuses = Vec::new();
-
-        if let Some(active_manifest) = results.active_manifest() {
-          for status in active_manifest.success() {
-            all_statuses.push(ValidationStatus {
-              code: status.code().to_string(),
-              url: status.url().map(|u| u.to_string()),
-              explanation: status.explanation().map(|e| e.to_string()),
-              ingredient_uri: status.ingredient_uri().map(|i| i.to_string()),
-              passed: status.passed(),
-            });
-          }
-          for status in active_manifest.informational() {
-            all_statuses.push(ValidationStatus {
-              code: status.code().to_string(),
-              url: status.url().map(|u| u.to_string()),
-              explanation: status.explanation().map(|e| e.to_string()),
-              ingredient_uri: status.ingredient_uri().map(|i| i.to_string()),
-              passed: status.passed(),
-            });
-          }
-          for status in active_manifest.failure() {
-            all_statuses.push(ValidationStatus {
-              code: status.code().to_string(),
-              url: status.url().map(|u| u.to_string()),
-              explanation: status.explanation().map(|e| e.to_string()),
-              ingredient_uri: status.ingredient_uri().map(|i| i.to_string()),
-              passed: status.passed(),
-            });
-          }
-        }
+/// Fetches the manifest store an asset points to (bounded by `max_manifest_size`)
+/// and validates it against the asset.
+#[cfg(feature = "c2pa")]
+fn read_remote_manifest(
+  url: &str,
+  limits: LimitsConfig,
+  format: &str,
+  asset: impl std::io::Read + std::io::Seek + Send,
+) -> EngineResult<Reader> {
+  trace_event!(debug, url, "fetching remote manifest");
+  let manifest = fetch_remote_manifest(url, limits)?;
+  Ok(Reader::from_manifest_data_and_stream(&manifest, format, asset)?)
+}
 
-        if let Some(ingredient_deltas) = results.ingredient_deltas() {
-          for delta_result in ingredient_deltas {
-            let validation_deltas = delta_result.validation_deltas();
-            for status in validation_deltas.success() {
-              all_statuses.push(ValidationStatus {
-                code: status.code().to_string(),
-                url: status.url().map(|u| u.to_string()),
-                explanation: status.explanation().map(|e| e.to_string()),
-                ingredient_uri: status.ingredient_uri().map(|i| i.to_string()),
-                passed: status.passed(),
-              });
-            }
-            for status in validation_deltas.informational() {
-              all_statuses.push(ValidationStatus {
-                code: status.code().to_string(),
-                url: status.url().map(|u| u.to_string()),
-                explanation: status.explanation().map(|e| e.to_string()),
-                ingredient_uri: status.ingredient_uri().map(|i| i.to_string()),
-                passed: status.passed(),
-              });
-            }
-            for status in validation_deltas.failure() {
-              all_statuses.push(ValidationStatus {
-                code: status.code().to_string(),
-                url: status.url().map(|u| u.to_string()),
-                explanation: status.explanation().map(|e| e.to_string()),
-                ingredient_uri: status.ingredient_uri().map(|i| i.to_string()),
-                passed: status.passed(),
-              });
-            }
-          }
-        }
+/// Rejects stores whose ingredient graph exceeds `max_ingredients` or
+/// `max_ingredient_depth`, before anything walks it.
+#[cfg(feature = "c2pa")]
+pub(super) fn check_ingredient_limits(reader: &Reader, limits: LimitsConfig) -> EngineResult<()> {
+  let manifests = reader.manifests();
+  let total: usize = manifests.values().map(|m| m.ingredients().len()).sum();
+  if total > limits.max_ingredients {
+    return Err(EngineError::Config(format!(
+      "manifest store has {total} ingredients, exceeding max_ingredients of {}",
+      limits.max_ingredients
+    )));
+  }
 
-        all_statuses
-      });
+  // Breadth-first from the active manifest; the visited set stops cycles.
+  let mut visited: HashSet<&str> = HashSet::new();
+  let mut level: Vec<&str> = reader.active_label().into_iter().collect();
+  let mut depth = 0;
+  while !level.is_empty() {
+    visited.extend(level.iter().copied());
+    let next: Vec<&str> = level
+      .iter()
+      .filter_map(|label| manifests.get(*label))
+      .flat_map(|m| m.ingredients().iter().filter_map(|i| i.active_manifest()))
+      .filter(|label| !visited.contains(label))
+      .collect();
+    if !next.is_empty() {
+      depth += 1;
+      if depth > limits.max_ingredient_depth {
+        return Err(EngineError::Config(format!(
+          "ingredient manifests are nested deeper than max_ingredient_depth of {}",
+          limits.max_ingredient_depth
+        )));
+      }
+    }
+    level = next;
+  }
+  Ok(())
+}
 
-      let verdict = status_vec.as_ref().map(|statuses| {
-        if statuses.iter().any(|s| !s.passed) {
-          Verdict::Rejected
-        } else if statuses.iter().any(|s| s.code.contains("warning")) {
-          Verdict::Warning
+/// Accepts a `format_override` only if the c2pa reader has a handler for it.
+#[cfg(feature = "c2pa")]
+fn check_format_override(format: &str) -> EngineResult<&str> {
+  if Reader::supported_mime_types().iter().any(|t| t.eq_ignore_ascii_case(format)) {
+    Ok(format)
+  } else {
+    Err(EngineError::Config(format!("unsupported format override: {format}")))
+  }
+}
+
+/// Builds the verification result from the reader's standard C2PA validation.
+/// CAWG results are attached separately, after the CAWG validator has run.
+#[cfg(feature = "c2pa")]
+pub(super) fn build_verification_result(
+  config: &C2paVerificationConfig,
+  reader: &Reader,
+) -> VerificationResult {
+  let report_str = match config.mode {
+    VerifyMode::Detailed | VerifyMode::Tree => format!("{:?}", reader),
+    VerifyMode::Info | VerifyMode::Summary => format!("{}", reader),
+  };
+  let is_update_manifest = is_update_manifest(reader);
+
+  let (is_embedded_opt, remote_url_opt) = {
+    let is_embedded = reader.is_embedded();
+    let remote_url = reader.remote_url();
+    (Some(is_embedded), remote_url.map(|u| u.to_string()))
+  };
+
+  let certificates = if config.include_certificates.unwrap_or(false) {
+    reader.active_label().map(|_| collect_certificates(reader))
+  } else {
+    None
+  };
+
+  let (status_vec, status_summary) = evaluate_statuses(config, reader);
+  let verdict = status_vec
+    .as_ref()
+    .map(|statuses| compute_verdict(statuses, config.unknown_status_severity));
+  let mut timestamps = reader.active_manifest().map(|_| collect_timestamps(reader));
+  if let (Some(timestamps), Some(statuses)) = (timestamps.as_mut(), status_vec.as_deref()) {
+    apply_timestamp_trust(timestamps, statuses);
+  }
+  let trust_anchor = status_vec
+    .as_deref()
+    .and_then(|statuses| matched_trust_anchor(config, reader, statuses));
+  trace_event!(
+    info,
+    verdict = ?verdict,
+    statuses = status_vec.as_ref().map_or(0, Vec::len),
+    "validation evaluated",
+  );
+
+  VerificationResult {
+    report: report_str,
+    certificates,
+    status: status_vec,
+    status_summary,
+    verdict,
+    is_embedded: is_embedded_opt,
+    remote_url: remote_url_opt,
+    manifest_location: Some(manifest_location(reader, None, || None)),
+    soft_bindings: None,
+    timestamps,
+    trust_anchor,
+    is_update_manifest,
+    provenance_summary: reader.active_manifest().and_then(provenance_summary),
+    raw_validation_results: config
+      .include_raw_validation_results
+      .then(|| reader.validation_results())
+      .flatten()
+      .and_then(|results| serde_json::to_value(results).ok()),
+    #[cfg(feature = "cawg")]
+    cawg: None,
+  }
+}
+
+/// Validation statuses of the store (plus the signer-name and fingerprint checks)
+/// with their severities, and the matching summary; both `None` when the store was not validated.
+#[cfg(feature = "c2pa")]
+fn evaluate_statuses(
+  config: &C2paVerificationConfig,
+  reader: &Reader,
+) -> (Option<Vec<ValidationStatus>>, Option<StatusSummary>) {
+  let ingredients = reader.active_manifest().map(|m| m.ingredients()).unwrap_or_default();
+  let mut status_vec = reader
+    .validation_results()
+    .map(|results| collect_validation_statuses(results, reader.active_label(), ingredients));
+  let mut status_summary = reader.validation_results().map(summarize_statuses);
+  if let Some(statuses) = status_vec.as_mut() {
+    let policy_statuses = [signer_name_status(config, reader), signer_fingerprint_status(config, reader)];
+    for status in policy_statuses.into_iter().flatten() {
+      if let Some(summary) = status_summary.as_mut() {
+        if status.passed {
+          summary.success += 1;
         } else {
-          Verdict::Allowed
+          summary.failure += 1;
         }
-      });
+      }
+      statuses.push(status);
+    }
+  }
+  for status in status_vec.iter_mut().flatten() {
+    status.severity = status_severity(status, config.unknown_status_severity);
+  }
+  (status_vec, status_summary)
+}
 
-      #[cfg(feature = "cawg")]
-      let cawg_verification: Option<crate::domain::cawg::CawgVerification> =
-        if let Some(cawg_opts) = &config.cawg {
-          if cawg_opts.validate {
-            Some(run_on_current_thread(cawg::validate_cawg(&mut reader, cawg_opts))?)
-          } else {
-            None
-          }
-        } else {
-          None
-        };
+/// Actions that bring the asset into existence rather than edit it.
+#[cfg(feature = "c2pa")]
+const NON_EDIT_ACTIONS: &[&str] = &["c2pa.created", "c2pa.opened"];
 
-      #[cfg(feature = "cawg")]
-      {
-        if let (Some(cawg_opts), Some(cawg_result)) =
-          (&config.cawg, &cawg_verification)
-        {
-          if cawg_opts.require_valid_identity
-            && (!cawg_result.present || !cawg_result.valid)
-          {
-            return Err(EngineError::VerificationFailed);
-          }
+/// Condenses a manifest's claim generator and `c2pa.actions`/`c2pa.actions.v2`
+/// assertions; `None` when it records no actions.
+#[cfg(feature = "c2pa")]
+fn provenance_summary(manifest: &c2pa::Manifest) -> Option<ProvenanceSummary> {
+  let actions: Vec<&serde_json::Value> = manifest
+    .assertions()
+    .iter()
+    .filter(|a| matches!(a.label(), "c2pa.actions" | "c2pa.actions.v2"))
+    .filter_map(|a| a.value().ok()?.get("actions")?.as_array())
+    .flatten()
+    .collect();
+  if actions.is_empty() {
+    return None;
+  }
+  let creation_source_type = actions
+    .iter()
+    .find(|a| action_label(a) == Some("c2pa.created"))
+    .and_then(|a| a.get("digitalSourceType")?.as_str())
+    .map(str::to_string);
+
+  let mut edit_actions: Vec<String> = Vec::new();
+  for label in actions.iter().filter_map(|a| action_label(a)) {
+    if !NON_EDIT_ACTIONS.contains(&label) && !edit_actions.iter().any(|e| e == label) {
+      edit_actions.push(label.to_string());
+    }
+  }
+
+  let generator = manifest.claim_generator().map(str::to_string).or_else(|| {
+    let info = manifest.claim_generator_info.as_ref()?.first()?;
+    Some(match &info.version {
+      Some(version) => format!("{}/{version}", info.name),
+      None => info.name.clone(),
+    })
+  });
+
+  Some(ProvenanceSummary { generator, creation_source_type, edit_actions })
+}
+
+#[cfg(feature = "c2pa")]
+fn action_label(action: &serde_json::Value) -> Option<&str> {
+  action.get("action")?.as_str()
+}
+
+/// Whether the active manifest is an update manifest. c2pa does not surface the
+/// flag, but an update manifest has no hard binding (`c2pa.hash.*`) and a single
+/// `parentOf` ingredient. Hard bindings are left out of `Manifest::assertions`, so
+/// the claim's assertion references are checked instead.
+#[cfg(feature = "c2pa")]
+fn is_update_manifest(reader: &Reader) -> Option<bool> {
+  let manifest = reader.active_manifest()?;
+  let hard_binding = manifest
+    .assertion_references()
+    .any(|uri| uri.url().rsplit('/').next().is_some_and(|label| label.starts_with("c2pa.hash.")));
+  let parents = manifest
+    .ingredients()
+    .iter()
+    .filter(|ingredient| *ingredient.relationship() == Relationship::ParentOf)
+    .count();
+  Some(!hard_binding && parents == 1)
+}
+
+/// The `que.signerName.*` status for a policy with `allowed_subjects` or
+/// `allowed_issuers`. It is evaluated on top of c2pa's own validation and only
+/// names the active signer; it never grants trust.
+#[cfg(feature = "c2pa")]
+fn signer_name_status(config: &C2paVerificationConfig, reader: &Reader) -> Option<ValidationStatus> {
+  let policy = config.policy.as_ref()?;
+  if policy.allowed_subjects.is_none() && policy.allowed_issuers.is_none() {
+    return None;
+  }
+  let label = reader.active_label()?;
+  let chain = reader.active_manifest()?.signature_info().map(|si| si.cert_chain.as_str()).unwrap_or_default();
+  let (code, explanation) =
+    match check_signer_name(chain, policy.allowed_subjects.as_deref(), policy.allowed_issuers.as_deref()) {
+      Ok(()) => (ValidationCode::SignerNameAllowed, "signer name is allowed by the trust policy".to_string()),
+      Err(reason) => (ValidationCode::SignerNameNotAllowed, reason),
+    };
+  Some(ValidationStatus {
+    code: code.as_str().to_string(),
+    url: Some(format!("self#jumbf=/c2pa/{label}/c2pa.signature")),
+    explanation: Some(explanation),
+    ingredient_uri: None,
+    manifest_label: Some(label.to_string()),
+    passed: code == ValidationCode::SignerNameAllowed,
+    severity: code.severity().unwrap_or(Severity::Fail),
+    code_enum: code,
+  })
+}
+
+/// The `que.signerFingerprint.*` status for `expected_signer_fingerprint`: whether the
+/// SHA-256 of the active signer's leaf certificate is the pinned one. Like the
+/// signer-name check, a match never grants trust on its own.
+#[cfg(feature = "c2pa")]
+fn signer_fingerprint_status(config: &C2paVerificationConfig, reader: &Reader) -> Option<ValidationStatus> {
+  let expected = normalize_fingerprint(config.expected_signer_fingerprint.as_deref()?).ok()?;
+  let label = reader.active_label()?;
+  let actual = reader
+    .active_manifest()?
+    .signature_info()
+    .and_then(|si| signer_certificate_der(&si.cert_chain))
+    .and_then(|der| c2pa::hash_stream_by_alg("sha256", &mut std::io::Cursor::new(der), None, true).ok())
+    .map(|digest| digest.iter().map(|b| format!("{b:02x}")).collect::<String>());
+  let (code, explanation) = match actual {
+    Some(actual) if actual == expected => {
+      (ValidationCode::SignerFingerprintMatch, format!("signer certificate fingerprint is {actual}"))
+    }
+    Some(actual) => (
+      ValidationCode::SignerFingerprintMismatch,
+      format!("signer certificate fingerprint {actual} does not match expected fingerprint {expected}"),
+    ),
+    None => (
+      ValidationCode::SignerFingerprintMismatch,
+      format!("signer certificate could not be read; expected fingerprint {expected}"),
+    ),
+  };
+  Some(ValidationStatus {
+    code: code.as_str().to_string(),
+    url: Some(format!("self#jumbf=/c2pa/{label}/c2pa.signature")),
+    explanation: Some(explanation),
+    ingredient_uri: None,
+    manifest_label: Some(label.to_string()),
+    passed: code == ValidationCode::SignerFingerprintMatch,
+    severity: code.severity().unwrap_or(Severity::Fail),
+    code_enum: code,
+  })
+}
+
+/// The policy anchor behind a trusted active signer, if the policy supplied anchors.
+#[cfg(feature = "c2pa")]
+fn matched_trust_anchor(
+  config: &C2paVerificationConfig,
+  reader: &Reader,
+  statuses: &[ValidationStatus],
+) -> Option<TrustAnchorInfo> {
+  let anchors = policy_trust_anchors(config.policy.as_ref()?).ok()??;
+  let trusted = statuses.iter().any(|s| {
+    s.passed && s.ingredient_uri.is_none() && s.code_enum == ValidationCode::SigningCredentialTrusted
+  });
+  if !trusted {
+    return None;
+  }
+  let chain = &reader.active_manifest()?.signature_info()?.cert_chain;
+  match_trust_anchor(chain, anchors.as_bytes())
+}
+
+/// Signing certificates of every manifest in the store, keyed by manifest label.
+/// The active manifest comes first, then its ingredients' manifests breadth-first;
+/// manifests the chain does not reach follow in label order. Each manifest's claim
+/// signature is followed by its identity assertion signatures.
+#[cfg(feature = "c2pa")]
+fn collect_certificates(reader: &Reader) -> Vec<CertInfo> {
+  let manifests = reader.manifests();
+  let mut order: Vec<&str> = reader.active_label().into_iter().collect();
+  let mut next = 0;
+  while let Some(label) = order.get(next).copied() {
+    next += 1;
+    let Some(manifest) = manifests.get(label) else { continue };
+    for ingredient in manifest.ingredients() {
+      if let Some(child) = ingredient.active_manifest() {
+        if !order.contains(&child) {
+          order.push(child);
         }
       }
+    }
+  }
+  let mut unreached: Vec<&str> = manifests
+    .keys()
+    .map(String::as_str)
+    .filter(|label| !order.contains(label))
+    .collect();
+  unreached.sort_unstable();
+  order.extend(unreached);
+
+  order
+    .into_iter()
+    .filter_map(|label| Some((label, manifests.get(label)?)))
+    .flat_map(|(label, manifest)| {
+      let claim_signature = manifest.signature_info().map(|ci| CertInfo {
+        manifest_label: Some(label.to_string()),
+        assertion_label: None,
+        alg: ci.alg.map(|a| a.to_string()),
+        issuer: ci.issuer.clone(),
+        cert_serial_number: ci.cert_serial_number.clone(),
+        time: ci.time.clone(),
+        revocation_status: ci.revocation_status,
+        chain_pem: (!ci.cert_chain.is_empty()).then(|| ci.cert_chain.clone()),
+      });
+      claim_signature.into_iter().chain(identity_certificates(manifest, label))
+    })
+    .collect()
+}
+
+/// Signers of the manifest's X.509 identity assertions (`cawg.x509.cose`), such as
+/// `C2paConfig::co_signers`. Only the COSE certificate chain is read; whether the
+/// signature verifies is reported by CAWG validation.
+#[cfg(feature = "c2pa")]
+fn identity_certificates(manifest: &c2pa::Manifest, label: &str) -> Vec<CertInfo> {
+  use c2pa::crypto::cose::{cert_chain_from_sign1, parse_cose_sign1, signing_alg_from_sign1};
+  use x509_parser::prelude::{FromDer, X509Certificate};
 
-      Ok(VerificationResult {
-        report: report_str,
-        certificates,
-        status: status_vec,
-        verdict,
-        is_embedded: is_embedded_opt,
-        remote_url: remote_url_opt,
-        #[cfg(feature = "cawg")]
-        cawg: cawg_verification,
+  manifest
+    .assertions()
+    .iter()
+    .filter(|a| a.label() == "cawg.identity" || a.label().starts_with("cawg.identity__"))
+    .filter_map(|assertion| {
+      let value = assertion.to_assertion::<serde_json::Value>().ok()?;
+      if value.pointer("/signer_payload/sig_type")?.as_str()? != "cawg.x509.cose" {
+        return None;
+      }
+      let signature: Vec<u8> = serde_json::from_value(value.get("signature")?.clone()).ok()?;
+      let sign1 = parse_cose_sign1(&signature, &[], &mut c2pa::status_tracker::StatusTracker::default()).ok()?;
+      let chain = cert_chain_from_sign1(&sign1).ok()?;
+      let (_, leaf) = X509Certificate::from_der(chain.first()?).ok()?;
+      Some(CertInfo {
+        manifest_label: Some(label.to_string()),
+        assertion_label: Some(assertion.label().to_string()),
+        alg: signing_alg_from_sign1(&sign1).ok().map(|a| a.to_string()),
+        // Same convention as c2pa's claim signature: the signer's organization
+        issuer: leaf.subject().iter_organization().last().and_then(|o| o.as_str().ok()).map(str::to_string),
+        cert_serial_number: Some(leaf.serial.to_string()),
+        time: None,
+        revocation_status: None,
+        chain_pem: Some(chain.iter().map(|der| to_pem(der)).collect()),
       })
     })
+    .collect()
+}
+
+/// The active manifest's status codes, followed by each ingredient's deltas, with
+/// the label of the manifest each group belongs to. A delta's manifest is found
+/// through the ingredient assertion it names among `ingredients`.
+#[cfg(feature = "c2pa")]
+fn status_code_groups<'a>(
+  results: &'a c2pa::ValidationResults,
+  active_label: Option<&'a str>,
+  ingredients: &'a [c2pa::Ingredient],
+) -> Vec<(Option<&'a str>, &'a c2pa::validation_results::StatusCodes)> {
+  let deltas = results.ingredient_deltas().into_iter().flatten().map(|delta| {
+    let assertion = delta.ingredient_assertion_uri().rsplit('/').next();
+    let label = ingredients
+      .iter()
+      .find(|ingredient| ingredient.label().is_some() && ingredient.label() == assertion)
+      .and_then(|ingredient| ingredient.active_manifest());
+    (label, delta.validation_deltas())
+  });
+  results
+    .active_manifest()
+    .map(|codes| (active_label, codes))
+    .into_iter()
+    .chain(deltas)
+    .collect()
+}
+
+/// The manifest label in a `self#jumbf=/c2pa/<label>/...` URI.
+#[cfg(feature = "c2pa")]
+fn manifest_label_from_uri(uri: &str) -> Option<&str> {
+  let path = uri.strip_prefix("self#jumbf=").unwrap_or(uri);
+  let mut parts = path.trim_start_matches('/').split('/');
+  match (parts.next(), parts.next()) {
+    (Some("c2pa"), Some(label)) if !label.is_empty() => Some(label),
+    _ => None,
+  }
+}
+
+/// Flattens the active manifest's statuses and any ingredient deltas into one list,
+/// attributing each entry to its group's manifest, or else to the manifest its URL
+/// points into. Severities use the default for unknown codes; verification
+/// re-resolves them with the call's `unknown_status_severity`.
+#[cfg(feature = "c2pa")]
+pub(super) fn collect_validation_statuses(
+  results: &c2pa::ValidationResults,
+  active_label: Option<&str>,
+  ingredients: &[c2pa::Ingredient],
+) -> Vec<ValidationStatus> {
+  status_code_groups(results, active_label, ingredients)
+    .into_iter()
+    .flat_map(|(label, codes)| {
+      let statuses = codes.success().iter().chain(codes.informational()).chain(codes.failure());
+      statuses.map(move |status| (label, status))
+    })
+    .map(|(label, status)| {
+      let code_enum = ValidationCode::parse(status.code());
+      let severity = match code_enum.severity() {
+        Some(severity) => severity,
+        None if status.passed() => Severity::Pass,
+        None => EngineDefaults::UNKNOWN_STATUS_SEVERITY,
+      };
+      let manifest_label = label.or_else(|| status.url().and_then(manifest_label_from_uri));
+      ValidationStatus {
+        code: status.code().to_string(),
+        code_enum,
+        url: status.url().map(|u| u.to_string()),
+        explanation: status.explanation().map(|e| e.to_string()),
+        ingredient_uri: status.ingredient_uri().map(|i| i.to_string()),
+        manifest_label: manifest_label.map(str::to_string),
+        passed: status.passed(),
+        severity,
+      }
+    })
+    .collect()
+}
+
+/// Counts the statuses `collect_validation_statuses` flattens, per c2pa list.
+#[cfg(feature = "c2pa")]
+fn summarize_statuses(results: &c2pa::ValidationResults) -> StatusSummary {
+  let mut summary = StatusSummary::default();
+  for (_, codes) in status_code_groups(results, None, &[]) {
+    summary.success += codes.success().len();
+    summary.informational += codes.informational().len();
+    summary.failure += codes.failure().len();
+  }
+  summary
+}
+
+/// Attaches the CAWG validation outcome and enforces `require_valid_identity`.
+#[cfg(feature = "cawg")]
+fn apply_cawg_result(
+  result: &mut VerificationResult,
+  cawg_opts: &crate::domain::cawg::CawgVerifyOptions,
+  cawg_result: crate::domain::cawg::CawgVerification,
+) -> EngineResult<()> {
+  // Identity requirements only apply when the validator actually ran.
+  if cawg_opts.validate
+    && cawg_opts.require_valid_identity
+    && (!cawg_result.present || !cawg_result.valid)
+  {
+    return Err(EngineError::VerificationFailed);
   }
-}
\ No newline at end of file
+  result.cawg = Some(cawg_result);
+  Ok(())
+}
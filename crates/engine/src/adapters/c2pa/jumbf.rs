@@ -0,0 +1,66 @@
+// Byte-level helpers for reading C2PA JUMBF manifest stores. Embedding and stripping
+// stores in assets is left to `c2pa::jumbf_io`.
+
+/// True if `store` looks like a C2PA manifest store: a JUMBF superbox labelled `c2pa`.
+pub fn is_c2pa_store(store: &[u8]) -> bool {
+  store.len() >= 8 && &store[4..8] == b"jumb" && has_c2pa_label(&store[8..])
+}
+
+fn has_c2pa_label(data: &[u8]) -> bool {
+  data.windows(4).take(64).any(|w| w == b"c2pa")
+}
+
+/// The claim signature (COSE_Sign1 bytes) of manifest `label` in a manifest store.
+///
+/// This is the content of the manifest's `c2pa.signature` box, i.e. the data an
+/// RFC 3161 time-stamp over that signature must cover.
+pub fn claim_signature(store: &[u8], label: &str) -> Option<Vec<u8>> {
+  let (_, store_payload) = jumbf_boxes(store).next()?;
+  let manifest = jumbf_children(store_payload).find(|(l, _)| *l == label)?.1;
+  let signature = jumbf_children(manifest).find(|(l, _)| *l == "c2pa.signature")?.1;
+  jumbf_boxes(signature)
+    .find(|(tbox, _)| tbox == b"cbor")
+    .map(|(_, payload)| payload.to_vec())
+}
+
+/// Top-level boxes in `data` as `(type, payload)`.
+fn jumbf_boxes(data: &[u8]) -> impl Iterator<Item = ([u8; 4], &[u8])> {
+  let mut rest = data;
+  std::iter::from_fn(move || {
+    if rest.len() < 8 {
+      return None;
+    }
+    let lbox = u32::from_be_bytes(rest[0..4].try_into().ok()?) as usize;
+    let tbox: [u8; 4] = rest[4..8].try_into().ok()?;
+    let (header, size) = match lbox {
+      0 => (8, rest.len()),
+      1 => (16, u64::from_be_bytes(rest.get(8..16)?.try_into().ok()?) as usize),
+      n => (8, n),
+    };
+    if size < header || size > rest.len() {
+      return None;
+    }
+    let payload = &rest[header..size];
+    rest = &rest[size..];
+    Some((tbox, payload))
+  })
+}
+
+/// Labelled `jumb` superboxes inside a superbox payload, as `(label, payload)`.
+fn jumbf_children(payload: &[u8]) -> impl Iterator<Item = (&str, &[u8])> {
+  jumbf_boxes(payload)
+    .filter(|(tbox, _)| tbox == b"jumb")
+    .filter_map(|(_, child)| Some((jumbf_label(child)?, child)))
+}
+
+/// Label from the `jumd` description box that opens a superbox payload.
+fn jumbf_label(payload: &[u8]) -> Option<&str> {
+  let (tbox, desc) = jumbf_boxes(payload).next()?;
+  // 16-byte type UUID, then a toggles byte; bit 1 marks a null-terminated label.
+  if &tbox != b"jumd" || desc.len() < 17 || desc[16] & 0x02 == 0 {
+    return None;
+  }
+  let label = &desc[17..];
+  let end = label.iter().position(|b| *b == 0)?;
+  std::str::from_utf8(&label[..end]).ok()
+}
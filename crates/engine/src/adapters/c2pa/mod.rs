@@ -5,10 +5,20 @@ mod content_detection;
 mod url_validation;
 mod asset_utils;
 mod settings;
+mod jumbf;
+mod system_trust;
+#[cfg(all(feature = "c2pa", feature = "tsa_client"))]
+mod tsa_client;
 
 #[cfg(feature = "cawg")]
 mod cawg;
 
 pub mod engine;
 
-pub use engine::C2pa;
\ No newline at end of file
+pub use engine::C2pa;
+pub use content_detection::{register_content_detector, ContentDetector};
+pub use asset_utils::{copy_with_limits, write_bytes_to_temp};
+#[cfg(feature = "c2pa")]
+pub use engine::{BatchSigner, COUNTERSIGN_FORMATS};
+#[cfg(all(feature = "c2pa", feature = "tsa_client"))]
+pub use tsa_client::TsaClient;
\ No newline at end of file
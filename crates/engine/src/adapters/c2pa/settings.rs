@@ -1,9 +1,14 @@
 use serde_json::Value;
+use std::cell::Cell;
 use std::panic::{catch_unwind, AssertUnwindSafe};
+#[cfg(target_arch = "wasm32")]
+use futures::FutureExt;
 
 use crate::domain::error::{EngineError, EngineResult};
 use crate::crypto::timestamper::Timestamper;
-use super::constants::{C2PA_SETTINGS_LOCK, BASE_SETTINGS};
+use crate::domain::manifest_builder::ManifestSource;
+use super::constants::BASE_SETTINGS;
+use crate::telemetry::trace_span;
 
 #[cfg(feature = "c2pa")]
 use c2pa::settings::Settings;
@@ -16,7 +21,11 @@ pub fn apply_settings(jsons: &[serde_json::Value]) -> EngineResult<()> {
   #[cfg(feature = "c2pa")]
   {
     // For JSON content, we need to use from_string with json format
+    let _span = trace_span!("apply_settings", layers = jsons.len());
 
+    // from_string merges into the current settings, so start from the SDK defaults
+    // or an earlier call's layers would carry over.
+    Settings::reset()?;
     let _ = Settings::from_string(BASE_SETTINGS, "json")?;
     for s in jsons {
       let _ = Settings::from_string(&s.to_string(), "json")?;
@@ -25,23 +34,90 @@ pub fn apply_settings(jsons: &[serde_json::Value]) -> EngineResult<()> {
   }
 }
 
+/// Append caller-supplied raw c2pa settings, which must be a JSON object.
+/// They go last so they take precedence over everything the engine applied.
+pub fn push_raw_settings(settings: &mut Vec<Value>, raw: Option<&Value>) -> EngineResult<()> {
+  if let Some(raw) = raw {
+    if !raw.is_object() {
+      return Err(EngineError::Config("raw_settings must be a JSON object".into()));
+    }
+    settings.push(raw.clone());
+  }
+  Ok(())
+}
+
+// c2pa keeps its settings in a thread-local, so a scope only has to exclude other
+// scopes on the same thread: calls on different threads run in parallel, each with
+// its own settings.
+thread_local! {
+  static SETTINGS_SCOPE_ACTIVE: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Marks this thread's settings as in use; restores the baseline when dropped.
+struct SettingsScope;
+
+impl SettingsScope {
+  fn try_enter() -> Option<Self> {
+    SETTINGS_SCOPE_ACTIVE.with(|active| (!active.replace(true)).then_some(SettingsScope))
+  }
+}
+
+impl Drop for SettingsScope {
+  fn drop(&mut self) {
+    #[cfg(feature = "c2pa")]
+    {
+      let _ = Settings::reset();
+      let _ = Settings::from_string(BASE_SETTINGS, "json");
+    }
+    SETTINGS_SCOPE_ACTIVE.with(|active| active.set(false));
+  }
+}
+
+/// Runs `f` with `settings` applied to the calling thread's c2pa settings.
+///
+/// Scopes on different threads do not block each other. A scope cannot be opened
+/// while another is active on the same thread (e.g. from a progress callback, or
+/// while a `*_async` call on this thread is suspended); that returns
+/// `EngineError::Config` rather than clobbering the outer call's settings.
 pub fn with_c2pa_settings<F, T>(settings: &[serde_json::Value], f: F) -> EngineResult<T>
 where
   F: FnOnce() -> EngineResult<T>,
 {
-  let _guard = C2PA_SETTINGS_LOCK
-    .lock()
-    .map_err(|_| EngineError::Panic("settings mutex poisoned".into()))?;
+  let _scope = SettingsScope::try_enter().ok_or_else(|| {
+    EngineError::Config("a c2pa call is already in progress on this thread".into())
+  })?;
 
   apply_settings(settings)?;
 
-  let result = catch_unwind(AssertUnwindSafe(|| f()));
+  match catch_unwind(AssertUnwindSafe(f)) {
+    Ok(r) => r,
+    Err(_) => Err(EngineError::Panic("c2pa adapter panicked".into())),
+  }
+}
+
+/// Async counterpart of [`with_c2pa_settings`].
+///
+/// If another task on this thread is inside a scope, this yields until it finishes
+/// instead of failing. The scope is held until `f`'s future completes, so settings
+/// stay applied across await points; the future is not `Send`, so it cannot move to
+/// another thread mid-call. Only wasm32 needs it: elsewhere the `*_async` calls run
+/// [`with_c2pa_settings`] on tokio's blocking pool.
+#[cfg(target_arch = "wasm32")]
+pub async fn with_c2pa_settings_async<F, Fut, T>(settings: &[serde_json::Value], f: F) -> EngineResult<T>
+where
+  F: FnOnce() -> Fut,
+  Fut: std::future::Future<Output = EngineResult<T>>,
+{
+  let _scope = loop {
+    match SettingsScope::try_enter() {
+      Some(scope) => break scope,
+      None => tokio::task::yield_now().await,
+    }
+  };
 
-  // Always attempt to restore baseline settings
-  #[cfg(feature = "c2pa")]
-  let _ = Settings::from_string(BASE_SETTINGS, "json");
+  apply_settings(settings)?;
 
-  match result {
+  match AssertUnwindSafe(f()).catch_unwind().await {
     Ok(r) => r,
     Err(_) => Err(EngineError::Panic("c2pa adapter panicked".into())),
   }
@@ -51,32 +127,42 @@ pub fn prepare_manifest_json(
   manifest_definition: Option<String>,
   timestamper: &Option<Timestamper>,
 ) -> EngineResult<String> {
-  match manifest_definition {
-    Some(json_str) => {
-      if let Some(tsa) = timestamper {
-        let mut manifest_val: Value = serde_json::from_str(&json_str)?;
-        if let Some(obj) = manifest_val.as_object_mut() {
-          if let Some(url) = tsa.resolve() {
-            let allow_http = false; // default secure: no HTTP
-            super::url_validation::validate_external_http_url(&url, allow_http)?;
-            obj.insert("ta_url".to_string(), Value::String(url));
-          }
-        }
-        Ok(serde_json::to_string(&manifest_val)?)
-      } else {
-        Ok(json_str)
+  prepare_manifest_source(manifest_definition.map(ManifestSource::Json), timestamper)
+}
+
+pub fn prepare_manifest_source(
+  manifest_source: Option<ManifestSource>,
+  timestamper: &Option<Timestamper>,
+) -> EngineResult<String> {
+  let ta_url = match timestamper.as_ref().and_then(|tsa| tsa.resolve()) {
+    Some(url) => {
+      let allow_http = false; // default secure: no HTTP
+      super::url_validation::validate_external_http_url(&url, allow_http)?;
+      if let Some(headers) = timestamper.as_ref().and_then(Timestamper::headers) {
+        super::url_validation::validate_request_headers(headers)?;
       }
+      Some(url)
     }
-    None => {
-      let mut manifest_val = serde_json::json!({});
-      if let Some(tsa) = timestamper {
-        if let Some(url) = tsa.resolve() {
-          let allow_http = false; // default secure: no HTTP
-          super::url_validation::validate_external_http_url(&url, allow_http)?;
-          manifest_val["ta_url"] = Value::String(url);
-        }
+    None => None,
+  };
+
+  let mut manifest_val = match manifest_source {
+    // Without a TSA to inject, JSON text is passed through untouched.
+    Some(ManifestSource::Json(json_str)) if ta_url.is_none() => return Ok(json_str),
+    Some(ManifestSource::Json(json_str)) => serde_json::from_str(&json_str)?,
+    Some(ManifestSource::Value(value)) => {
+      if !value.is_object() {
+        return Err(EngineError::Config(
+          "manifest definition must be a JSON object".into(),
+        ));
       }
-      Ok(serde_json::to_string(&manifest_val)?)
+      value
     }
+    None => serde_json::json!({}),
+  };
+
+  if let (Some(url), Some(obj)) = (ta_url, manifest_val.as_object_mut()) {
+    obj.insert("ta_url".to_string(), Value::String(url));
   }
+  Ok(serde_json::to_string(&manifest_val)?)
 }
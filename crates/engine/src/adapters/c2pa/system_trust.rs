@@ -0,0 +1,75 @@
+// adapters/c2pa/system_trust.rs
+
+//! Loads the operating system's root certificates for `TrustPolicyConfig::use_system_trust`.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use x509_parser::pem::Pem;
+
+use crate::domain::error::{EngineError, EngineResult};
+
+/// CA bundle locations used by common Unix distributions and macOS, in the order
+/// OpenSSL-based tools usually probe them.
+#[cfg(unix)]
+const SYSTEM_BUNDLES: &[&str] = &[
+  "/etc/ssl/certs/ca-certificates.crt",                // Debian, Ubuntu, Alpine, Arch
+  "/etc/pki/ca-trust/extracted/pem/tls-ca-bundle.pem", // Fedora, RHEL
+  "/etc/pki/tls/certs/ca-bundle.crt",                  // older Fedora, RHEL
+  "/etc/ssl/ca-bundle.pem",                            // openSUSE
+  "/etc/ssl/cert.pem",                                 // macOS, OpenBSD
+  "/usr/local/etc/ssl/cert.pem",                       // FreeBSD
+];
+
+/// The system's trusted root certificates as a PEM bundle.
+///
+/// `SSL_CERT_FILE` wins when set, as it does for OpenSSL. Only `CERTIFICATE` blocks
+/// are kept, so comments and trust metadata in distribution bundles do not reach c2pa.
+pub fn load_system_trust_anchors() -> EngineResult<String> {
+  let path = system_bundle_path()?;
+  let data = std::fs::read(&path)?;
+  let pem: String = Pem::iter_from_buffer(&data)
+    .filter_map(Result::ok)
+    .filter(|p| p.label == "CERTIFICATE")
+    .map(|p| to_pem(&p.contents))
+    .collect();
+  if pem.is_empty() {
+    return Err(EngineError::Config(format!(
+      "system trust store {} contains no certificates",
+      path.display()
+    )));
+  }
+  Ok(pem)
+}
+
+#[cfg(unix)]
+fn system_bundle_path() -> EngineResult<std::path::PathBuf> {
+  if let Some(path) = std::env::var_os("SSL_CERT_FILE") {
+    return Ok(path.into());
+  }
+  SYSTEM_BUNDLES
+    .iter()
+    .map(std::path::PathBuf::from)
+    .find(|path| path.is_file())
+    .ok_or_else(|| EngineError::Config("no system trust store found; set SSL_CERT_FILE".into()))
+}
+
+#[cfg(not(unix))]
+fn system_bundle_path() -> EngineResult<std::path::PathBuf> {
+  match std::env::var_os("SSL_CERT_FILE") {
+    Some(path) => Ok(path.into()),
+    None => Err(EngineError::Config(
+      "use_system_trust is not supported on this platform; set SSL_CERT_FILE".into(),
+    )),
+  }
+}
+
+pub(super) fn to_pem(der: &[u8]) -> String {
+  let b64 = BASE64.encode(der);
+  let mut pem = String::from("-----BEGIN CERTIFICATE-----\n");
+  for chunk in b64.as_bytes().chunks(64) {
+    pem.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+    pem.push('\n');
+  }
+  pem.push_str("-----END CERTIFICATE-----\n");
+  pem
+}
@@ -0,0 +1,161 @@
+// adapters/c2pa/tsa_client.rs
+
+//! Shared RFC 3161 client for batch signing: one pooled HTTP agent for every
+//! time-stamp request, and a short-lived cache of responses keyed by imprint.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use c2pa::crypto::cose::CertificateTrustPolicy;
+use c2pa::crypto::time_stamp::{default_rfc3161_message, verify_time_stamp};
+use c2pa::status_tracker::StatusTracker;
+
+use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::types::default_limits;
+use super::url_validation::{external_agent, is_timeout};
+
+/// Largest time-stamp response read from a TSA.
+const MAX_RESPONSE_SIZE: u64 = 1024 * 1024;
+
+/// Responses are cached for a minute by default.
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60);
+const DEFAULT_MAX_CACHED: usize = 256;
+
+/// (TSA URL, SHA-256 imprint of the time-stamped message)
+type CacheKey = (String, Vec<u8>);
+
+/// A time-stamp client shared by the assets of a [`BatchSigner`](super::BatchSigner),
+/// and by several batch signers when cloned.
+///
+/// Requests go through one HTTP agent, so connections to the TSA are kept alive and
+/// reused instead of being opened for every asset. Responses are cached by TSA URL
+/// and the SHA-256 imprint of the message they cover: a token is only ever reused
+/// for the exact message it was issued for, never for a different digest. A cached
+/// token keeps the time of the original request, so keep the TTL short.
+#[derive(Clone)]
+pub struct TsaClient {
+  inner: Arc<Inner>,
+}
+
+struct Inner {
+  agent: ureq::Agent,
+  cache_ttl: Duration,
+  max_cached: usize,
+  cache: Mutex<HashMap<CacheKey, (Instant, Vec<u8>)>>,
+}
+
+impl TsaClient {
+  /// A client caching up to 256 responses for 60 seconds.
+  pub fn new() -> Self {
+    Self::with_cache(DEFAULT_CACHE_TTL, DEFAULT_MAX_CACHED)
+  }
+
+  /// A client caching up to `max_cached` responses for `ttl` each. A zero `ttl` or
+  /// `max_cached` disables the cache and keeps only connection reuse.
+  pub fn with_cache(ttl: Duration, max_cached: usize) -> Self {
+    let timeout = Duration::from_secs(default_limits().max_stream_read_timeout_secs);
+    Self {
+      inner: Arc::new(Inner {
+        agent: external_agent(timeout),
+        cache_ttl: ttl,
+        max_cached,
+        cache: Mutex::new(HashMap::new()),
+      }),
+    }
+  }
+
+  /// Drops every cached response.
+  pub fn clear_cache(&self) {
+    self.cache().clear();
+  }
+
+  /// Number of responses currently cached, including expired ones not yet evicted.
+  pub fn cached_responses(&self) -> usize {
+    self.cache().len()
+  }
+
+  /// An RFC 3161 response from `url` covering `message`, from the cache when one was
+  /// issued for the same message within the TTL.
+  pub(crate) fn timestamp(
+    &self,
+    url: &str,
+    headers: &[(String, String)],
+    message: &[u8],
+  ) -> EngineResult<Vec<u8>> {
+    let key = (url.to_string(), imprint(message)?);
+    if let Some((at, token)) = self.cache().get(&key) {
+      if at.elapsed() < self.inner.cache_ttl {
+        return Ok(token.clone());
+      }
+    }
+
+    let token = self.request(url, headers, message)?;
+    if !self.inner.cache_ttl.is_zero() && self.inner.max_cached > 0 {
+      let mut cache = self.cache();
+      cache.retain(|_, (at, _)| at.elapsed() < self.inner.cache_ttl);
+      if cache.len() >= self.inner.max_cached {
+        if let Some(oldest) = cache.iter().min_by_key(|(_, (at, _))| *at).map(|(k, _)| k.clone()) {
+          cache.remove(&oldest);
+        }
+      }
+      cache.insert(key, (Instant::now(), token.clone()));
+    }
+    Ok(token)
+  }
+
+  fn request(&self, url: &str, headers: &[(String, String)], message: &[u8]) -> EngineResult<Vec<u8>> {
+    let body = default_rfc3161_message(message).map_err(c2pa::Error::from)?;
+    let mut request = self.inner.agent.post(url);
+    for (name, value) in headers {
+      request = request.set(name, value);
+    }
+    let response = match request.set("Content-Type", "application/timestamp-query").send_bytes(&body) {
+      Ok(response) => response,
+      Err(ureq::Error::Status(code, _)) => {
+        return Err(EngineError::Config(format!("time-stamp request to {url} failed: HTTP {code}")));
+      }
+      Err(e) if is_timeout(&e) => return Err(EngineError::StreamTimeout),
+      Err(e) => return Err(EngineError::Config(format!("time-stamp request to {url} failed: {e}"))),
+    };
+    if response.status() != 200 || response.content_type() != "application/timestamp-reply" {
+      return Err(EngineError::Config(format!(
+        "time-stamp request to {url} failed: HTTP {} with content type {}",
+        response.status(),
+        response.content_type(),
+      )));
+    }
+
+    let mut token = Vec::new();
+    response.into_reader().take(MAX_RESPONSE_SIZE).read_to_end(&mut token)?;
+    // As c2pa does for its own requests: the token must cover `message` before it is used.
+    verify_time_stamp(&token, message, &CertificateTrustPolicy::passthrough(), &mut StatusTracker::default())
+      .map_err(c2pa::Error::from)?;
+    Ok(token)
+  }
+
+  fn cache(&self) -> std::sync::MutexGuard<'_, HashMap<CacheKey, (Instant, Vec<u8>)>> {
+    self.inner.cache.lock().unwrap_or_else(PoisonError::into_inner)
+  }
+}
+
+impl Default for TsaClient {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl std::fmt::Debug for TsaClient {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("TsaClient")
+      .field("cache_ttl", &self.inner.cache_ttl)
+      .field("max_cached", &self.inner.max_cached)
+      .field("cached_responses", &self.cached_responses())
+      .finish()
+  }
+}
+
+fn imprint(message: &[u8]) -> EngineResult<Vec<u8>> {
+  Ok(c2pa::hash_stream_by_alg("sha256", &mut std::io::Cursor::new(message), None, true)?)
+}
@@ -3,37 +3,36 @@ use url::{Host, Url};
 use std::net::ToSocketAddrs;
 
 use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::types::LimitsConfig;
+#[cfg(any(feature = "remote_assets", feature = "remote_manifests"))]
+use super::asset_utils::copy_with_limits;
 
 pub fn validate_external_http_url(url_str: &str, allow_http: bool) -> EngineResult<()> {
   let url = Url::parse(url_str)
-    .map_err(|_| EngineError::Config("invalid URL".into()))?;
+    .map_err(|_| EngineError::InvalidUrl("invalid URL".into()))?;
   match url.scheme() {
     "https" => {}
     "http" => {
       #[cfg(not(feature = "http_urls"))]
       {
-        if !allow_http { return Err(EngineError::Config("HTTP URLs are not allowed".into())); }
+        if !allow_http { return Err(EngineError::InvalidUrl("HTTP URLs are not allowed".into())); }
         return Err(EngineError::Feature("http_urls"));
       }
       #[cfg(feature = "http_urls")]
       {
-        if !allow_http { return Err(EngineError::Config("HTTP URLs are not allowed".into())); }
+        if !allow_http { return Err(EngineError::InvalidUrl("HTTP URLs are not allowed".into())); }
       }
     }
-    _ => return Err(EngineError::Config("unsupported URL scheme".into())),
+    _ => return Err(EngineError::InvalidUrl("unsupported URL scheme".into())),
   }
-  let host = url.host().ok_or_else(|| EngineError::Config("URL missing host".into()))?;
+  let host = url.host().ok_or_else(|| EngineError::InvalidUrl("URL missing host".into()))?;
   if let Some(ip) = match host {
     Host::Ipv4(a) => Some(IpAddr::V4(a)),
     Host::Ipv6(a) => Some(IpAddr::V6(a)),
     Host::Domain(_) => None,
   } {
-    let is_blocked = match ip {
-      IpAddr::V4(v4) => v4.is_private() || v4.is_loopback() || v4.is_link_local() || v4.is_broadcast() || v4.is_documentation() || v4.is_unspecified(),
-      IpAddr::V6(v6) => v6.is_loopback() || v6.is_unique_local() || v6.is_unicast_link_local() || v6.is_unspecified() || v6.is_multicast(),
-    };
-    if is_blocked {
-      return Err(EngineError::Config("URL host is not allowed (private/link-local/loopback)".into()));
+    if is_blocked_ip(ip) {
+      return Err(EngineError::InvalidUrl("URL host is not allowed (private/link-local/loopback)".into()));
     }
   }
   // DNS resolution hardening: block domains resolving to private/link-local IPs
@@ -42,13 +41,8 @@ pub fn validate_external_http_url(url_str: &str, allow_http: bool) -> EngineResu
     if default_port != 0 {
       if let Ok(addrs) = (domain, default_port).to_socket_addrs() {
         for addr in addrs {
-          let ip = addr.ip();
-          let is_blocked = match ip {
-            IpAddr::V4(v4) => v4.is_private() || v4.is_loopback() || v4.is_link_local() || v4.is_broadcast() || v4.is_documentation() || v4.is_unspecified(),
-            IpAddr::V6(v6) => v6.is_loopback() || v6.is_unique_local() || v6.is_unicast_link_local() || v6.is_unspecified() || v6.is_multicast(),
-          };
-          if is_blocked {
-            return Err(EngineError::Config("URL resolves to a disallowed private/loopback address".into()));
+          if is_blocked_ip(addr.ip()) {
+            return Err(EngineError::InvalidUrl("URL resolves to a disallowed private/loopback address".into()));
           }
         }
       }
@@ -57,32 +51,217 @@ pub fn validate_external_http_url(url_str: &str, allow_http: bool) -> EngineResu
   Ok(())
 }
 
-/// Enhanced URL validation for production use with content fetching policies
-/// This function should be called BEFORE fetching any remote asset
-pub fn validate_and_fetch_remote_asset(
+/// Checks extra HTTP request headers: names must be RFC 9110 tokens and values may
+/// not contain control characters (so no CR/LF header injection). `Content-Type` is
+/// rejected because c2pa sets it on timestamp requests itself.
+pub fn validate_request_headers(headers: &[(String, String)]) -> EngineResult<()> {
+  let is_tchar = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+  for (name, value) in headers {
+    if name.is_empty() || !name.chars().all(is_tchar) {
+      return Err(EngineError::Config(format!("invalid HTTP header name {name:?}")));
+    }
+    if name.eq_ignore_ascii_case("content-type") {
+      return Err(EngineError::Config("the Content-Type header is set by c2pa and cannot be overridden".into()));
+    }
+    if value.chars().any(|c| c.is_control() && c != '\t') {
+      return Err(EngineError::Config(format!("value of HTTP header {name:?} contains control characters")));
+    }
+  }
+  Ok(())
+}
+
+fn is_blocked_ip(ip: IpAddr) -> bool {
+  match ip {
+    IpAddr::V4(v4) => v4.is_private() || v4.is_loopback() || v4.is_link_local() || v4.is_broadcast() || v4.is_documentation() || v4.is_unspecified(),
+    IpAddr::V6(v6) => v6.is_loopback() || v6.is_unique_local() || v6.is_unicast_link_local() || v6.is_unspecified() || v6.is_multicast(),
+  }
+}
+
+/// Content types accepted from remote asset fetches.
+#[cfg(feature = "remote_assets")]
+const SUPPORTED_REMOTE_CONTENT_TYPES: &[&str] = &[
+  "image/jpeg", "image/png", "image/gif", "image/webp",
+  "video/mp4", "audio/mpeg", "application/pdf"
+];
+
+/// A remote asset fetched into memory.
+pub struct FetchedAsset {
+  /// Content type reported by the server, without parameters.
+  pub content_type: Option<String>,
+  pub data: Vec<u8>,
+}
+
+/// Fetch a remote asset with the engine's SSRF protections:
+/// - the URL passes `validate_external_http_url` (HTTPS only unless `allow_http`)
+/// - every address the host resolves to at connect time is re-checked, so DNS
+///   rebinding cannot reach private/loopback ranges
+/// - redirects are not followed
+/// - `Content-Length` and the body are capped at `limits.max_stream_copy_size`
+/// - the request times out after `limits.max_stream_read_timeout_secs`
+/// - only `SUPPORTED_REMOTE_CONTENT_TYPES` are accepted (a missing header is
+///   left to content sniffing)
+#[cfg(feature = "remote_assets")]
+pub fn fetch_remote_asset(
+  url_str: &str,
+  allow_http: bool,
+  limits: LimitsConfig,
+) -> EngineResult<FetchedAsset> {
+  let response = guarded_get(url_str, allow_http, limits, limits.max_stream_copy_size, |size, limit| {
+    EngineError::AssetTooLarge { size, limit }
+  })?;
+
+  let content_type = response
+    .header("content-type")
+    .map(|ct| ct.split(';').next().unwrap_or_default().trim().to_ascii_lowercase());
+  if let Some(ct) = &content_type {
+    if !SUPPORTED_REMOTE_CONTENT_TYPES.contains(&ct.as_str()) {
+      return Err(EngineError::UnsupportedFormat(format!(
+        "unsupported content type {ct} for remote asset {url_str}"
+      )));
+    }
+  }
+
+  let mut data = Vec::new();
+  copy_with_limits(&mut response.into_reader(), &mut data, limits.max_stream_copy_size)?;
+  Ok(FetchedAsset { content_type, data })
+}
+
+/// Fetch a remote manifest store with the same protections as `fetch_remote_asset`
+/// (HTTPS only), capped at `limits.max_manifest_size` instead of the asset limits.
+#[cfg(feature = "remote_manifests")]
+pub fn fetch_remote_manifest(url_str: &str, limits: LimitsConfig) -> EngineResult<Vec<u8>> {
+  let too_large = |size: u64, limit: u64| EngineError::ManifestTooLarge { size, limit };
+  let response = guarded_get(url_str, false, limits, limits.max_manifest_size, too_large)?;
+
+  let mut data = Vec::new();
+  copy_with_limits(&mut response.into_reader(), &mut data, limits.max_manifest_size).map_err(|e| match e {
+    EngineError::AssetTooLarge { size, limit } => too_large(size, limit),
+    e => e,
+  })?;
+  Ok(data)
+}
+
+/// Check with a HEAD request that a `remote_manifest_url` answers with a success
+/// status, with the connection protections of `fetch_remote_manifest`. The error
+/// carries the HTTP status when the server answered.
+#[cfg(feature = "remote_manifests")]
+pub fn check_remote_url_reachable(url_str: &str, allow_http: bool, limits: LimitsConfig) -> EngineResult<()> {
+  validate_external_http_url(url_str, allow_http)?;
+
+  let agent = external_agent(std::time::Duration::from_secs(limits.max_stream_read_timeout_secs));
+  let status = match agent.head(url_str).call() {
+    Ok(response) => response.status(),
+    Err(ureq::Error::Status(code, _)) => code,
+    Err(e) if is_timeout(&e) => return Err(EngineError::StreamTimeout),
+    Err(e) => {
+      return Err(EngineError::Config(format!("remote manifest URL {url_str} is not reachable: {e}")));
+    }
+  };
+  match status {
+    200..=299 => Ok(()),
+    300..=399 => Err(EngineError::Config(format!(
+      "remote manifest URL {url_str} is not reachable: HTTP {status} (redirects are not followed)"
+    ))),
+    _ => Err(EngineError::Config(format!("remote manifest URL {url_str} is not reachable: HTTP {status}"))),
+  }
+}
+
+/// Issue the GET shared by the remote fetches, returning the `200` response once its
+/// `Content-Length` (if any) fits in `max_bytes`. `too_large` builds the error for an
+/// oversized body, so callers can report assets and manifests differently.
+#[cfg(any(feature = "remote_assets", feature = "remote_manifests"))]
+fn guarded_get(
   url_str: &str,
-  allowed_http: bool,
-  _max_content_length: Option<u64>,
-) -> EngineResult<(String, Vec<u8>)> {
-  // First validate the URL structure and security
-  validate_external_http_url(url_str, allowed_http)?;
-
-  // Parse URL for additional checks
-  let _url = Url::parse(url_str)
-    .map_err(|_| EngineError::Config("invalid URL".into()))?;
-
-  // Only allow specific MIME types that we support
-  let _supported_types = [
-    "image/jpeg", "image/png", "image/gif", "image/webp",
-    "video/mp4", "audio/mpeg", "application/pdf"
-  ];
-
-  // For now, return a placeholder - actual implementation would:
-  // 1. Make HEAD request to check Content-Length and Content-Type
-  // 2. Validate against max_content_length (default 1GB)
-  // 3. Check Content-Type against supported_types
-  // 4. Fetch with timeout and size limits
-  // 5. Store to temp file and return AssetRef::Path
-
-  Err(EngineError::Config("Remote asset fetching not yet implemented - use AssetRef::Path after secure fetching".into()))
+  allow_http: bool,
+  limits: LimitsConfig,
+  max_bytes: usize,
+  too_large: fn(u64, u64) -> EngineError,
+) -> EngineResult<ureq::Response> {
+  validate_external_http_url(url_str, allow_http)?;
+
+  let agent = external_agent(std::time::Duration::from_secs(limits.max_stream_read_timeout_secs));
+  let response = match agent.get(url_str).call() {
+    Ok(response) => response,
+    Err(ureq::Error::Status(code, _)) => {
+      return Err(EngineError::Config(format!("failed to fetch {url_str}: HTTP {code}")));
+    }
+    Err(e) if is_timeout(&e) => return Err(EngineError::StreamTimeout),
+    Err(e) => return Err(EngineError::Config(format!("failed to fetch {url_str}: {e}"))),
+  };
+  if response.status() != 200 {
+    return Err(EngineError::Config(format!(
+      "failed to fetch {url_str}: HTTP {} (redirects are not followed)",
+      response.status()
+    )));
+  }
+
+  if let Some(len) = response.header("content-length").and_then(|v| v.parse::<u64>().ok()) {
+    if len > max_bytes as u64 {
+      return Err(too_large(len, max_bytes as u64));
+    }
+  }
+  Ok(response)
+}
+
+/// An HTTP agent for external hosts: redirects are not followed, and hosts that
+/// resolve only to private/loopback addresses are refused at connect time.
+#[cfg(any(feature = "remote_assets", feature = "remote_manifests", feature = "tsa_client"))]
+pub(super) fn external_agent(timeout: std::time::Duration) -> ureq::Agent {
+  use std::net::ToSocketAddrs as _;
+  use std::net::SocketAddr;
+
+  ureq::AgentBuilder::new()
+    .redirects(0)
+    .timeout(timeout)
+    .resolver(|netloc: &str| -> std::io::Result<Vec<SocketAddr>> {
+      let addrs: Vec<SocketAddr> = netloc
+        .to_socket_addrs()?
+        .filter(|addr| !is_blocked_ip(addr.ip()))
+        .collect();
+      if addrs.is_empty() {
+        return Err(std::io::Error::new(
+          std::io::ErrorKind::PermissionDenied,
+          "host resolves only to disallowed private/loopback addresses",
+        ));
+      }
+      Ok(addrs)
+    })
+    .build()
+}
+
+/// Whether a transport error was the request timing out (at connect or while reading).
+#[cfg(any(feature = "remote_assets", feature = "remote_manifests", feature = "tsa_client"))]
+pub(super) fn is_timeout(e: &ureq::Error) -> bool {
+  let mut source = std::error::Error::source(e);
+  while let Some(err) = source {
+    if let Some(io) = err.downcast_ref::<std::io::Error>() {
+      if matches!(io.kind(), std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock) {
+        return true;
+      }
+    }
+    source = err.source();
+  }
+  false
+}
+
+#[cfg(not(feature = "remote_assets"))]
+pub fn fetch_remote_asset(
+  url_str: &str,
+  allow_http: bool,
+  _limits: LimitsConfig,
+) -> EngineResult<FetchedAsset> {
+  validate_external_http_url(url_str, allow_http)?;
+  Err(EngineError::Feature("remote_assets"))
+}
+
+#[cfg(not(feature = "remote_manifests"))]
+pub fn fetch_remote_manifest(url_str: &str, _limits: LimitsConfig) -> EngineResult<Vec<u8>> {
+  validate_external_http_url(url_str, false)?;
+  Err(EngineError::Feature("remote_manifests"))
+}
+
+#[cfg(not(feature = "remote_manifests"))]
+pub fn check_remote_url_reachable(url_str: &str, allow_http: bool, _limits: LimitsConfig) -> EngineResult<()> {
+  validate_external_http_url(url_str, allow_http)?;
+  Err(EngineError::Feature("remote_manifests"))
 }
@@ -1 +1,7 @@
-pub mod c2pa;
\ No newline at end of file
+pub mod c2pa;
+
+#[cfg(feature = "watermark")]
+pub mod watermark;
+
+#[cfg(feature = "phash")]
+pub mod phash;
@@ -0,0 +1,81 @@
+// adapters/phash.rs
+
+//! Built-in DCT perceptual hash fingerprint.
+//!
+//! The image is reduced to a 32x32 grayscale thumbnail and the lowest 8x8 frequencies
+//! of its 2D DCT are compared against their median, giving a 64-bit hash. Visually
+//! similar images (re-encoded, lightly resized or recoloured) yield hashes within a
+//! small Hamming distance; comparing hashes is left to the lookup service.
+
+use image::imageops::FilterType;
+
+use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::soft_binding::Fingerprinter;
+
+const SAMPLE: usize = 32;
+const HASH: usize = 8;
+
+/// 64-bit DCT perceptual hash for raster images.
+#[derive(Debug, Clone, Default)]
+pub struct PHashFingerprinter;
+
+impl PHashFingerprinter {
+  pub const ALG: &'static str = "que.fingerprint.phash.1";
+}
+
+impl Fingerprinter for PHashFingerprinter {
+  fn alg(&self) -> &str {
+    Self::ALG
+  }
+
+  fn fingerprint(&self, asset: &[u8], format: &str) -> EngineResult<Vec<u8>> {
+    let img = image::load_from_memory(asset).map_err(|e| {
+      EngineError::UnsupportedFormat(format!("fingerprinting is not supported for {format} assets: {e}"))
+    })?;
+    let gray = image::imageops::resize(
+      &img.to_luma8(),
+      SAMPLE as u32,
+      SAMPLE as u32,
+      FilterType::Triangle,
+    );
+    let pixels: Vec<f32> = gray.as_raw().iter().map(|p| *p as f32).collect();
+
+    let coeffs = low_frequencies(&pixels);
+    // The DC term only reflects overall brightness, so it is left out of the median.
+    let mut sorted = coeffs[1..].to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let median = sorted[sorted.len() / 2];
+
+    let hash = coeffs
+      .iter()
+      .enumerate()
+      .fold(0u64, |acc, (i, c)| if *c > median { acc | (1u64 << i) } else { acc });
+    Ok(hash.to_be_bytes().to_vec())
+  }
+}
+
+/// The top-left HASH x HASH coefficients of the 2D DCT-II of a SAMPLE x SAMPLE image.
+fn low_frequencies(pixels: &[f32]) -> Vec<f32> {
+  let n = SAMPLE as f32;
+  let basis: Vec<f32> = (0..HASH)
+    .flat_map(|u| {
+      (0..SAMPLE).map(move |x| {
+        ((2.0 * x as f32 + 1.0) * u as f32 * std::f32::consts::PI / (2.0 * n)).cos()
+      })
+    })
+    .collect();
+
+  let mut coeffs = Vec::with_capacity(HASH * HASH);
+  for v in 0..HASH {
+    for u in 0..HASH {
+      let mut sum = 0.0f32;
+      for y in 0..SAMPLE {
+        for x in 0..SAMPLE {
+          sum += pixels[y * SAMPLE + x] * basis[u * SAMPLE + x] * basis[v * SAMPLE + y];
+        }
+      }
+      coeffs.push(sum);
+    }
+  }
+  coeffs
+}
@@ -0,0 +1,156 @@
+// adapters/watermark.rs
+
+//! Built-in block-mean luminance watermark.
+//!
+//! A 64-bit payload is spread over pairs of 8x8 pixel blocks: each pair encodes one
+//! bit as the sign of the difference between the two blocks' mean luminance, and the
+//! payload repeats across the image so detection can take a majority vote per bit.
+//! Mean luminance survives moderate re-compression and format conversion, which is
+//! the point of a soft binding; it does not survive cropping or rescaling.
+
+use image::{DynamicImage, ImageOutputFormat, RgbImage};
+
+use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::soft_binding::{SoftBinding, SoftBindingAlgorithm};
+
+const BLOCK: u32 = 8;
+const PAYLOAD_BITS: usize = 64;
+const JPEG_QUALITY: u8 = 95;
+
+/// Block-mean luminance watermark carrying a 64-bit content identifier.
+#[derive(Debug, Clone)]
+pub struct BlockMeanWatermark {
+  /// Target luminance difference between the two blocks of a pair (0-255 scale).
+  pub strength: f32,
+}
+
+impl Default for BlockMeanWatermark {
+  fn default() -> Self {
+    Self { strength: 6.0 }
+  }
+}
+
+impl BlockMeanWatermark {
+  pub const ALG: &'static str = "que.watermark.block-mean.1";
+}
+
+impl SoftBindingAlgorithm for BlockMeanWatermark {
+  fn alg(&self) -> &str {
+    Self::ALG
+  }
+
+  fn embed(&self, asset: &[u8], format: &str) -> EngineResult<SoftBinding> {
+    let output_format = output_format(format)?;
+    let mut rgb = decode(asset)?.to_rgb8();
+    let pairs = block_pairs(&rgb);
+    if pairs.len() < PAYLOAD_BITS {
+      return Err(EngineError::Config("image too small to carry a watermark".into()));
+    }
+
+    let payload = fnv1a64(rgb.as_raw());
+    for (i, (a, b)) in pairs.iter().enumerate() {
+      let bit = (payload >> (i % PAYLOAD_BITS)) & 1 == 1;
+      let target = if bit { self.strength } else { -self.strength };
+      let diff = block_luma(&rgb, *a) - block_luma(&rgb, *b);
+      let satisfied = if bit { diff >= target } else { diff <= target };
+      if !satisfied {
+        let shift = (target - diff) / 2.0;
+        shift_block(&mut rgb, *a, shift);
+        shift_block(&mut rgb, *b, -shift);
+      }
+    }
+
+    let mut out = std::io::Cursor::new(Vec::new());
+    DynamicImage::ImageRgb8(rgb)
+      .write_to(&mut out, output_format)
+      .map_err(|e| EngineError::Config(format!("failed to encode watermarked image: {e}")))?;
+
+    Ok(SoftBinding {
+      asset: out.into_inner(),
+      value: payload.to_be_bytes().to_vec(),
+    })
+  }
+
+  fn detect(&self, asset: &[u8], _format: &str) -> EngineResult<Option<Vec<u8>>> {
+    let rgb = decode(asset)?.to_rgb8();
+    let pairs = block_pairs(&rgb);
+    if pairs.len() < PAYLOAD_BITS {
+      return Ok(None);
+    }
+
+    let mut votes = [0i32; PAYLOAD_BITS];
+    let mut confident = 0usize;
+    for (i, (a, b)) in pairs.iter().enumerate() {
+      let diff = block_luma(&rgb, *a) - block_luma(&rgb, *b);
+      votes[i % PAYLOAD_BITS] += if diff >= 0.0 { 1 } else { -1 };
+      if diff.abs() >= self.strength / 2.0 {
+        confident += 1;
+      }
+    }
+
+    // Unmarked content has pair differences scattered around zero.
+    if confident * 4 < pairs.len() * 3 {
+      return Ok(None);
+    }
+
+    let payload = votes
+      .iter()
+      .enumerate()
+      .fold(0u64, |acc, (i, v)| if *v > 0 { acc | (1u64 << i) } else { acc });
+    Ok(Some(payload.to_be_bytes().to_vec()))
+  }
+}
+
+fn output_format(format: &str) -> EngineResult<ImageOutputFormat> {
+  match format {
+    "image/jpeg" => Ok(ImageOutputFormat::Jpeg(JPEG_QUALITY)),
+    "image/png" => Ok(ImageOutputFormat::Png),
+    other => Err(EngineError::UnsupportedFormat(format!(
+      "watermarking is not supported for {other} assets"
+    ))),
+  }
+}
+
+fn decode(asset: &[u8]) -> EngineResult<DynamicImage> {
+  image::load_from_memory(asset)
+    .map_err(|e| EngineError::Config(format!("failed to decode image: {e}")))
+}
+
+/// Adjacent block pairs in row-major order, as top-left block coordinates.
+fn block_pairs(img: &RgbImage) -> Vec<((u32, u32), (u32, u32))> {
+  let cols = img.width() / BLOCK;
+  let rows = img.height() / BLOCK;
+  let blocks: Vec<(u32, u32)> = (0..rows)
+    .flat_map(|r| (0..cols).map(move |c| (c * BLOCK, r * BLOCK)))
+    .collect();
+  blocks.chunks_exact(2).map(|p| (p[0], p[1])).collect()
+}
+
+fn block_luma(img: &RgbImage, (x0, y0): (u32, u32)) -> f32 {
+  let mut sum = 0.0f32;
+  for y in y0..y0 + BLOCK {
+    for x in x0..x0 + BLOCK {
+      let [r, g, b] = img.get_pixel(x, y).0;
+      sum += 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+    }
+  }
+  sum / (BLOCK * BLOCK) as f32
+}
+
+fn shift_block(img: &mut RgbImage, (x0, y0): (u32, u32), shift: f32) {
+  for y in y0..y0 + BLOCK {
+    for x in x0..x0 + BLOCK {
+      let px = img.get_pixel_mut(x, y);
+      for c in px.0.iter_mut() {
+        *c = (*c as f32 + shift).round().clamp(0.0, 255.0) as u8;
+      }
+    }
+  }
+}
+
+/// FNV-1a, used to derive a stable content identifier from the original pixels.
+fn fnv1a64(data: &[u8]) -> u64 {
+  data.iter().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+    (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+  })
+}
@@ -1,2 +1,4 @@
 pub mod signer;
+#[cfg(feature = "c2pa")]
+mod signer_cache;
 pub mod timestamper;
\ No newline at end of file
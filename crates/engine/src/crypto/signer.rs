@@ -1,13 +1,20 @@
 // crates/engine/src/crypto/signer.rs
 
 //! Signer abstraction for the engine.
-//! Today supports local files or env variables (dev). KMS/HSM/Enclave come next.
+//! Today supports local files, env variables (dev) and in-memory PEM. KMS/HSM/Enclave come next.
 
 use std::path::PathBuf;
 use std::str::FromStr;
 
 use thiserror::Error;
+use zeroize::Zeroizing;
 use crate::domain::error::{EngineError, EngineResult};
+#[cfg(feature = "c2pa")]
+use crate::domain::types::SigAlg;
+#[cfg(feature = "c2pa")]
+use crate::telemetry::trace_span;
+#[cfg(feature = "c2pa")]
+use super::signer_cache::{self, SignerIdentity};
 
 #[derive(Debug, Error)]
 pub enum SignerError {
@@ -27,10 +34,32 @@ impl From<SignerError> for EngineError {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub enum Signer {
     Local { cert_path: PathBuf, key_path: PathBuf },
     Env { cert_var: String, key_var: String },
+    /// PEM certificate chain and private key held in memory. Has no URI form.
+    /// The key is zeroized when the signer is dropped.
+    Pem { cert_pem: Vec<u8>, key_pem: Zeroizing<Vec<u8>> },
+}
+
+// Hand-written so `Pem` key material never reaches logs.
+impl std::fmt::Debug for Signer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Signer::Local { cert_path, key_path } => f
+                .debug_struct("Local")
+                .field("cert_path", cert_path)
+                .field("key_path", key_path)
+                .finish(),
+            Signer::Env { cert_var, key_var } => f
+                .debug_struct("Env")
+                .field("cert_var", cert_var)
+                .field("key_var", key_var)
+                .finish(),
+            Signer::Pem { .. } => f.debug_struct("Pem").finish_non_exhaustive(),
+        }
+    }
 }
 
 impl FromStr for Signer {
@@ -67,18 +96,42 @@ impl FromStr for Signer {
 }
 
 impl Signer {
+    /// Signer reading a PEM certificate chain and private key from files at signing time.
+    pub fn from_files(cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        Signer::Local { cert_path: cert_path.into(), key_path: key_path.into() }
+    }
+
+    /// Signer reading PEM from the environment variables `cert_var` and `key_var`
+    /// at signing time.
+    pub fn from_env(cert_var: impl Into<String>, key_var: impl Into<String>) -> Self {
+        Signer::Env { cert_var: cert_var.into(), key_var: key_var.into() }
+    }
+
+    /// Signer over a PEM certificate chain and private key already in memory,
+    /// without touching disk or the environment. The PEM is only parsed when signing.
+    pub fn from_pem(cert_pem: &[u8], key_pem: &[u8]) -> Self {
+        Signer::Pem { cert_pem: cert_pem.to_vec(), key_pem: Zeroizing::new(key_pem.to_vec()) }
+    }
+
     #[cfg(feature = "c2pa")]
     pub fn resolve(&self, alg: c2pa::SigningAlg) -> EngineResult<Box<dyn c2pa::Signer>> {
+        let _span = trace_span!("resolve_signer", signer = self.kind(), alg = %alg);
+        if let (Some(identity), Some(ttl)) = (self.cache_identity(), signer_cache::ttl()) {
+            let (cert_pem, key_pem) = self.load_pem()?;
+            return signer_cache::resolve(identity, &cert_pem, &key_pem, alg, ttl);
+        }
         match self {
             Signer::Local { cert_path, key_path } => {
                 let signer = c2pa::create_signer::from_files(cert_path, key_path, alg, None)
-                    .map_err(EngineError::C2pa)?;
+                    .map_err(EngineError::from)?;
                 Ok(signer)
             }
             Signer::Env { cert_var, key_var } => {
                 let cert_pem = std::env::var(cert_var)
                     .map_err(|_| SignerError::EnvVarNotFound(cert_var.clone()))?;
+                // Cleared on drop, once c2pa has parsed the key.
                 let key_pem = std::env::var(key_var)
+                    .map(Zeroizing::new)
                     .map_err(|_| SignerError::EnvVarNotFound(key_var.clone()))?;
 
                 let signer = c2pa::create_signer::from_keys(
@@ -86,10 +139,89 @@ impl Signer {
                     key_pem.as_bytes(),
                     alg,
                     None,
-                ).map_err(EngineError::C2pa)?;
+                ).map_err(EngineError::from)?;
                 
                 Ok(signer)
             }
+            Signer::Pem { cert_pem, key_pem } => {
+                let signer = c2pa::create_signer::from_keys(cert_pem, key_pem, alg, None)
+                    .map_err(EngineError::from)?;
+                Ok(signer)
+            }
+        }
+    }
+
+    /// Keep the parsed keys of `Local` and `Env` signers for up to `ttl`, so repeated
+    /// calls skip PEM parsing and key setup. Process-wide and off by default; `None`
+    /// turns it off and clears the cache.
+    ///
+    /// Credentials are still read on every call and a changed certificate or key is
+    /// parsed afresh. Entries are keyed by file paths or variable names and algorithm;
+    /// parsed keys stay in memory until they expire or `clear_cache` is called. `Pem`
+    /// signers are not cached.
+    #[cfg(feature = "c2pa")]
+    pub fn set_cache_ttl(ttl: Option<std::time::Duration>) {
+        signer_cache::set_ttl(ttl);
+    }
+
+    /// Drops every cached signer, releasing their parsed keys.
+    #[cfg(feature = "c2pa")]
+    pub fn clear_cache() {
+        signer_cache::clear();
+    }
+
+    /// Number of signers currently cached, including expired ones not yet evicted.
+    #[cfg(feature = "c2pa")]
+    pub fn cached_signers() -> usize {
+        signer_cache::len()
+    }
+
+    #[cfg(feature = "c2pa")]
+    fn cache_identity(&self) -> Option<SignerIdentity> {
+        match self {
+            Signer::Local { cert_path, key_path } => {
+                Some(SignerIdentity::Local { cert_path: cert_path.clone(), key_path: key_path.clone() })
+            }
+            Signer::Env { cert_var, key_var } => {
+                Some(SignerIdentity::Env { cert_var: cert_var.clone(), key_var: key_var.clone() })
+            }
+            Signer::Pem { .. } => None,
+        }
+    }
+
+    /// The PEM certificate chain and private key, read from files or the
+    /// environment for `Local` and `Env` signers.
+    pub(crate) fn load_pem(&self) -> EngineResult<(Vec<u8>, Zeroizing<Vec<u8>>)> {
+        match self {
+            Signer::Local { cert_path, key_path } => {
+                Ok((std::fs::read(cert_path)?, Zeroizing::new(std::fs::read(key_path)?)))
+            }
+            Signer::Env { cert_var, key_var } => {
+                let cert_pem = std::env::var(cert_var)
+                    .map_err(|_| SignerError::EnvVarNotFound(cert_var.clone()))?;
+                let key_pem = std::env::var(key_var)
+                    .map_err(|_| SignerError::EnvVarNotFound(key_var.clone()))?;
+                Ok((cert_pem.into_bytes(), Zeroizing::new(key_pem.into_bytes())))
+            }
+            Signer::Pem { cert_pem, key_pem } => Ok((cert_pem.clone(), key_pem.clone())),
+        }
+    }
+
+    /// Bytes c2pa would reserve for this signer's signature box with `alg`: the
+    /// signature, the certificate chain and time-stamp headroom. A starting point
+    /// for `C2paConfig::reserve_size`; resolves (and so reads) the credentials.
+    #[cfg(feature = "c2pa")]
+    pub fn reserve_size(&self, alg: SigAlg) -> EngineResult<usize> {
+        Ok(self.resolve(alg.to_c2pa())?.reserve_size())
+    }
+
+    /// Signer variant name, without any of its configuration.
+    #[cfg(feature = "tracing")]
+    fn kind(&self) -> &'static str {
+        match self {
+            Signer::Local { .. } => "local",
+            Signer::Env { .. } => "env",
+            Signer::Pem { .. } => "pem",
         }
     }
 }
\ No newline at end of file
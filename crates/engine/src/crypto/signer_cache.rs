@@ -0,0 +1,150 @@
+// crates/engine/src/crypto/signer_cache.rs
+
+//! Process-wide cache of parsed signing keys for `Local` and `Env` signers.
+//!
+//! Off until `Signer::set_cache_ttl` is called; until then c2pa builds a signer on
+//! every call. Entries are keyed by the signer's file paths or variable names and
+//! the algorithm, never by key material. The credentials are still read on every
+//! call and compared by SHA-256 digest, so a rotated key or certificate is picked
+//! up immediately rather than after the TTL.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock};
+use std::time::{Duration, Instant};
+
+use c2pa::crypto::raw_signature::{signer_from_cert_chain_and_private_key, RawSigner};
+use c2pa::SigningAlg;
+
+use crate::domain::error::EngineResult;
+
+/// Where a cached signer's credentials come from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum SignerIdentity {
+    Local { cert_path: std::path::PathBuf, key_path: std::path::PathBuf },
+    Env { cert_var: String, key_var: String },
+}
+
+type CacheKey = (SignerIdentity, String);
+
+struct Entry {
+    created: Instant,
+    /// SHA-256 over the certificate chain and key the signer was built from.
+    digest: Vec<u8>,
+    signer: Arc<dyn RawSigner + Send + Sync>,
+}
+
+/// TTL set with `Signer::set_cache_ttl`; `None` disables the cache.
+static TTL: RwLock<Option<Duration>> = RwLock::new(None);
+
+static CACHE: Mutex<Option<HashMap<CacheKey, Entry>>> = Mutex::new(None);
+
+pub(crate) fn set_ttl(ttl: Option<Duration>) {
+    let ttl = ttl.filter(|ttl| !ttl.is_zero());
+    *TTL.write().unwrap_or_else(PoisonError::into_inner) = ttl;
+    if ttl.is_none() {
+        clear();
+    }
+}
+
+pub(crate) fn clear() {
+    // Dropping the entries drops the parsed keys.
+    *cache() = None;
+}
+
+pub(crate) fn len() -> usize {
+    cache().as_ref().map_or(0, HashMap::len)
+}
+
+pub(crate) fn ttl() -> Option<Duration> {
+    *TTL.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// A signer for `identity` over `cert_pem` and `key_pem`, reusing the parsed key
+/// from an earlier call when it is younger than `ttl` and the credentials are unchanged.
+pub(crate) fn resolve(
+    identity: SignerIdentity,
+    cert_pem: &[u8],
+    key_pem: &[u8],
+    alg: SigningAlg,
+    ttl: Duration,
+) -> EngineResult<Box<dyn c2pa::Signer>> {
+    let key = (identity, alg.to_string());
+    let digest = digest(cert_pem, key_pem)?;
+    if let Some(entry) = cache().as_ref().and_then(|entries| entries.get(&key)) {
+        if entry.created.elapsed() < ttl && entry.digest == digest {
+            return Ok(Box::new(CachedSigner(entry.signer.clone())));
+        }
+    }
+
+    // Parsed outside the lock; a concurrent miss for the same key builds its own.
+    let signer = build(cert_pem, key_pem, alg)?;
+    let mut cache = cache();
+    let entries = cache.get_or_insert_with(HashMap::new);
+    entries.retain(|_, entry| entry.created.elapsed() < ttl);
+    entries.insert(key, Entry { created: Instant::now(), digest, signer: signer.clone() });
+    Ok(Box::new(CachedSigner(signer)))
+}
+
+fn build(cert_pem: &[u8], key_pem: &[u8], alg: SigningAlg) -> EngineResult<Arc<dyn RawSigner + Send + Sync>> {
+    let signer = signer_from_cert_chain_and_private_key(cert_pem, key_pem, alg, None)
+        .map_err(c2pa::Error::from)?;
+    Ok(Arc::from(signer))
+}
+
+fn digest(cert_pem: &[u8], key_pem: &[u8]) -> EngineResult<Vec<u8>> {
+    let mut credentials = zeroize::Zeroizing::new(Vec::with_capacity(cert_pem.len() + key_pem.len()));
+    credentials.extend_from_slice(cert_pem);
+    credentials.extend_from_slice(key_pem);
+    Ok(c2pa::hash_stream_by_alg("sha256", &mut std::io::Cursor::new(&credentials[..]), None, true)?)
+}
+
+fn cache() -> MutexGuard<'static, Option<HashMap<CacheKey, Entry>>> {
+    CACHE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// c2pa signer over a shared raw signer, forwarding like c2pa's own wrapper.
+struct CachedSigner(Arc<dyn RawSigner + Send + Sync>);
+
+impl c2pa::Signer for CachedSigner {
+    fn sign(&self, data: &[u8]) -> c2pa::Result<Vec<u8>> {
+        Ok(self.0.sign(data)?)
+    }
+
+    fn alg(&self) -> SigningAlg {
+        self.0.alg()
+    }
+
+    fn certs(&self) -> c2pa::Result<Vec<Vec<u8>>> {
+        Ok(self.0.cert_chain()?)
+    }
+
+    fn reserve_size(&self) -> usize {
+        self.0.reserve_size()
+    }
+
+    fn ocsp_val(&self) -> Option<Vec<u8>> {
+        self.0.ocsp_response()
+    }
+
+    fn time_authority_url(&self) -> Option<String> {
+        self.0.time_stamp_service_url()
+    }
+
+    fn timestamp_request_headers(&self) -> Option<Vec<(String, String)>> {
+        self.0.time_stamp_request_headers()
+    }
+
+    fn timestamp_request_body(&self, message: &[u8]) -> c2pa::Result<Vec<u8>> {
+        Ok(self.0.time_stamp_request_body(message)?)
+    }
+
+    fn send_timestamp_request(&self, message: &[u8]) -> Option<c2pa::Result<Vec<u8>>> {
+        self.0
+            .send_time_stamp_request(message)
+            .map(|r| r.map_err(Into::into))
+    }
+
+    fn raw_signer(&self) -> Option<Box<&dyn RawSigner>> {
+        Some(Box::new(&*self.0))
+    }
+}
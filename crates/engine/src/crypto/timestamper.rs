@@ -12,10 +12,33 @@ pub enum TimestamperError {
 }
 
 /// Source for a cryptographic timestamp.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub enum Timestamper {
     Digicert,
     Custom(String),
+    /// An RFC 3161 TSA that needs extra request headers, such as `Authorization`.
+    /// The request is sent by the engine's signer rather than from the manifest's
+    /// `ta_url`, so the headers reach the TSA. `Content-Type` is set by c2pa and may
+    /// not be overridden.
+    CustomWithHeaders {
+        url: String,
+        headers: Vec<(String, String)>,
+    },
+}
+
+impl std::fmt::Debug for Timestamper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Timestamper::Digicert => f.write_str("Digicert"),
+            Timestamper::Custom(url) => f.debug_tuple("Custom").field(url).finish(),
+            // Header values are often credentials; only their names are shown.
+            Timestamper::CustomWithHeaders { url, headers } => f
+                .debug_struct("CustomWithHeaders")
+                .field("url", url)
+                .field("headers", &headers.iter().map(|(name, _)| name).collect::<Vec<_>>())
+                .finish(),
+        }
+    }
 }
 
 impl FromStr for Timestamper {
@@ -38,7 +61,15 @@ impl Timestamper {
             Timestamper::Digicert => {
                 Some("https://timestamp.digicert.com".to_string())
             }
-            Timestamper::Custom(url) => Some(url.clone()),
+            Timestamper::Custom(url) | Timestamper::CustomWithHeaders { url, .. } => Some(url.clone()),
+        }
+    }
+
+    /// Extra headers to send with the timestamp request, if any.
+    pub fn headers(&self) -> Option<&[(String, String)]> {
+        match self {
+            Timestamper::CustomWithHeaders { headers, .. } => Some(headers),
+            _ => None,
         }
     }
 }
\ No newline at end of file
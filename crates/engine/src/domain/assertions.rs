@@ -0,0 +1,563 @@
+// crates/engine/src/domain/assertions.rs
+
+//! Typed builders for common assertions (`c2pa.actions.v2`, `stds.exif`, `stds.iptc`,
+//! `cawg.training-mining`).
+//! Each serializes to the JSON expected in a manifest definition, e.g. via
+//! `ManifestBuilder::add_assertion`.
+
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Map, Value};
+
+use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::manifest_builder::{Action, IngredientRelationship, INGREDIENT_IDS};
+
+const EXIF_NS: &str = "http://ns.adobe.com/exif/1.0/";
+const EXIF_EX_NS: &str = "http://cipa.jp/exif/2.32/";
+const TIFF_NS: &str = "http://ns.adobe.com/tiff/1.0/";
+const DC_NS: &str = "http://purl.org/dc/elements/1.1/";
+const PHOTOSHOP_NS: &str = "http://ns.adobe.com/photoshop/1.0/";
+const IPTC_EXT_NS: &str = "http://iptc.org/std/Iptc4xmpExt/2008-02-29/";
+const XMP_RIGHTS_NS: &str = "http://ns.adobe.com/xap/1.0/rights/";
+
+/// Prefix for the IPTC digital source type vocabulary.
+const DIGITAL_SOURCE_TYPE_BASE: &str = "https://cv.iptc.org/newscodes/digitalsourcetype/";
+
+/// A `c2pa.actions.v2` assertion whose ingredient references are checked before signing.
+///
+/// Ingredients are declared by the `label` they carry in the manifest definition.
+/// `to_value` fails when an action references an undeclared ingredient, or when
+/// `c2pa.opened` lacks a `parentOf` ingredient or `c2pa.placed`/`c2pa.removed` lacks a
+/// `componentOf` one, which c2pa would otherwise only report after signing.
+///
+/// ```
+/// use que_engine::{Action, ActionsBuilder, IngredientRelationship};
+///
+/// let actions = ActionsBuilder::new()
+///     .ingredient("original.jpg", IngredientRelationship::ParentOf)
+///     .add_action(Action::new("c2pa.opened").ingredient("original.jpg"))
+///     .add_action(Action::new("c2pa.color_adjustments"));
+/// assert!(actions.to_value().is_ok());
+///
+/// let missing = ActionsBuilder::new().add_action(Action::new("c2pa.opened"));
+/// assert!(missing.to_value().is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActionsBuilder {
+    ingredients: Vec<(String, IngredientRelationship)>,
+    actions: Vec<Action>,
+}
+
+impl ActionsBuilder {
+    pub const LABEL: &'static str = "c2pa.actions.v2";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare an ingredient that actions may reference, by its `label`.
+    pub fn ingredient(mut self, id: impl Into<String>, relationship: IngredientRelationship) -> Self {
+        self.ingredients.push((id.into(), relationship));
+        self
+    }
+
+    pub fn add_action(mut self, action: Action) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// The assertion data; every problem found is listed in the `Config` error.
+    pub fn to_value(&self) -> EngineResult<Value> {
+        let declared: HashMap<&str, IngredientRelationship> =
+            self.ingredients.iter().map(|(id, rel)| (id.as_str(), *rel)).collect();
+        let mut problems = Vec::new();
+
+        for (index, action) in self.actions.iter().enumerate() {
+            let name = &action.action;
+            let ids = match referenced_ids(action) {
+                Ok(ids) => ids,
+                Err(problem) => {
+                    problems.push(format!("action {index} ({name}): {problem}"));
+                    continue;
+                }
+            };
+            let mut relationships = Vec::new();
+            for id in &ids {
+                match declared.get(id.as_str()) {
+                    Some(rel) => relationships.push(*rel),
+                    None => problems.push(format!("action {index} ({name}) references unknown ingredient '{id}'")),
+                }
+            }
+            if let Some(required) = required_relationship(name) {
+                if !relationships.contains(&required) {
+                    problems.push(format!(
+                        "action {index} ({name}) must reference a {} ingredient",
+                        required.as_str()
+                    ));
+                }
+            }
+        }
+
+        if !problems.is_empty() {
+            return Err(EngineError::Config(format!("invalid actions: {}", problems.join("; "))));
+        }
+        let actions: Vec<Value> = self.actions.iter().map(Action::to_value).collect();
+        Ok(json!({ "actions": actions }))
+    }
+}
+
+/// Ingredient ids from `Action::ingredient` and any `ingredientIds` already in the parameters.
+fn referenced_ids(action: &Action) -> Result<Vec<String>, &'static str> {
+    let mut ids = action.ingredient_ids.clone();
+    match &action.parameters {
+        None => {}
+        Some(Value::Object(params)) => match params.get(INGREDIENT_IDS) {
+            None => {}
+            Some(Value::Array(values)) => {
+                for value in values {
+                    ids.push(value.as_str().ok_or("ingredientIds must be strings")?.to_owned());
+                }
+            }
+            Some(_) => return Err("ingredientIds must be an array"),
+        },
+        Some(_) => return Err("parameters must be a JSON object"),
+    }
+    Ok(ids)
+}
+
+/// Relationship the C2PA action rules require of at least one referenced ingredient.
+fn required_relationship(action: &str) -> Option<IngredientRelationship> {
+    match action {
+        "c2pa.opened" => Some(IngredientRelationship::ParentOf),
+        "c2pa.placed" | "c2pa.removed" => Some(IngredientRelationship::ComponentOf),
+        _ => None,
+    }
+}
+
+/// Camera and location metadata for a `stds.exif` assertion.
+///
+/// ```
+/// use que_engine::{ExifAssertion, ManifestBuilder};
+///
+/// let exif = ExifAssertion::new()
+///     .gps(39.3517, -74.4429)
+///     .altitude(3.38)
+///     .make("CameraCompany")
+///     .model("Shooter S1");
+/// let manifest = ManifestBuilder::new()
+///     .add_assertion(ExifAssertion::LABEL, exif.to_value().unwrap())
+///     .build();
+/// assert!(manifest.contains("39,21.102N"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExifAssertion {
+    gps: Option<(f64, f64)>,
+    altitude: Option<f64>,
+    gps_timestamp: Option<SystemTime>,
+    date_time_original: Option<SystemTime>,
+    make: Option<String>,
+    model: Option<String>,
+    lens_model: Option<String>,
+}
+
+impl ExifAssertion {
+    pub const LABEL: &'static str = "stds.exif";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Position in decimal degrees; north and east are positive.
+    pub fn gps(mut self, latitude: f64, longitude: f64) -> Self {
+        self.gps = Some((latitude, longitude));
+        self
+    }
+
+    /// Altitude in metres; negative values are below sea level.
+    pub fn altitude(mut self, meters: f64) -> Self {
+        self.altitude = Some(meters);
+        self
+    }
+
+    /// Time of the GPS fix.
+    pub fn gps_timestamp(mut self, time: SystemTime) -> Self {
+        self.gps_timestamp = Some(time);
+        self
+    }
+
+    /// Time the picture was taken.
+    pub fn date_time_original(mut self, time: SystemTime) -> Self {
+        self.date_time_original = Some(time);
+        self
+    }
+
+    pub fn make(mut self, make: impl Into<String>) -> Self {
+        self.make = Some(make.into());
+        self
+    }
+
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    pub fn lens_model(mut self, lens_model: impl Into<String>) -> Self {
+        self.lens_model = Some(lens_model.into());
+        self
+    }
+
+    /// The assertion data. Fails when coordinates or altitude are out of range or not finite.
+    pub fn to_value(&self) -> EngineResult<Value> {
+        let mut context = Map::new();
+        let mut data = Map::new();
+
+        if let Some((latitude, longitude)) = self.gps {
+            if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+                return Err(EngineError::Config(format!(
+                    "GPS position out of range: {latitude}, {longitude}"
+                )));
+            }
+            data.insert("exif:GPSVersionID".into(), "2.2.0.0".into());
+            data.insert("exif:GPSLatitude".into(), gps_coordinate(latitude, 'N', 'S').into());
+            data.insert("exif:GPSLongitude".into(), gps_coordinate(longitude, 'E', 'W').into());
+        }
+        if let Some(meters) = self.altitude {
+            if !meters.is_finite() {
+                return Err(EngineError::Config(format!("GPS altitude is not finite: {meters}")));
+            }
+            data.insert("exif:GPSAltitudeRef".into(), u8::from(meters < 0.0).into());
+            data.insert("exif:GPSAltitude".into(), rational(meters.abs()).into());
+        }
+        if let Some(time) = self.gps_timestamp {
+            data.insert("exif:GPSTimeStamp".into(), rfc3339(time).into());
+        }
+        if let Some(time) = self.date_time_original {
+            data.insert("exif:DateTimeOriginal".into(), rfc3339(time).into());
+        }
+        if !data.is_empty() {
+            context.insert("exif".into(), EXIF_NS.into());
+        }
+
+        if self.make.is_some() || self.model.is_some() {
+            context.insert("tiff".into(), TIFF_NS.into());
+        }
+        insert_opt(&mut data, "tiff:Make", &self.make);
+        insert_opt(&mut data, "tiff:Model", &self.model);
+        if self.lens_model.is_some() {
+            context.insert("exifEX".into(), EXIF_EX_NS.into());
+        }
+        insert_opt(&mut data, "exifEX:LensModel", &self.lens_model);
+
+        Ok(with_context(context, data))
+    }
+}
+
+/// Creator and rights metadata for a `stds.iptc` assertion.
+///
+/// ```
+/// use que_engine::IptcAssertion;
+///
+/// let iptc = IptcAssertion::new()
+///     .creator("Jane Doe")
+///     .rights("Copyright (C) 2024 Jane Doe")
+///     .digital_source_type("digitalCapture")
+///     .to_value();
+/// assert_eq!(iptc["dc:creator"][0], "Jane Doe");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IptcAssertion {
+    creators: Vec<String>,
+    rights: Option<String>,
+    web_statement: Option<String>,
+    digital_source_type: Option<String>,
+    headline: Option<String>,
+    description: Option<String>,
+    keywords: Vec<String>,
+    credit: Option<String>,
+    date_created: Option<SystemTime>,
+}
+
+impl IptcAssertion {
+    pub const LABEL: &'static str = "stds.iptc";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a creator (`dc:creator`).
+    pub fn creator(mut self, creator: impl Into<String>) -> Self {
+        self.creators.push(creator.into());
+        self
+    }
+
+    /// Copyright notice (`dc:rights`).
+    pub fn rights(mut self, rights: impl Into<String>) -> Self {
+        self.rights = Some(rights.into());
+        self
+    }
+
+    /// URL of the usage terms (`xmpRights:WebStatement`).
+    pub fn web_statement(mut self, url: impl Into<String>) -> Self {
+        self.web_statement = Some(url.into());
+        self
+    }
+
+    /// IPTC digital source type, either a full vocabulary URI or a bare code
+    /// such as `digitalCapture` or `trainedAlgorithmicMedia`.
+    pub fn digital_source_type(mut self, source_type: impl Into<String>) -> Self {
+        self.digital_source_type = Some(source_type.into());
+        self
+    }
+
+    pub fn headline(mut self, headline: impl Into<String>) -> Self {
+        self.headline = Some(headline.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Append a keyword (`dc:subject`).
+    pub fn keyword(mut self, keyword: impl Into<String>) -> Self {
+        self.keywords.push(keyword.into());
+        self
+    }
+
+    pub fn credit(mut self, credit: impl Into<String>) -> Self {
+        self.credit = Some(credit.into());
+        self
+    }
+
+    pub fn date_created(mut self, time: SystemTime) -> Self {
+        self.date_created = Some(time);
+        self
+    }
+
+    /// The assertion data.
+    pub fn to_value(&self) -> Value {
+        let mut context = Map::new();
+        let mut data = Map::new();
+
+        if !self.creators.is_empty() {
+            data.insert("dc:creator".into(), self.creators.clone().into());
+        }
+        insert_opt(&mut data, "dc:rights", &self.rights);
+        insert_opt(&mut data, "dc:description", &self.description);
+        if !self.keywords.is_empty() {
+            data.insert("dc:subject".into(), self.keywords.clone().into());
+        }
+        if !data.is_empty() {
+            context.insert("dc".into(), DC_NS.into());
+        }
+
+        if let Some(url) = &self.web_statement {
+            context.insert("xmpRights".into(), XMP_RIGHTS_NS.into());
+            data.insert("xmpRights:WebStatement".into(), url.as_str().into());
+        }
+        if let Some(source_type) = &self.digital_source_type {
+            let uri = if source_type.contains("://") {
+                source_type.clone()
+            } else {
+                format!("{DIGITAL_SOURCE_TYPE_BASE}{source_type}")
+            };
+            context.insert("Iptc4xmpExt".into(), IPTC_EXT_NS.into());
+            data.insert("Iptc4xmpExt:DigitalSourceType".into(), uri.into());
+        }
+        if self.headline.is_some() || self.credit.is_some() || self.date_created.is_some() {
+            context.insert("photoshop".into(), PHOTOSHOP_NS.into());
+        }
+        insert_opt(&mut data, "photoshop:Headline", &self.headline);
+        insert_opt(&mut data, "photoshop:Credit", &self.credit);
+        if let Some(time) = self.date_created {
+            data.insert("photoshop:DateCreated".into(), rfc3339(time).into());
+        }
+
+        with_context(context, data)
+    }
+}
+
+/// Permission for one training/data-mining category.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrainingMiningUse {
+    Allowed,
+    NotAllowed,
+    /// Allowed under conditions described by `info` (e.g. a contact or licence URL).
+    Constrained { info: Option<String> },
+}
+
+impl TrainingMiningUse {
+    fn to_value(&self) -> Value {
+        match self {
+            TrainingMiningUse::Allowed => json!({ "use": "allowed" }),
+            TrainingMiningUse::NotAllowed => json!({ "use": "notAllowed" }),
+            TrainingMiningUse::Constrained { info: None } => json!({ "use": "constrained" }),
+            TrainingMiningUse::Constrained { info: Some(info) } => {
+                json!({ "use": "constrained", "constraint_info": info })
+            }
+        }
+    }
+
+    fn from_value(category: &str, value: &Value) -> EngineResult<Self> {
+        match value.get("use").and_then(Value::as_str) {
+            Some("allowed") => Ok(TrainingMiningUse::Allowed),
+            Some("notAllowed") => Ok(TrainingMiningUse::NotAllowed),
+            Some("constrained") => Ok(TrainingMiningUse::Constrained {
+                info: value.get("constraint_info").and_then(Value::as_str).map(str::to_owned),
+            }),
+            other => Err(EngineError::Config(format!(
+                "invalid use {other:?} for {category} in {}",
+                TrainingMiningAssertion::LABEL
+            ))),
+        }
+    }
+}
+
+/// The `cawg.training-mining` assertion. Categories left as `None` are omitted.
+///
+/// ```
+/// use que_engine::{TrainingMiningAssertion, TrainingMiningUse};
+///
+/// let tdm = TrainingMiningAssertion {
+///     ai_training: Some(TrainingMiningUse::NotAllowed),
+///     data_mining: Some(TrainingMiningUse::Constrained { info: Some("licensing@example.com".into()) }),
+///     ..Default::default()
+/// };
+/// let value = tdm.to_value();
+/// assert_eq!(value["entries"]["cawg.ai_training"]["use"], "notAllowed");
+/// assert_eq!(TrainingMiningAssertion::from_value(&value).unwrap(), tdm);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrainingMiningAssertion {
+    pub ai_generative_training: Option<TrainingMiningUse>,
+    pub ai_inference: Option<TrainingMiningUse>,
+    pub ai_training: Option<TrainingMiningUse>,
+    pub data_mining: Option<TrainingMiningUse>,
+}
+
+impl TrainingMiningAssertion {
+    pub const LABEL: &'static str = "cawg.training-mining";
+
+    fn categories(&self) -> [(&'static str, &Option<TrainingMiningUse>); 4] {
+        [
+            ("cawg.ai_generative_training", &self.ai_generative_training),
+            ("cawg.ai_inference", &self.ai_inference),
+            ("cawg.ai_training", &self.ai_training),
+            ("cawg.data_mining", &self.data_mining),
+        ]
+    }
+
+    /// The assertion data.
+    pub fn to_value(&self) -> Value {
+        let entries: Map<String, Value> = self
+            .categories()
+            .into_iter()
+            .filter_map(|(key, permission)| permission.as_ref().map(|p| (key.to_owned(), p.to_value())))
+            .collect();
+        json!({ "entries": entries })
+    }
+
+    /// Parse assertion data. Unknown categories are ignored; a missing or unknown
+    /// `use` value is a `Config` error.
+    pub fn from_value(data: &Value) -> EngineResult<Self> {
+        let entries = data
+            .get("entries")
+            .and_then(Value::as_object)
+            .ok_or_else(|| EngineError::Config(format!("{} has no entries object", Self::LABEL)))?;
+        let parse = |key: &str| {
+            entries.get(key).map(|v| TrainingMiningUse::from_value(key, v)).transpose()
+        };
+        Ok(Self {
+            ai_generative_training: parse("cawg.ai_generative_training")?,
+            ai_inference: parse("cawg.ai_inference")?,
+            ai_training: parse("cawg.ai_training")?,
+            data_mining: parse("cawg.data_mining")?,
+        })
+    }
+
+    /// Read the assertion from the active manifest of a JSON verification report
+    /// (`VerifyMode::Summary` or `Info`). `None` when the manifest does not carry it.
+    pub fn from_report(report: &str) -> EngineResult<Option<Self>> {
+        let report: Value = serde_json::from_str(report)?;
+        let Some(active) = report.get("active_manifest").and_then(Value::as_str) else {
+            return Ok(None);
+        };
+        let assertions = report["manifests"][active]["assertions"].as_array();
+        assertions
+            .into_iter()
+            .flatten()
+            .find(|a| a["label"].as_str().is_some_and(|l| l == Self::LABEL))
+            .map(|a| Self::from_value(&a["data"]))
+            .transpose()
+    }
+}
+
+fn insert_opt(data: &mut Map<String, Value>, key: &str, value: &Option<String>) {
+    if let Some(value) = value {
+        data.insert(key.into(), value.as_str().into());
+    }
+}
+
+fn with_context(context: Map<String, Value>, data: Map<String, Value>) -> Value {
+    let mut obj = Map::new();
+    obj.insert("@context".into(), Value::Object(context));
+    obj.extend(data);
+    Value::Object(obj)
+}
+
+/// XMP `GPSCoordinate` form: whole degrees, decimal minutes and a hemisphere letter,
+/// e.g. `39,21.102N`.
+fn gps_coordinate(degrees: f64, positive: char, negative: char) -> String {
+    let hemisphere = if degrees < 0.0 { negative } else { positive };
+    let degrees = degrees.abs();
+    let mut whole = degrees.trunc() as u32;
+    let mut minutes = format!("{:.6}", degrees.fract() * 60.0);
+    // Rounding can carry into the next degree (e.g. 59.9999999 minutes).
+    if minutes.starts_with("60") {
+        whole += 1;
+        minutes = "0".into();
+    }
+    let minutes = minutes.trim_end_matches('0').trim_end_matches('.');
+    format!("{whole},{minutes}{hemisphere}")
+}
+
+/// A non-negative value as an EXIF rational, to the millimetre.
+fn rational(value: f64) -> String {
+    let numerator = (value * 1000.0).round() as u64;
+    let divisor = gcd(numerator, 1000);
+    format!("{}/{}", numerator / divisor, 1000 / divisor)
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// UTC timestamp with second precision, e.g. `2019-09-22T18:22:57Z`.
+/// Times before the Unix epoch are clamped to it.
+fn rfc3339(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+    let (year, month, day) = civil_from_days(days as i64);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+/// Gregorian date for a day count since 1970-01-01 (Howard Hinnant's algorithm).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
@@ -0,0 +1,74 @@
+// crates/engine/src/domain/cancel.rs
+
+//! Cooperative cancellation for long-running sign and verify calls.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use super::error::{EngineError, EngineResult};
+
+/// Shared flag asking an in-flight call to stop. Clones share the flag, so a token
+/// can be kept by the caller while a clone travels in the config.
+///
+/// Cancellation is cooperative: the engine checks the flag between pipeline phases
+/// and while copying or reading the asset, then fails with
+/// [`EngineError::Cancelled`]. A call that has already finished is not undone.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// A guard that cancels this token when dropped, unless disarmed first. Held
+    /// inside a future that hands work to another thread, it turns dropping the
+    /// future into a cancellation of that work.
+    pub fn drop_guard(&self) -> CancelOnDrop {
+        CancelOnDrop(Some(self.clone()))
+    }
+}
+
+/// Cancels its token on drop; see [`CancellationToken::drop_guard`].
+#[derive(Debug)]
+pub struct CancelOnDrop(Option<CancellationToken>);
+
+impl CancelOnDrop {
+    /// Releases the guard without cancelling, returning the token.
+    pub fn disarm(mut self) -> CancellationToken {
+        self.0.take().unwrap_or_default()
+    }
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if let Some(token) = self.0.take() {
+            token.cancel();
+        }
+    }
+}
+
+/// `Err(EngineError::Cancelled)` once `token` has been cancelled.
+pub(crate) fn check_cancelled(token: Option<&CancellationToken>) -> EngineResult<()> {
+    match token {
+        Some(token) if token.is_cancelled() => Err(EngineError::Cancelled),
+        _ => Ok(()),
+    }
+}
+
+/// A failure seen after cancellation is reported as `Cancelled`: the SDK surfaces an
+/// interrupted read as its own I/O error.
+pub(crate) fn or_cancelled<T>(token: Option<&CancellationToken>, result: EngineResult<T>) -> EngineResult<T> {
+    match result {
+        Err(_) if token.is_some_and(CancellationToken::is_cancelled) => Err(EngineError::Cancelled),
+        result => result,
+    }
+}
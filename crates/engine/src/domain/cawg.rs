@@ -3,7 +3,7 @@ use crate::crypto::signer::Signer;
 use crate::crypto::timestamper::Timestamper;
 use crate::SigAlg;
 
-/// CAWG X.509 identity configuration for signing.
+/// CAWG identity credential source for signing.
 /// This defines the identity assertion that will be added to the C2PA manifest.
 #[cfg(feature = "cawg")]
 #[derive(Debug, Clone)]
@@ -12,6 +12,17 @@ pub enum CawgSigner {
     UseMainSigner,
     /// Use a separate certificate/key for CAWG identity
     Separate(Signer),
+    /// DID-based identity issued as a W3C verifiable credential
+    /// (CAWG identity claims aggregation).
+    Did {
+        /// The issuing DID, e.g. `did:web:example.com`
+        did: String,
+        /// Verified identity claims placed in the credential subject
+        /// (e.g. `{ "verifiedIdentities": [...] }`)
+        credential: serde_json::Value,
+        /// Key referenced by the DID document's verification method
+        signer: Signer,
+    },
 }
 
 /// CAWG X.509 identity configuration for signing.
@@ -53,4 +64,16 @@ pub struct CawgVerification {
     /// Signature information extracted from the CAWG identity assertion
     #[serde(skip_serializing_if = "Option::is_none")]
     pub signature_info: Option<serde_json::Value>,
+    /// Human-readable name of the verified identity (named actor / credential subject).
+    /// Only populated when the identity assertion was successfully validated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity_name: Option<String>,
+    /// Verified identity claims (the credential's `verifiedIdentities`, or X.509 subject details).
+    /// Only populated when the identity assertion was successfully validated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claims: Option<serde_json::Value>,
+    /// Issuing DID for DID-based identities.
+    /// Only populated when the identity assertion was successfully validated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub did: Option<String>,
 }
@@ -0,0 +1,123 @@
+// crates/engine/src/domain/diff.rs
+
+//! Differences between the active manifests of two assets.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// How an entry differs between the two manifests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// Present only in the second manifest.
+    Added,
+    /// Present only in the first manifest.
+    Removed,
+    /// Present in both with different content.
+    Changed,
+}
+
+/// One differing entry, with its parsed JSON on each side.
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestChange {
+    /// Assertion label, ingredient title or `signature_info` field name. Repeated
+    /// assertion labels and ingredient titles are numbered in order: `label#2`.
+    pub key: String,
+    pub kind: ChangeKind,
+    /// Value in the first manifest; `None` when `Added`.
+    pub before: Option<Value>,
+    /// Value in the second manifest; `None` when `Removed`.
+    pub after: Option<Value>,
+}
+
+/// Result of [`diff_manifests`](crate::diff_manifests).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ManifestDiff {
+    pub assertions: Vec<ManifestChange>,
+    pub ingredients: Vec<ManifestChange>,
+    /// Differences in the claim signature's `signature_info` (issuer, serial, time, ...).
+    pub signer: Vec<ManifestChange>,
+}
+
+impl ManifestDiff {
+    /// Whether the two manifests have no differences.
+    pub fn is_empty(&self) -> bool {
+        self.assertions.is_empty() && self.ingredients.is_empty() && self.signer.is_empty()
+    }
+
+    /// Compare two manifests in the c2pa manifest JSON layout (`assertions`,
+    /// `ingredients`, `signature_info`).
+    pub fn between(a: &Value, b: &Value) -> Self {
+        ManifestDiff {
+            assertions: diff_keyed(&keyed_entries(a, "assertions", "label"), &keyed_entries(b, "assertions", "label")),
+            ingredients: diff_keyed(&keyed_entries(a, "ingredients", "title"), &keyed_entries(b, "ingredients", "title")),
+            signer: diff_keyed(&object_entries(a, "signature_info"), &object_entries(b, "signature_info")),
+        }
+    }
+}
+
+/// Entries of the array `field`, keyed by their `key_field`, numbering repeats.
+fn keyed_entries(manifest: &Value, field: &str, key_field: &str) -> Vec<(String, Value)> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let items = manifest.get(field).and_then(Value::as_array).into_iter().flatten();
+    items
+        .enumerate()
+        .map(|(i, item)| {
+            let base = item
+                .get(key_field)
+                .and_then(Value::as_str)
+                .map_or_else(|| format!("#{}", i + 1), str::to_string);
+            let count = seen.entry(base.clone()).or_default();
+            *count += 1;
+            let key = if *count == 1 { base } else { format!("{base}#{count}") };
+            (key, item.clone())
+        })
+        .collect()
+}
+
+/// Fields of the object `field`.
+fn object_entries(manifest: &Value, field: &str) -> Vec<(String, Value)> {
+    manifest
+        .get(field)
+        .and_then(Value::as_object)
+        .map(|obj| obj.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+        .unwrap_or_default()
+}
+
+/// Removed and changed entries in `a`'s order, then added entries in `b`'s order.
+fn diff_keyed(a: &[(String, Value)], b: &[(String, Value)]) -> Vec<ManifestChange> {
+    let find = |entries: &[(String, Value)], key: &str| {
+        entries.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+    };
+    let mut changes = Vec::new();
+    for (key, before) in a {
+        match find(b, key) {
+            None => changes.push(ManifestChange {
+                key: key.clone(),
+                kind: ChangeKind::Removed,
+                before: Some(before.clone()),
+                after: None,
+            }),
+            Some(after) if &after != before => changes.push(ManifestChange {
+                key: key.clone(),
+                kind: ChangeKind::Changed,
+                before: Some(before.clone()),
+                after: Some(after),
+            }),
+            Some(_) => {}
+        }
+    }
+    for (key, after) in b {
+        if find(a, key).is_none() {
+            changes.push(ManifestChange {
+                key: key.clone(),
+                kind: ChangeKind::Added,
+                before: None,
+                after: Some(after.clone()),
+            });
+        }
+    }
+    changes
+}
@@ -6,6 +6,43 @@ pub enum EngineError {
   #[error("configuration: {0}")]
   Config(String),
 
+  /// An input exceeds its `LimitsConfig` bound (`max_in_memory_asset_size` or
+  /// `max_stream_copy_size`). `size` is the number of bytes seen when the limit tripped.
+  #[error("configuration: asset too large: {size} bytes exceeds limit of {limit}")]
+  AssetTooLarge { size: u64, limit: u64 },
+
+  /// A result exceeds `LimitsConfig::max_in_memory_output_size`.
+  #[error("configuration: output too large to return in memory: {size} bytes exceeds limit of {limit}")]
+  OutputTooLarge { size: u64, limit: u64 },
+
+  /// A manifest store (remote, sidecar or extracted) exceeds `LimitsConfig::max_manifest_size`.
+  #[error("configuration: manifest too large: {size} bytes exceeds limit of {limit}")]
+  ManifestTooLarge { size: u64, limit: u64 },
+
+  /// The asset's format is unknown or not supported by the requested operation.
+  #[error("configuration: {0}")]
+  UnsupportedFormat(String),
+
+  /// A URL was rejected: malformed, disallowed scheme, or a private/loopback host.
+  #[error("configuration: {0}")]
+  InvalidUrl(String),
+
+  /// Reading a stream or remote asset timed out.
+  #[error("stream read timed out")]
+  StreamTimeout,
+
+  /// No temporary directory could be created under `path` (the config's `temp_dir`,
+  /// or the system default), e.g. on a read-only root filesystem.
+  #[error(
+    "configuration: cannot create a temporary directory in {}: {source}; set temp_dir on the config to a writable directory, or sign Bytes or Stream sources to OutputTarget::Memory, which needs no temporary files",
+    path.display()
+  )]
+  TempDirUnavailable { path: std::path::PathBuf, source: std::io::Error },
+
+  /// The signing certificate is outside its validity period.
+  #[error("the certificate was not valid at time of signing")]
+  CertificateExpired,
+
   #[error(transparent)]
   Io(#[from] std::io::Error),
 
@@ -16,9 +53,14 @@ pub enum EngineError {
   #[error(transparent)]
   Glob(#[from] glob::PatternError),
 
+  /// One or more init-segment groups failed; the remaining groups were still signed.
+  #[cfg(feature = "bmff")]
+  #[error("{} fragmented BMFF segment group(s) failed: {}", failures.len(), describe_failures(failures))]
+  FragmentedBmff { failures: Vec<SegmentFailure> },
+
   #[cfg(feature = "c2pa")]
   #[error(transparent)]
-  C2pa(#[from] c2pa::Error),
+  C2pa(c2pa::Error),
 
   #[error("feature not enabled: {0}")]
   Feature(&'static str),
@@ -26,9 +68,78 @@ pub enum EngineError {
   #[error("verification failed")]
   VerificationFailed,
 
+  /// The call's `CancellationToken` was cancelled before it finished.
+  #[error("operation cancelled")]
+  Cancelled,
+
   // Useful when we catch_unwind to avoid crossing FFI boundaries with panics.
   #[error("internal panic: {0}")]
   Panic(String),
 }
 
+impl EngineError {
+  /// Whether the failure is about the signer's credentials or the manifest's
+  /// signature rather than the input or the engine: a rejected or untrusted
+  /// certificate, an invalid signature or time-stamp, or a failed verification.
+  pub fn is_trust_failure(&self) -> bool {
+    match self {
+      EngineError::VerificationFailed | EngineError::CertificateExpired => true,
+      #[cfg(feature = "c2pa")]
+      EngineError::C2pa(e) => matches!(
+        e,
+        c2pa::Error::CertificateProfileError(_)
+          | c2pa::Error::CoseCertRevoked
+          | c2pa::Error::CoseCertUntrusted
+          | c2pa::Error::CoseInvalidCert
+          | c2pa::Error::CoseX5ChainMissing
+          | c2pa::Error::CoseSignature
+          | c2pa::Error::CoseInvalidTimeStamp
+          | c2pa::Error::CoseTimeStampValidity
+          | c2pa::Error::CoseTimeStampMismatch
+      ),
+      _ => false,
+    }
+  }
+}
+
+#[cfg(feature = "c2pa")]
+impl From<c2pa::Error> for EngineError {
+  fn from(e: c2pa::Error) -> Self {
+    use c2pa::crypto::cose::CertificateProfileError;
+    match e {
+      c2pa::Error::CoseCertExpiration
+      | c2pa::Error::CertificateProfileError(CertificateProfileError::CertificateNotValidAtTime) => {
+        EngineError::CertificateExpired
+      }
+      c2pa::Error::IoError(io) if io.kind() == std::io::ErrorKind::TimedOut => EngineError::StreamTimeout,
+      e => EngineError::C2pa(e),
+    }
+  }
+}
+
+/// Unreadable glob matches surface as the underlying I/O error.
+#[cfg(feature = "bmff")]
+impl From<glob::GlobError> for EngineError {
+  fn from(e: glob::GlobError) -> Self {
+    EngineError::Io(e.into_error())
+  }
+}
+
+/// A failed init-segment group from `generate_fragmented_bmff`.
+#[cfg(feature = "bmff")]
+#[derive(Debug, Clone)]
+pub struct SegmentFailure {
+  pub init_path: std::path::PathBuf,
+  pub message: String,
+}
+
+#[cfg(feature = "bmff")]
+fn describe_failures(failures: &[SegmentFailure]) -> String {
+  failures
+    .iter()
+    .map(|f| format!("{}: {}", f.init_path.display(), f.message))
+    .collect::<Vec<_>>()
+    .join("; ")
+}
+
 pub type EngineResult<T> = Result<T, EngineError>;
\ No newline at end of file
@@ -0,0 +1,46 @@
+// crates/engine/src/domain/info.rs
+
+//! Build and capability report for support diagnostics.
+
+use serde::Serialize;
+
+/// What this engine build can do: versions, formats and compiled-in features.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct EngineInfo {
+    /// Version of the `que-engine` crate.
+    pub engine_version: String,
+    /// Version of the c2pa SDK linked in; `None` without the `c2pa` feature.
+    pub c2pa_version: Option<String>,
+    /// Formats (MIME types and extensions) the SDK can read manifests from, sorted.
+    pub supported_read_formats: Vec<String>,
+    /// Formats the SDK can embed a manifest in, sorted.
+    pub supported_write_formats: Vec<String>,
+    /// Cargo features the engine was compiled with.
+    pub features: Vec<String>,
+}
+
+/// Cargo features of this build, in manifest order.
+pub(crate) fn compiled_features() -> Vec<String> {
+    [
+        ("c2pa", cfg!(feature = "c2pa")),
+        ("openssl", cfg!(feature = "openssl")),
+        ("local", cfg!(feature = "local")),
+        ("enclave", cfg!(feature = "enclave")),
+        ("wasm", cfg!(feature = "wasm")),
+        ("ffi", cfg!(feature = "ffi")),
+        ("bmff", cfg!(feature = "bmff")),
+        ("remote_manifests", cfg!(feature = "remote_manifests")),
+        ("http_urls", cfg!(feature = "http_urls")),
+        ("cawg", cfg!(feature = "cawg")),
+        ("watermark", cfg!(feature = "watermark")),
+        ("phash", cfg!(feature = "phash")),
+        ("thumbnail", cfg!(feature = "thumbnail")),
+        ("remote_assets", cfg!(feature = "remote_assets")),
+        ("tracing", cfg!(feature = "tracing")),
+        ("metrics", cfg!(feature = "metrics")),
+    ]
+    .into_iter()
+    .filter(|(_, enabled)| *enabled)
+    .map(|(name, _)| name.to_string())
+    .collect()
+}
@@ -0,0 +1,220 @@
+// crates/engine/src/domain/manifest_builder.rs
+
+//! Typed construction of manifest definitions, as an alternative to hand-written JSON.
+
+use serde_json::{json, Map, Value};
+
+const ACTIONS_LABEL: &str = "c2pa.actions";
+/// Action parameter naming the ingredients an action refers to.
+pub(crate) const INGREDIENT_IDS: &str = "ingredientIds";
+
+/// A single entry in the manifest's `c2pa.actions` assertion.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Action {
+    /// Action label, e.g. `c2pa.created` or `c2pa.edited`.
+    pub action: String,
+    pub software_agent: Option<String>,
+    /// Free-form action parameters.
+    pub parameters: Option<Value>,
+    /// Ingredients this action refers to, by the ingredient's `label` in the manifest
+    /// definition. Emitted as the `ingredientIds` parameter.
+    pub ingredient_ids: Vec<String>,
+}
+
+impl Action {
+    pub fn new(action: impl Into<String>) -> Self {
+        Self { action: action.into(), software_agent: None, parameters: None, ingredient_ids: Vec::new() }
+    }
+
+    pub fn software_agent(mut self, agent: impl Into<String>) -> Self {
+        self.software_agent = Some(agent.into());
+        self
+    }
+
+    pub fn parameters(mut self, parameters: Value) -> Self {
+        self.parameters = Some(parameters);
+        self
+    }
+
+    /// Reference an ingredient by its `label`.
+    pub fn ingredient(mut self, id: impl Into<String>) -> Self {
+        self.ingredient_ids.push(id.into());
+        self
+    }
+
+    pub(crate) fn to_value(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("action".into(), Value::String(self.action.clone()));
+        if let Some(agent) = &self.software_agent {
+            obj.insert("softwareAgent".into(), Value::String(agent.clone()));
+        }
+        let mut parameters = self.parameters.clone();
+        if !self.ingredient_ids.is_empty() {
+            let ids = Value::from(self.ingredient_ids.clone());
+            match &mut parameters {
+                Some(Value::Object(map)) => {
+                    map.insert(INGREDIENT_IDS.into(), ids);
+                }
+                _ => parameters = Some(json!({ INGREDIENT_IDS: ids })),
+            }
+        }
+        if let Some(parameters) = parameters {
+            obj.insert("parameters".into(), parameters);
+        }
+        Value::Object(obj)
+    }
+}
+
+/// How an ingredient relates to the asset being signed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IngredientRelationship {
+    ParentOf,
+    ComponentOf,
+    InputTo,
+}
+
+impl IngredientRelationship {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IngredientRelationship::ParentOf => "parentOf",
+            IngredientRelationship::ComponentOf => "componentOf",
+            IngredientRelationship::InputTo => "inputTo",
+        }
+    }
+}
+
+/// Builds the manifest definition JSON consumed by signing.
+///
+/// ```
+/// use que_engine::{Action, IngredientRelationship, ManifestBuilder};
+///
+/// let manifest = ManifestBuilder::new()
+///     .title("photo.jpg")
+///     .format("image/jpeg")
+///     .add_action(Action::new("c2pa.created").software_agent("my-app"))
+///     .add_ingredient("background.png", "image/png", IngredientRelationship::ComponentOf)
+///     .add_assertion("org.example.note", serde_json::json!({ "note": "hello" }))
+///     .build();
+/// assert!(manifest.contains("c2pa.actions"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ManifestBuilder {
+    title: Option<String>,
+    format: Option<String>,
+    actions: Vec<Action>,
+    ingredients: Vec<Value>,
+    assertions: Vec<(String, Value)>,
+}
+
+impl ManifestBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// MIME type of the asset being signed.
+    pub fn format(mut self, format: impl Into<String>) -> Self {
+        self.format = Some(format.into());
+        self
+    }
+
+    /// Append an action; all actions are emitted in one `c2pa.actions` assertion.
+    pub fn add_action(mut self, action: Action) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// Declare an ingredient by title and format. Ingredient content is supplied
+    /// separately (e.g. `C2paConfig::parent`).
+    pub fn add_ingredient(
+        mut self,
+        title: impl Into<String>,
+        format: impl Into<String>,
+        relationship: IngredientRelationship,
+    ) -> Self {
+        self.ingredients.push(json!({
+            "title": title.into(),
+            "format": format.into(),
+            "relationship": relationship.as_str(),
+        }));
+        self
+    }
+
+    /// Append an arbitrary assertion.
+    pub fn add_assertion(mut self, label: impl Into<String>, data: Value) -> Self {
+        self.assertions.push((label.into(), data));
+        self
+    }
+
+    /// The manifest definition as a JSON value.
+    pub fn to_value(&self) -> Value {
+        let mut manifest = Map::new();
+        if let Some(title) = &self.title {
+            manifest.insert("title".into(), Value::String(title.clone()));
+        }
+        if let Some(format) = &self.format {
+            manifest.insert("format".into(), Value::String(format.clone()));
+        }
+        if !self.ingredients.is_empty() {
+            manifest.insert("ingredients".into(), Value::Array(self.ingredients.clone()));
+        }
+
+        let mut assertions = Vec::new();
+        if !self.actions.is_empty() {
+            let actions: Vec<Value> = self.actions.iter().map(Action::to_value).collect();
+            assertions.push(json!({ "label": ACTIONS_LABEL, "data": { "actions": actions } }));
+        }
+        for (label, data) in &self.assertions {
+            assertions.push(json!({ "label": label, "data": data }));
+        }
+        if !assertions.is_empty() {
+            manifest.insert("assertions".into(), Value::Array(assertions));
+        }
+
+        Value::Object(manifest)
+    }
+
+    /// The manifest definition as a JSON string, as accepted by
+    /// `C2paConfig::manifest_definition`.
+    pub fn build(&self) -> String {
+        self.to_value().to_string()
+    }
+}
+
+/// A manifest definition supplied either as JSON text or as an already-parsed value.
+///
+/// `Value` skips the serialize/parse round trip for callers that build manifests with
+/// `serde_json`; `ManifestBuilder` converts into it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ManifestSource {
+    Json(String),
+    Value(Value),
+}
+
+impl From<String> for ManifestSource {
+    fn from(json: String) -> Self {
+        ManifestSource::Json(json)
+    }
+}
+
+impl From<Value> for ManifestSource {
+    fn from(value: Value) -> Self {
+        ManifestSource::Value(value)
+    }
+}
+
+impl From<ManifestBuilder> for ManifestSource {
+    fn from(builder: ManifestBuilder) -> Self {
+        ManifestSource::Value(builder.to_value())
+    }
+}
+
+impl From<&ManifestBuilder> for ManifestSource {
+    fn from(builder: &ManifestBuilder) -> Self {
+        ManifestSource::Value(builder.to_value())
+    }
+}
@@ -0,0 +1,258 @@
+// crates/engine/src/domain/manifest_validation.rs
+
+//! Structural checks on manifest definitions, run before the c2pa SDK sees them so
+//! mistakes are reported against the offending field rather than as an opaque SDK error.
+
+use serde_json::Value;
+
+use crate::domain::error::{EngineError, EngineResult};
+
+/// Standard assertion labels (without instance suffixes such as `__1`).
+const KNOWN_ASSERTION_LABELS: &[&str] = &[
+    "c2pa.actions",
+    "c2pa.actions.v2",
+    "c2pa.asset-ref",
+    "c2pa.asset-type",
+    "c2pa.certificate-status",
+    "c2pa.cloud-data",
+    "c2pa.depthmap.GDepth",
+    "c2pa.embedded-data",
+    "c2pa.hash.bmff",
+    "c2pa.hash.bmff.v2",
+    "c2pa.hash.bmff.v3",
+    "c2pa.hash.boxes",
+    "c2pa.hash.collection.data",
+    "c2pa.hash.data",
+    "c2pa.icon",
+    "c2pa.ingredient",
+    "c2pa.ingredient.v2",
+    "c2pa.ingredient.v3",
+    "c2pa.metadata",
+    "c2pa.soft-binding",
+    "c2pa.time-stamp",
+    "c2pa.training-mining",
+    "stds.exif",
+    "stds.iptc",
+    "stds.iptc.photo-metadata",
+    "stds.schema-org.ClaimReview",
+    "stds.schema-org.CreativeWork",
+];
+
+/// Label families whose suffix varies (e.g. `c2pa.thumbnail.claim.jpeg`).
+const KNOWN_ASSERTION_PREFIXES: &[&str] = &["c2pa.thumbnail.claim", "c2pa.thumbnail.ingredient"];
+
+const KNOWN_ACTIONS: &[&str] = &[
+    "c2pa.color_adjustments",
+    "c2pa.converted",
+    "c2pa.created",
+    "c2pa.cropped",
+    "c2pa.deleted",
+    "c2pa.drawing",
+    "c2pa.dubbed",
+    "c2pa.edited",
+    "c2pa.edited.metadata",
+    "c2pa.enhanced",
+    "c2pa.filtered",
+    "c2pa.managed",
+    "c2pa.opened",
+    "c2pa.orientation",
+    "c2pa.placed",
+    "c2pa.printed",
+    "c2pa.produced",
+    "c2pa.published",
+    "c2pa.redacted",
+    "c2pa.removed",
+    "c2pa.repackaged",
+    "c2pa.resized",
+    "c2pa.transcoded",
+    "c2pa.translated",
+    "c2pa.trimmed",
+    "c2pa.unknown",
+    "c2pa.versioned",
+    "c2pa.watermarked",
+    "c2pa.watermarked.bound",
+];
+
+const KNOWN_ACTION_PREFIXES: &[&str] = &["c2pa.font."];
+
+const KNOWN_RELATIONSHIPS: &[&str] = &["parentOf", "componentOf", "inputTo"];
+
+/// Check a manifest definition before signing.
+///
+/// Structural errors (malformed JSON, wrong field types, missing required fields) are
+/// returned as `EngineError::Config`, listing every offending field path. Likely
+/// mistakes that the SDK would accept or reject opaquely, such as an unknown `c2pa.`
+/// assertion label or action, are returned as warnings.
+pub fn validate_manifest_definition(json: &str) -> EngineResult<Vec<String>> {
+    let manifest: Value = serde_json::from_str(json)
+        .map_err(|e| EngineError::Config(format!("invalid manifest definition: {e}")))?;
+    validate_manifest_value(&manifest)
+}
+
+/// [`validate_manifest_definition`] for an already-parsed manifest.
+pub fn validate_manifest_value(manifest: &Value) -> EngineResult<Vec<String>> {
+    let mut report = Report::default();
+
+    let Some(obj) = manifest.as_object() else {
+        return Err(EngineError::Config(
+            "invalid manifest definition: $: expected a JSON object".into(),
+        ));
+    };
+
+    for field in ["title", "format", "vendor", "label", "ta_url"] {
+        if let Some(v) = obj.get(field) {
+            if !v.is_string() {
+                report.error(field, "expected a string");
+            }
+        }
+    }
+
+    if let Some(assertions) = obj.get("assertions") {
+        match assertions.as_array() {
+            Some(items) => {
+                for (i, assertion) in items.iter().enumerate() {
+                    check_assertion(&mut report, &format!("assertions[{i}]"), assertion);
+                }
+            }
+            None => report.error("assertions", "expected an array"),
+        }
+    }
+
+    if let Some(ingredients) = obj.get("ingredients") {
+        match ingredients.as_array() {
+            Some(items) => {
+                for (i, ingredient) in items.iter().enumerate() {
+                    check_ingredient(&mut report, &format!("ingredients[{i}]"), ingredient);
+                }
+            }
+            None => report.error("ingredients", "expected an array"),
+        }
+    }
+
+    report.finish()
+}
+
+#[derive(Default)]
+struct Report {
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl Report {
+    fn error(&mut self, path: &str, message: &str) {
+        self.errors.push(format!("{path}: {message}"));
+    }
+
+    fn warn(&mut self, path: &str, message: String) {
+        self.warnings.push(format!("{path}: {message}"));
+    }
+
+    fn finish(self) -> EngineResult<Vec<String>> {
+        if self.errors.is_empty() {
+            Ok(self.warnings)
+        } else {
+            Err(EngineError::Config(format!(
+                "invalid manifest definition: {}",
+                self.errors.join("; ")
+            )))
+        }
+    }
+}
+
+fn check_assertion(report: &mut Report, path: &str, assertion: &Value) {
+    let Some(obj) = assertion.as_object() else {
+        report.error(path, "expected an object");
+        return;
+    };
+
+    let label = match obj.get("label") {
+        Some(Value::String(label)) => label.as_str(),
+        Some(_) => {
+            report.error(&format!("{path}.label"), "expected a string");
+            return;
+        }
+        None => {
+            report.error(&format!("{path}.label"), "missing required field");
+            return;
+        }
+    };
+    if !obj.contains_key("data") {
+        report.error(&format!("{path}.data"), "missing required field");
+        return;
+    }
+
+    let base = base_label(label);
+    if is_reserved_namespace(base) && !is_known_assertion(base) {
+        report.warn(
+            &format!("{path}.label"),
+            format!("unknown assertion label '{label}'"),
+        );
+    }
+
+    if base == "c2pa.actions" || base == "c2pa.actions.v2" {
+        check_actions(report, &format!("{path}.data"), &obj["data"]);
+    }
+}
+
+fn check_actions(report: &mut Report, path: &str, data: &Value) {
+    let actions = match data.get("actions") {
+        Some(Value::Array(actions)) => actions,
+        Some(_) => {
+            report.error(&format!("{path}.actions"), "expected an array");
+            return;
+        }
+        None => {
+            report.error(&format!("{path}.actions"), "missing required field");
+            return;
+        }
+    };
+
+    for (i, action) in actions.iter().enumerate() {
+        let action_path = format!("{path}.actions[{i}].action");
+        match action.get("action") {
+            Some(Value::String(name)) => {
+                if name.starts_with("c2pa.") && !is_known_action(name) {
+                    report.warn(&action_path, format!("unknown action '{name}'"));
+                }
+            }
+            Some(_) => report.error(&action_path, "expected a string"),
+            None => report.error(&action_path, "missing required field"),
+        }
+    }
+}
+
+fn check_ingredient(report: &mut Report, path: &str, ingredient: &Value) {
+    let Some(obj) = ingredient.as_object() else {
+        report.error(path, "expected an object");
+        return;
+    };
+    match obj.get("relationship") {
+        Some(Value::String(rel)) if !KNOWN_RELATIONSHIPS.contains(&rel.as_str()) => report.warn(
+            &format!("{path}.relationship"),
+            format!("unknown relationship '{rel}'"),
+        ),
+        Some(Value::String(_)) | None => {}
+        Some(_) => report.error(&format!("{path}.relationship"), "expected a string"),
+    }
+}
+
+/// Strip a multiple-instance suffix such as `__2`.
+fn base_label(label: &str) -> &str {
+    match label.rsplit_once("__") {
+        Some((base, n)) if !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()) => base,
+        _ => label,
+    }
+}
+
+fn is_reserved_namespace(label: &str) -> bool {
+    label.starts_with("c2pa.") || label.starts_with("stds.")
+}
+
+fn is_known_assertion(label: &str) -> bool {
+    KNOWN_ASSERTION_LABELS.contains(&label)
+        || KNOWN_ASSERTION_PREFIXES.iter().any(|p| label.starts_with(p))
+}
+
+fn is_known_action(name: &str) -> bool {
+    KNOWN_ACTIONS.contains(&name) || KNOWN_ACTION_PREFIXES.iter().any(|p| name.starts_with(p))
+}
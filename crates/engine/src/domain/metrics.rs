@@ -0,0 +1,192 @@
+// crates/engine/src/domain/metrics.rs
+
+//! Numeric metrics for sign and verify calls (feature `metrics`).
+//!
+//! Register an [`EngineMetrics`] with [`set_metrics`] to receive a [`MetricEvent`] when
+//! an operation starts and finishes, and for each trust verdict. Until one is
+//! registered nothing is measured: each call costs a single lock read. Without the
+//! feature the hooks compile to nothing.
+
+use std::path::PathBuf;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::types::{AssetRef, DetachedManifest, OutputTarget, SignOutcome};
+use crate::domain::verify::{VerificationResult, Verdict};
+
+/// Engine operation a [`MetricEvent`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MetricOperation {
+    /// `sign_c2pa` and its variants, including detached and async signing.
+    Sign,
+    /// `verify_c2pa` and its variants, including `verify_verdict`, async and each
+    /// entry of `verify_c2pa_batch`.
+    Verify,
+}
+
+/// How an operation ended, for failure counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MetricOutcome {
+    Success,
+    /// Failed on the signer's credentials or the manifest's signature
+    /// (see `EngineError::is_trust_failure`).
+    TrustFailure,
+    /// The call's `CancellationToken` was cancelled.
+    Cancelled,
+    /// Any other error.
+    Error,
+}
+
+impl MetricOutcome {
+    fn of<T>(result: &EngineResult<T>) -> Self {
+        match result {
+            Ok(_) => MetricOutcome::Success,
+            Err(EngineError::Cancelled) => MetricOutcome::Cancelled,
+            Err(e) if e.is_trust_failure() => MetricOutcome::TrustFailure,
+            Err(_) => MetricOutcome::Error,
+        }
+    }
+}
+
+/// A measurement reported to [`EngineMetrics::record`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetricEvent {
+    /// An operation began. `input_bytes` is the source size when known without
+    /// reading it: in-memory bytes, or a path's file size. Streams and URLs report `None`.
+    Started { operation: MetricOperation, input_bytes: Option<u64> },
+    /// Trust was evaluated for the active manifest of a verified asset. Emitted
+    /// before the operation's `Finished` event.
+    TrustEvaluated { operation: MetricOperation, verdict: Verdict },
+    /// An operation ended. `output_bytes` is the size of a successful result: the
+    /// signed asset (plus the manifest store when detached), or `None` for verification.
+    Finished {
+        operation: MetricOperation,
+        duration: Duration,
+        input_bytes: Option<u64>,
+        output_bytes: Option<u64>,
+        outcome: MetricOutcome,
+    },
+}
+
+/// Receiver for [`MetricEvent`]s, e.g. an adapter onto a metrics library.
+///
+/// `record` runs on the calling thread, inside the engine call, so it should only
+/// update counters or histograms and return.
+pub trait EngineMetrics: Send + Sync {
+    fn record(&self, event: MetricEvent);
+}
+
+impl<F: Fn(MetricEvent) + Send + Sync> EngineMetrics for F {
+    fn record(&self, event: MetricEvent) {
+        self(event)
+    }
+}
+
+static METRICS: RwLock<Option<Arc<dyn EngineMetrics>>> = RwLock::new(None);
+
+/// Register the process-wide metrics receiver, replacing any previous one. Calls
+/// already in progress keep reporting to the receiver they started with.
+pub fn set_metrics(metrics: Arc<dyn EngineMetrics>) {
+    *METRICS.write().unwrap_or_else(PoisonError::into_inner) = Some(metrics);
+}
+
+/// Remove the registered receiver; later calls are not measured.
+pub fn clear_metrics() {
+    *METRICS.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+fn registered() -> Option<Arc<dyn EngineMetrics>> {
+    METRICS.read().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+/// An operation being measured, from `metrics_start!` to `metrics_finish!`.
+pub(crate) struct Measurement {
+    metrics: Arc<dyn EngineMetrics>,
+    operation: MetricOperation,
+    started: Instant,
+    input_bytes: Option<u64>,
+    /// Where a `Path` output is written, sized once the call succeeds.
+    output_path: Option<PathBuf>,
+}
+
+impl Measurement {
+    /// Emits `Started`, or returns `None` when no receiver is registered.
+    pub(crate) fn start(operation: MetricOperation, source: &AssetRef, output: Option<&OutputTarget>) -> Option<Self> {
+        let metrics = registered()?;
+        let input_bytes = match source {
+            AssetRef::Bytes { data } => Some(data.len() as u64),
+            AssetRef::Path(path) => std::fs::metadata(path).ok().map(|meta| meta.len()),
+            AssetRef::Stream { .. } | AssetRef::Url(_) => None,
+        };
+        let output_path = match output {
+            Some(OutputTarget::Path(path)) => Some(path.clone()),
+            _ => None,
+        };
+        metrics.record(MetricEvent::Started { operation, input_bytes });
+        Some(Self { metrics, operation, started: Instant::now(), input_bytes, output_path })
+    }
+
+    /// Emits `TrustEvaluated` for a verdict in `result`, then `Finished`.
+    pub(crate) fn finish<T: Measured>(self, result: &EngineResult<T>) {
+        let duration = self.started.elapsed();
+        let ok = result.as_ref().ok();
+        if let Some(verdict) = ok.and_then(Measured::verdict) {
+            self.metrics.record(MetricEvent::TrustEvaluated { operation: self.operation, verdict });
+        }
+        let output_bytes = ok.and_then(|value| {
+            value.output_bytes().or_else(|| {
+                let path = self.output_path.as_ref()?;
+                std::fs::metadata(path).ok().map(|meta| meta.len())
+            })
+        });
+        self.metrics.record(MetricEvent::Finished {
+            operation: self.operation,
+            duration,
+            input_bytes: self.input_bytes,
+            output_bytes,
+            outcome: MetricOutcome::of(result),
+        });
+    }
+}
+
+/// What a successful operation reports beyond its duration.
+pub(crate) trait Measured {
+    fn output_bytes(&self) -> Option<u64> {
+        None
+    }
+
+    fn verdict(&self) -> Option<Verdict> {
+        None
+    }
+}
+
+impl Measured for Option<Vec<u8>> {
+    fn output_bytes(&self) -> Option<u64> {
+        self.as_ref().map(|buf| buf.len() as u64)
+    }
+}
+
+impl Measured for SignOutcome {
+    fn output_bytes(&self) -> Option<u64> {
+        self.artifact.output_bytes()
+    }
+}
+
+impl Measured for DetachedManifest {
+    fn output_bytes(&self) -> Option<u64> {
+        Some((self.asset.len() + self.manifest.len()) as u64)
+    }
+}
+
+impl Measured for VerificationResult {
+    fn verdict(&self) -> Option<Verdict> {
+        self.verdict
+    }
+}
+
+impl Measured for Verdict {
+    fn verdict(&self) -> Option<Verdict> {
+        Some(*self)
+    }
+}
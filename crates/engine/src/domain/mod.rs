@@ -1,7 +1,20 @@
 pub mod manifest_engine;
 pub mod types;
 pub mod verify;
+pub mod verdict;
+pub mod validation_code;
 pub mod error;
+pub mod soft_binding;
+pub mod manifest_builder;
+pub mod assertions;
+pub mod manifest_validation;
+pub mod diff;
+pub mod progress;
+pub mod cancel;
+pub mod info;
+
+#[cfg(feature = "metrics")]
+pub mod metrics;
 
 #[cfg(feature = "cawg")]
 pub mod cawg;
\ No newline at end of file
@@ -0,0 +1,47 @@
+// crates/engine/src/domain/progress.rs
+
+//! Progress reporting for long-running sign and verify calls.
+
+/// Stage of the operation a [`ProgressEvent`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressPhase {
+    /// Copying the source into a temporary file or buffer.
+    Staging,
+    /// The SDK reading the asset to hash it and writing the signed output.
+    Signing,
+    /// The SDK reading the asset to validate its manifest.
+    Verifying,
+}
+
+/// A progress update. Counts are approximate: the SDK may read parts of the asset
+/// more than once, so `bytes_processed` can pass `total_bytes` before a phase ends.
+#[derive(Debug, Clone, Copy)]
+pub struct ProgressEvent {
+    pub phase: ProgressPhase,
+    pub bytes_processed: u64,
+    /// Size of the asset, when known.
+    pub total_bytes: Option<u64>,
+}
+
+/// Callback invoked with [`ProgressEvent`]s.
+///
+/// It runs on the calling thread while the SDK holds the asset stream, so it should
+/// return quickly (e.g. post to a channel). `Sync` is required because the SDK's
+/// stream bound is `Send` and the stream borrows the callback.
+pub struct ProgressCallback(Box<dyn Fn(ProgressEvent) + Send + Sync>);
+
+impl ProgressCallback {
+    pub fn new(f: impl Fn(ProgressEvent) + Send + Sync + 'static) -> Self {
+        Self(Box::new(f))
+    }
+
+    pub fn emit(&self, phase: ProgressPhase, bytes_processed: u64, total_bytes: Option<u64>) {
+        (self.0)(ProgressEvent { phase, bytes_processed, total_bytes });
+    }
+}
+
+impl std::fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
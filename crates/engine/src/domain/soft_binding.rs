@@ -0,0 +1,101 @@
+// crates/engine/src/domain/soft_binding.rs
+
+//! Soft bindings (watermarks and fingerprints) that let an asset be matched back to
+//! its manifest after the manifest itself has been stripped, e.g. by a re-encode.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::error::EngineResult;
+
+/// Assertion label for C2PA soft bindings.
+pub const SOFT_BINDING_LABEL: &str = "c2pa.soft-binding";
+
+/// Output of [`SoftBindingAlgorithm::embed`].
+#[derive(Debug, Clone)]
+pub struct SoftBinding {
+    /// Asset bytes to sign. Watermarks return the marked asset; algorithms that
+    /// only observe the content return the input unchanged.
+    pub asset: Vec<u8>,
+    /// Binding value recorded in the `c2pa.soft-binding` assertion.
+    pub value: Vec<u8>,
+}
+
+/// A pluggable soft-binding algorithm.
+pub trait SoftBindingAlgorithm: Send + Sync {
+    /// Identifier recorded in the assertion's `alg` field.
+    fn alg(&self) -> &str;
+
+    /// Compute the binding for `asset` (of MIME type `format`), marking the asset
+    /// if the algorithm is a watermark.
+    fn embed(&self, asset: &[u8], format: &str) -> EngineResult<SoftBinding>;
+
+    /// Recover the binding value from `asset`, or `None` if no binding is found.
+    fn detect(&self, asset: &[u8], format: &str) -> EngineResult<Option<Vec<u8>>>;
+}
+
+/// Opt-in soft binding for signing, and detection during verification.
+#[derive(Clone)]
+pub struct SoftBindingConfig {
+    pub algorithm: Arc<dyn SoftBindingAlgorithm>,
+}
+
+impl SoftBindingConfig {
+    pub fn new(algorithm: impl SoftBindingAlgorithm + 'static) -> Self {
+        Self { algorithm: Arc::new(algorithm) }
+    }
+}
+
+impl std::fmt::Debug for SoftBindingConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SoftBindingConfig")
+            .field("algorithm", &self.algorithm.alg())
+            .finish()
+    }
+}
+
+/// Computes a perceptual fingerprint of an asset without modifying it.
+///
+/// Fingerprints are recorded as `c2pa.soft-binding` assertions so a lookup service
+/// can match a stripped asset back to its manifest. Similarity matching itself is
+/// left to that service.
+pub trait Fingerprinter: Send + Sync {
+    /// Identifier recorded in the assertion's `alg` field.
+    fn alg(&self) -> &str;
+
+    /// Compute the fingerprint of `asset` (of MIME type `format`).
+    fn fingerprint(&self, asset: &[u8], format: &str) -> EngineResult<Vec<u8>>;
+}
+
+/// Opt-in fingerprint generation at sign time.
+#[derive(Clone)]
+pub struct FingerprintConfig {
+    pub fingerprinter: Arc<dyn Fingerprinter>,
+}
+
+impl FingerprintConfig {
+    pub fn new(fingerprinter: impl Fingerprinter + 'static) -> Self {
+        Self { fingerprinter: Arc::new(fingerprinter) }
+    }
+}
+
+impl std::fmt::Debug for FingerprintConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FingerprintConfig")
+            .field("fingerprinter", &self.fingerprinter.alg())
+            .finish()
+    }
+}
+
+/// A soft binding (watermark or fingerprint) found in the active manifest.
+#[derive(Debug, Serialize, Clone)]
+pub struct SoftBindingInfo {
+    pub alg: String,
+    /// Base64 binding value as recorded in the manifest.
+    pub value: String,
+    /// Whether the value was detected in the asset itself. `None` unless a matching
+    /// algorithm was supplied in the verification config.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detected: Option<bool>,
+}
@@ -2,11 +2,20 @@ use std::path::PathBuf;
 use std::cell::RefCell;
 use std::io::{Read, Seek};
 
-// Trait alias for streaming readers
+// Trait alias for streaming readers. WASM is single-threaded, so the `Send` bound is
+// dropped there, matching c2pa's own `CAIRead`.
+#[cfg(not(target_arch = "wasm32"))]
 pub trait StreamReader: Read + Seek + Send {}
 
+#[cfg(not(target_arch = "wasm32"))]
 impl<T: Read + Seek + Send> StreamReader for T {}
 
+#[cfg(target_arch = "wasm32")]
+pub trait StreamReader: Read + Seek {}
+
+#[cfg(target_arch = "wasm32")]
+impl<T: Read + Seek> StreamReader for T {}
+
 /// A reference to an asset, which can be a path, in-memory bytes, or a stream.
 ///
 /// ## Memory Considerations
@@ -20,15 +29,16 @@ impl<T: Read + Seek + Send> StreamReader for T {}
 /// - **Stream**: Use for files > 10MB. Always provide `content_type` when possible.
 /// - **Path**: Use for local files or after secure URL fetching (see URL handling below).
 ///
-/// ## URL Handling (API Layer)
-/// For remote assets, fetch to temp file first with these policies:
-/// - HTTPS only by default
-/// - HEAD request first; enforce max Content-Length (< 1GB)
-/// - No redirects or limited redirects with re-validation
-/// - Connect/read timeouts (< 5 minutes)
-/// - Allow only specific MIME types you support
-/// - DNS re-resolution with IP verification
-/// After fetching, use AssetRef::Path for processing.
+/// ## URL Handling
+/// `Url` sources are fetched by the engine (feature `remote_assets`) with these policies:
+/// - HTTPS only
+/// - Private, loopback and link-local hosts rejected, re-checked at connect time
+/// - No redirects
+/// - Content-Length and body capped at `LimitsConfig::max_stream_copy_size`
+/// - Request timeout of `LimitsConfig::max_stream_read_timeout_secs`
+/// - Only supported MIME types accepted
+///
+/// Without the feature, fetch to a temp file yourself and use `AssetRef::Path`.
 pub enum AssetRef {
     Path(PathBuf),
     Bytes {
@@ -42,6 +52,8 @@ pub enum AssetRef {
         /// If None, the engine will attempt to detect from stream content
         content_type: Option<String>,
     },
+    /// A remote HTTPS asset, downloaded on use (requires feature `remote_assets`).
+    Url(String),
 }
 
 impl std::fmt::Debug for AssetRef {
@@ -54,6 +66,7 @@ impl std::fmt::Debug for AssetRef {
             AssetRef::Stream { reader: _, content_type } => f.debug_struct("Stream")
                 .field("content_type", content_type)
                 .finish(),
+            AssetRef::Url(url) => f.debug_tuple("Url").field(url).finish(),
         }
     }
 }
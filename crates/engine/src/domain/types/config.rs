@@ -1,8 +1,15 @@
 use std::path::PathBuf;
+use std::sync::{PoisonError, RwLock};
 use crate::crypto::signer::Signer;
 use crate::crypto::timestamper::Timestamper;
+use crate::domain::manifest_builder::{IngredientRelationship, ManifestSource};
+use crate::domain::progress::ProgressCallback;
+use crate::domain::cancel::CancellationToken;
+use crate::domain::soft_binding::{FingerprintConfig, SoftBindingConfig};
 
-use super::core::{SigAlg, VerifyMode, OutputTarget};
+use crate::domain::verdict::Severity;
+
+use super::core::{HashAlg, SigAlg, VerifyMode, OutputTarget, PostSignMode};
 use super::asset::AssetRef;
 use super::trust::TrustPolicyConfig;
 
@@ -14,22 +21,41 @@ impl EngineDefaults {
     // Security defaults
     pub const ALLOW_INSECURE_HTTP: Option<bool> = None; // Secure default: HTTPS only
     pub const ALLOW_REMOTE_MANIFESTS: bool = false; // Secure default: no network fetches
+    pub const VERIFY_REMOTE_URL_REACHABLE: bool = false; // No network calls at sign time
     pub const INCLUDE_CERTIFICATES: Option<bool> = None; // Privacy default: no certs included
+    pub const UNKNOWN_STATUS_SEVERITY: Severity = Severity::Fail; // Unrecognised failures still reject
     pub const EMBED_MANIFESTS: bool = true; // Standard C2PA behavior
     pub const SKIP_POST_SIGN_VALIDATION: bool = false; // Quality assurance default
+    pub const POST_SIGN_MODE: PostSignMode = PostSignMode::ValidateStrict; // Invalid output fails the call
+    pub const VALIDATE_MANIFEST: bool = false; // Pre-sign manifest checks are opt-in
+    pub const CHECK_DECLARED_FORMAT: bool = false; // Trust declared content types; sniffing is best-effort
+    pub const INCLUDE_RAW_VALIDATION_RESULTS: bool = false; // Flattened statuses are enough for most callers
+    pub const PARENT_READY_INGREDIENT: bool = false; // Plain ingredient JSON, as c2pa writes it
 
     // Performance defaults
     pub const SIGNING_ALGORITHM: SigAlg = SigAlg::Es256; // Best compatibility
+    pub const HASH_ALGORITHM: Option<HashAlg> = None; // c2pa default (SHA-256)
     pub const VERIFICATION_MODE: VerifyMode = VerifyMode::Summary; // Fastest
     pub const OUTPUT_TARGET: OutputTarget = OutputTarget::Memory; // API convenience
+    pub const BMFF_PARALLELISM: Option<usize> = None; // Sequential segment signing
+    pub const BMFF_LAYOUT: BmffLayout = BmffLayout::Segmented; // Separate init + fragment files
 
     // Feature defaults
     pub const HAS_TRUST_POLICY: Option<TrustPolicyConfig> = None; // Bring-your-own-trust
     pub const HAS_TIMESTAMPER: Option<Timestamper> = None; // Cost control
     pub const HAS_MANIFEST_DEFINITION: Option<String> = None; // Use built-in
+    pub const HAS_DEFAULT_FORMAT: Option<String> = None; // Unidentifiable streams are rejected
+    pub const HAS_ALLOWED_FORMATS: Option<Vec<String>> = None; // Anything c2pa supports
     pub const HAS_PARENT: Option<AssetRef> = None; // No parent by default
     pub const HAS_PARENT_BASE_DIR: Option<PathBuf> = None; // No base dir override
     pub const HAS_REMOTE_MANIFEST_URL: Option<String> = None; // No remote URL
+    pub const HAS_RESERVE_SIZE: Option<usize> = None; // Signer's own estimate
+    pub const HAS_INTERMEDIATES: Option<Vec<u8>> = None; // Chain as bundled with the signer
+    pub const HAS_REDACTIONS: Option<Vec<String>> = None; // Keep parent assertions intact
+    pub const HAS_AUTO_THUMBNAIL: Option<ThumbnailConfig> = None; // Use caller-supplied thumbnails only
+    pub const THUMBNAIL_MAX_DIMENSION: u32 = 1024;
+    pub const THUMBNAIL_FORMAT: ThumbnailFormat = ThumbnailFormat::Jpeg;
+    pub const HAS_CLAIM_GENERATOR: Option<ClaimGeneratorInfo> = None; // Keep the manifest's or c2pa's generator
 
     // CAWG defaults
     #[cfg(feature = "cawg")]
@@ -51,6 +77,12 @@ pub struct LimitsConfig {
     pub max_stream_copy_size: usize,
     /// Max time (in seconds) allowed for stream reads/copies.
     pub max_stream_read_timeout_secs: u64,
+    /// Max number of ingredients across all manifests in a verified store.
+    pub max_ingredients: usize,
+    /// Max nesting of ingredient manifests below the active manifest.
+    pub max_ingredient_depth: usize,
+    /// Max size of a manifest store fetched from a remote URL, read from a sidecar or extracted.
+    pub max_manifest_size: usize,
 }
 
 impl LimitsConfig {
@@ -61,6 +93,130 @@ impl LimitsConfig {
             max_in_memory_output_size: 128 * 1024 * 1024,     // 128 MB
             max_stream_copy_size: 1024 * 1024 * 1024,         // 1 GB
             max_stream_read_timeout_secs: 300,                 // 5 minutes
+            max_ingredients: 1024,
+            max_ingredient_depth: 32,
+            max_manifest_size: 64 * 1024 * 1024,              // 64 MB
+        }
+    }
+}
+
+impl Default for LimitsConfig {
+    /// `LimitsConfig::defaults()`. Process-wide limits from `set_default_limits` are
+    /// not consulted; use `default_limits()` for those.
+    fn default() -> Self {
+        Self::defaults()
+    }
+}
+
+impl std::fmt::Display for LimitsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "in-memory asset {}, in-memory output {}, stream copy {}, stream read timeout {} s, \
+             {} ingredients, ingredient depth {}, manifest {}",
+            ByteSize(self.max_in_memory_asset_size),
+            ByteSize(self.max_in_memory_output_size),
+            ByteSize(self.max_stream_copy_size),
+            self.max_stream_read_timeout_secs,
+            self.max_ingredients,
+            self.max_ingredient_depth,
+            ByteSize(self.max_manifest_size),
+        )
+    }
+}
+
+/// A byte count in the largest binary unit that divides it exactly.
+struct ByteSize(usize);
+
+impl std::fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: [(usize, &str); 3] = [(1 << 30, "GiB"), (1 << 20, "MiB"), (1 << 10, "KiB")];
+        match UNITS.iter().find(|(size, _)| self.0 != 0 && self.0.is_multiple_of(*size)) {
+            Some((size, unit)) => write!(f, "{} {unit}", self.0 / size),
+            None => write!(f, "{} B", self.0),
+        }
+    }
+}
+
+/// Limits installed with `set_default_limits`; `None` until then.
+static DEFAULT_LIMITS: RwLock<Option<LimitsConfig>> = RwLock::new(None);
+
+/// Install process-wide default limits, used by every `secure_default` constructor
+/// (and by calls that take no `LimitsConfig`) from then on.
+///
+/// Precedence: limits set explicitly on a config always win, then these, then
+/// `LimitsConfig::defaults()`. Configs already constructed keep the limits they were
+/// built with, so call this once at startup.
+pub fn set_default_limits(limits: LimitsConfig) {
+    *DEFAULT_LIMITS.write().unwrap_or_else(PoisonError::into_inner) = Some(limits);
+}
+
+/// The limits new configs start from: those passed to `set_default_limits`, or
+/// `LimitsConfig::defaults()` if none were set.
+pub fn default_limits() -> LimitsConfig {
+    DEFAULT_LIMITS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .unwrap_or_else(LimitsConfig::defaults)
+}
+
+/// Encoding used for generated claim thumbnails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThumbnailFormat {
+    Jpeg,
+    Png,
+}
+
+impl ThumbnailFormat {
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ThumbnailFormat::Jpeg => "image/jpeg",
+            ThumbnailFormat::Png => "image/png",
+        }
+    }
+}
+
+/// Claim thumbnail generated from the source at sign time (requires `thumbnail` feature).
+#[derive(Debug, Clone, Copy)]
+pub struct ThumbnailConfig {
+    /// Longest edge of the thumbnail in pixels; smaller images are not upscaled.
+    pub max_dimension: u32,
+    pub format: ThumbnailFormat,
+}
+
+/// Product identity recorded as the manifest's claim generator.
+#[derive(Debug, Clone)]
+pub struct ClaimGeneratorInfo {
+    pub name: String,
+    pub version: Option<String>,
+    /// Icon embedded in the manifest as a resource.
+    pub icon: Option<ClaimGeneratorIcon>,
+}
+
+/// Icon image for `ClaimGeneratorInfo`.
+#[derive(Debug, Clone)]
+pub struct ClaimGeneratorIcon {
+    /// MIME type of `data`, e.g. `image/png` or `image/svg+xml`.
+    pub format: String,
+    pub data: Vec<u8>,
+}
+
+/// Reproducible signing for tests that compare signed outputs. Not for production:
+/// the IDs it derives repeat across runs by design.
+#[derive(Debug, Clone, Default)]
+pub struct DeterministicConfig {
+    /// RFC 3339 time recorded as `when` on every action that has none.
+    pub fixed_time: Option<String>,
+    /// Derive the manifest label and ingredient instance IDs from the manifest
+    /// definition instead of generating random UUIDs.
+    pub fixed_instance_ids: bool,
+}
+
+impl Default for ThumbnailConfig {
+    fn default() -> Self {
+        Self {
+            max_dimension: EngineDefaults::THUMBNAIL_MAX_DIMENSION,
+            format: EngineDefaults::THUMBNAIL_FORMAT,
         }
     }
 }
@@ -71,23 +227,96 @@ pub struct C2paConfig {
     pub source: AssetRef,
     pub output: OutputTarget,
     pub manifest_definition: Option<String>,
+    /// Manifest definition as JSON text or a parsed value (e.g. from `ManifestBuilder`).
+    /// Alternative to `manifest_definition`; set at most one of the two.
+    pub manifest_source: Option<ManifestSource>,
+    /// Check the manifest definition with `validate_manifest_definition` before
+    /// signing; any error or warning aborts the operation.
+    pub validate_manifest: bool,
+    /// Reject a source whose stream `content_type` or path extension conflicts with
+    /// the format sniffed from its bytes. Sources that can't be sniffed pass.
+    pub check_declared_format: bool,
+    /// Format (MIME type or extension) to sign a stream source as when it has no
+    /// `content_type` and its bytes can't be sniffed. Without one, such a stream is
+    /// rejected with `UnsupportedFormat`.
+    pub default_format: Option<String>,
+    /// Formats this deployment signs, as MIME types or extensions. A source whose
+    /// declared or sniffed format is not listed, or whose format can't be determined,
+    /// fails with `UnsupportedFormat` before c2pa is called. `None` allows anything
+    /// c2pa supports.
+    pub allowed_formats: Option<Vec<String>>,
     pub parent: Option<AssetRef>,
     /// Optional base directory for resolving resources in a parent ingredient
     /// when the parent is provided as in-memory bytes.
     pub parent_base_dir: Option<PathBuf>,
     pub signer: Signer,
     pub signing_alg: SigAlg,
+    /// Additional signers applied in the same pass, each as a CAWG X.509 identity
+    /// assertion over the claim (requires the `cawg` feature). The pinned c2pa SDK
+    /// supports one identity assertion per manifest, so at most one entry, and none
+    /// alongside `cawg_identity`. Unlike `countersign`, nothing is appended later.
+    pub co_signers: Vec<(Signer, SigAlg)>,
+    /// PEM intermediate certificates appended to the signer's certificate chain, for
+    /// signers whose certificate file holds only the leaf. Certificates already in
+    /// the chain are skipped.
+    pub intermediates: Option<Vec<u8>>,
+    /// Digest for the content hash assertions. `None` keeps the c2pa default (SHA-256).
+    pub hash_alg: Option<HashAlg>,
     pub timestamper: Option<Timestamper>,
+    /// Bytes reserved for the signature box, replacing the signer's own estimate.
+    /// Signing fails with `CoseSigboxTooSmall` ("COSE Signature too big for JUMBF
+    /// box") when the signature, certificate chain and time-stamp outgrow the
+    /// reservation; raise it for long chains or remote signers, starting from
+    /// `Signer::reserve_size`. `None` keeps the signer's estimate.
+    pub reserve_size: Option<usize>,
     pub remote_manifest_url: Option<String>,
     pub embed: bool,
+    /// Opt-in: before signing, send a HEAD request to `remote_manifest_url` and fail
+    /// unless it answers with a success status (requires the `remote_manifests`
+    /// feature). The manifest store, or a placeholder, must already be served there.
+    pub verify_remote_url_reachable: bool,
     /// Optional trust policy to apply when verifying immediately after signing.
     /// Mirrors options supported by the verify API.
     pub trust_policy: Option<TrustPolicyConfig>,
+    /// Shorthand for `post_sign: PostSignMode::Skip`; when `true` it overrides `post_sign`.
     pub skip_post_sign_validation: bool,
+    /// Validation after signing: none, strict (failures abort signing) or a report
+    /// returned by `sign_c2pa_with_report`.
+    pub post_sign: PostSignMode,
     /// Opt-in: allow insecure HTTP for remote manifest URL (requires feature)
     pub allow_insecure_remote_http: Option<bool>,
     /// Per-call limits. Defaults are tuned for production safety.
     pub limits: LimitsConfig,
+    /// Directory for this call's temporary files, e.g. a private tmpfs when `/tmp` is
+    /// read-only. `None` uses the system temp directory.
+    pub temp_dir: Option<PathBuf>,
+    /// Assertions to redact from the parent ingredient's active manifest.
+    /// Entries are assertion labels (e.g. `stds.exif`) or full `self#jumbf=` URIs.
+    pub redactions: Option<Vec<String>>,
+    /// Opt-in soft binding (watermark/fingerprint) recorded as a `c2pa.soft-binding`
+    /// assertion. Watermarking algorithms modify the asset before it is signed.
+    pub soft_binding: Option<SoftBindingConfig>,
+    /// Opt-in perceptual fingerprint recorded as a `c2pa.soft-binding` assertion.
+    /// Computed over the asset as signed (after any watermark).
+    pub fingerprint: Option<FingerprintConfig>,
+    /// Generate the claim thumbnail from raster sources. Non-raster sources are
+    /// signed without a generated thumbnail.
+    pub auto_thumbnail: Option<ThumbnailConfig>,
+    /// Claim generator recorded in the manifest, replacing any `claim_generator_info`
+    /// in the manifest definition. `None` keeps the definition's, or c2pa's default.
+    pub claim_generator: Option<ClaimGeneratorInfo>,
+    /// Raw c2pa settings (a JSON object), applied after the engine's own settings.
+    /// Escape hatch for SDK options not modelled here; it can override engine
+    /// defaults, including security-relevant ones such as trust verification.
+    pub raw_settings: Option<serde_json::Value>,
+    /// Called with approximate byte counts while the source is staged and signed.
+    pub progress: Option<ProgressCallback>,
+    /// Checked while the source is copied and between pipeline phases; once
+    /// cancelled, the call fails with `EngineError::Cancelled`.
+    pub cancel: Option<CancellationToken>,
+    /// Testing only: make the manifest reproducible across runs. Any time-stamp
+    /// authority is dropped, since a time-stamp differs on every call.
+    pub deterministic: Option<DeterministicConfig>,
     /// Optional CAWG identity configuration (requires feature)
     #[cfg(feature = "cawg")]
     pub cawg_identity: Option<crate::domain::cawg::CawgIdentity>,
@@ -104,6 +333,37 @@ pub struct C2paVerificationConfig {
     pub include_certificates: Option<bool>,
     /// Per-call limits. Used when converting inputs to temp files.
     pub limits: LimitsConfig,
+    /// Optional soft-binding algorithm used to check bindings recorded in the
+    /// manifest against the asset content.
+    pub soft_binding: Option<SoftBindingConfig>,
+    /// Raw c2pa settings (a JSON object), applied after the engine's own settings.
+    /// Can override engine defaults, including trust verification.
+    pub raw_settings: Option<serde_json::Value>,
+    /// Severity given to failed status codes missing from the verdict table
+    /// (see `domain::verdict`). Codes that passed always count as `Pass`.
+    pub unknown_status_severity: Severity,
+    /// Called with approximate byte counts while the source is staged and read.
+    pub progress: Option<ProgressCallback>,
+    /// Checked while the source is copied and read and between verification
+    /// phases; once cancelled, the call fails with `EngineError::Cancelled`.
+    pub cancel: Option<CancellationToken>,
+    /// MIME type to read the source as, bypassing content sniffing and any
+    /// stream `content_type`. Must be a format the c2pa reader supports.
+    pub format_override: Option<String>,
+    /// Reject a source whose declared format (`format_override`, stream
+    /// `content_type` or path extension) conflicts with its sniffed bytes.
+    pub check_declared_format: bool,
+    /// Formats this deployment verifies; see `C2paConfig::allowed_formats`.
+    pub allowed_formats: Option<Vec<String>>,
+    /// Manifest store URL to verify `source` against, for manifests kept apart from
+    /// the asset. Fetched over HTTPS (requires the `remote_manifests` feature).
+    pub remote_manifest: Option<String>,
+    /// Opt-in: include c2pa's `ValidationResults` verbatim as JSON in
+    /// `VerificationResult::raw_validation_results`.
+    pub include_raw_validation_results: bool,
+    /// SHA-256 fingerprint of the certificate expected to have signed the active
+    /// manifest (hex, colons optional). Adds a `que.signerFingerprint.*` status.
+    pub expected_signer_fingerprint: Option<String>,
     /// Optional CAWG verification options (requires feature)
     #[cfg(feature = "cawg")]
     pub cawg: Option<crate::domain::cawg::CawgVerifyOptions>,
@@ -117,7 +377,18 @@ impl Default for C2paVerificationConfig {
             policy: EngineDefaults::HAS_TRUST_POLICY,
             allow_remote_manifests: EngineDefaults::ALLOW_REMOTE_MANIFESTS,
             include_certificates: EngineDefaults::INCLUDE_CERTIFICATES,
-            limits: LimitsConfig::defaults(),
+            limits: default_limits(),
+            soft_binding: None,
+            raw_settings: None,
+            unknown_status_severity: EngineDefaults::UNKNOWN_STATUS_SEVERITY,
+            progress: None,
+            cancel: None,
+            format_override: None,
+            check_declared_format: EngineDefaults::CHECK_DECLARED_FORMAT,
+            allowed_formats: EngineDefaults::HAS_ALLOWED_FORMATS,
+            remote_manifest: None,
+            include_raw_validation_results: EngineDefaults::INCLUDE_RAW_VALIDATION_RESULTS,
+            expected_signer_fingerprint: None,
             #[cfg(feature = "cawg")]
             cawg: None, // CAWG validation disabled by default (secure)
         }
@@ -131,23 +402,215 @@ impl C2paConfig {
             source,
             output: EngineDefaults::OUTPUT_TARGET,
             manifest_definition: EngineDefaults::HAS_MANIFEST_DEFINITION,
+            manifest_source: None,
+            validate_manifest: EngineDefaults::VALIDATE_MANIFEST,
+            check_declared_format: EngineDefaults::CHECK_DECLARED_FORMAT,
+            default_format: EngineDefaults::HAS_DEFAULT_FORMAT,
+            allowed_formats: EngineDefaults::HAS_ALLOWED_FORMATS,
             parent: EngineDefaults::HAS_PARENT,
             parent_base_dir: EngineDefaults::HAS_PARENT_BASE_DIR,
             signer,
             signing_alg,
+            co_signers: Vec::new(),
+            intermediates: EngineDefaults::HAS_INTERMEDIATES,
+            hash_alg: EngineDefaults::HASH_ALGORITHM,
             timestamper: EngineDefaults::HAS_TIMESTAMPER,
+            reserve_size: EngineDefaults::HAS_RESERVE_SIZE,
             remote_manifest_url: EngineDefaults::HAS_REMOTE_MANIFEST_URL,
             embed: EngineDefaults::EMBED_MANIFESTS,
+            verify_remote_url_reachable: EngineDefaults::VERIFY_REMOTE_URL_REACHABLE,
             trust_policy: EngineDefaults::HAS_TRUST_POLICY,
             skip_post_sign_validation: EngineDefaults::SKIP_POST_SIGN_VALIDATION,
+            post_sign: EngineDefaults::POST_SIGN_MODE,
             allow_insecure_remote_http: EngineDefaults::ALLOW_INSECURE_HTTP,
-            limits: LimitsConfig::defaults(),
+            limits: default_limits(),
+            temp_dir: None,
+            redactions: EngineDefaults::HAS_REDACTIONS,
+            soft_binding: None, // Opt-in: soft bindings alter or inspect content
+            fingerprint: None,
+            auto_thumbnail: EngineDefaults::HAS_AUTO_THUMBNAIL,
+            claim_generator: EngineDefaults::HAS_CLAIM_GENERATOR,
+            raw_settings: None,
+            progress: None,
+            cancel: None,
+            deterministic: None,
             #[cfg(feature = "cawg")]
             cawg_identity: None, // CAWG disabled by default (secure)
         }
     }
 }
 
+impl C2paConfig {
+    /// Start a [`C2paConfigBuilder`] from `secure_default(source, signer, signing_alg)`.
+    pub fn builder(source: AssetRef, signer: Signer, signing_alg: SigAlg) -> C2paConfigBuilder {
+        C2paConfigBuilder { config: Self::secure_default(source, signer, signing_alg) }
+    }
+}
+
+/// Fluent construction of a [`C2paConfig`]. Fields not set keep their
+/// `secure_default` values; anything without a setter can still be assigned on the
+/// built struct.
+///
+/// ```
+/// use que_engine::{AssetRef, C2paConfig, ManifestBuilder, OutputTarget, SigAlg};
+///
+/// let config = C2paConfig::builder(
+///     AssetRef::Path("photo.jpg".into()),
+///     "env:CERT_PEM,KEY_PEM".parse().unwrap(),
+///     SigAlg::Es256,
+/// )
+/// .manifest(ManifestBuilder::new().title("photo.jpg").format("image/jpeg"))
+/// .output(OutputTarget::Memory)
+/// .build();
+/// assert!(config.manifest_source.is_some());
+/// assert!(config.timestamper.is_none());
+/// ```
+#[derive(Debug)]
+pub struct C2paConfigBuilder {
+    config: C2paConfig,
+}
+
+impl C2paConfigBuilder {
+    /// Manifest definition as JSON text, a `serde_json::Value` or a `ManifestBuilder`.
+    pub fn manifest(mut self, manifest: impl Into<ManifestSource>) -> Self {
+        self.config.manifest_source = Some(manifest.into());
+        self
+    }
+
+    pub fn output(mut self, output: OutputTarget) -> Self {
+        self.config.output = output;
+        self
+    }
+
+    /// Format for a stream source that has no `content_type` and can't be sniffed.
+    pub fn default_format(mut self, format: impl Into<String>) -> Self {
+        self.config.default_format = Some(format.into());
+        self
+    }
+
+    /// Restrict the formats that may be signed; see `C2paConfig::allowed_formats`.
+    pub fn allowed_formats<S: Into<String>>(mut self, formats: impl IntoIterator<Item = S>) -> Self {
+        self.config.allowed_formats = Some(formats.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn parent(mut self, parent: AssetRef) -> Self {
+        self.config.parent = Some(parent);
+        self
+    }
+
+    pub fn timestamper(mut self, timestamper: Timestamper) -> Self {
+        self.config.timestamper = Some(timestamper);
+        self
+    }
+
+    /// Trust policy for the verification run right after signing.
+    pub fn trust_policy(mut self, policy: TrustPolicyConfig) -> Self {
+        self.config.trust_policy = Some(policy);
+        self
+    }
+
+    /// Add a CAWG identity assertion (requires the `cawg` feature).
+    #[cfg(feature = "cawg")]
+    pub fn cawg(mut self, identity: crate::domain::cawg::CawgIdentity) -> Self {
+        self.config.cawg_identity = Some(identity);
+        self
+    }
+
+    /// Add a co-signer; see `C2paConfig::co_signers`.
+    pub fn co_signer(mut self, signer: Signer, signing_alg: SigAlg) -> Self {
+        self.config.co_signers.push((signer, signing_alg));
+        self
+    }
+
+    pub fn intermediates(mut self, pem: Vec<u8>) -> Self {
+        self.config.intermediates = Some(pem);
+        self
+    }
+
+    pub fn hash_alg(mut self, hash_alg: HashAlg) -> Self {
+        self.config.hash_alg = Some(hash_alg);
+        self
+    }
+
+    pub fn reserve_size(mut self, reserve_size: usize) -> Self {
+        self.config.reserve_size = Some(reserve_size);
+        self
+    }
+
+    /// Reference the manifest store at `url`; `embed` controls whether it is also embedded.
+    pub fn remote_manifest_url(mut self, url: impl Into<String>, embed: bool) -> Self {
+        self.config.remote_manifest_url = Some(url.into());
+        self.config.embed = embed;
+        self
+    }
+
+    pub fn limits(mut self, limits: LimitsConfig) -> Self {
+        self.config.limits = limits;
+        self
+    }
+
+    /// Stage temporary files under `dir`; see `C2paConfig::temp_dir`.
+    pub fn temp_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.config.temp_dir = Some(dir.into());
+        self
+    }
+
+    pub fn auto_thumbnail(mut self, thumbnail: ThumbnailConfig) -> Self {
+        self.config.auto_thumbnail = Some(thumbnail);
+        self
+    }
+
+    pub fn claim_generator(mut self, generator: ClaimGeneratorInfo) -> Self {
+        self.config.claim_generator = Some(generator);
+        self
+    }
+
+    pub fn soft_binding(mut self, soft_binding: SoftBindingConfig) -> Self {
+        self.config.soft_binding = Some(soft_binding);
+        self
+    }
+
+    pub fn fingerprint(mut self, fingerprint: FingerprintConfig) -> Self {
+        self.config.fingerprint = Some(fingerprint);
+        self
+    }
+
+    pub fn redactions(mut self, redactions: Vec<String>) -> Self {
+        self.config.redactions = Some(redactions);
+        self
+    }
+
+    pub fn progress(mut self, progress: ProgressCallback) -> Self {
+        self.config.progress = Some(progress);
+        self
+    }
+
+    pub fn cancel(mut self, cancel: CancellationToken) -> Self {
+        self.config.cancel = Some(cancel);
+        self
+    }
+
+    pub fn deterministic(mut self, deterministic: DeterministicConfig) -> Self {
+        self.config.deterministic = Some(deterministic);
+        self
+    }
+
+    pub fn skip_post_sign_validation(mut self, skip: bool) -> Self {
+        self.config.skip_post_sign_validation = skip;
+        self
+    }
+
+    pub fn post_sign(mut self, mode: PostSignMode) -> Self {
+        self.config.post_sign = mode;
+        self
+    }
+
+    pub fn build(self) -> C2paConfig {
+        self.config
+    }
+}
+
 impl C2paVerificationConfig {
     /// Secure opinionated defaults; caller supplies source.
     pub fn secure_default(source: AssetRef) -> Self {
@@ -157,7 +620,18 @@ impl C2paVerificationConfig {
             policy: EngineDefaults::HAS_TRUST_POLICY,
             allow_remote_manifests: EngineDefaults::ALLOW_REMOTE_MANIFESTS,
             include_certificates: EngineDefaults::INCLUDE_CERTIFICATES,
-            limits: LimitsConfig::defaults(),
+            limits: default_limits(),
+            soft_binding: None,
+            raw_settings: None,
+            unknown_status_severity: EngineDefaults::UNKNOWN_STATUS_SEVERITY,
+            progress: None,
+            cancel: None,
+            format_override: None,
+            check_declared_format: EngineDefaults::CHECK_DECLARED_FORMAT,
+            allowed_formats: EngineDefaults::HAS_ALLOWED_FORMATS,
+            remote_manifest: None,
+            include_raw_validation_results: EngineDefaults::INCLUDE_RAW_VALIDATION_RESULTS,
+            expected_signer_fingerprint: None,
             #[cfg(feature = "cawg")]
             cawg: None, // CAWG validation disabled by default (secure)
         }
@@ -173,6 +647,19 @@ pub struct IngredientConfig {
     pub output: OutputTarget,
     /// Per-call limits. Used when converting inputs to temp files.
     pub limits: LimitsConfig,
+    /// Directory for temporary files; `None` uses the system temp directory.
+    pub temp_dir: Option<PathBuf>,
+    /// Overrides the title derived from the asset's file name.
+    pub title: Option<String>,
+    /// Relationship recorded in the ingredient (`c2pa` defaults to `componentOf`).
+    pub relationship: Option<IngredientRelationship>,
+    /// Custom fields merged into the ingredient's `metadata`. Must be a JSON object.
+    pub metadata: Option<serde_json::Value>,
+    /// Produce an ingredient that can be passed back as `C2paConfig.parent`
+    /// (`AssetRef::Bytes`): relationship `parentOf`, `active_manifest` set, and the
+    /// manifest store and thumbnail embedded in the JSON. The asset must carry a
+    /// C2PA manifest.
+    pub parent_ready: bool,
 }
 
 impl IngredientConfig {
@@ -181,11 +668,51 @@ impl IngredientConfig {
         Self {
             source,
             output: EngineDefaults::OUTPUT_TARGET,
-            limits: LimitsConfig::defaults(),
+            limits: default_limits(),
+            temp_dir: None,
+            title: None,
+            relationship: None,
+            metadata: None,
+            parent_ready: EngineDefaults::PARENT_READY_INGREDIENT,
+        }
+    }
+}
+
+/// Configuration for stripping an embedded C2PA manifest store from an asset.
+#[derive(Debug)]
+pub struct RemoveManifestConfig {
+    pub source: AssetRef,
+    /// If Path, write the stripped asset to the file. If Memory, return its bytes.
+    pub output: OutputTarget,
+    /// Per-call limits. The asset is processed in memory.
+    pub limits: LimitsConfig,
+    /// Directory the asset is staged in while the store is stripped; `None` uses the
+    /// system temp directory.
+    pub temp_dir: Option<PathBuf>,
+}
+
+impl RemoveManifestConfig {
+    /// Secure opinionated defaults; caller supplies source.
+    pub fn secure_default(source: AssetRef) -> Self {
+        Self {
+            source,
+            output: EngineDefaults::OUTPUT_TARGET,
+            limits: default_limits(),
+            temp_dir: None,
         }
     }
 }
 
+/// How fragmented BMFF input is laid out on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BmffLayout {
+    /// Separate init segment and fragment files matched by `init_glob` / `fragments_glob`.
+    Segmented,
+    /// Self-contained fragmented MP4 files (`moov` + `moof` boxes) matched by `init_glob`;
+    /// `fragments_glob` is ignored. Each file is written to `output_dir` under its own name.
+    SingleFile,
+}
+
 /// Configuration for generating a manifest into fragmented BMFF content.
 #[derive(Debug, Clone)]
 pub struct FragmentedBmffConfig {
@@ -205,6 +732,11 @@ pub struct FragmentedBmffConfig {
     pub allow_insecure_remote_http: Option<bool>,
     /// Per-call limits for any size-sensitive operations.
     pub limits: LimitsConfig,
+    /// Opt-in: number of worker threads used to sign independent init-segment
+    /// groups concurrently. `None` or `Some(1)` signs sequentially.
+    pub parallelism: Option<usize>,
+    /// Segmented directories (default) or single fragmented MP4 files.
+    pub layout: BmffLayout,
 }
 
 impl FragmentedBmffConfig {
@@ -228,7 +760,36 @@ impl FragmentedBmffConfig {
             embed: EngineDefaults::EMBED_MANIFESTS,
             skip_post_sign_validation: EngineDefaults::SKIP_POST_SIGN_VALIDATION,
             allow_insecure_remote_http: EngineDefaults::ALLOW_INSECURE_HTTP,
-            limits: LimitsConfig::defaults(),
+            limits: default_limits(),
+            parallelism: EngineDefaults::BMFF_PARALLELISM,
+            layout: EngineDefaults::BMFF_LAYOUT,
+        }
+    }
+}
+
+/// Configuration for verifying fragmented BMFF content (init segment plus fragments).
+#[derive(Debug, Clone)]
+pub struct FragmentedBmffVerifyConfig {
+    /// Glob matching init segments; each match is verified as its own group.
+    pub init_glob: PathBuf,
+    /// Glob for fragments, resolved relative to each init segment's directory.
+    /// Fragment names must sort in playback order.
+    pub fragments_glob: PathBuf,
+    pub mode: VerifyMode,
+    pub policy: Option<TrustPolicyConfig>,
+    /// Opt-in: include signing certificates in each result
+    pub include_certificates: Option<bool>,
+}
+
+impl FragmentedBmffVerifyConfig {
+    /// Secure opinionated defaults; caller supplies the globs.
+    pub fn secure_default(init_glob: PathBuf, fragments_glob: PathBuf) -> Self {
+        Self {
+            init_glob,
+            fragments_glob,
+            mode: EngineDefaults::VERIFICATION_MODE,
+            policy: EngineDefaults::HAS_TRUST_POLICY,
+            include_certificates: EngineDefaults::INCLUDE_CERTIFICATES,
         }
     }
 }
@@ -1,5 +1,5 @@
 /// Supported signature algorithms for the engine. Mapped to c2pa internally.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SigAlg {
     Es256,
     Es384,
@@ -19,6 +19,25 @@ impl SigAlg {
     }
 }
 
+/// Digest used for the content hash assertions (`c2pa.hash.data`, `c2pa.hash.bmff`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlg {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlg {
+    /// Name used in c2pa settings and assertion data.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HashAlg::Sha256 => "sha256",
+            HashAlg::Sha384 => "sha384",
+            HashAlg::Sha512 => "sha512",
+        }
+    }
+}
+
 /// Where verification output should be focused.
 #[derive(Debug, Clone, Copy)]
 pub enum VerifyMode {
@@ -28,9 +47,75 @@ pub enum VerifyMode {
     Tree,
 }
 
+/// What signing does once the manifest is embedded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostSignMode {
+    /// No validation after signing.
+    Skip,
+    /// c2pa validates the signed asset and any failure aborts signing.
+    ValidateStrict,
+    /// The engine verifies the signed asset, like `verify_c2pa` would, and
+    /// `sign_c2pa_with_report` returns the result with it. Failures never abort signing.
+    ValidateReport,
+}
+
 /// A target for the output of a generation operation.
 #[derive(Debug, Clone)]
 pub enum OutputTarget {
     Path(std::path::PathBuf),
     Memory,
 }
+
+/// An asset and its manifest store kept apart, as produced by `sign_c2pa_detached`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DetachedManifest {
+    /// The asset, unchanged unless `remote_manifest_url` was set (the URL is then
+    /// recorded in its XMP).
+    pub asset: Vec<u8>,
+    /// The manifest store (JUMBF), as written to a `.c2pa` sidecar.
+    pub manifest: Vec<u8>,
+}
+
+impl DetachedManifest {
+    /// Write the asset to `asset_path` and the manifest next to it with a `.c2pa`
+    /// extension, where verifying `asset_path` finds it.
+    pub fn write_to(&self, asset_path: impl AsRef<std::path::Path>) -> crate::domain::error::EngineResult<()> {
+        let asset_path = asset_path.as_ref();
+        std::fs::write(asset_path, &self.asset)?;
+        std::fs::write(asset_path.with_extension("c2pa"), &self.manifest)?;
+        Ok(())
+    }
+}
+
+/// A signed asset together with the verification run on it after signing, as
+/// produced by `sign_c2pa_with_report`.
+#[derive(Clone)]
+pub struct SignOutcome {
+    /// The signed asset for `OutputTarget::Memory`; `None` when written to a path.
+    pub artifact: Option<Vec<u8>>,
+    /// The engine's verification of the signed asset; `None` when post-sign
+    /// validation is skipped or could not run.
+    pub verification: Option<crate::domain::verify::VerificationResult>,
+    /// Why the verification could not run (e.g. the signed output exceeds the limits
+    /// when re-read). The asset is signed regardless.
+    pub verification_error: Option<String>,
+}
+
+impl std::fmt::Debug for SignOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignOutcome")
+            .field("artifact_len", &self.artifact.as_ref().map(Vec::len))
+            .field("verification", &self.verification)
+            .field("verification_error", &self.verification_error)
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for DetachedManifest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DetachedManifest")
+            .field("asset_len", &self.asset.len())
+            .field("manifest_len", &self.manifest.len())
+            .finish()
+    }
+}
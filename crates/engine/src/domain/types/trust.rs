@@ -11,4 +11,20 @@ pub struct TrustPolicyConfig {
 
     /// Enable trust checks for identity assertions (c2pa >= 0.59)
     pub verify_identity_trust: Option<bool>,
+
+    /// Signer subject names to accept: a common name, an organization, or the full
+    /// distinguished name. Checked on the active signer's leaf certificate.
+    pub allowed_subjects: Option<Vec<String>>,
+    /// Issuer names of the signer's leaf certificate to accept, matched like `allowed_subjects`.
+    pub allowed_issuers: Option<Vec<String>>,
+
+    /// PEM intermediate certificates for signers whose manifests omit them. Only
+    /// intermediates that chain to `anchors` (or the system roots) are used; others
+    /// are ignored rather than trusted on their own.
+    pub intermediates: Option<Vec<u8>>,
+
+    /// Opt-in: add the operating system's root certificates to `anchors`.
+    /// This trusts every CA the OS trusts for TLS, which is far broader than a
+    /// C2PA-specific bundle.
+    pub use_system_trust: Option<bool>,
 }
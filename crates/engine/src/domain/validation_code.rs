@@ -0,0 +1,170 @@
+// crates/engine/src/domain/validation_code.rs
+
+//! Typed C2PA validation status codes.
+
+use std::convert::Infallible;
+use std::str::FromStr;
+
+use serde::{Serialize, Serializer};
+
+use crate::domain::verdict::Severity;
+
+macro_rules! validation_codes {
+    ($($variant:ident => $code:literal, $severity:ident;)*) => {
+        /// A C2PA validation status code, as documented in the C2PA specification,
+        /// or one of the engine's own `que.` policy codes.
+        ///
+        /// Codes the engine does not know are kept verbatim in `Other`, so matching on
+        /// this enum never loses information.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum ValidationCode {
+            $($variant,)*
+            Other(String),
+        }
+
+        impl ValidationCode {
+            /// Parse a status code string; unknown codes become `Other`.
+            pub fn parse(code: &str) -> Self {
+                match code {
+                    $($code => ValidationCode::$variant,)*
+                    other => ValidationCode::Other(other.to_string()),
+                }
+            }
+
+            /// The code as it appears in c2pa validation results.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(ValidationCode::$variant => $code,)*
+                    ValidationCode::Other(code) => code,
+                }
+            }
+
+            /// Severity from the specification's success/informational/failure lists,
+            /// or `None` for `Other`.
+            pub fn severity(&self) -> Option<Severity> {
+                match self {
+                    $(ValidationCode::$variant => Some(Severity::$severity),)*
+                    ValidationCode::Other(_) => None,
+                }
+            }
+        }
+    };
+}
+
+validation_codes! {
+    // Success codes
+    ClaimSignatureInsideValidity => "claimSignature.insideValidity", Pass;
+    ClaimSignatureValidated => "claimSignature.validated", Pass;
+    SigningCredentialTrusted => "signingCredential.trusted", Pass;
+    SigningCredentialOcspNotRevoked => "signingCredential.ocsp.notRevoked", Pass;
+    TimeStampTrusted => "timeStamp.trusted", Pass;
+    TimeStampValidated => "timeStamp.validated", Pass;
+    AssertionHashedUriMatch => "assertion.hashedURI.match", Pass;
+    AssertionDataHashMatch => "assertion.dataHash.match", Pass;
+    AssertionBmffHashMatch => "assertion.bmffHash.match", Pass;
+    AssertionBoxesHashMatch => "assertion.boxesHash.match", Pass;
+    AssertionCollectionHashMatch => "assertion.collectionHash.match", Pass;
+    AssertionAccessible => "assertion.accessible", Pass;
+    IngredientManifestValidated => "ingredient.manifest.validated", Pass;
+    IngredientClaimSignatureValidated => "ingredient.claimSignature.validated", Pass;
+    // Informational codes: the manifest is intact but something deserves attention
+    AlgorithmDeprecated => "algorithm.deprecated", Warn;
+    SigningCredentialOcspInaccessible => "signingCredential.ocsp.inaccessible", Warn;
+    SigningCredentialOcspSkipped => "signingCredential.ocsp.skipped", Warn;
+    SigningCredentialOcspUnknown => "signingCredential.ocsp.unknown", Warn;
+    TimeOfSigningInsideValidity => "timeOfSigning.insideValidity", Warn;
+    TimeOfSigningOutsideValidity => "timeOfSigning.outsideValidity", Warn;
+    TimeStampUntrusted => "timeStamp.untrusted", Warn;
+    IngredientUnknownProvenance => "ingredient.unknownProvenance", Warn;
+    ManifestUnknownProvenance => "manifest.unknownProvenance", Warn;
+    // Failure codes
+    GeneralError => "general.error", Fail;
+    AlgorithmUnsupported => "algorithm.unsupported", Fail;
+    ClaimMissing => "claim.missing", Fail;
+    ClaimMultiple => "claim.multiple", Fail;
+    ClaimMalformed => "claim.malformed", Fail;
+    ClaimCborInvalid => "claim.cbor.invalid", Fail;
+    ClaimHardBindingsMissing => "claim.hardBindings.missing", Fail;
+    ClaimRequiredMissing => "claim.required.missing", Fail;
+    ClaimSignatureMissing => "claimSignature.missing", Fail;
+    ClaimSignatureMismatch => "claimSignature.mismatch", Fail;
+    ClaimSignatureOutsideValidity => "claimSignature.outsideValidity", Fail;
+    ManifestInaccessible => "manifest.inaccessible", Fail;
+    ManifestMultipleParents => "manifest.multipleParents", Fail;
+    ManifestUpdateInvalid => "manifest.update.invalid", Fail;
+    ManifestUpdateWrongParents => "manifest.update.wrongParents", Fail;
+    ManifestCompressedInvalid => "manifest.compressed.invalid", Fail;
+    ManifestTimestampInvalid => "manifest.timestamp.invalid", Fail;
+    ManifestTimestampWrongParents => "manifest.timestamp.wrongParents", Fail;
+    SigningCredentialUntrusted => "signingCredential.untrusted", Fail;
+    SigningCredentialInvalid => "signingCredential.invalid", Fail;
+    SigningCredentialRevoked => "signingCredential.revoked", Fail;
+    SigningCredentialExpired => "signingCredential.expired", Fail;
+    SigningCredentialOcspRevoked => "signingCredential.ocsp.revoked", Fail;
+    TimeStampMismatch => "timeStamp.mismatch", Fail;
+    TimeStampMalformed => "timeStamp.malformed", Fail;
+    TimeStampOutsideValidity => "timeStamp.outsideValidity", Fail;
+    HashedUriMissing => "hashedURI.missing", Fail;
+    HashedUriMismatch => "hashedURI.mismatch", Fail;
+    AssertionHashedUriMismatch => "assertion.hashedURI.mismatch", Fail;
+    AssertionMissing => "assertion.missing", Fail;
+    AssertionMultipleHardBindings => "assertion.multipleHardBindings", Fail;
+    AssertionUndeclared => "assertion.undeclared", Fail;
+    AssertionInaccessible => "assertion.inaccessible", Fail;
+    AssertionNotRedacted => "assertion.notRedacted", Fail;
+    AssertionSelfRedacted => "assertion.selfRedacted", Fail;
+    AssertionJsonInvalid => "assertion.json.invalid", Fail;
+    AssertionCborInvalid => "assertion.cbor.invalid", Fail;
+    AssertionActionIngredientMismatch => "assertion.action.ingredientMismatch", Fail;
+    AssertionActionMalformed => "assertion.action.malformed", Fail;
+    AssertionActionRedacted => "assertion.action.redacted", Fail;
+    AssertionActionRedactionMismatch => "assertion.action.redactionMismatch", Fail;
+    AssertionDataHashMismatch => "assertion.dataHash.mismatch", Fail;
+    AssertionDataHashMalformed => "assertion.dataHash.malformed", Fail;
+    AssertionBmffHashMismatch => "assertion.bmffHash.mismatch", Fail;
+    AssertionBmffHashMalformed => "assertion.bmffHash.malformed", Fail;
+    AssertionBoxesHashMismatch => "assertion.boxesHash.mismatch", Fail;
+    AssertionBoxesHashUnknownBox => "assertion.boxesHash.unknownBox", Fail;
+    AssertionCollectionHashMismatch => "assertion.collectionHash.mismatch", Fail;
+    AssertionCollectionHashIncorrectFileCount => "assertion.collectionHash.incorrectFileCount", Fail;
+    AssertionCollectionHashInvalidUri => "assertion.collectionHash.invalidURI", Fail;
+    AssertionCollectionHashMalformed => "assertion.collectionHash.malformed", Fail;
+    AssertionCloudDataHardBinding => "assertion.cloud-data.hardBinding", Fail;
+    AssertionCloudDataActions => "assertion.cloud-data.actions", Fail;
+    AssertionCloudDataMalformed => "assertion.cloud-data.malformed", Fail;
+    AssertionCloudDataLabelMismatch => "assertion.cloud-data.labelMismatch", Fail;
+    AssertionIngredientMalformed => "assertion.ingredient.malformed", Fail;
+    AssertionTimestampMalformed => "assertion.timestamp.malformed", Fail;
+    IngredientManifestMissing => "ingredient.manifest.missing", Fail;
+    IngredientManifestMismatch => "ingredient.manifest.mismatch", Fail;
+    IngredientClaimSignatureMissing => "ingredient.claimSignature.missing", Fail;
+    IngredientClaimSignatureMismatch => "ingredient.claimSignature.mismatch", Fail;
+    IngredientHashedUriMissing => "ingredient.hashedURI.missing", Fail;
+    IngredientHashedUriMismatch => "ingredient.hashedURI.mismatch", Fail;
+    // Engine policy codes, outside the C2PA specification
+    SignerNameAllowed => "que.signerName.allowed", Pass;
+    SignerNameNotAllowed => "que.signerName.notAllowed", Fail;
+    SignerFingerprintMatch => "que.signerFingerprint.match", Pass;
+    SignerFingerprintMismatch => "que.signerFingerprint.mismatch", Fail;
+}
+
+impl FromStr for ValidationCode {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(ValidationCode::parse(s))
+    }
+}
+
+impl std::fmt::Display for ValidationCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Serialized as the raw code string.
+impl Serialize for ValidationCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
@@ -0,0 +1,52 @@
+// crates/engine/src/domain/verdict.rs
+
+//! Severity of C2PA validation status codes, and the overall [`Verdict`] derived
+//! from them.
+//!
+//! Codes are classified through [`ValidationCode`] rather than by inspecting their text,
+//! so a new or renamed c2pa code falls through to a caller-chosen default instead of
+//! being silently misclassified.
+
+use serde::Serialize;
+
+use crate::domain::validation_code::ValidationCode;
+use crate::domain::verify::{ValidationStatus, Verdict};
+
+/// How much a single validation status counts against the asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum Severity {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// Severity of a known status code, or `None` if the code is not in the table.
+pub fn code_severity(code: &str) -> Option<Severity> {
+    ValidationCode::parse(code).severity()
+}
+
+/// Severity of one status entry.
+///
+/// Known codes use the table. Unknown codes that passed are `Pass`; unknown codes
+/// that did not pass take `unknown_severity`.
+pub fn status_severity(status: &ValidationStatus, unknown_severity: Severity) -> Severity {
+    match status.code_enum.severity() {
+        Some(severity) => severity,
+        None if status.passed => Severity::Pass,
+        None => unknown_severity,
+    }
+}
+
+/// The overall verdict: the most severe status decides.
+pub fn compute_verdict(statuses: &[ValidationStatus], unknown_severity: Severity) -> Verdict {
+    let worst = statuses
+        .iter()
+        .map(|s| status_severity(s, unknown_severity))
+        .max()
+        .unwrap_or(Severity::Pass);
+    match worst {
+        Severity::Pass => Verdict::Allowed,
+        Severity::Warn => Verdict::Warning,
+        Severity::Fail => Verdict::Rejected,
+    }
+}
@@ -1,9 +1,28 @@
 // crates/engine/src/domain/verify.rs
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
 use serde::Serialize;
+use serde_json::{Map, Value};
+use x509_parser::certificate::X509Certificate;
+use x509_parser::extensions::ParsedExtension;
+use x509_parser::parse_x509_certificate;
+use x509_parser::pem::Pem;
+use x509_parser::x509::X509Name;
+
+use crate::domain::error::{EngineError, EngineResult};
+use crate::domain::validation_code::ValidationCode;
+use crate::domain::verdict::Severity;
 
-/// Certificate summary extracted from the active claim signature.
+/// Certificate summary extracted from a manifest's claim signature.
 #[derive(Debug, Serialize, Clone, Default)]
 pub struct CertInfo {
+    /// Label of the manifest this signature belongs to.
+    pub manifest_label: Option<String>,
+    /// Label of the identity assertion carrying this signature (e.g. a co-signer's
+    /// `cawg.identity`); `None` for the claim signature.
+    pub assertion_label: Option<String>,
     pub alg: Option<String>,
     pub issuer: Option<String>,
     pub cert_serial_number: Option<String>,
@@ -13,18 +32,100 @@ pub struct CertInfo {
     pub chain_pem: Option<String>,
 }
 
+impl CertInfo {
+    /// Write each certificate of `chain_pem` to its own file in `dir`, as `cert_0.pem`
+    /// (the signer), `cert_1.pem`, and so on, for inspection with `openssl x509`.
+    ///
+    /// Every block must be a `CERTIFICATE` that parses as X.509; nothing is written
+    /// otherwise. `dir` is created if needed. Returns the written paths in chain order.
+    pub fn write_chain(&self, dir: &Path) -> EngineResult<Vec<PathBuf>> {
+        let pem = self
+            .chain_pem
+            .as_deref()
+            .ok_or_else(|| EngineError::Config("certificate has no chain_pem".into()))?;
+        let blocks = split_certificate_pem(pem)?;
+        if blocks.is_empty() {
+            return Err(EngineError::Config("chain_pem contains no certificates".into()));
+        }
+
+        std::fs::create_dir_all(dir)?;
+        let mut paths = Vec::with_capacity(blocks.len());
+        for (i, block) in blocks.iter().enumerate() {
+            let path = dir.join(format!("cert_{i}.pem"));
+            std::fs::write(&path, block)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+/// Splits concatenated PEM into `CERTIFICATE` blocks, checking that each decodes to
+/// a single X.509 certificate.
+fn split_certificate_pem(pem: &str) -> EngineResult<Vec<String>> {
+    const BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+    const END: &str = "-----END CERTIFICATE-----";
+
+    let mut blocks = Vec::new();
+    let mut body: Option<Vec<&str>> = None;
+    for line in pem.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        match (&mut body, line) {
+            (None, BEGIN) => body = Some(Vec::new()),
+            (Some(lines), END) => {
+                let b64 = lines.concat();
+                let der = BASE64.decode(&b64).map_err(|e| {
+                    EngineError::Config(format!("malformed PEM in certificate {}: {e}", blocks.len()))
+                })?;
+                match parse_x509_certificate(&der) {
+                    Ok(([], _)) => {}
+                    _ => {
+                        return Err(EngineError::Config(format!(
+                            "certificate {} is not a valid X.509 certificate",
+                            blocks.len()
+                        )))
+                    }
+                }
+                let mut block = String::from(BEGIN);
+                for chunk in b64.as_bytes().chunks(64) {
+                    block.push('\n');
+                    block.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+                }
+                block.push('\n');
+                block.push_str(END);
+                block.push('\n');
+                blocks.push(block);
+                body = None;
+            }
+            (Some(lines), l) if !l.starts_with("-----") => lines.push(l),
+            (_, l) => {
+                return Err(EngineError::Config(format!(
+                    "malformed PEM: unexpected line '{l}' in certificate chain"
+                )))
+            }
+        }
+    }
+    if body.is_some() {
+        return Err(EngineError::Config("malformed PEM: unterminated certificate block".into()));
+    }
+    Ok(blocks)
+}
+
 /// Generic verification result. For now, a string report like c2pa::Reader
 /// produces; can be made more structured later.
 #[derive(Debug, Serialize, Clone)]
 pub struct VerificationResult {
     pub report: String,
-    /// Optional list of certificates involved in signing.
+    /// Signing certificates of every manifest in the store, active manifest first.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub certificates: Option<Vec<CertInfo>>,
     /// Structured validation statuses mapped from c2pa validation results.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<Vec<ValidationStatus>>,
 
+    /// How many entries of `status` c2pa reported as success, informational or
+    /// failure. Set together with `status`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_summary: Option<StatusSummary>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub verdict: Option<Verdict>,
 
@@ -36,25 +137,335 @@ pub struct VerificationResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub remote_url: Option<String>,
 
+    /// Where the manifest store was read from. Unlike `is_embedded` and `remote_url`,
+    /// tells an embedded store that also references a remote URL from one that was
+    /// fetched over the network.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest_location: Option<ManifestLocation>,
+
+    /// Soft bindings recorded in the active manifest.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub soft_bindings: Option<Vec<crate::domain::soft_binding::SoftBindingInfo>>,
+
+    /// Trusted times attached to manifest signatures, whether embedded in the
+    /// signature or added later by a time-stamp manifest.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamps: Option<Vec<TimestampInfo>>,
+
+    /// The trust anchor the active manifest's signing chain was validated against.
+    /// Only set when a trust policy with anchors is applied and the signer is trusted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trust_anchor: Option<TrustAnchorInfo>,
+
+    /// Whether the active manifest is an update manifest: a signed claim over its
+    /// parent with no hard binding of its own. `None` when there is no active manifest.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_update_manifest: Option<bool>,
+
+    /// Claim generator and recorded actions of the active manifest, condensed for
+    /// display. `None` when the manifest has no `c2pa.actions` assertion.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provenance_summary: Option<ProvenanceSummary>,
+
+    /// c2pa's validation results verbatim: the active manifest's success,
+    /// informational and failure partitions plus the per-ingredient deltas. Only set
+    /// when `C2paVerificationConfig::include_raw_validation_results` is enabled and
+    /// the store was validated. The layout follows c2pa and may change with it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_validation_results: Option<Value>,
+
     /// CAWG identity verification results (requires feature)
     #[cfg(feature = "cawg")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cawg: Option<crate::domain::cawg::CawgVerification>,
 }
 
+impl VerificationResult {
+    /// The result as JSON with a stable layout for downstream tools.
+    ///
+    /// Keys are `report`, `verdict` (`"allowed"`, `"warning"`, `"rejected"` or `null`
+    /// when there is no manifest), `status`, `certificates`, `is_embedded`,
+    /// `remote_url`, `manifest_location`, `soft_bindings`, `timestamps`, `trust_anchor`, `is_update_manifest`, `provenance_summary` and `raw_validation_results`, plus `cawg` with the
+    /// `cawg` feature.
+    /// Absent values are `null` rather than omitted.
+    pub fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("report".into(), Value::String(self.report.clone()));
+        obj.insert(
+            "verdict".into(),
+            self.verdict.map_or(Value::Null, |v| Value::String(v.to_string())),
+        );
+        obj.insert("status".into(), to_value_or_null(&self.status));
+        obj.insert("status_summary".into(), to_value_or_null(&self.status_summary));
+        obj.insert("certificates".into(), to_value_or_null(&self.certificates));
+        obj.insert("is_embedded".into(), to_value_or_null(&self.is_embedded));
+        obj.insert("remote_url".into(), to_value_or_null(&self.remote_url));
+        obj.insert("manifest_location".into(), to_value_or_null(&self.manifest_location));
+        obj.insert("soft_bindings".into(), to_value_or_null(&self.soft_bindings));
+        obj.insert("timestamps".into(), to_value_or_null(&self.timestamps));
+        obj.insert("trust_anchor".into(), to_value_or_null(&self.trust_anchor));
+        obj.insert("is_update_manifest".into(), to_value_or_null(&self.is_update_manifest));
+        obj.insert("provenance_summary".into(), to_value_or_null(&self.provenance_summary));
+        obj.insert("raw_validation_results".into(), to_value_or_null(&self.raw_validation_results));
+        #[cfg(feature = "cawg")]
+        obj.insert("cawg".into(), to_value_or_null(&self.cawg));
+        Value::Object(obj)
+    }
+}
+
+/// Where a verified manifest store came from.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub enum ManifestLocation {
+    /// Embedded in the asset.
+    Embedded,
+    /// Fetched from this URL during verification; network access was involved.
+    Remote(String),
+    /// Embedded in the asset, which also references a remote copy at this URL.
+    /// The embedded store was used and nothing was fetched.
+    EmbeddedWithRemoteRef(String),
+    /// Read from a `.c2pa` sidecar next to the asset, or from manifest bytes
+    /// supplied separately.
+    Sidecar,
+}
+
+fn to_value_or_null<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+/// An RFC 3161 time-stamp over a manifest's claim signature.
+#[derive(Debug, Serialize, Clone)]
+pub struct TimestampInfo {
+    /// Label of the manifest whose signature is time-stamped.
+    pub manifest_label: String,
+    /// Time asserted by the time-stamp authority (RFC 3339).
+    pub time: String,
+    /// Label of the time-stamp manifest that added this time-stamp, or `None` when it
+    /// is embedded in the signature itself.
+    pub added_by: Option<String>,
+    /// Whether the time-stamp authority's certificate chain is trusted. c2pa checks it
+    /// against the same trust anchors as signers; `Some(false)` comes with a
+    /// `timeStamp.untrusted` (or `assertion.timestamp.malformed`) status. `None` when
+    /// the store was not validated or reported nothing for this time-stamp.
+    pub valid: Option<bool>,
+}
+
+/// How the active manifest says its asset came about: the tool that signed it and
+/// the actions it recorded.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct ProvenanceSummary {
+    /// Claim generator, as `name/version` when the generator info carries a version.
+    pub generator: Option<String>,
+    /// `digitalSourceType` of the `c2pa.created` action, if there is one.
+    pub creation_source_type: Option<String>,
+    /// Labels of the editing actions (e.g. `c2pa.resized`, `c2pa.color_adjustments`)
+    /// in recorded order, each listed once. `c2pa.created` and `c2pa.opened` are not
+    /// edits and are left out.
+    pub edit_actions: Vec<String>,
+}
+
+/// A trust anchor that validated a signing certificate chain.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct TrustAnchorInfo {
+    /// Subject distinguished name of the anchor certificate.
+    pub subject: String,
+    /// Serial number of the anchor certificate, in decimal like `CertInfo::cert_serial_number`.
+    pub serial: String,
+}
+
+/// Finds the anchor in `anchors_pem` that `chain_pem` (signer first) chains to.
+///
+/// An anchor matches when it is one of the chain's certificates, or when it issued the
+/// last one: its subject equals that certificate's issuer and, if both carry key
+/// identifiers, its subject key identifier equals the authority key identifier.
+/// Signatures are not re-checked; this only names the anchor c2pa already trusted.
+pub(crate) fn match_trust_anchor(chain_pem: &str, anchors_pem: &[u8]) -> Option<TrustAnchorInfo> {
+    let chain = parse_pem_certificates(chain_pem.as_bytes());
+    let anchors = parse_pem_certificates(anchors_pem);
+    let top = chain.last()?;
+    let top_cert = parse_x509_certificate(top).ok()?.1;
+
+    anchors.iter().find_map(|der| {
+        let (_, anchor) = parse_x509_certificate(der).ok()?;
+        let in_chain = chain.contains(der);
+        let issued_top = anchor.subject().as_raw() == top_cert.issuer().as_raw()
+            && match (key_identifiers(&anchor).0, key_identifiers(&top_cert).1) {
+                (Some(ski), Some(aki)) => ski == aki,
+                _ => true,
+            };
+        (in_chain || issued_top).then(|| TrustAnchorInfo {
+            subject: anchor.subject().to_string(),
+            serial: anchor.serial.to_string(),
+        })
+    })
+}
+
+/// Checks the leaf of `chain_pem` against `allowed_subjects` / `allowed_issuers`.
+///
+/// An entry matches a name when it equals, ignoring ASCII case, one of its common
+/// names or organizations, or the whole distinguished name. Lists that are `None` are
+/// not checked. Returns the reason the signer was refused, if it was.
+pub(crate) fn check_signer_name(
+    chain_pem: &str,
+    allowed_subjects: Option<&[String]>,
+    allowed_issuers: Option<&[String]>,
+) -> Result<(), String> {
+    let chain = parse_pem_certificates(chain_pem.as_bytes());
+    let leaf = chain
+        .first()
+        .and_then(|der| parse_x509_certificate(der).ok())
+        .map(|(_, cert)| cert)
+        .ok_or_else(|| "signer certificate could not be parsed".to_string())?;
+
+    if let Some(allowed) = allowed_subjects {
+        if !name_allowed(leaf.subject(), allowed) {
+            return Err(format!("signer subject \"{}\" is not in allowed_subjects", leaf.subject()));
+        }
+    }
+    if let Some(allowed) = allowed_issuers {
+        if !name_allowed(leaf.issuer(), allowed) {
+            return Err(format!("signer issuer \"{}\" is not in allowed_issuers", leaf.issuer()));
+        }
+    }
+    Ok(())
+}
+
+/// Normalizes a SHA-256 certificate fingerprint to 64 lowercase hex digits. Colons,
+/// whitespace and case are ignored, so `openssl x509 -fingerprint -sha256` output is
+/// accepted as is.
+pub(crate) fn normalize_fingerprint(fingerprint: &str) -> EngineResult<String> {
+    let hex: String = fingerprint
+        .chars()
+        .filter(|c| *c != ':' && !c.is_whitespace())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(EngineError::Config(format!(
+            "expected_signer_fingerprint must be a SHA-256 fingerprint (64 hex digits), got \"{fingerprint}\""
+        )));
+    }
+    Ok(hex)
+}
+
+/// DER bytes of the leaf (first) certificate of `chain_pem`.
+pub(crate) fn signer_certificate_der(chain_pem: &str) -> Option<Vec<u8>> {
+    parse_pem_certificates(chain_pem.as_bytes()).into_iter().next()
+}
+
+fn name_allowed(name: &X509Name<'_>, allowed: &[String]) -> bool {
+    let full = name.to_string();
+    let parts: Vec<&str> = name
+        .iter_common_name()
+        .chain(name.iter_organization())
+        .filter_map(|attr| attr.as_str().ok())
+        .collect();
+    allowed.iter().any(|entry| {
+        entry.eq_ignore_ascii_case(&full) || parts.iter().any(|part| entry.eq_ignore_ascii_case(part))
+    })
+}
+
+/// DER bytes of every `CERTIFICATE` block in `pem`; other blocks and text are skipped.
+fn parse_pem_certificates(pem: &[u8]) -> Vec<Vec<u8>> {
+    Pem::iter_from_buffer(pem)
+        .filter_map(Result::ok)
+        .filter(|p| p.label == "CERTIFICATE")
+        .map(|p| p.contents)
+        .collect()
+}
+
+/// The certificate's (subject, authority) key identifiers, when present.
+fn key_identifiers<'a>(cert: &'a X509Certificate<'_>) -> (Option<&'a [u8]>, Option<&'a [u8]>) {
+    let mut ids = (None, None);
+    for ext in cert.extensions() {
+        match ext.parsed_extension() {
+            ParsedExtension::SubjectKeyIdentifier(ski) => ids.0 = Some(ski.0),
+            ParsedExtension::AuthorityKeyIdentifier(aki) => {
+                ids.1 = aki.key_identifier.as_ref().map(|k| k.0)
+            }
+            _ => {}
+        }
+    }
+    ids
+}
+
 /// Structured validation status entry.
 #[derive(Debug, Serialize, Clone)]
 pub struct ValidationStatus {
     pub code: String,
+    /// `code` parsed into a typed code; unknown codes are kept in `ValidationCode::Other`.
+    pub code_enum: ValidationCode,
     pub url: Option<String>,
     pub explanation: Option<String>,
     pub ingredient_uri: Option<String>,
+    /// Label of the manifest whose validation reported this entry: the active
+    /// manifest, or an ingredient's manifest for ingredient deltas. `None` when it
+    /// can't be determined.
+    pub manifest_label: Option<String>,
     pub passed: bool,
+    /// Weight of this entry in the verdict (see `domain::verdict`). Failed codes
+    /// missing from the table take the call's `unknown_status_severity`.
+    pub severity: Severity,
 }
 
-#[derive(Debug, Serialize, Clone)]
+/// Counts of validation statuses by the list c2pa reported them in. Informational
+/// entries are notes such as a skipped OCSP check; only `failure` entries mean
+/// validation failed.
+#[derive(Debug, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatusSummary {
+    pub success: usize,
+    pub informational: usize,
+    pub failure: usize,
+}
+
+/// An ingredient together with the validation state of its own provenance.
+#[derive(Debug, Clone)]
+pub struct IngredientReport {
+    /// Serialized `ingredient.json`.
+    pub json: Vec<u8>,
+    /// Validation statuses of the ingredient's manifest; empty when unsigned.
+    pub validation_status: Vec<ValidationStatus>,
+    /// Whether the asset carried a C2PA manifest.
+    pub is_signed: bool,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
 pub enum Verdict {
     Allowed,
     Warning,
     Rejected,
 }
+
+impl Verdict {
+    /// Process exit code: 0 for `Allowed` and `Warning`, 1 for `Rejected`.
+    pub fn exit_code(&self) -> i32 {
+        self.exit_code_with(0)
+    }
+
+    /// Like [`Verdict::exit_code`], with a caller-chosen code for `Warning`
+    /// (e.g. 2 for pipelines that must not pass on warnings).
+    pub fn exit_code_with(&self, warning_code: i32) -> i32 {
+        match self {
+            Verdict::Allowed => 0,
+            Verdict::Warning => warning_code,
+            Verdict::Rejected => 1,
+        }
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Allowed => "allowed",
+            Verdict::Warning => "warning",
+            Verdict::Rejected => "rejected",
+        })
+    }
+}
+
+/// Verification outcome for one init-segment group of fragmented BMFF content.
+#[derive(Debug)]
+pub struct FragmentedBmffVerification {
+    pub init_path: PathBuf,
+    /// Fragments verified with the init segment, in playback order.
+    pub fragments: Vec<PathBuf>,
+    pub result: EngineResult<VerificationResult>,
+}
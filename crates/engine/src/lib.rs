@@ -6,10 +6,14 @@
 pub mod adapters;
 pub mod crypto;
 pub mod domain;
+pub mod util;
+mod telemetry;
 
 use domain::error::{EngineResult};
-pub use domain::types::{AssetRef, C2paConfig, C2paVerificationConfig, OutputTarget, EngineDefaults, IngredientConfig, FragmentedBmffConfig, LimitsConfig};
+pub use domain::types::{AssetRef, C2paConfig, C2paConfigBuilder, C2paVerificationConfig, OutputTarget, EngineDefaults, IngredientConfig, BmffLayout, FragmentedBmffConfig, FragmentedBmffVerifyConfig, LimitsConfig, RemoveManifestConfig, ThumbnailConfig, ThumbnailFormat, ClaimGeneratorInfo, ClaimGeneratorIcon, DeterministicConfig, DetachedManifest, PostSignMode, SignOutcome};
 pub use domain::error::EngineError;
+pub use domain::types::{default_limits, set_default_limits};
+pub use domain::info::EngineInfo;
 
 /// High-level helpers for the common "C2PA default" path.
 /// Internally call the C2PA adapter. These give QueCloud a simple entrypoint.
@@ -18,6 +22,14 @@ pub fn sign_c2pa(cfg: C2paConfig) -> EngineResult<Option<Vec<u8>>> {
     adapters::c2pa::C2pa::generate(cfg)
 }
 
+/// Like [`sign_c2pa`], also returning the engine's verification of the signed asset,
+/// so callers see what was validated on their behalf. `verification` is `None` when
+/// post-sign validation is skipped. With `PostSignMode::ValidateReport` a failed
+/// verification does not fail the call; inspect `verification.verdict` instead.
+pub fn sign_c2pa_with_report(cfg: C2paConfig) -> EngineResult<SignOutcome> {
+    adapters::c2pa::C2pa::generate_with_report(cfg)
+}
+
 pub fn sign_c2pa_bytes(
     bytes: &[u8],
     mut cfg: C2paConfig,
@@ -36,24 +48,189 @@ pub fn verify_c2pa(cfg: C2paVerificationConfig) -> EngineResult<VerificationResu
     adapters::c2pa::C2pa::verify(cfg)
 }
 
+/// Just the [`Verdict`] for `source` under `policy`, for callers that gate on the
+/// outcome alone. Runs a `Summary` verification with the secure defaults but skips
+/// the report, certificate, timestamp and assertion extraction `verify_c2pa` does.
+/// A manifest store that was not validated yields `Rejected`.
+pub fn verify_verdict(source: AssetRef, policy: Option<TrustPolicyConfig>) -> EngineResult<Verdict> {
+    adapters::c2pa::C2pa::verify_verdict(source, policy)
+}
+
+/// Verify several assets in one call, returning one result per config in input order.
+/// Assets are verified on a few worker threads, and c2pa settings are applied once per
+/// worker when the configs share them. A failing asset only fails its own entry.
+pub fn verify_c2pa_batch(cfgs: Vec<C2paVerificationConfig>) -> Vec<EngineResult<VerificationResult>> {
+    adapters::c2pa::C2pa::verify_batch(cfgs)
+}
+
+/// Sign with a detached manifest and return the asset and the manifest store bytes
+/// together, for storing the manifest apart from the asset without a
+/// `remote_manifest_url`. `cfg.output` and `cfg.embed` are ignored. Written side by side
+/// with [`DetachedManifest::write_to`], the pair verifies with `verify_c2pa` on the
+/// asset path.
+pub fn sign_c2pa_detached(cfg: C2paConfig) -> EngineResult<DetachedManifest> {
+    adapters::c2pa::C2pa::generate_detached(cfg)
+}
+
+/// Dry run of [`sign_c2pa`]: the manifest that would be signed for `cfg`, as JSON.
+/// The signer is never resolved or called, so no KMS/HSM or TSA request is made.
+/// Soft-binding and fingerprint assertions, which depend on the asset content, are
+/// not included.
+pub fn preview_manifest(cfg: &C2paConfig) -> EngineResult<String> {
+    adapters::c2pa::C2pa::preview_manifest(cfg)
+}
+
+/// Async variant of [`sign_c2pa`] for callers running inside a tokio runtime.
+/// The call runs on tokio's blocking pool rather than a runtime worker and holds a
+/// pool thread until it finishes, CAWG signing included. The returned future is
+/// `Send` (except on wasm32).
+pub async fn sign_c2pa_async(cfg: C2paConfig) -> EngineResult<Option<Vec<u8>>> {
+    adapters::c2pa::C2pa::generate_async(cfg).await
+}
+
+/// Async variant of [`sign_c2pa_with_report`] for callers running inside a tokio runtime.
+pub async fn sign_c2pa_with_report_async(cfg: C2paConfig) -> EngineResult<SignOutcome> {
+    adapters::c2pa::C2pa::generate_with_report_async(cfg).await
+}
+
+/// Async variant of [`verify_c2pa`] for callers running inside a tokio runtime.
+/// Like [`sign_c2pa_async`], it runs on the blocking pool and its future is `Send`.
+pub async fn verify_c2pa_async(cfg: C2paVerificationConfig) -> EngineResult<VerificationResult> {
+    adapters::c2pa::C2pa::verify_async(cfg).await
+}
+
 /// Create an ingredient from an asset. If `output` is `Memory`, returns the serialized
 /// `ingredient.json` bytes. If `Path(dir)`, writes files to the folder.
 pub fn create_ingredient(cfg: IngredientConfig) -> EngineResult<Option<Vec<u8>>> {
     adapters::c2pa::C2pa::create_ingredient(cfg)
 }
 
+/// Like [`create_ingredient`], but also reports whether the ingredient is signed and
+/// the validation statuses of its manifest, saving a separate verification pass.
+pub fn create_ingredient_report(cfg: IngredientConfig) -> EngineResult<IngredientReport> {
+    adapters::c2pa::C2pa::create_ingredient_report(cfg)
+}
+
+/// Strip the embedded C2PA manifest store from an asset, in any format c2pa can write.
+/// If `output` is `Memory`, returns the stripped bytes. If `Path`, writes the file.
+pub fn remove_manifest(cfg: RemoveManifestConfig) -> EngineResult<Option<Vec<u8>>> {
+    adapters::c2pa::C2pa::remove_manifest(cfg)
+}
+
+/// Add a second signature to an already-signed asset. The existing manifest store is
+/// kept and a new manifest referencing the current active manifest as its parent is
+/// appended, so verification reports both. Supported formats are listed in
+/// [`COUNTERSIGN_FORMATS`]; others (including PDF) return `EngineError::UnsupportedFormat`.
+#[cfg(feature = "c2pa")]
+pub fn countersign(
+    source: AssetRef,
+    signer: Signer,
+    alg: SigAlg,
+    timestamper: Option<Timestamper>,
+    output: OutputTarget,
+) -> EngineResult<Option<Vec<u8>>> {
+    adapters::c2pa::C2pa::countersign(source, signer, alg, timestamper, output)
+}
+
+/// Add a trusted time-stamp to an already-signed asset without re-signing its claim.
+/// A token over the active manifest's signature is recorded in a new time-stamp
+/// manifest signed by `signer`; verification reports it in `timestamps`. Errors if the
+/// active manifest is already time-stamped, unless `force` is set.
+#[cfg(feature = "c2pa")]
+pub fn add_timestamp(
+    source: AssetRef,
+    signer: Signer,
+    alg: SigAlg,
+    timestamper: Timestamper,
+    force: bool,
+    output: OutputTarget,
+) -> EngineResult<Option<Vec<u8>>> {
+    adapters::c2pa::C2pa::add_timestamp(source, signer, alg, timestamper, force, output)
+}
+
+/// Compare the active manifests of two assets: added, removed and changed assertions,
+/// ingredient differences and signer differences. Useful for seeing what changed
+/// between two signings of the same asset.
+#[cfg(feature = "c2pa")]
+pub fn diff_manifests(a: AssetRef, b: AssetRef) -> EngineResult<ManifestDiff> {
+    adapters::c2pa::C2pa::diff_manifests(a, b)
+}
+
+/// Return the raw embedded manifest store (JUMBF) bytes of an asset.
+/// Errors when the asset carries no embedded manifest.
+#[cfg(feature = "c2pa")]
+pub fn extract_manifest_store(source: AssetRef, limits: LimitsConfig) -> EngineResult<Vec<u8>> {
+    adapters::c2pa::C2pa::extract_manifest_store(source, limits)
+}
+
+/// Return an asset's manifest store as JSON, or `None` when it has no manifest.
+/// Skips validation and trust evaluation, so it is much cheaper than `verify_c2pa`
+/// but says nothing about whether the manifest can be trusted.
+#[cfg(feature = "c2pa")]
+pub fn read_manifest_json(source: AssetRef, limits: LimitsConfig) -> EngineResult<Option<String>> {
+    adapters::c2pa::C2pa::read_manifest_json(source, limits)
+}
+
+/// Versions, supported formats and compiled-in features of this build, for support
+/// diagnostics and for gating behavior on capabilities.
+pub fn engine_info() -> EngineInfo {
+    adapters::c2pa::C2pa::engine_info()
+}
+
+/// Attach pre-built manifest store bytes to an asset without re-signing.
+/// `embed` writes the store into the asset (any format c2pa can write); otherwise the asset is copied to
+/// the `Path` output and the store is written alongside it as a `.c2pa` sidecar.
+#[cfg(feature = "c2pa")]
+pub fn apply_sidecar_manifest(
+    asset: AssetRef,
+    manifest_bytes: Vec<u8>,
+    output: OutputTarget,
+    embed: bool,
+) -> EngineResult<Option<Vec<u8>>> {
+    adapters::c2pa::C2pa::apply_sidecar_manifest(asset, manifest_bytes, output, embed)
+}
+
 /// Embed a manifest into fragmented BMFF assets (init + fragments) using glob patterns.
 #[cfg(all(feature = "c2pa", feature = "bmff"))]
 pub fn generate_fragmented_bmff(cfg: FragmentedBmffConfig) -> EngineResult<()> {
     adapters::c2pa::C2pa::generate_fragmented_bmff(cfg)
 }
 
+#[cfg(all(feature = "c2pa", feature = "bmff"))]
+pub fn verify_fragmented_bmff(
+    cfg: FragmentedBmffVerifyConfig,
+) -> EngineResult<Vec<FragmentedBmffVerification>> {
+    adapters::c2pa::C2pa::verify_fragmented_bmff(cfg)
+}
+
 // Re-exports for convenience
+#[cfg(feature = "c2pa")]
+pub use adapters::c2pa::{BatchSigner, COUNTERSIGN_FORMATS};
+#[cfg(all(feature = "c2pa", feature = "tsa_client"))]
+pub use adapters::c2pa::TsaClient;
+pub use adapters::c2pa::{register_content_detector, ContentDetector};
 pub use crypto::signer::Signer;
 pub use crypto::timestamper::Timestamper;
 pub use domain::manifest_engine::ManifestEngine;
-pub use domain::types::{SigAlg, VerifyMode, TrustPolicyConfig};
-pub use domain::verify::VerificationResult;
+pub use domain::types::{HashAlg, SigAlg, VerifyMode, TrustPolicyConfig};
+pub use domain::verify::{CertInfo, FragmentedBmffVerification, IngredientReport, ManifestLocation, ProvenanceSummary, StatusSummary, TimestampInfo, TrustAnchorInfo, VerificationResult, Verdict};
+pub use domain::diff::{ChangeKind, ManifestChange, ManifestDiff};
+pub use domain::progress::{ProgressCallback, ProgressEvent, ProgressPhase};
+pub use domain::cancel::{CancelOnDrop, CancellationToken};
+pub use domain::verdict::{compute_verdict, Severity};
+pub use domain::validation_code::ValidationCode;
+pub use domain::manifest_builder::{Action, IngredientRelationship, ManifestBuilder, ManifestSource};
+pub use domain::assertions::{ActionsBuilder, ExifAssertion, IptcAssertion, TrainingMiningAssertion, TrainingMiningUse};
+pub use domain::manifest_validation::{validate_manifest_definition, validate_manifest_value};
+pub use domain::soft_binding::{
+    FingerprintConfig, Fingerprinter, SoftBindingAlgorithm, SoftBindingConfig, SoftBindingInfo,
+};
+#[cfg(feature = "watermark")]
+pub use adapters::watermark::BlockMeanWatermark;
+#[cfg(feature = "phash")]
+pub use adapters::phash::PHashFingerprinter;
+#[cfg(feature = "metrics")]
+pub use domain::metrics::{clear_metrics, set_metrics, EngineMetrics, MetricEvent, MetricOperation, MetricOutcome};
 
 // Attach example docs to a private const to satisfy rustdoc
 #[allow(dead_code)]
@@ -75,6 +252,17 @@ const _DOCS_EXAMPLES: () = {
 ///     allow_remote_manifests: false,
 ///     include_certificates: None,
 ///     limits: LimitsConfig::defaults(),
+///     soft_binding: None,
+///     raw_settings: None,
+///     unknown_status_severity: que_engine::Severity::Fail,
+///     progress: None,
+///     cancel: None,
+///     format_override: None,
+///     check_declared_format: false,
+///     allowed_formats: None,
+///     remote_manifest: None,
+///     include_raw_validation_results: false,
+///     expected_signer_fingerprint: None,
 ///     #[cfg(feature = "cawg")] cawg: None,
 /// };
 /// let _ = verify_c2pa(cfg);
@@ -106,6 +294,32 @@ pub fn create_cawg_x509_config(
     }
 }
 
+/// Helper function to create CAWG DID identity configuration.
+/// Use this when identities are issued as W3C verifiable credentials (e.g. `did:web`).
+///
+/// # Arguments
+/// * `did` - The issuing DID
+/// * `credential` - Verified identity claims to place in the credential subject
+/// * `signer` - Key referenced by the DID document's verification method
+/// * `referenced_assertions` - List of assertion labels that this identity should reference
+///
+/// # Returns
+/// A `CawgIdentity` configured with Ed25519 algorithm and no timestamping
+#[cfg(feature = "cawg")]
+pub fn create_cawg_did_config(
+    did: String,
+    credential: serde_json::Value,
+    signer: Signer,
+    referenced_assertions: Vec<String>,
+) -> CawgIdentity {
+    CawgIdentity {
+        signer: crate::domain::cawg::CawgSigner::Did { did, credential, signer },
+        signing_alg: EngineDefaults::CAWG_SIGNING_ALGORITHM,
+        referenced_assertions,
+        timestamper: None,
+    }
+}
+
 /// Helper function to create CAWG verification options.
 /// This provides a convenient way to set up CAWG validation with sensible defaults.
 ///
@@ -0,0 +1,108 @@
+// crates/engine/src/telemetry.rs
+
+//! `tracing` hooks for the signing and verification pipeline (feature `tracing`).
+//!
+//! Without the feature the macros expand to nothing and their field expressions are
+//! never evaluated, so call sites need no `cfg` of their own. Spans and events carry
+//! operation metadata only: signer kind, algorithm, format, sizes and counts. Key
+//! material, certificate contents and file paths are never recorded.
+//!
+//! Spans are entered only in synchronous code; async paths emit events instead.
+//!
+//! `metrics_start!` and `metrics_finish!` bracket an operation for the registered
+//! `EngineMetrics` (feature `metrics`) and likewise expand to nothing without it.
+
+#[cfg(feature = "tracing")]
+macro_rules! trace_span {
+    ($($args:tt)*) => {
+        tracing::info_span!($($args)*).entered()
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_span {
+    ($($args:tt)*) => {
+        $crate::telemetry::NoSpan
+    };
+}
+
+#[cfg(feature = "tracing")]
+macro_rules! trace_event {
+    ($level:ident, $($args:tt)*) => {
+        tracing::$level!($($args)*)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_event {
+    ($($args:tt)*) => {};
+}
+
+#[cfg(feature = "metrics")]
+macro_rules! metrics_start {
+    ($operation:ident, $source:expr, $output:expr) => {
+        $crate::domain::metrics::Measurement::start(
+            $crate::domain::metrics::MetricOperation::$operation,
+            $source,
+            $output,
+        )
+    };
+}
+
+#[cfg(not(feature = "metrics"))]
+macro_rules! metrics_start {
+    ($($args:tt)*) => {
+        $crate::telemetry::NoMeasurement
+    };
+}
+
+#[cfg(feature = "metrics")]
+macro_rules! metrics_finish {
+    ($measurement:expr, $result:expr) => {
+        if let Some(measurement) = $measurement {
+            measurement.finish($result);
+        }
+    };
+}
+
+#[cfg(not(feature = "metrics"))]
+macro_rules! metrics_finish {
+    ($measurement:expr, $($args:tt)*) => {
+        let _ = $measurement;
+    };
+}
+
+pub(crate) use metrics_finish;
+pub(crate) use metrics_start;
+pub(crate) use trace_event;
+pub(crate) use trace_span;
+
+/// Stand-in guard for `trace_span!` when the feature is off.
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct NoSpan;
+
+/// Stand-in for a metrics measurement when the `metrics` feature is off.
+#[cfg(not(feature = "metrics"))]
+pub(crate) struct NoMeasurement;
+
+/// Kind of asset source, for span fields.
+#[cfg(feature = "tracing")]
+pub(crate) fn asset_kind(asset: &crate::domain::types::AssetRef) -> &'static str {
+    use crate::domain::types::AssetRef;
+    match asset {
+        AssetRef::Path(_) => "path",
+        AssetRef::Bytes { .. } => "bytes",
+        AssetRef::Stream { .. } => "stream",
+        AssetRef::Url(_) => "url",
+    }
+}
+
+/// Kind of output target, for span fields.
+#[cfg(feature = "tracing")]
+pub(crate) fn output_kind(output: &crate::domain::types::OutputTarget) -> &'static str {
+    use crate::domain::types::OutputTarget;
+    match output {
+        OutputTarget::Path(_) => "path",
+        OutputTarget::Memory => "memory",
+    }
+}
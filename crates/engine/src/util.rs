@@ -0,0 +1,10 @@
+// crates/engine/src/util.rs
+
+//! Helpers the engine uses to stage assets, for integrators who fetch or buffer
+//! assets themselves (e.g. a custom fetcher feeding `AssetRef::Bytes` or `Path`).
+//!
+//! Pair `copy_with_limits` with the `LimitsConfig` passed to the engine, e.g.
+//! `max_stream_copy_size`, so oversized input fails the same way it would inside
+//! the engine: with `EngineError::AssetTooLarge`.
+
+pub use crate::adapters::c2pa::{copy_with_limits, write_bytes_to_temp};
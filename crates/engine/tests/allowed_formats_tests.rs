@@ -0,0 +1,99 @@
+mod common;
+
+use que_engine as qe;
+
+fn allowed(formats: &[&str]) -> Option<Vec<String>> {
+    Some(formats.iter().map(|f| f.to_string()).collect())
+}
+
+fn is_not_allowed(err: &qe::EngineError) -> bool {
+    matches!(err, qe::EngineError::UnsupportedFormat(msg) if msg.contains("allowed_formats"))
+}
+
+#[test]
+fn listed_formats_pass_whether_named_by_mime_type_or_extension() {
+    for formats in [&["image/jpeg"][..], &["JPG"], &["image/png", ".jpeg"], &["image/jpg"]] {
+        let mut cfg =
+            qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() });
+        cfg.allowed_formats = allowed(formats);
+        if let Err(e) = qe::verify_c2pa(cfg) {
+            assert!(!is_not_allowed(&e), "{formats:?} rejected a JPEG: {e}");
+        }
+    }
+}
+
+#[test]
+fn unlisted_format_is_rejected_before_verifying() {
+    let mut cfg =
+        qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() });
+    cfg.allowed_formats = allowed(&["image/png", "mp4"]);
+
+    match qe::verify_c2pa(cfg) {
+        Err(qe::EngineError::UnsupportedFormat(msg)) => assert_eq!(msg, "image/jpeg is not in allowed_formats"),
+        other => panic!("expected UnsupportedFormat, got {other:?}"),
+    }
+}
+
+#[test]
+fn declared_format_must_be_listed_too() {
+    // JPEG bytes behind a .png name: the bytes are allowed, the extension is not.
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("photo.png");
+    std::fs::write(&path, common::make_test_jpeg_bytes()).unwrap();
+
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Path(path));
+    cfg.allowed_formats = allowed(&["image/jpeg"]);
+    let err = qe::verify_c2pa(cfg).expect_err("declared png should be rejected");
+    assert!(is_not_allowed(&err), "unexpected error: {err}");
+}
+
+#[test]
+fn undeterminable_format_is_rejected() {
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: vec![0u8; 64] });
+    cfg.allowed_formats = allowed(&["image/jpeg"]);
+
+    match qe::verify_c2pa(cfg) {
+        Err(qe::EngineError::UnsupportedFormat(msg)) => assert!(msg.contains("could not be determined"), "{msg}"),
+        other => panic!("expected UnsupportedFormat, got {other:?}"),
+    }
+}
+
+#[test]
+fn signing_checks_allowed_formats_before_the_sdk() {
+    let chain = common::generate_es256_chain();
+    let build = |formats: &[&str]| {
+        qe::C2paConfig::builder(
+            qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+            qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+            qe::SigAlg::Es256,
+        )
+        .intermediates(chain.intermediate_pem.clone().into_bytes())
+        .manifest(common::minimal_manifest_def("image/jpeg"))
+        .allowed_formats(formats.iter().copied())
+        .build()
+    };
+
+    let err = qe::sign_c2pa(build(&["image/png"])).expect_err("jpeg is not allowed");
+    assert!(is_not_allowed(&err), "unexpected error: {err}");
+
+    let signed = qe::sign_c2pa(build(&["image/jpeg"])).expect("jpeg is allowed");
+    assert!(signed.is_some_and(|bytes| !bytes.is_empty()));
+}
+
+#[test]
+fn signing_fetches_url_sources_before_checking_allowed_formats() {
+    let chain = common::generate_es256_chain();
+    let cfg = qe::C2paConfig::builder(
+        qe::AssetRef::Url("https://127.0.0.1/image.jpg".into()),
+        qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .allowed_formats(["image/jpeg"])
+    .build();
+    // The URL is fetched (and refused here) rather than rejected for an unknown format.
+    match qe::sign_c2pa(cfg) {
+        Err(qe::EngineError::InvalidUrl(_)) => {}
+        other => panic!("expected InvalidUrl, got {other:?}"),
+    }
+}
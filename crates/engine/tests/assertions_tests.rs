@@ -0,0 +1,220 @@
+mod common;
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use que_engine as qe;
+
+#[test]
+fn exif_assertion_uses_xmp_formats() {
+    let taken = UNIX_EPOCH + Duration::from_secs(1_569_176_577); // 2019-09-22T18:22:57Z
+    let value = qe::ExifAssertion::new()
+        .gps(39.3517, -74.442895)
+        .altitude(-3.5)
+        .gps_timestamp(taken)
+        .date_time_original(taken)
+        .make("CameraCompany")
+        .model("Shooter S1")
+        .to_value()
+        .unwrap();
+
+    assert_eq!(value["@context"]["exif"], "http://ns.adobe.com/exif/1.0/");
+    assert_eq!(value["@context"]["tiff"], "http://ns.adobe.com/tiff/1.0/");
+    assert_eq!(value["exif:GPSLatitude"], "39,21.102N");
+    assert_eq!(value["exif:GPSLongitude"], "74,26.5737W");
+    assert_eq!(value["exif:GPSAltitudeRef"], 1);
+    assert_eq!(value["exif:GPSAltitude"], "7/2");
+    assert_eq!(value["exif:GPSTimeStamp"], "2019-09-22T18:22:57Z");
+    assert_eq!(value["exif:DateTimeOriginal"], "2019-09-22T18:22:57Z");
+    assert_eq!(value["tiff:Make"], "CameraCompany");
+    assert!(value.get("exifEX:LensModel").is_none());
+}
+
+#[test]
+fn exif_assertion_rejects_out_of_range_coordinates() {
+    for (lat, lon) in [(91.0, 0.0), (0.0, -180.5), (f64::NAN, 0.0)] {
+        assert!(matches!(
+            qe::ExifAssertion::new().gps(lat, lon).to_value(),
+            Err(qe::EngineError::Config(_))
+        ));
+    }
+    assert!(qe::ExifAssertion::new().altitude(f64::INFINITY).to_value().is_err());
+}
+
+#[test]
+fn iptc_assertion_expands_digital_source_type() {
+    let value = qe::IptcAssertion::new()
+        .creator("Test Photographer")
+        .rights("Copyright (C) 2024 Test. All Rights Reserved.")
+        .web_statement("http://example.com/terms.html")
+        .digital_source_type("digitalCapture")
+        .keyword("news")
+        .to_value();
+
+    assert_eq!(value["dc:creator"][0], "Test Photographer");
+    assert_eq!(value["dc:subject"][0], "news");
+    assert_eq!(
+        value["Iptc4xmpExt:DigitalSourceType"],
+        "https://cv.iptc.org/newscodes/digitalsourcetype/digitalCapture"
+    );
+    assert_eq!(value["@context"]["xmpRights"], "http://ns.adobe.com/xap/1.0/rights/");
+    assert!(value["@context"].get("photoshop").is_none());
+}
+
+#[test]
+fn sign_with_typed_metadata_assertions() {
+    let manifest = qe::ManifestBuilder::new()
+        .title("typed metadata")
+        .format("image/jpeg")
+        .add_action(qe::Action::new("c2pa.created").software_agent("que-engine-tests"))
+        .add_assertion(
+            qe::ExifAssertion::LABEL,
+            qe::ExifAssertion::new().gps(51.5007, -0.1246).make("CameraCompany").to_value().unwrap(),
+        )
+        .add_assertion(qe::IptcAssertion::LABEL, qe::IptcAssertion::new().creator("Tester").to_value());
+
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer,
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_source = Some(manifest.into());
+
+    let signed = match qe::sign_c2pa(cfg) {
+        Ok(Some(signed)) => signed,
+        Ok(None) => panic!("expected memory output"),
+        Err(_e) => return, // allow environments that cannot sign with self-signed cert
+    };
+
+    let mut vcfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+    vcfg.mode = qe::VerifyMode::Info;
+    let res = qe::verify_c2pa(vcfg).expect("verify");
+    assert!(res.report.contains("51,30.042N"));
+}
+
+#[test]
+fn opened_action_without_ingredient_is_rejected_before_signing() {
+    let actions = qe::ActionsBuilder::new().add_action(qe::Action::new("c2pa.opened"));
+
+    match actions.to_value() {
+        Err(qe::EngineError::Config(msg)) => assert!(msg.contains("parentOf"), "{msg}"),
+        other => panic!("expected Config error, got {other:?}"),
+    }
+}
+
+#[test]
+fn actions_referencing_unknown_or_mismatched_ingredients_are_rejected() {
+    let unknown = qe::ActionsBuilder::new()
+        .ingredient("logo", qe::IngredientRelationship::ComponentOf)
+        .add_action(qe::Action::new("c2pa.placed").ingredient("missing"));
+    match unknown.to_value() {
+        Err(qe::EngineError::Config(msg)) => assert!(msg.contains("unknown ingredient 'missing'"), "{msg}"),
+        other => panic!("expected Config error, got {other:?}"),
+    }
+
+    let mismatched = qe::ActionsBuilder::new()
+        .ingredient("original", qe::IngredientRelationship::ParentOf)
+        .add_action(qe::Action::new("c2pa.placed").ingredient("original"));
+    assert!(mismatched.to_value().is_err());
+
+    let bad_parameters = qe::ActionsBuilder::new()
+        .add_action(qe::Action::new("c2pa.edited").parameters(serde_json::json!({ "ingredientIds": "original" })));
+    assert!(bad_parameters.to_value().is_err());
+}
+
+#[test]
+fn actions_builder_emits_ingredient_ids() {
+    let value = qe::ActionsBuilder::new()
+        .ingredient("original", qe::IngredientRelationship::ParentOf)
+        .add_action(
+            qe::Action::new("c2pa.opened")
+                .parameters(serde_json::json!({ "description": "import" }))
+                .ingredient("original"),
+        )
+        .to_value()
+        .unwrap();
+
+    let params = &value["actions"][0]["parameters"];
+    assert_eq!(params["ingredientIds"][0], "original");
+    assert_eq!(params["description"], "import");
+}
+
+#[test]
+fn sign_with_placed_action_linked_to_ingredient() {
+    let actions = qe::ActionsBuilder::new()
+        .ingredient("logo", qe::IngredientRelationship::ComponentOf)
+        .add_action(qe::Action::new("c2pa.created").software_agent("que-engine-tests"))
+        .add_action(qe::Action::new("c2pa.placed").ingredient("logo"));
+    let mut manifest = qe::ManifestBuilder::new()
+        .title("placed logo")
+        .format("image/jpeg")
+        .add_ingredient("logo.png", "image/png", qe::IngredientRelationship::ComponentOf)
+        .add_assertion(qe::ActionsBuilder::LABEL, actions.to_value().unwrap())
+        .to_value();
+    manifest["ingredients"][0]["label"] = "logo".into();
+
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer,
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_source = Some(manifest.into());
+
+    // Post-sign validation stays on; self-signed certs may still be rejected.
+    let _ = qe::sign_c2pa(cfg);
+}
+
+#[test]
+fn training_mining_assertion_round_trips() {
+    let tdm = qe::TrainingMiningAssertion {
+        ai_generative_training: Some(qe::TrainingMiningUse::NotAllowed),
+        ai_training: Some(qe::TrainingMiningUse::Allowed),
+        data_mining: Some(qe::TrainingMiningUse::Constrained { info: Some("contact@example.com".into()) }),
+        ..Default::default()
+    };
+    let value = tdm.to_value();
+
+    let entries = value["entries"].as_object().unwrap();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries["cawg.ai_generative_training"]["use"], "notAllowed");
+    assert_eq!(entries["cawg.data_mining"]["constraint_info"], "contact@example.com");
+    assert_eq!(qe::TrainingMiningAssertion::from_value(&value).unwrap(), tdm);
+
+    let bad = serde_json::json!({ "entries": { "cawg.ai_training": { "use": "maybe" } } });
+    assert!(matches!(qe::TrainingMiningAssertion::from_value(&bad), Err(qe::EngineError::Config(_))));
+}
+
+#[test]
+fn training_mining_assertion_is_read_back_from_verification() {
+    let tdm = qe::TrainingMiningAssertion {
+        ai_inference: Some(qe::TrainingMiningUse::NotAllowed),
+        ..Default::default()
+    };
+    let manifest = qe::ManifestBuilder::new()
+        .title("training and mining")
+        .format("image/jpeg")
+        .add_action(qe::Action::new("c2pa.created").software_agent("que-engine-tests"))
+        .add_assertion(qe::TrainingMiningAssertion::LABEL, tdm.to_value());
+
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer,
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_source = Some(manifest.into());
+
+    let signed = match qe::sign_c2pa(cfg) {
+        Ok(Some(signed)) => signed,
+        Ok(None) => panic!("expected memory output"),
+        Err(_e) => return, // allow environments that cannot sign with self-signed cert
+    };
+
+    let res = qe::verify_c2pa(qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed }))
+        .expect("verify");
+    assert_eq!(qe::TrainingMiningAssertion::from_report(&res.report).unwrap(), Some(tdm));
+}
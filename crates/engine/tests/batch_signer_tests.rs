@@ -0,0 +1,71 @@
+mod common;
+
+use que_engine as qe;
+
+fn signer() -> qe::Signer {
+    let chain = common::generate_es256_chain();
+    qe::Signer::from_pem(
+        format!("{}{}", chain.leaf_pem, chain.intermediate_pem).as_bytes(),
+        chain.key_pem.as_bytes(),
+    )
+}
+
+#[test]
+fn batch_sign_returns_one_result_per_asset_in_order() {
+    let batch = qe::BatchSigner::new(
+        Some(common::minimal_manifest_def("image/jpeg")),
+        signer(),
+        qe::SigAlg::Es256,
+        None,
+    )
+    .expect("batch signer");
+
+    let sources = vec![
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::AssetRef::Bytes { data: b"not an image".to_vec() },
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+    ];
+
+    let results = batch.sign_all(&sources).expect("batch settings");
+    assert_eq!(results.len(), 3);
+    // A bad asset must not abort the remaining assets.
+    assert!(results[1].is_err());
+    for signed in [&results[0], &results[2]] {
+        assert!(!signed.as_ref().expect("jpeg signs").is_empty());
+    }
+}
+
+#[test]
+fn batch_sign_to_path_outputs() {
+    let batch = qe::BatchSigner::new(
+        Some(common::minimal_manifest_def("image/jpeg")),
+        signer(),
+        qe::SigAlg::Es256,
+        None,
+    )
+    .expect("batch signer");
+
+    let tmp = tempfile::tempdir().unwrap();
+    let dest = tmp.path().join("out.jpg");
+    let jobs = vec![(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::OutputTarget::Path(dest.clone()),
+    )];
+
+    let results = batch.sign_all_to(&jobs).expect("batch settings");
+    assert_eq!(results.len(), 1);
+    let out = results[0].as_ref().expect("jpeg signs");
+    assert!(out.is_none());
+    assert!(dest.exists());
+}
+
+#[test]
+fn batch_signer_rejects_invalid_template() {
+    let res = qe::BatchSigner::new(
+        Some("{ not json".into()),
+        signer(),
+        qe::SigAlg::Es256,
+        None,
+    );
+    assert!(res.is_err());
+}
@@ -29,3 +29,253 @@ fn bmff_generate_uses_paths() {
 
 
 
+
+#[test]
+fn bmff_parallel_reports_per_segment_failures() {
+    let (_tmp, signer_uri) = common::setup_local_signer_files();
+    let signer: qe::Signer = signer_uri.parse().unwrap();
+
+    // Two representations whose "init segments" are not valid BMFF.
+    let src = tempfile::tempdir().unwrap();
+    for rep in ["rep_a", "rep_b"] {
+        let dir = src.path().join(rep);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("init.mp4"), b"not bmff").unwrap();
+        std::fs::write(dir.join("seg1.m4s"), b"not bmff").unwrap();
+    }
+    let out_dir = tempfile::tempdir().unwrap();
+
+    let mut cfg = qe::FragmentedBmffConfig::secure_default(
+        src.path().join("*/init.mp4"),
+        "*.m4s".into(),
+        out_dir.path().to_path_buf(),
+        signer,
+        qe::SigAlg::Es256,
+    );
+    cfg.manifest_definition = Some(common::minimal_manifest_def("video/mp4"));
+    cfg.parallelism = Some(2);
+
+    match qe::generate_fragmented_bmff(cfg) {
+        Err(qe::EngineError::FragmentedBmff { failures }) => {
+            // Both groups are attempted even though the first one fails.
+            assert_eq!(failures.len(), 2);
+            assert!(failures[0].init_path.ends_with("rep_a/init.mp4"));
+            assert!(failures[1].init_path.ends_with("rep_b/init.mp4"));
+        }
+        Err(_e) => { /* signer could not be loaded in this environment */ }
+        Ok(()) => panic!("invalid segments should not sign"),
+    }
+}
+
+/// Minimal fragment: `moof` containing an `mfhd` with the given sequence number.
+fn fragment_bytes(sequence: u32) -> Vec<u8> {
+    let mut mfhd = Vec::new();
+    mfhd.extend_from_slice(&16u32.to_be_bytes());
+    mfhd.extend_from_slice(b"mfhd");
+    mfhd.extend_from_slice(&[0, 0, 0, 0]);
+    mfhd.extend_from_slice(&sequence.to_be_bytes());
+
+    let mut moof = Vec::new();
+    moof.extend_from_slice(&((8 + mfhd.len()) as u32).to_be_bytes());
+    moof.extend_from_slice(b"moof");
+    moof.extend_from_slice(&mfhd);
+    moof
+}
+
+fn full_box(kind: &[u8; 4], flags: u32, body: &[u8]) -> Vec<u8> {
+    let mut out = ((12 + body.len()) as u32).to_be_bytes().to_vec();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(&flags.to_be_bytes()); // version 0
+    out.extend_from_slice(body);
+    out
+}
+
+fn plain_box(kind: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+    let mut out = ((8 + payload.len()) as u32).to_be_bytes().to_vec();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(payload);
+    out
+}
+
+/// Minimal signable init segment: `ftyp` and a `moov` holding one track and `mvex`.
+fn init_segment_bytes() -> Vec<u8> {
+    let mut mvhd = vec![0u8; 96];
+    mvhd[8..12].copy_from_slice(&1000u32.to_be_bytes()); // timescale
+    mvhd[16..20].copy_from_slice(&0x0001_0000u32.to_be_bytes()); // rate 1.0
+    mvhd[92..96].copy_from_slice(&2u32.to_be_bytes()); // next track id
+    let mut tkhd = vec![0u8; 80];
+    tkhd[8..12].copy_from_slice(&1u32.to_be_bytes()); // track id
+    let mut trex = 1u32.to_be_bytes().to_vec();
+    trex.extend_from_slice(&[0u8; 16]);
+
+    let mut moov = full_box(b"mvhd", 0, &mvhd);
+    moov.extend(plain_box(b"trak", &full_box(b"tkhd", 3, &tkhd)));
+    moov.extend(plain_box(b"mvex", &full_box(b"trex", 0, &trex)));
+    let mut out = plain_box(b"ftyp", b"isom\0\0\0\0isomiso6");
+    out.extend(plain_box(b"moov", &moov));
+    out
+}
+
+#[test]
+fn bmff_parallel_workers_apply_the_run_settings() {
+    let src = tempfile::tempdir().unwrap();
+    for rep in ["rep_a", "rep_b"] {
+        let dir = src.path().join(rep);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("init.mp4"), init_segment_bytes()).unwrap();
+        for seq in 1..=2u32 {
+            let mut fragment = fragment_bytes(seq);
+            fragment.extend(plain_box(b"mdat", &[seq as u8; 32]));
+            std::fs::write(dir.join(format!("seg{seq}.m4s")), fragment).unwrap();
+        }
+    }
+
+    // A key that does not match the certificate signs, but fails c2pa's
+    // verify-after-sign check, so the outcome shows whether that check ran.
+    let chain = common::generate_es256_chain();
+    let other = common::generate_es256_chain();
+    let run = |skip_post_sign_validation: bool| {
+        let out_dir = tempfile::tempdir().unwrap();
+        let cert_chain = format!("{}{}", chain.leaf_pem, chain.intermediate_pem);
+        let mut cfg = qe::FragmentedBmffConfig::secure_default(
+            src.path().join("*/init.mp4"),
+            "seg*.m4s".into(),
+            out_dir.path().to_path_buf(),
+            qe::Signer::from_pem(cert_chain.as_bytes(), other.key_pem.as_bytes()),
+            qe::SigAlg::Es256,
+        );
+        cfg.manifest_definition = Some(common::minimal_manifest_def("video/mp4"));
+        cfg.parallelism = Some(2);
+        cfg.skip_post_sign_validation = skip_post_sign_validation;
+        qe::generate_fragmented_bmff(cfg)
+    };
+
+    run(true).expect("both workers skip post-sign validation");
+    match run(false) {
+        Err(qe::EngineError::FragmentedBmff { failures }) => assert_eq!(failures.len(), 2),
+        other => panic!("expected both groups to fail validation, got {other:?}"),
+    }
+}
+
+#[test]
+fn bmff_verify_reports_missing_and_out_of_order_fragments() {
+    let src = tempfile::tempdir().unwrap();
+    let layouts: [(&str, &[u32]); 3] = [
+        ("rep_gap", &[1, 2, 4]),
+        ("rep_order", &[2, 1]),
+        ("rep_empty", &[]),
+    ];
+    for (rep, sequences) in layouts {
+        let dir = src.path().join(rep);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("init.mp4"), b"not bmff").unwrap();
+        for (i, seq) in sequences.iter().enumerate() {
+            std::fs::write(dir.join(format!("seg{}.m4s", i + 1)), fragment_bytes(*seq)).unwrap();
+        }
+    }
+
+    let cfg = qe::FragmentedBmffVerifyConfig::secure_default(
+        src.path().join("*/init.mp4"),
+        "*.m4s".into(),
+    );
+    let results = qe::verify_fragmented_bmff(cfg).expect("per-group results");
+    assert_eq!(results.len(), 3);
+
+    let message = |r: &qe::FragmentedBmffVerification| match &r.result {
+        Err(qe::EngineError::Config(msg)) => msg.clone(),
+        other => panic!("expected config error, got {other:?}"),
+    };
+    assert!(message(&results[0]).contains("no fragments"));
+    assert!(message(&results[1]).contains("missing fragment"));
+    assert!(message(&results[2]).contains("out of order"));
+}
+
+fn fixture_path(name: &str) -> std::path::PathBuf {
+    common::engine_crate_dir().join("tests/fixtures").join(name)
+}
+
+fn chain_signer() -> qe::Signer {
+    let chain = common::generate_es256_chain();
+    qe::Signer::from_pem(
+        format!("{}{}", chain.leaf_pem, chain.intermediate_pem).as_bytes(),
+        chain.key_pem.as_bytes(),
+    )
+}
+
+#[test]
+fn bmff_sign_then_verify_roundtrip() {
+    let out_dir = tempfile::tempdir().unwrap();
+
+    let mut cfg = qe::FragmentedBmffConfig::secure_default(
+        fixture_path("fragmented/init.mp4"),
+        "seg*.m4s".into(),
+        out_dir.path().to_path_buf(),
+        chain_signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.manifest_definition = Some(common::minimal_manifest_def("video/mp4"));
+    qe::generate_fragmented_bmff(cfg).expect("fragmented signing");
+
+    let vcfg = qe::FragmentedBmffVerifyConfig::secure_default(
+        out_dir.path().join("*/init.mp4"),
+        "seg*.m4s".into(),
+    );
+    let results = qe::verify_fragmented_bmff(vcfg).expect("verify");
+    assert_eq!(results.len(), 1);
+    let result = results[0].result.as_ref().expect("group verifies");
+    assert!(!result.report.is_empty());
+}
+
+#[test]
+fn bmff_single_file_rejects_progressive_mp4() {
+    // ftyp + moov without mvex, and no moof: a progressive layout.
+    let src = tempfile::tempdir().unwrap();
+    let mut mp4 = Vec::new();
+    mp4.extend_from_slice(&16u32.to_be_bytes());
+    mp4.extend_from_slice(b"ftypisom");
+    mp4.extend_from_slice(&[0, 0, 0, 0]);
+    mp4.extend_from_slice(&8u32.to_be_bytes());
+    mp4.extend_from_slice(b"moov");
+    std::fs::write(src.path().join("movie.mp4"), &mp4).unwrap();
+    let out_dir = tempfile::tempdir().unwrap();
+
+    let mut cfg = qe::FragmentedBmffConfig::secure_default(
+        src.path().join("*.mp4"),
+        "".into(),
+        out_dir.path().to_path_buf(),
+        chain_signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.layout = qe::BmffLayout::SingleFile;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("video/mp4"));
+
+    match qe::generate_fragmented_bmff(cfg) {
+        Err(qe::EngineError::FragmentedBmff { failures }) => {
+            assert_eq!(failures.len(), 1);
+            assert!(failures[0].message.contains("progressive"), "{}", failures[0].message);
+        }
+        other => panic!("expected the progressive MP4 to be rejected, got {other:?}"),
+    }
+}
+
+#[test]
+fn bmff_single_file_signs_fragmented_mp4() {
+    let out_dir = tempfile::tempdir().unwrap();
+
+    let mut cfg = qe::FragmentedBmffConfig::secure_default(
+        fixture_path("fragmented.mp4"),
+        "".into(),
+        out_dir.path().to_path_buf(),
+        chain_signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.layout = qe::BmffLayout::SingleFile;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("video/mp4"));
+    qe::generate_fragmented_bmff(cfg).expect("single-file signing");
+
+    // The whole file is signed in one pass, so it verifies as an ordinary asset.
+    let signed = out_dir.path().join("fragmented.mp4");
+    let res = qe::verify_c2pa(qe::C2paVerificationConfig::secure_default(qe::AssetRef::Path(signed)))
+        .expect("verify");
+    assert!(res.report.contains("c2pa.hash.bmff"), "{}", res.report);
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use std::io::{Read, Seek, SeekFrom};
+
+use que_engine as qe;
+
+/// Cancels `token` on its first read, standing in for a user pressing "Cancel"
+/// while the asset is being read.
+struct CancellingReader {
+    inner: std::io::Cursor<Vec<u8>>,
+    token: qe::CancellationToken,
+}
+
+impl Read for CancellingReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.token.cancel();
+        self.inner.read(buf)
+    }
+}
+
+impl Seek for CancellingReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn clones_share_the_flag_and_guards_cancel_on_drop() {
+    let token = qe::CancellationToken::new();
+    let clone = token.clone();
+    assert!(!clone.is_cancelled());
+
+    let _ = token.drop_guard().disarm();
+    assert!(!clone.is_cancelled());
+
+    drop(token.drop_guard());
+    assert!(clone.is_cancelled());
+}
+
+#[test]
+fn sign_with_cancelled_token_returns_cancelled() {
+    let token = qe::CancellationToken::new();
+    token.cancel();
+    let cfg = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        common::setup_env_signer_vars().parse().unwrap(),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .cancel(token)
+    .build();
+
+    assert!(matches!(qe::sign_c2pa(cfg), Err(qe::EngineError::Cancelled)));
+}
+
+#[test]
+fn verify_cancelled_while_reading_returns_cancelled() {
+    let token = qe::CancellationToken::new();
+    let reader: Box<dyn qe::domain::types::StreamReader> = Box::new(CancellingReader {
+        inner: std::io::Cursor::new(common::make_test_jpeg_bytes()),
+        token: token.clone(),
+    });
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Stream {
+        reader: std::cell::RefCell::new(reader),
+        content_type: Some("image/jpeg".into()),
+    });
+    cfg.cancel = Some(token);
+
+    assert!(matches!(qe::verify_c2pa(cfg), Err(qe::EngineError::Cancelled)));
+}
+
+#[test]
+fn uncancelled_token_does_not_affect_verification() {
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes {
+        data: common::make_test_jpeg_bytes(),
+    });
+    cfg.cancel = Some(qe::CancellationToken::new());
+    assert!(!matches!(qe::verify_c2pa(cfg), Err(qe::EngineError::Cancelled)));
+}
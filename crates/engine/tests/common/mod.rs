@@ -32,6 +32,67 @@ pub fn generate_es256_pem_pair() -> (String, String) {
     (cert_pem, key_pem)
 }
 
+/// An ES256 root, intermediate and leaf; `leaf_pem` holds only the leaf.
+///
+/// Unlike the self-signed pair above, this leaf passes c2pa's certificate profile
+/// checks, so signing with it succeeds.
+pub struct TestChain {
+    pub root_pem: String,
+    pub intermediate_pem: String,
+    pub leaf_pem: String,
+    pub key_pem: String,
+}
+
+fn chain_params(name: &str) -> CertificateParams {
+    let alg = &rcgen::PKCS_ECDSA_P256_SHA256;
+    let mut params = CertificateParams::new(vec![]);
+    params.alg = alg;
+    params.key_pair = Some(KeyPair::generate(alg).expect("keypair"));
+    let mut dn = rcgen::DistinguishedName::new();
+    dn.push(rcgen::DnType::CommonName, name);
+    dn.push(rcgen::DnType::OrganizationName, "que-engine test");
+    params.distinguished_name = dn;
+    params.use_authority_key_identifier_extension = true;
+    params
+}
+
+fn chain_ca(name: &str) -> Certificate {
+    let mut params = chain_params(name);
+    params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+    params.key_usages = vec![rcgen::KeyUsagePurpose::KeyCertSign, rcgen::KeyUsagePurpose::CrlSign];
+    Certificate::from_params(params).expect("ca cert")
+}
+
+/// Generate a fresh [`TestChain`].
+pub fn generate_es256_chain() -> TestChain {
+    es256_chain(|_| {})
+}
+
+/// Generate a [`TestChain`] whose leaf expired in 2001, so signing with it fails the
+/// certificate validity check deterministically.
+pub fn generate_expired_es256_chain() -> TestChain {
+    es256_chain(|leaf| {
+        leaf.not_before = rcgen::date_time_ymd(2000, 1, 1);
+        leaf.not_after = rcgen::date_time_ymd(2001, 1, 1);
+    })
+}
+
+fn es256_chain(customize_leaf: impl FnOnce(&mut CertificateParams)) -> TestChain {
+    let root = chain_ca("Test Root");
+    let intermediate = chain_ca("Test Intermediate");
+    let mut leaf = chain_params("Test Signer");
+    leaf.key_usages = vec![rcgen::KeyUsagePurpose::DigitalSignature];
+    leaf.extended_key_usages = vec![rcgen::ExtendedKeyUsagePurpose::EmailProtection];
+    customize_leaf(&mut leaf);
+    let leaf = Certificate::from_params(leaf).expect("leaf cert");
+    TestChain {
+        root_pem: root.serialize_pem().expect("root pem"),
+        intermediate_pem: intermediate.serialize_pem_with_signer(&root).expect("intermediate pem"),
+        leaf_pem: leaf.serialize_pem_with_signer(&intermediate).expect("leaf pem"),
+        key_pem: leaf.serialize_private_key_pem(),
+    }
+}
+
 /// Configure env vars with generated PEM data and return a signer URI string for env.
 pub fn setup_env_signer_vars() -> String {
     let (cert_pem, key_pem) = generate_es256_pem_pair();
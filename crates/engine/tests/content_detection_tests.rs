@@ -0,0 +1,115 @@
+mod common;
+
+use que_engine as qe;
+
+const SVG_BODY: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="4" height="4"><rect width="4" height="4"/></svg>
+"#;
+
+/// The format the engine sniffs, read from the declared-format check's error;
+/// `None` when nothing was detected.
+fn detected_format(data: &[u8]) -> Option<String> {
+    let reader: Box<dyn qe::domain::types::StreamReader> = Box::new(std::io::Cursor::new(data.to_vec()));
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Stream {
+        reader: std::cell::RefCell::new(reader),
+        content_type: Some("application/x-declared".into()),
+    });
+    cfg.check_declared_format = true;
+    match qe::verify_c2pa(cfg) {
+        Err(qe::EngineError::Config(msg)) if msg.contains("does not match") => {
+            Some(msg.rsplit(' ').next().unwrap().to_string())
+        }
+        _ => None,
+    }
+}
+
+#[test]
+fn svg_is_detected_after_bom_whitespace_and_prologue() {
+    let variants = [
+        format!("\u{feff}{SVG_BODY}"),
+        format!("\n  \t{SVG_BODY}"),
+        format!("<!-- exported by hand -->\n{SVG_BODY}"),
+        format!("\u{feff}<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{SVG_BODY}"),
+    ];
+    for svg in &variants {
+        assert_eq!(detected_format(svg.as_bytes()).as_deref(), Some("image/svg+xml"), "{svg:?}");
+    }
+
+    assert_eq!(detected_format(b"<?xml version=\"1.0\"?>\n<rss version=\"2.0\"></rss>"), None);
+    assert_eq!(detected_format(b"   svg but not markup"), None);
+}
+
+#[test]
+fn bom_prefixed_svg_signs() {
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: format!("\u{feff}{SVG_BODY}").into_bytes() },
+        signer,
+        qe::SigAlg::Es256,
+    );
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/svg+xml"));
+    cfg.output = qe::OutputTarget::Memory;
+
+    let signed = match qe::sign_c2pa(cfg) {
+        Ok(Some(signed)) => signed,
+        Ok(None) => panic!("expected memory output"),
+        Err(e) if e.to_string().contains("type is unsupported") => panic!("SVG was not detected"),
+        Err(_e) => return, // allow environments that cannot sign with self-signed cert
+    };
+    let res = qe::verify_c2pa(qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed }))
+        .expect("verify");
+    assert!(res.report.contains("active_manifest"));
+}
+
+#[test]
+fn mp3_needs_a_plausible_frame_header() {
+    let mut frame = vec![0xFF, 0xFB, 0x90, 0x64]; // MPEG-1 Layer III, 128 kbit/s, 44.1 kHz
+    frame.resize(512, 0);
+    assert_eq!(detected_format(&frame).as_deref(), Some("audio/mpeg"));
+
+    let mut id3 = b"ID3\x04\0\0\0\0\0\0".to_vec();
+    id3.resize(512, 0);
+    assert_eq!(detected_format(&id3).as_deref(), Some("audio/mpeg"));
+
+    // All of these satisfy the bare 11-bit frame sync.
+    for header in [
+        [0xFF, 0xFF, 0xFF, 0xFF], // invalid bitrate and sample rate
+        [0xFF, 0xF1, 0x50, 0x80], // ADTS AAC: layer 00
+        [0xFF, 0xEA, 0x90, 0x00], // reserved version 01
+        [0xFF, 0xFB, 0x0C, 0x00], // free bitrate, reserved sample rate
+    ] {
+        let mut data = header.to_vec();
+        data.extend((0..508u32).map(|i| (i.wrapping_mul(2654435761) >> 24) as u8));
+        assert_eq!(detected_format(&data), None, "{header:02X?} was detected");
+    }
+}
+
+/// An `ftyp` box with `major` and `compatible` brands, followed by an empty `mdat`.
+fn ftyp(major: &[u8; 4], compatible: &[&[u8; 4]]) -> Vec<u8> {
+    let size = 16 + 4 * compatible.len() as u32;
+    let mut data = size.to_be_bytes().to_vec();
+    data.extend_from_slice(b"ftyp");
+    data.extend_from_slice(major);
+    data.extend_from_slice(&0u32.to_be_bytes());
+    for brand in compatible {
+        data.extend_from_slice(*brand);
+    }
+    data.extend_from_slice(&8u32.to_be_bytes());
+    data.extend_from_slice(b"mdat");
+    data
+}
+
+#[test]
+fn heif_sequences_are_not_detected_as_stills() {
+    let cases: [(Vec<u8>, &str); 7] = [
+        (ftyp(b"msf1", &[b"msf1", b"hevc", b"mif1", b"heic"]), "image/heic-sequence"),
+        (ftyp(b"hevc", &[b"mif1", b"heic"]), "image/heic-sequence"),
+        (ftyp(b"msf1", &[b"msf1", b"mif1"]), "image/heif-sequence"),
+        (ftyp(b"avis", &[b"avif", b"msf1", b"miaf"]), "image/avif"),
+        (ftyp(b"mif1", &[b"mif1", b"heic"]), "image/heic"),
+        (ftyp(b"mif1", &[b"mif1", b"avif"]), "image/avif"),
+        (ftyp(b"mif1", &[b"mif1"]), "image/heif"),
+    ];
+    for (data, expected) in &cases {
+        assert_eq!(detected_format(data).as_deref(), Some(*expected), "{:?}", &data[8..12]);
+    }
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use que_engine as qe;
+
+// The registry is process-wide; each test uses a signature no other test produces.
+
+/// A bare manifest store opens with a JUMBF superbox, which the built-in table lacks.
+fn detect_manifest_store(data: &[u8]) -> Option<&'static str> {
+    (data.len() >= 8 && &data[4..8] == b"jumb").then_some("c2pa")
+}
+
+fn detect_tagged_png(data: &[u8]) -> Option<&'static str> {
+    data.starts_with(b"\x89PNG\r\n\x1a\nQUE!").then_some("webp")
+}
+
+fn stream(data: Vec<u8>, content_type: Option<&str>) -> qe::AssetRef {
+    let reader: Box<dyn qe::domain::types::StreamReader> = Box::new(std::io::Cursor::new(data));
+    qe::AssetRef::Stream { reader: std::cell::RefCell::new(reader), content_type: content_type.map(Into::into) }
+}
+
+#[test]
+fn registered_detector_lets_c2pa_read_an_unrecognised_format() {
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer,
+        qe::SigAlg::Es256,
+    );
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    cfg.output = qe::OutputTarget::Memory;
+    let signed = match qe::sign_c2pa(cfg) {
+        Ok(Some(signed)) => signed,
+        Ok(None) => panic!("expected memory output"),
+        Err(_e) => return, // allow environments that cannot sign with self-signed cert
+    };
+    let store = qe::extract_manifest_store(qe::AssetRef::Bytes { data: signed }, qe::LimitsConfig::defaults())
+        .expect("extract store");
+
+    qe::register_content_detector(detect_manifest_store);
+
+    let res = qe::verify_c2pa(qe::C2paVerificationConfig::secure_default(stream(store, None)))
+        .expect("verify detected manifest store");
+    assert!(res.report.contains("active_manifest"));
+}
+
+#[test]
+fn custom_detectors_run_before_built_in_signatures() {
+    qe::register_content_detector(detect_tagged_png);
+
+    let mut data = b"\x89PNG\r\n\x1a\nQUE!".to_vec();
+    data.resize(256, 0);
+    let mut cfg = qe::C2paVerificationConfig::secure_default(stream(data, Some("image/png")));
+    cfg.check_declared_format = true;
+
+    // Built-in detection alone would agree with the declared PNG.
+    match qe::verify_c2pa(cfg) {
+        Err(qe::EngineError::Config(msg)) => assert!(msg.ends_with("detected format image/webp"), "{msg}"),
+        other => panic!("expected the custom detection to win, got {other:?}"),
+    }
+}
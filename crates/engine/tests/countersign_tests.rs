@@ -0,0 +1,69 @@
+mod common;
+
+use que_engine as qe;
+
+fn signer() -> qe::Signer {
+    let chain = common::generate_es256_chain();
+    qe::Signer::from_pem(
+        format!("{}{}", chain.leaf_pem, chain.intermediate_pem).as_bytes(),
+        chain.key_pem.as_bytes(),
+    )
+}
+
+#[test]
+fn countersign_rejects_unsigned_asset() {
+    let res = qe::countersign(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+        None,
+        qe::OutputTarget::Memory,
+    );
+    assert!(matches!(res, Err(qe::EngineError::Config(_))));
+}
+
+#[test]
+fn countersign_rejects_unsupported_format() {
+    let res = qe::countersign(
+        qe::AssetRef::Bytes { data: b"%PDF-1.7\n%%EOF\n".to_vec() },
+        signer(),
+        qe::SigAlg::Es256,
+        None,
+        qe::OutputTarget::Memory,
+    );
+    assert!(res.is_err());
+}
+
+#[test]
+fn countersign_appends_second_manifest() {
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+
+    let signed = qe::sign_c2pa(cfg).expect("signing").expect("memory output");
+
+    let countersigned = qe::countersign(
+        qe::AssetRef::Bytes { data: signed },
+        signer(),
+        qe::SigAlg::Es256,
+        None,
+        qe::OutputTarget::Memory,
+    )
+    .expect("countersigning")
+    .expect("memory output");
+
+    let res = qe::verify_c2pa(qe::C2paVerificationConfig::secure_default(
+        qe::AssetRef::Bytes { data: countersigned },
+    ))
+    .expect("verify");
+    let report: serde_json::Value = serde_json::from_str(&res.report).expect("report json");
+    let manifests = report["manifests"].as_object().expect("manifests");
+    assert_eq!(manifests.len(), 2);
+    // The appended manifest carries its own hard binding, so it is a full claim.
+    assert_eq!(res.is_update_manifest, Some(false));
+    assert_eq!(res.to_json()["is_update_manifest"], serde_json::Value::Bool(false));
+}
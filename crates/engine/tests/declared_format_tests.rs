@@ -0,0 +1,66 @@
+mod common;
+
+use que_engine as qe;
+
+fn stream(data: Vec<u8>, content_type: &str) -> qe::AssetRef {
+    let reader: Box<dyn qe::domain::types::StreamReader> = Box::new(std::io::Cursor::new(data));
+    qe::AssetRef::Stream { reader: std::cell::RefCell::new(reader), content_type: Some(content_type.into()) }
+}
+
+fn is_mismatch(err: &qe::EngineError) -> bool {
+    matches!(err, qe::EngineError::Config(msg) if msg.contains("does not match detected format"))
+}
+
+#[test]
+fn mismatched_stream_content_type_is_rejected_when_checked() {
+    let mut cfg = qe::C2paVerificationConfig::secure_default(stream(common::make_test_jpeg_bytes(), "image/png"));
+    cfg.check_declared_format = true;
+
+    match qe::verify_c2pa(cfg) {
+        Err(qe::EngineError::Config(msg)) => {
+            assert_eq!(msg, "declared format image/png does not match detected format image/jpeg");
+        }
+        other => panic!("expected mismatch error, got {other:?}"),
+    }
+}
+
+#[test]
+fn mismatch_check_is_opt_in() {
+    let cfg = qe::C2paVerificationConfig::secure_default(stream(common::make_test_jpeg_bytes(), "image/png"));
+    if let Err(e) = qe::verify_c2pa(cfg) {
+        assert!(!is_mismatch(&e), "check ran without opting in: {e}");
+    }
+}
+
+#[test]
+fn mismatched_path_extension_is_rejected_before_signing() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("photo.png");
+    std::fs::write(&path, common::make_test_jpeg_bytes()).unwrap();
+
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(qe::AssetRef::Path(path), signer, qe::SigAlg::Es256);
+    cfg.check_declared_format = true;
+
+    let err = qe::sign_c2pa(cfg).expect_err("mismatch should fail");
+    assert!(is_mismatch(&err), "unexpected error: {err}");
+}
+
+#[test]
+fn unsniffable_and_related_formats_pass_the_check() {
+    // Nothing recognisable in the bytes: the declared type is trusted.
+    let mut cfg = qe::C2paVerificationConfig::secure_default(stream(vec![0u8; 64], "image/x-exotic"));
+    cfg.check_declared_format = true;
+    if let Err(e) = qe::verify_c2pa(cfg) {
+        assert!(!is_mismatch(&e), "unexpected mismatch: {e}");
+    }
+
+    // An ISO-BMFF brand the sniffer reports as MP4 may still be declared as HEIF.
+    let mut data = vec![0, 0, 0, 24];
+    data.extend_from_slice(b"ftypmif1\0\0\0\0mif1heic");
+    let mut cfg = qe::C2paVerificationConfig::secure_default(stream(data, "image/heif"));
+    cfg.check_declared_format = true;
+    if let Err(e) = qe::verify_c2pa(cfg) {
+        assert!(!is_mismatch(&e), "unexpected mismatch: {e}");
+    }
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use que_engine as qe;
+
+// The default limits are process-wide, so this file holds a single test.
+#[test]
+fn global_default_limits_apply_to_new_configs_only() {
+    assert_eq!(qe::default_limits().max_ingredients, qe::LimitsConfig::defaults().max_ingredients);
+
+    let before = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes {
+        data: common::make_test_jpeg_bytes(),
+    });
+
+    let mut strict = qe::LimitsConfig::defaults();
+    strict.max_in_memory_asset_size = 16;
+    strict.max_ingredients = 8;
+    qe::set_default_limits(strict);
+    assert_eq!(qe::default_limits().max_ingredients, 8);
+
+    // Already-built configs keep their limits.
+    assert_eq!(before.limits.max_in_memory_asset_size, qe::LimitsConfig::defaults().max_in_memory_asset_size);
+
+    let data = common::make_test_jpeg_bytes();
+    let cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: data.clone() });
+    assert_eq!(cfg.limits.max_ingredients, 8);
+    assert!(matches!(qe::verify_c2pa(cfg), Err(qe::EngineError::AssetTooLarge { limit: 16, .. })));
+
+    // Per-call limits win over the global default.
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data });
+    cfg.limits = qe::LimitsConfig::defaults();
+    assert!(!matches!(qe::verify_c2pa(cfg), Err(qe::EngineError::AssetTooLarge { .. })));
+
+    let sign_cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        common::setup_env_signer_vars().parse().unwrap(),
+        qe::SigAlg::Es256,
+    );
+    assert_eq!(sign_cfg.limits.max_ingredients, 8);
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use que_engine as qe;
+
+fn builder(chain: &common::TestChain) -> qe::C2paConfigBuilder {
+    qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .intermediates(chain.intermediate_pem.clone().into_bytes())
+}
+
+#[test]
+fn detached_signing_returns_the_unchanged_asset_and_its_manifest() {
+    let chain = common::generate_es256_chain();
+    let Ok(detached) = qe::sign_c2pa_detached(builder(&chain).build()) else {
+        return; // allow environments that cannot sign
+    };
+    assert_eq!(detached.asset, common::make_test_jpeg_bytes());
+    // JUMBF superbox type
+    assert_eq!(&detached.manifest[4..8], b"jumb");
+}
+
+#[test]
+fn detached_pair_verifies_side_by_side() {
+    let chain = common::generate_es256_chain();
+    let Ok(detached) = qe::sign_c2pa_detached(builder(&chain).build()) else {
+        return;
+    };
+    let dir = tempfile::tempdir().unwrap();
+    let asset_path = dir.path().join("asset.jpg");
+    detached.write_to(&asset_path).unwrap();
+    assert!(asset_path.with_extension("c2pa").exists());
+
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Path(asset_path));
+    cfg.policy = Some(qe::TrustPolicyConfig { anchors: Some(chain.root_pem.into_bytes()), ..Default::default() });
+    let result = qe::verify_c2pa(cfg).expect("verified");
+    assert_eq!(result.is_embedded, Some(false));
+    assert_eq!(result.manifest_location, Some(qe::ManifestLocation::Sidecar));
+    assert_eq!(result.verdict, Some(qe::Verdict::Allowed));
+}
+
+#[test]
+fn detached_signing_reads_streams() {
+    let chain = common::generate_es256_chain();
+    let Ok(expected) = qe::sign_c2pa_detached(builder(&chain).build()) else {
+        return;
+    };
+    let mut cfg = builder(&chain).build();
+    cfg.source = qe::AssetRef::Stream {
+        reader: std::cell::RefCell::new(Box::new(std::io::Cursor::new(common::make_test_jpeg_bytes()))),
+        content_type: Some("image/jpeg".into()),
+    };
+    let detached = qe::sign_c2pa_detached(cfg).expect("signed");
+    assert_eq!(detached.asset, expected.asset);
+    assert!(!detached.manifest.is_empty());
+}
+
+#[test]
+fn detached_signing_rejects_validate_report() {
+    let chain = common::generate_es256_chain();
+    let cfg = builder(&chain).post_sign(qe::PostSignMode::ValidateReport).build();
+    match qe::sign_c2pa_detached(cfg) {
+        Err(qe::EngineError::Config(msg)) => assert!(msg.contains("ValidateReport"), "{msg}"),
+        other => panic!("expected Config error, got {other:?}"),
+    }
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use que_engine as qe;
+
+const FIXED_TIME: &str = "2024-01-01T00:00:00Z";
+
+fn deterministic() -> qe::DeterministicConfig {
+    qe::DeterministicConfig { fixed_time: Some(FIXED_TIME.into()), fixed_instance_ids: true }
+}
+
+fn builder(chain: &common::TestChain) -> qe::C2paConfigBuilder {
+    qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .skip_post_sign_validation(true)
+}
+
+/// The active manifest's JSON, without the claim `instance_id` c2pa regenerates on
+/// every signing.
+fn active_manifest(signed: Vec<u8>) -> serde_json::Value {
+    let json = qe::read_manifest_json(qe::AssetRef::Bytes { data: signed }, qe::LimitsConfig::defaults())
+        .expect("read")
+        .expect("manifest");
+    let store: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let label = store["active_manifest"].as_str().unwrap();
+    let mut manifest = store["manifests"][label].clone();
+    manifest.as_object_mut().unwrap().remove("instance_id");
+    manifest
+}
+
+#[test]
+fn deterministic_signing_repeats_the_manifest() {
+    let chain = common::generate_es256_chain();
+    let sign = || qe::sign_c2pa(builder(&chain).deterministic(deterministic()).build()).ok().flatten();
+    let (Some(first), Some(second)) = (sign(), sign()) else {
+        return; // allow environments that cannot sign
+    };
+
+    let first = active_manifest(first);
+    assert_eq!(first, active_manifest(second));
+    let actions = first["assertions"][0]["data"]["actions"].as_array().unwrap();
+    assert!(actions.iter().all(|action| action["when"] == FIXED_TIME));
+}
+
+#[test]
+fn manifest_labels_vary_without_fixed_instance_ids() {
+    let chain = common::generate_es256_chain();
+    let config = qe::DeterministicConfig { fixed_time: Some(FIXED_TIME.into()), fixed_instance_ids: false };
+    let sign = || qe::sign_c2pa(builder(&chain).deterministic(config.clone()).build()).ok().flatten();
+    let (Some(first), Some(second)) = (sign(), sign()) else {
+        return;
+    };
+    assert_ne!(active_manifest(first)["label"], active_manifest(second)["label"]);
+}
+
+#[test]
+fn deterministic_signing_skips_the_timestamper() {
+    let chain = common::generate_es256_chain();
+    if qe::sign_c2pa(builder(&chain).build()).is_err() {
+        return;
+    }
+    // Unreachable TSA: contacting it would fail the call.
+    let cfg = builder(&chain)
+        .timestamper(qe::Timestamper::Custom("https://127.0.0.1:9/tsa".into()))
+        .deterministic(deterministic())
+        .build();
+    assert!(qe::sign_c2pa(cfg).is_ok());
+}
+
+#[test]
+fn invalid_fixed_time_is_rejected() {
+    let chain = common::generate_es256_chain();
+    let config = qe::DeterministicConfig { fixed_time: Some("yesterday".into()), fixed_instance_ids: false };
+    let cfg = builder(&chain).deterministic(config).build();
+    assert!(matches!(qe::sign_c2pa(cfg), Err(qe::EngineError::Config(_))));
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use que_engine as qe;
+use serde_json::json;
+
+fn summary(changes: &[qe::ManifestChange]) -> Vec<(&str, qe::ChangeKind)> {
+    changes.iter().map(|c| (c.key.as_str(), c.kind)).collect()
+}
+
+#[test]
+fn between_reports_added_removed_and_changed_entries() {
+    let a = json!({
+        "assertions": [
+            { "label": "c2pa.actions", "data": { "actions": [{ "action": "c2pa.created" }] } },
+            { "label": "org.example.note", "data": { "note": "old" } },
+            { "label": "org.example.gone", "data": {} }
+        ],
+        "ingredients": [{ "title": "parent.jpg", "relationship": "parentOf" }],
+        "signature_info": { "issuer": "Org A", "cert_serial_number": "1" }
+    });
+    let b = json!({
+        "assertions": [
+            { "label": "c2pa.actions", "data": { "actions": [{ "action": "c2pa.created" }] } },
+            { "label": "org.example.note", "data": { "note": "new" } },
+            { "label": "org.example.new", "data": {} }
+        ],
+        "ingredients": [
+            { "title": "parent.jpg", "relationship": "parentOf" },
+            { "title": "overlay.png", "relationship": "componentOf" }
+        ],
+        "signature_info": { "issuer": "Org B", "cert_serial_number": "1" }
+    });
+
+    let diff = qe::ManifestDiff::between(&a, &b);
+    assert_eq!(
+        summary(&diff.assertions),
+        vec![
+            ("org.example.note", qe::ChangeKind::Changed),
+            ("org.example.gone", qe::ChangeKind::Removed),
+            ("org.example.new", qe::ChangeKind::Added),
+        ]
+    );
+    assert_eq!(summary(&diff.ingredients), vec![("overlay.png", qe::ChangeKind::Added)]);
+    assert_eq!(summary(&diff.signer), vec![("issuer", qe::ChangeKind::Changed)]);
+    assert_eq!(diff.signer[0].before, Some(json!("Org A")));
+    assert_eq!(diff.signer[0].after, Some(json!("Org B")));
+
+    assert!(qe::ManifestDiff::between(&a, &a).is_empty());
+}
+
+#[test]
+fn between_numbers_repeated_labels() {
+    let a = json!({ "assertions": [
+        { "label": "org.example.note", "data": { "n": 1 } },
+        { "label": "org.example.note", "data": { "n": 2 } }
+    ] });
+    let b = json!({ "assertions": [
+        { "label": "org.example.note", "data": { "n": 1 } }
+    ] });
+
+    let diff = qe::ManifestDiff::between(&a, &b);
+    assert_eq!(diff.assertions.len(), 1);
+    assert_eq!(diff.assertions[0].key, "org.example.note#2");
+    assert_eq!(diff.assertions[0].kind, qe::ChangeKind::Removed);
+}
+
+#[test]
+fn diff_manifests_requires_a_manifest() {
+    let res = qe::diff_manifests(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+    );
+    assert!(res.is_err());
+}
+
+#[test]
+fn diff_manifests_shows_added_assertion_between_signings() {
+    let sign = |manifest: String| {
+        let mut cfg = qe::C2paConfig::secure_default(
+            qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+            common::setup_env_signer_vars().parse().unwrap(),
+            qe::SigAlg::Es256,
+        );
+        cfg.output = qe::OutputTarget::Memory;
+        cfg.manifest_definition = Some(manifest);
+        qe::sign_c2pa(cfg)
+    };
+
+    let first = sign(common::minimal_manifest_def("image/jpeg"));
+    let mut def: serde_json::Value = serde_json::from_str(&common::minimal_manifest_def("image/jpeg")).unwrap();
+    def["assertions"].as_array_mut().unwrap().push(json!({ "label": "org.example.note", "data": { "note": "v2" } }));
+    let second = sign(def.to_string());
+
+    let (first, second) = match (first, second) {
+        (Ok(Some(a)), Ok(Some(b))) => (a, b),
+        _ => return, // allow environments that cannot sign with self-signed cert
+    };
+
+    let diff = qe::diff_manifests(qe::AssetRef::Bytes { data: first }, qe::AssetRef::Bytes { data: second })
+        .expect("diff");
+    assert!(diff
+        .assertions
+        .iter()
+        .any(|c| c.key == "org.example.note" && c.kind == qe::ChangeKind::Added));
+}
@@ -0,0 +1,19 @@
+mod common;
+
+use que_engine as qe;
+
+#[test]
+fn engine_info_reports_build_capabilities() {
+    let info = qe::engine_info();
+    assert_eq!(info.engine_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(info.features.contains(&"cawg".to_string()), cfg!(feature = "cawg"));
+
+    #[cfg(feature = "c2pa")]
+    {
+        assert!(info.c2pa_version.as_deref().is_some_and(|v| v.starts_with("0.")));
+        assert!(info.features.contains(&"c2pa".to_string()));
+        assert!(info.supported_read_formats.iter().any(|f| f == "image/jpeg"));
+        assert!(info.supported_write_formats.iter().any(|f| f == "image/jpeg"));
+        assert!(info.supported_read_formats.windows(2).all(|w| w[0] < w[1]));
+    }
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use que_engine as qe;
+
+fn signed_jpeg() -> Option<Vec<u8>> {
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer,
+        qe::SigAlg::Es256,
+    );
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    cfg.output = qe::OutputTarget::Memory;
+    match qe::sign_c2pa(cfg) {
+        Ok(Some(signed)) => Some(signed),
+        Ok(None) => panic!("expected memory output"),
+        Err(_e) => None, // allow environments that cannot sign with self-signed cert
+    }
+}
+
+#[test]
+fn format_override_takes_precedence_over_stream_content_type() {
+    let Some(signed) = signed_jpeg() else { return };
+    let reader: Box<dyn qe::domain::types::StreamReader> = Box::new(std::io::Cursor::new(signed));
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Stream {
+        reader: std::cell::RefCell::new(reader),
+        content_type: Some("image/png".into()),
+    });
+    cfg.format_override = Some("image/jpeg".into());
+
+    let res = qe::verify_c2pa(cfg).expect("verify with override");
+    assert!(res.report.contains("active_manifest"));
+}
+
+#[test]
+fn format_override_applies_to_byte_sources() {
+    let Some(signed) = signed_jpeg() else { return };
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+    cfg.format_override = Some("image/jpeg".into());
+
+    let res = qe::verify_c2pa(cfg).expect("verify with override");
+    assert!(res.report.contains("active_manifest"));
+}
+
+#[test]
+fn unknown_format_override_is_rejected() {
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes {
+        data: common::make_test_jpeg_bytes(),
+    });
+    cfg.format_override = Some("image/x-not-a-format".into());
+
+    match qe::verify_c2pa(cfg) {
+        Err(qe::EngineError::Config(msg)) => assert!(msg.contains("image/x-not-a-format")),
+        other => panic!("expected Config error, got {other:?}"),
+    }
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use que_engine as qe;
+
+// Kept in its own test binary: it points TMPDIR at a missing directory for the whole process.
+#[test]
+fn bytes_to_memory_signing_does_not_need_a_temp_dir() {
+    std::env::set_var("TMPDIR", std::env::temp_dir().join("que-engine-missing-tmp"));
+    assert!(tempfile::tempdir().is_err(), "TMPDIR override did not take effect");
+
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        common::setup_env_signer_vars().parse().unwrap(),
+        qe::SigAlg::Es256,
+    );
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    cfg.output = qe::OutputTarget::Memory;
+
+    // Self-signed test certs may be rejected by the SDK, but never for lack of a temp dir.
+    match qe::sign_c2pa(cfg) {
+        Ok(out) => assert!(out.is_some_and(|bytes| !bytes.is_empty())),
+        Err(e) => assert!(!matches!(e, qe::EngineError::Io(_)), "unexpected I/O error: {e}"),
+    }
+}
+
+// Verification reads every source in place, including extensionless paths and bytes
+// whose format can't be sniffed, so it needs no temp dir either.
+#[test]
+fn verification_does_not_need_a_temp_dir() {
+    std::env::set_var("TMPDIR", std::env::temp_dir().join("que-engine-missing-tmp"));
+
+    let root = std::path::PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("in_memory_verification");
+    std::fs::create_dir_all(&root).unwrap();
+    let extensionless = root.join("source");
+    std::fs::write(&extensionless, common::make_test_jpeg_bytes()).unwrap();
+
+    for source in [
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::AssetRef::Path(extensionless),
+        qe::AssetRef::Bytes { data: b"not an asset".to_vec() },
+    ] {
+        let verified = qe::verify_c2pa(qe::C2paVerificationConfig::secure_default(source));
+        assert!(
+            !matches!(verified, Err(qe::EngineError::TempDirUnavailable { .. } | qe::EngineError::Io(_))),
+            "{verified:?}"
+        );
+    }
+}
@@ -306,3 +306,175 @@ fn ingredient_size_limits_enforced() {
     }
 }
 
+
+#[test]
+fn ingredient_report_for_unsigned_asset() {
+    let mut cfg = qe::IngredientConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() }
+    );
+    cfg.output = qe::OutputTarget::Memory;
+
+    let report = qe::create_ingredient_report(cfg).expect("ingredient report");
+    assert!(!report.is_signed);
+    assert!(report.validation_status.is_empty());
+    let _: serde_json::Value = serde_json::from_slice(&report.json).expect("valid JSON");
+}
+
+#[test]
+fn ingredient_report_for_signed_asset() {
+    let chain = common::generate_es256_chain();
+    let mut sign_cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::Signer::from_pem(
+            format!("{}{}", chain.leaf_pem, chain.intermediate_pem).as_bytes(),
+            chain.key_pem.as_bytes(),
+        ),
+        qe::SigAlg::Es256,
+    );
+    sign_cfg.output = qe::OutputTarget::Memory;
+    sign_cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    let signed = qe::sign_c2pa(sign_cfg).expect("signing").expect("memory output");
+
+    let tmp = tempfile::tempdir().unwrap();
+    let mut cfg = qe::IngredientConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+    cfg.output = qe::OutputTarget::Path(tmp.path().to_path_buf());
+
+    let report = qe::create_ingredient_report(cfg).expect("ingredient report");
+    assert!(report.is_signed);
+    assert!(!report.json.is_empty());
+    assert!(tmp.path().join("ingredient.json").exists());
+}
+
+#[test]
+fn ingredient_from_url_rejects_plain_http() {
+    let mut cfg = qe::IngredientConfig::secure_default(
+        qe::AssetRef::Url("http://example.com/image.jpg".into())
+    );
+    cfg.output = qe::OutputTarget::Memory;
+
+    let err = qe::create_ingredient(cfg).expect_err("plain HTTP must be rejected");
+    assert!(matches!(err, qe::EngineError::InvalidUrl(_) | qe::EngineError::Feature(_)), "unexpected error: {err:?}");
+}
+
+#[test]
+fn ingredient_from_url_rejects_loopback_host() {
+    let mut cfg = qe::IngredientConfig::secure_default(
+        qe::AssetRef::Url("https://127.0.0.1/image.jpg".into())
+    );
+    cfg.output = qe::OutputTarget::Memory;
+
+    let err = qe::create_ingredient(cfg).expect_err("loopback hosts must be rejected");
+    assert!(matches!(err, qe::EngineError::InvalidUrl(_)), "unexpected error: {err:?}");
+}
+
+#[test]
+fn ingredient_applies_title_relationship_and_metadata() {
+    let mut cfg = qe::IngredientConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() }
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.title = Some("background.jpg".into());
+    cfg.relationship = Some(qe::IngredientRelationship::ParentOf);
+    cfg.metadata = Some(serde_json::json!({ "org.example.batch": "42" }));
+
+    let json = qe::create_ingredient(cfg).unwrap().unwrap();
+    let ingredient: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(ingredient["title"], "background.jpg");
+    assert_eq!(ingredient["relationship"], "parentOf");
+    assert_eq!(ingredient["metadata"]["org.example.batch"], "42");
+}
+
+#[test]
+fn ingredient_metadata_must_be_an_object() {
+    let mut cfg = qe::IngredientConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() }
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.metadata = Some(serde_json::json!(["not", "an", "object"]));
+
+    let err = qe::create_ingredient(cfg).expect_err("non-object metadata must be rejected");
+    assert!(matches!(err, qe::EngineError::Config(_)), "unexpected error: {err:?}");
+}
+
+fn sign_jpeg(chain: &common::TestChain, parent: Option<qe::AssetRef>) -> Option<Vec<u8>> {
+    let mut builder = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .intermediates(chain.intermediate_pem.clone().into_bytes());
+    if let Some(parent) = parent {
+        builder = builder.parent(parent);
+    }
+    qe::sign_c2pa(builder.build()).ok().flatten()
+}
+
+fn active_manifest(signed: &[u8]) -> (String, serde_json::Value) {
+    let cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed.to_vec() });
+    let report: serde_json::Value = serde_json::from_str(&qe::verify_c2pa(cfg).unwrap().report).unwrap();
+    let label = report["active_manifest"].as_str().unwrap().to_string();
+    let manifest = report["manifests"][&label].clone();
+    (label, manifest)
+}
+
+#[test]
+fn parent_ready_ingredient_round_trips_as_parent() {
+    let chain = common::generate_es256_chain();
+    let Some(original) = sign_jpeg(&chain, None) else {
+        return; // allow environments that cannot sign
+    };
+    let (original_label, _) = active_manifest(&original);
+
+    let mut cfg = qe::IngredientConfig::secure_default(qe::AssetRef::Bytes { data: original });
+    cfg.parent_ready = true;
+    let json = qe::create_ingredient(cfg).unwrap().unwrap();
+    let ingredient: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(ingredient["relationship"], "parentOf");
+    assert_eq!(ingredient["active_manifest"], original_label);
+    assert!(ingredient["que.resources"].as_object().is_some_and(|r| !r.is_empty()));
+
+    let derived = sign_jpeg(&chain, Some(qe::AssetRef::Bytes { data: json })).expect("sign with parent-ready ingredient");
+    let (_, manifest) = active_manifest(&derived);
+    let parent = &manifest["ingredients"][0];
+    assert_eq!(parent["relationship"], "parentOf");
+    assert_eq!(parent["active_manifest"], original_label);
+}
+
+#[test]
+fn parent_ready_requires_a_signed_asset() {
+    let mut cfg = qe::IngredientConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() }
+    );
+    cfg.parent_ready = true;
+    let err = qe::create_ingredient(cfg).expect_err("unsigned assets cannot be parent-ready");
+    assert!(matches!(err, qe::EngineError::Config(_)), "unexpected error: {err:?}");
+}
+
+fn stream(data: Vec<u8>, content_type: &str) -> qe::AssetRef {
+    qe::AssetRef::Stream {
+        reader: std::cell::RefCell::new(Box::new(Cursor::new(data))),
+        content_type: Some(content_type.to_string()),
+    }
+}
+
+#[test]
+fn parent_ingredient_from_stream() {
+    let chain = common::generate_es256_chain();
+    let Some(original) = sign_jpeg(&chain, None) else {
+        return; // allow environments that cannot sign
+    };
+    let (original_label, _) = active_manifest(&original);
+
+    let mut cfg = qe::IngredientConfig::secure_default(qe::AssetRef::Bytes { data: original.clone() });
+    cfg.parent_ready = true;
+    let json = qe::create_ingredient(cfg).unwrap().unwrap();
+
+    for parent in [stream(original, "image/jpeg"), stream(json, "application/json")] {
+        let derived = sign_jpeg(&chain, Some(parent)).expect("sign with streamed parent");
+        let (_, manifest) = active_manifest(&derived);
+        let parent = &manifest["ingredients"][0];
+        assert_eq!(parent["relationship"], "parentOf");
+        assert_eq!(parent["active_manifest"], original_label);
+    }
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use que_engine as qe;
+
+fn sign(chain: &common::TestChain, intermediates: Option<&str>) -> Option<Vec<u8>> {
+    let mut builder = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .skip_post_sign_validation(true);
+    if let Some(pem) = intermediates {
+        builder = builder.intermediates(pem.as_bytes().to_vec());
+    }
+    qe::sign_c2pa(builder.build()).ok().flatten()
+}
+
+fn verdict(signed: &[u8], anchors: &str, intermediates: Option<&str>) -> Option<qe::Verdict> {
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed.to_vec() });
+    cfg.policy = Some(qe::TrustPolicyConfig {
+        anchors: Some(anchors.as_bytes().to_vec()),
+        intermediates: intermediates.map(|pem| pem.as_bytes().to_vec()),
+        ..Default::default()
+    });
+    qe::verify_c2pa(cfg).expect("verified").verdict
+}
+
+#[test]
+fn signing_intermediates_complete_the_embedded_chain() {
+    let chain = common::generate_es256_chain();
+    let Some(signed) = sign(&chain, Some(&chain.intermediate_pem)) else {
+        return; // allow environments that cannot sign
+    };
+    assert_eq!(verdict(&signed, &chain.root_pem, None), Some(qe::Verdict::Allowed));
+}
+
+#[test]
+fn policy_intermediates_complete_a_leaf_only_chain() {
+    let chain = common::generate_es256_chain();
+    let Some(signed) = sign(&chain, None) else {
+        return;
+    };
+    assert_eq!(verdict(&signed, &chain.root_pem, None), Some(qe::Verdict::Rejected));
+    assert_eq!(
+        verdict(&signed, &chain.root_pem, Some(&chain.intermediate_pem)),
+        Some(qe::Verdict::Allowed)
+    );
+}
+
+#[test]
+fn intermediates_not_issued_by_the_anchors_are_ignored() {
+    let chain = common::generate_es256_chain();
+    let Some(signed) = sign(&chain, None) else {
+        return;
+    };
+    // The intermediate is genuine but the anchor is an unrelated root.
+    let other_root = common::generate_es256_chain().root_pem;
+    assert_eq!(
+        verdict(&signed, &other_root, Some(&chain.intermediate_pem)),
+        Some(qe::Verdict::Rejected)
+    );
+}
+
+#[test]
+fn intermediates_without_certificates_are_rejected() {
+    let chain = common::generate_es256_chain();
+    let cfg = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .intermediates(b"not a certificate".to_vec())
+    .build();
+    assert!(matches!(qe::sign_c2pa(cfg), Err(qe::EngineError::Config(_))));
+}
@@ -44,3 +44,17 @@ fn create_ingredient_memory_and_path_output() {
 }
 
 
+
+#[test]
+fn limits_config_default_and_display() {
+    let limits = qe::LimitsConfig { max_ingredients: 8, ..Default::default() };
+    assert_eq!(limits.max_manifest_size, qe::LimitsConfig::defaults().max_manifest_size);
+
+    assert_eq!(
+        qe::LimitsConfig::defaults().to_string(),
+        "in-memory asset 128 MiB, in-memory output 128 MiB, stream copy 1 GiB, stream read timeout 300 s, \
+         1024 ingredients, ingredient depth 32, manifest 64 MiB"
+    );
+    let odd = qe::LimitsConfig { max_manifest_size: 1000, ..limits };
+    assert!(odd.to_string().ends_with("8 ingredients, ingredient depth 32, manifest 1000 B"));
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use que_engine as qe;
+
+#[test]
+fn manifest_builder_produces_expected_json() {
+    let value = qe::ManifestBuilder::new()
+        .title("photo.jpg")
+        .format("image/jpeg")
+        .add_action(qe::Action::new("c2pa.created").software_agent("tests"))
+        .add_action(qe::Action::new("c2pa.edited"))
+        .add_ingredient("bg.png", "image/png", qe::IngredientRelationship::ComponentOf)
+        .add_assertion("org.example.note", serde_json::json!({ "note": "hi" }))
+        .to_value();
+
+    assert_eq!(value["title"], "photo.jpg");
+    assert_eq!(value["format"], "image/jpeg");
+    assert_eq!(value["ingredients"][0]["relationship"], "componentOf");
+    let assertions = value["assertions"].as_array().unwrap();
+    assert_eq!(assertions.len(), 2);
+    assert_eq!(assertions[0]["label"], "c2pa.actions");
+    assert_eq!(assertions[0]["data"]["actions"][0]["softwareAgent"], "tests");
+    assert_eq!(assertions[0]["data"]["actions"][1]["action"], "c2pa.edited");
+    assert_eq!(assertions[1]["data"]["note"], "hi");
+}
+
+#[test]
+fn sign_with_manifest_builder() {
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer,
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_source = Some(
+        qe::ManifestBuilder::new()
+            .title("que-engine test")
+            .format("image/jpeg")
+            .add_action(qe::Action::new("c2pa.created").software_agent("que-engine-tests"))
+            .into(),
+    );
+
+    // Allow environments that cannot sign with self-signed cert.
+    if let Ok(out) = qe::sign_c2pa(cfg) {
+        assert!(out.is_some_and(|b| !b.is_empty()));
+    }
+}
+
+#[test]
+fn manifest_source_and_definition_are_exclusive() {
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer,
+        qe::SigAlg::Es256,
+    );
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    cfg.manifest_source = Some(qe::ManifestBuilder::new().title("x").into());
+
+    assert!(matches!(qe::sign_c2pa(cfg), Err(qe::domain::error::EngineError::Config(_))));
+}
+
+#[test]
+fn sign_with_manifest_value() {
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer,
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    let value: serde_json::Value =
+        serde_json::from_str(&common::minimal_manifest_def("image/jpeg")).unwrap();
+    cfg.manifest_source = Some(qe::ManifestSource::Value(value));
+
+    // Allow environments that cannot sign with self-signed cert.
+    if let Ok(out) = qe::sign_c2pa(cfg) {
+        assert!(out.is_some_and(|b| !b.is_empty()));
+    }
+}
+
+#[test]
+fn non_object_manifest_value_is_rejected() {
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer,
+        qe::SigAlg::Es256,
+    );
+    cfg.manifest_source = Some(serde_json::json!(["not", "a", "manifest"]).into());
+
+    assert!(matches!(qe::sign_c2pa(cfg), Err(qe::domain::error::EngineError::Config(_))));
+}
@@ -0,0 +1,236 @@
+mod common;
+
+use que_engine as qe;
+
+fn signer() -> qe::Signer {
+    let chain = common::generate_es256_chain();
+    qe::Signer::from_pem(
+        format!("{}{}", chain.leaf_pem, chain.intermediate_pem).as_bytes(),
+        chain.key_pem.as_bytes(),
+    )
+}
+
+#[test]
+fn extract_manifest_store_from_signed_asset() {
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+
+    let signed = qe::sign_c2pa(cfg).expect("signing").expect("memory output");
+
+    let store = qe::extract_manifest_store(
+        qe::AssetRef::Bytes { data: signed.clone() },
+        qe::LimitsConfig::defaults(),
+    )
+    .expect("manifest store");
+    // JUMBF superbox type
+    assert_eq!(&store[4..8], b"jumb");
+
+    // Extraction is stable across calls.
+    let again = qe::extract_manifest_store(
+        qe::AssetRef::Bytes { data: signed },
+        qe::LimitsConfig::defaults(),
+    )
+    .expect("manifest store");
+    assert_eq!(store, again);
+}
+
+#[test]
+fn extract_manifest_store_errors_without_manifest() {
+    let res = qe::extract_manifest_store(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::LimitsConfig::defaults(),
+    );
+    assert!(res.is_err());
+}
+
+#[test]
+fn apply_sidecar_manifest_reembeds_extracted_store() {
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+
+    let signed = qe::sign_c2pa(cfg).expect("signing").expect("memory output");
+
+    let store = qe::extract_manifest_store(
+        qe::AssetRef::Bytes { data: signed.clone() },
+        qe::LimitsConfig::defaults(),
+    )
+    .expect("manifest store");
+    let stripped = qe::remove_manifest(qe::RemoveManifestConfig::secure_default(
+        qe::AssetRef::Bytes { data: signed },
+    ))
+    .expect("remove")
+    .expect("memory output");
+
+    let reembedded = qe::apply_sidecar_manifest(
+        qe::AssetRef::Bytes { data: stripped.clone() },
+        store.clone(),
+        qe::OutputTarget::Memory,
+        true,
+    )
+    .expect("embedding")
+    .expect("memory output");
+    let roundtrip = qe::extract_manifest_store(
+        qe::AssetRef::Bytes { data: reembedded },
+        qe::LimitsConfig::defaults(),
+    )
+    .expect("manifest store");
+    assert_eq!(roundtrip, store);
+
+    // Detached: asset copied unchanged, store written as a .c2pa sidecar.
+    let tmp = tempfile::tempdir().unwrap();
+    let dest = tmp.path().join("asset.jpg");
+    qe::apply_sidecar_manifest(
+        qe::AssetRef::Bytes { data: stripped },
+        store.clone(),
+        qe::OutputTarget::Path(dest.clone()),
+        false,
+    )
+    .expect("detached sidecar");
+    assert_eq!(std::fs::read(dest.with_extension("c2pa")).unwrap(), store);
+}
+
+#[test]
+fn apply_sidecar_manifest_rejects_store_for_a_different_asset() {
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    let signed = qe::sign_c2pa(cfg).expect("signing").expect("memory output");
+
+    let store = qe::extract_manifest_store(
+        qe::AssetRef::Bytes { data: signed.clone() },
+        qe::LimitsConfig::defaults(),
+    )
+    .expect("manifest store");
+    let mut other = qe::remove_manifest(qe::RemoveManifestConfig::secure_default(
+        qe::AssetRef::Bytes { data: signed },
+    ))
+    .expect("remove")
+    .expect("memory output");
+    // Alter the entropy-coded data just before the EOI marker.
+    let at = other.len() - 3;
+    other[at] ^= 0xff;
+
+    let res = qe::apply_sidecar_manifest(
+        qe::AssetRef::Bytes { data: other },
+        store,
+        qe::OutputTarget::Memory,
+        true,
+    );
+    assert!(
+        matches!(res, Err(qe::EngineError::C2pa(c2pa::Error::HashMismatch(_)))),
+        "expected a hash mismatch, got {res:?}"
+    );
+}
+
+#[test]
+fn apply_sidecar_manifest_rejects_non_manifest_bytes() {
+    let res = qe::apply_sidecar_manifest(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        b"definitely not jumbf".to_vec(),
+        qe::OutputTarget::Memory,
+        true,
+    );
+    assert!(matches!(res, Err(qe::EngineError::Config(_))));
+}
+
+#[test]
+fn extract_manifest_store_enforces_max_manifest_size() {
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+
+    let signed = qe::sign_c2pa(cfg).expect("signing").expect("memory output");
+
+    let mut limits = qe::LimitsConfig::defaults();
+    limits.max_manifest_size = 16;
+    let res = qe::extract_manifest_store(qe::AssetRef::Bytes { data: signed }, limits);
+    assert!(matches!(res, Err(qe::EngineError::ManifestTooLarge { limit: 16, .. })));
+}
+
+#[test]
+fn apply_sidecar_manifest_rejects_oversized_manifest() {
+    let limit = qe::LimitsConfig::defaults().max_manifest_size;
+    let res = qe::apply_sidecar_manifest(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        vec![0u8; limit + 1],
+        qe::OutputTarget::Memory,
+        true,
+    );
+    match res {
+        Err(e @ qe::EngineError::ManifestTooLarge { .. }) => {
+            assert!(e.to_string().contains("manifest too large"));
+        }
+        other => panic!("expected ManifestTooLarge, got {other:?}"),
+    }
+}
+
+#[test]
+fn read_manifest_json_without_manifest_is_none() {
+    let json = qe::read_manifest_json(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::LimitsConfig::defaults(),
+    )
+    .expect("read");
+    assert!(json.is_none());
+}
+
+#[test]
+fn read_manifest_json_skips_validation() {
+    let cfg = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .build();
+    let signed = qe::sign_c2pa(cfg).expect("signing").expect("memory output");
+
+    let json = qe::read_manifest_json(qe::AssetRef::Bytes { data: signed }, qe::LimitsConfig::defaults())
+        .expect("read")
+        .expect("manifest");
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert!(value["active_manifest"].is_string());
+    assert!(value.get("validation_status").is_none());
+}
+
+#[test]
+fn read_manifest_json_leaves_later_verification_intact() {
+    let chain = common::generate_es256_chain();
+    let cfg = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .skip_post_sign_validation(true)
+    .build();
+    let Ok(Some(signed)) = qe::sign_c2pa(cfg) else {
+        return;
+    };
+
+    qe::read_manifest_json(qe::AssetRef::Bytes { data: signed.clone() }, qe::LimitsConfig::defaults())
+        .expect("read");
+    let result = qe::verify_c2pa(qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes {
+        data: signed,
+    }))
+    .expect("verified");
+    assert!(result.status_summary.expect("summary").success > 0);
+}
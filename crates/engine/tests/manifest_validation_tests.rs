@@ -0,0 +1,72 @@
+mod common;
+
+use que_engine as qe;
+use qe::domain::error::EngineError;
+
+#[test]
+fn minimal_manifest_has_no_warnings() {
+    let warnings = qe::validate_manifest_definition(&common::minimal_manifest_def("image/jpeg"))
+        .expect("valid manifest");
+    assert!(warnings.is_empty(), "{warnings:?}");
+}
+
+#[test]
+fn misspellings_are_reported_as_warnings() {
+    let json = serde_json::json!({
+        "title": "t",
+        "assertions": [
+            { "label": "c2pa.action", "data": { "actions": [] } },
+            { "label": "c2pa.actions", "data": { "actions": [ { "action": "c2pa.create" } ] } },
+            { "label": "com.example.note", "data": {} },
+            { "label": "stds.exif__2", "data": {} }
+        ]
+    })
+    .to_string();
+
+    let warnings = qe::validate_manifest_definition(&json).expect("structurally valid");
+    assert_eq!(warnings.len(), 2, "{warnings:?}");
+    assert!(warnings[0].starts_with("assertions[0].label:"));
+    assert!(warnings[1].starts_with("assertions[1].data.actions[0].action:"));
+}
+
+#[test]
+fn structural_errors_name_the_field_path() {
+    let json = serde_json::json!({
+        "title": 5,
+        "assertions": [
+            { "data": {} },
+            { "label": "c2pa.actions", "data": { "actions": [ { "softwareAgent": "x" } ] } }
+        ]
+    })
+    .to_string();
+
+    match qe::validate_manifest_definition(&json) {
+        Err(EngineError::Config(msg)) => {
+            assert!(msg.contains("title: expected a string"), "{msg}");
+            assert!(msg.contains("assertions[0].label: missing required field"), "{msg}");
+            assert!(msg.contains("assertions[1].data.actions[0].action: missing required field"), "{msg}");
+        }
+        other => panic!("expected config error, got {other:?}"),
+    }
+
+    assert!(matches!(qe::validate_manifest_definition("{not json"), Err(EngineError::Config(_))));
+}
+
+#[test]
+fn validate_manifest_flag_rejects_before_signing() {
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer,
+        qe::SigAlg::Es256,
+    );
+    cfg.validate_manifest = true;
+    cfg.manifest_definition = Some(
+        serde_json::json!({ "assertions": [ { "label": "c2pa.actionz", "data": {} } ] }).to_string(),
+    );
+
+    match qe::sign_c2pa(cfg) {
+        Err(EngineError::Config(msg)) => assert!(msg.contains("c2pa.actionz"), "{msg}"),
+        other => panic!("expected config error, got {other:?}"),
+    }
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "metrics")]
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use que_engine as qe;
+
+/// The receiver is process-wide, so tests registering one run one at a time.
+static REGISTRY: Mutex<()> = Mutex::new(());
+
+fn record<T>(f: impl FnOnce() -> T) -> (T, Vec<qe::MetricEvent>) {
+    let _guard = REGISTRY.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    qe::set_metrics(Arc::new(move |event| sink.lock().unwrap().push(event)));
+    let out = f();
+    qe::clear_metrics();
+    let events = std::mem::take(&mut *events.lock().unwrap());
+    (out, events)
+}
+
+#[test]
+fn sign_and_verify_report_sizes_durations_and_verdict() {
+    let chain = common::generate_es256_chain();
+    let jpeg = common::make_test_jpeg_bytes();
+    let cfg = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: jpeg.clone() },
+        qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+        qe::SigAlg::Es256,
+    )
+    .intermediates(chain.intermediate_pem.clone().into_bytes())
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .skip_post_sign_validation(true)
+    .build();
+
+    let (signed, events) = record(|| qe::sign_c2pa(cfg));
+    let Ok(Some(signed)) = signed else { return }; // allow environments that cannot sign
+    assert_eq!(
+        events[0],
+        qe::MetricEvent::Started { operation: qe::MetricOperation::Sign, input_bytes: Some(jpeg.len() as u64) }
+    );
+    match &events[1..] {
+        [qe::MetricEvent::Finished { operation, input_bytes, output_bytes, outcome, .. }] => {
+            assert_eq!(*operation, qe::MetricOperation::Sign);
+            assert_eq!(*input_bytes, Some(jpeg.len() as u64));
+            assert_eq!(*output_bytes, Some(signed.len() as u64));
+            assert_eq!(*outcome, qe::MetricOutcome::Success);
+        }
+        other => panic!("unexpected sign events: {other:?}"),
+    }
+
+    let cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed.clone() });
+    let (verified, events) = record(|| qe::verify_c2pa(cfg));
+    let verdict = verified.expect("verify").verdict.expect("verdict");
+    match &events[..] {
+        [qe::MetricEvent::Started { input_bytes, .. }, qe::MetricEvent::TrustEvaluated { verdict: evaluated, .. }, qe::MetricEvent::Finished { operation, outcome, output_bytes, .. }] =>
+        {
+            assert_eq!(*input_bytes, Some(signed.len() as u64));
+            assert_eq!(*evaluated, verdict);
+            assert_eq!(*operation, qe::MetricOperation::Verify);
+            assert_eq!(*outcome, qe::MetricOutcome::Success);
+            assert_eq!(*output_bytes, None);
+        }
+        other => panic!("unexpected verify events: {other:?}"),
+    }
+}
+
+#[test]
+fn failures_are_counted_and_nothing_is_recorded_once_cleared() {
+    let cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Path("/nonexistent/asset.jpg".into()));
+    let (verified, events) = record(|| qe::verify_c2pa(cfg));
+    assert!(verified.is_err());
+    match events.last() {
+        Some(qe::MetricEvent::Finished { outcome, input_bytes, .. }) => {
+            assert_eq!(*outcome, qe::MetricOutcome::Error);
+            assert_eq!(*input_bytes, None);
+        }
+        other => panic!("expected a Finished event, got {other:?}"),
+    }
+    assert!(!events.iter().any(|e| matches!(e, qe::MetricEvent::TrustEvaluated { .. })));
+
+    let _guard = REGISTRY.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    qe::set_metrics(Arc::new(move |event| sink.lock().unwrap().push(event)));
+    qe::clear_metrics();
+    let cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() });
+    let _ = qe::verify_c2pa(cfg);
+    assert!(events.lock().unwrap().is_empty());
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use que_engine as qe;
+
+const MINIMAL_PDF: &[u8] = b"%PDF-1.4\n\
+1 0 obj\n<< /Type /Catalog /Pages 2 0 R >>\nendobj\n\
+2 0 obj\n<< /Type /Pages /Kids [] /Count 0 >>\nendobj\n\
+trailer\n<< /Root 1 0 R >>\n%%EOF\n";
+
+fn supports(formats: &[String]) -> bool {
+    formats.iter().any(|f| f == "application/pdf")
+}
+
+fn sign(source: qe::AssetRef) -> Result<Option<Vec<u8>>, qe::EngineError> {
+    let chain = common::generate_es256_chain();
+    let cfg = qe::C2paConfig::builder(
+        source,
+        qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("application/pdf"))
+    .build();
+    qe::sign_c2pa(cfg)
+}
+
+#[test]
+fn signing_a_pdf_is_reported_as_unsupported() {
+    if supports(&qe::engine_info().supported_write_formats) {
+        return; // an SDK that can write PDFs signs them normally
+    }
+    match sign(qe::AssetRef::Bytes { data: MINIMAL_PDF.to_vec() }) {
+        Err(qe::EngineError::UnsupportedFormat(msg)) => assert!(msg.starts_with("pdf:"), "{msg}"),
+        other => panic!("expected UnsupportedFormat, got {other:?}"),
+    }
+
+    // Sniffed from streams too, without consuming them.
+    let stream = qe::AssetRef::Stream {
+        reader: std::cell::RefCell::new(Box::new(std::io::Cursor::new(MINIMAL_PDF.to_vec()))),
+        content_type: None,
+    };
+    assert!(matches!(sign(stream), Err(qe::EngineError::UnsupportedFormat(_))));
+}
+
+#[test]
+fn verifying_a_pdf_is_reported_as_unsupported() {
+    if supports(&qe::engine_info().supported_read_formats) {
+        return;
+    }
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("doc.pdf");
+    std::fs::write(&path, MINIMAL_PDF).unwrap();
+
+    let res = qe::verify_c2pa(qe::C2paVerificationConfig::secure_default(qe::AssetRef::Path(path)));
+    match res {
+        Err(qe::EngineError::UnsupportedFormat(msg)) => assert!(msg.starts_with("pdf:"), "{msg}"),
+        other => panic!("expected UnsupportedFormat, got {other:?}"),
+    }
+}
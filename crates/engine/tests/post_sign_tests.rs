@@ -0,0 +1,102 @@
+mod common;
+
+use que_engine as qe;
+
+fn config(chain: &common::TestChain, mode: qe::PostSignMode) -> qe::C2paConfig {
+    qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .intermediates(chain.intermediate_pem.clone().into_bytes())
+    .post_sign(mode)
+    .build()
+}
+
+#[test]
+fn validate_report_returns_the_verification() {
+    let chain = common::generate_es256_chain();
+    let mut cfg = config(&chain, qe::PostSignMode::ValidateReport);
+    cfg.trust_policy = Some(qe::TrustPolicyConfig {
+        anchors: Some(chain.root_pem.clone().into_bytes()),
+        ..Default::default()
+    });
+    let Ok(outcome) = qe::sign_c2pa_with_report(cfg) else {
+        return; // allow environments that cannot sign
+    };
+    let signed = outcome.artifact.expect("signed bytes");
+    let report = outcome.verification.expect("post-sign verification");
+    assert_eq!(report.verdict, Some(qe::Verdict::Allowed));
+    assert!(report.status.expect("statuses").iter().any(|s| s.code == "signingCredential.trusted"));
+    assert!(!signed.is_empty());
+}
+
+#[test]
+fn validate_report_does_not_fail_on_an_untrusted_signer() {
+    let chain = common::generate_es256_chain();
+    let other = common::generate_es256_chain();
+    let mut cfg = config(&chain, qe::PostSignMode::ValidateReport);
+    cfg.trust_policy = Some(qe::TrustPolicyConfig {
+        anchors: Some(other.root_pem.into_bytes()),
+        ..Default::default()
+    });
+    let Ok(outcome) = qe::sign_c2pa_with_report(cfg) else {
+        return; // allow environments that cannot sign
+    };
+    assert!(outcome.artifact.is_some());
+    let report = outcome.verification.expect("post-sign verification");
+    assert_eq!(report.verdict, Some(qe::Verdict::Rejected));
+}
+
+#[test]
+fn strict_mode_reports_what_was_validated() {
+    let chain = common::generate_es256_chain();
+    let Ok(outcome) = qe::sign_c2pa_with_report(config(&chain, qe::PostSignMode::ValidateStrict)) else {
+        return; // allow environments that cannot sign
+    };
+    assert!(outcome.artifact.is_some());
+    let report = outcome.verification.expect("post-sign verification");
+    assert!(report.status.expect("statuses").iter().any(|s| s.code == "claimSignature.validated"));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn async_variant_returns_the_report() {
+    let chain = common::generate_es256_chain();
+    let Ok(outcome) = qe::sign_c2pa_with_report_async(config(&chain, qe::PostSignMode::ValidateReport)).await else {
+        return; // allow environments that cannot sign
+    };
+    assert!(outcome.artifact.is_some());
+    assert!(outcome.verification.is_some());
+}
+
+#[test]
+fn skipped_validation_returns_no_report() {
+    let chain = common::generate_es256_chain();
+    let Ok(outcome) = qe::sign_c2pa_with_report(config(&chain, qe::PostSignMode::Skip)) else {
+        return; // allow environments that cannot sign
+    };
+    assert!(outcome.artifact.is_some());
+    assert!(outcome.verification.is_none());
+
+    // `skip_post_sign_validation` overrides the mode.
+    let mut cfg = config(&chain, qe::PostSignMode::ValidateReport);
+    cfg.skip_post_sign_validation = true;
+    let outcome = qe::sign_c2pa_with_report(cfg).unwrap();
+    assert!(outcome.verification.is_none());
+}
+
+#[test]
+fn verification_that_cannot_run_keeps_the_signed_output() {
+    let chain = common::generate_es256_chain();
+    let source_len = common::make_test_jpeg_bytes().len();
+    let mut cfg = config(&chain, qe::PostSignMode::ValidateReport);
+    // Room for the source, but not for the signed asset when it is re-read to verify.
+    cfg.limits.max_in_memory_asset_size = source_len + 16;
+
+    let outcome = qe::sign_c2pa_with_report(cfg).expect("signing succeeds");
+    assert!(outcome.artifact.expect("signed bytes").len() > source_len + 16);
+    assert!(outcome.verification.is_none());
+    let error = outcome.verification_error.expect("verification error");
+    assert!(error.contains("asset too large"), "{error}");
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use que_engine as qe;
+
+/// A signer that fails if anything tries to load it.
+fn unusable_signer() -> qe::Signer {
+    qe::Signer::from_pem(b"not a certificate", b"not a key")
+}
+
+fn preview(cfg: &qe::C2paConfig) -> serde_json::Value {
+    serde_json::from_str(&qe::preview_manifest(cfg).expect("preview")).unwrap()
+}
+
+#[test]
+fn preview_resolves_the_manifest_without_the_signer() {
+    let cfg = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        unusable_signer(),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .timestamper(qe::Timestamper::Custom("https://93.184.216.34/tsa".into()))
+    .remote_manifest_url("https://93.184.216.34/manifest.c2pa", false)
+    .build();
+
+    let manifest = preview(&cfg);
+    assert_eq!(manifest["title"], "que-engine test");
+    assert_eq!(manifest["assertions"][0]["label"], "c2pa.actions");
+    assert_eq!(manifest["ta_url"], "https://93.184.216.34/tsa");
+    assert_eq!(manifest["remote_url"], "https://93.184.216.34/manifest.c2pa");
+    assert_eq!(manifest["no_embed"], true);
+}
+
+#[test]
+fn preview_includes_the_parent_ingredient() {
+    let chain = common::generate_es256_chain();
+    let signed = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .skip_post_sign_validation(true)
+    .build();
+    let Ok(Some(parent)) = qe::sign_c2pa(signed) else {
+        return; // allow environments that cannot sign
+    };
+    let dir = tempfile::tempdir().unwrap();
+    let parent_path = dir.path().join("parent.jpg");
+    std::fs::write(&parent_path, parent).unwrap();
+
+    let cfg = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        unusable_signer(),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .parent(qe::AssetRef::Path(parent_path))
+    .build();
+
+    let ingredients = preview(&cfg)["ingredients"].as_array().unwrap().clone();
+    assert_eq!(ingredients.len(), 1);
+    assert_eq!(ingredients[0]["relationship"], "parentOf");
+    assert!(ingredients[0]["active_manifest"].is_string());
+}
+
+#[test]
+fn deterministic_preview_predicts_the_manifest_label() {
+    let chain = common::generate_es256_chain();
+    let builder = |signer: qe::Signer| {
+        qe::C2paConfig::builder(
+            qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+            signer,
+            qe::SigAlg::Es256,
+        )
+        .manifest(common::minimal_manifest_def("image/jpeg"))
+        .deterministic(qe::DeterministicConfig { fixed_time: None, fixed_instance_ids: true })
+        .skip_post_sign_validation(true)
+    };
+    let label = preview(&builder(unusable_signer()).build())["label"].clone();
+    assert!(label.is_string());
+
+    let signer = qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes());
+    let Ok(Some(signed)) = qe::sign_c2pa(builder(signer).build()) else {
+        return;
+    };
+    let json = qe::read_manifest_json(qe::AssetRef::Bytes { data: signed }, qe::LimitsConfig::defaults())
+        .unwrap()
+        .unwrap();
+    let store: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(store["active_manifest"], label);
+}
+
+#[test]
+fn preview_rejects_invalid_remote_urls() {
+    let cfg = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        unusable_signer(),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .remote_manifest_url("ftp://example.com/manifest.c2pa", false)
+    .build();
+    assert!(matches!(qe::preview_manifest(&cfg), Err(qe::EngineError::InvalidUrl(_))));
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use que_engine as qe;
+
+fn recorder() -> (qe::ProgressCallback, Arc<Mutex<Vec<qe::ProgressEvent>>>) {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let callback = qe::ProgressCallback::new(move |event| sink.lock().unwrap().push(event));
+    (callback, events)
+}
+
+#[test]
+fn verify_stream_reports_verifying_progress() {
+    let data = common::make_test_jpeg_bytes();
+    let total = data.len() as u64;
+    let reader: Box<dyn qe::domain::types::StreamReader> = Box::new(std::io::Cursor::new(data));
+    let (callback, events) = recorder();
+
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Stream {
+        reader: std::cell::RefCell::new(reader),
+        content_type: Some("image/jpeg".into()),
+    });
+    cfg.progress = Some(callback);
+    let _ = qe::verify_c2pa(cfg); // unsigned: the result does not matter here
+
+    let events = events.lock().unwrap();
+    assert!(!events.is_empty());
+    assert!(events.iter().all(|e| e.phase == qe::ProgressPhase::Verifying));
+    assert_eq!(events[0].bytes_processed, 0);
+    assert_eq!(events[0].total_bytes, Some(total));
+}
+
+#[test]
+fn sign_bytes_reports_staging_then_signing() {
+    let data = common::make_test_jpeg_bytes();
+    let total = data.len() as u64;
+    let (callback, events) = recorder();
+
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data },
+        common::setup_env_signer_vars().parse().unwrap(),
+        qe::SigAlg::Es256,
+    );
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    cfg.progress = Some(callback);
+    let signed = qe::sign_c2pa(cfg).is_ok();
+
+    let events = events.lock().unwrap();
+    let phases: Vec<_> = events.iter().map(|e| e.phase).collect();
+    assert_eq!(phases.first(), Some(&qe::ProgressPhase::Staging));
+    assert!(phases.contains(&qe::ProgressPhase::Signing));
+    assert!(events.iter().all(|e| e.total_bytes == Some(total)));
+    if signed {
+        let last = events.last().unwrap();
+        assert_eq!((last.phase, last.bytes_processed), (qe::ProgressPhase::Signing, total));
+    }
+}
+
+#[test]
+fn no_callback_is_a_no_op() {
+    let cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes {
+        data: common::make_test_jpeg_bytes(),
+    });
+    assert!(cfg.progress.is_none());
+    let _ = qe::verify_c2pa(cfg);
+}
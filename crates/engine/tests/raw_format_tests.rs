@@ -0,0 +1,111 @@
+mod common;
+
+use que_engine as qe;
+
+/// Little-endian TIFF header with one IFD at offset 8. Each entry is
+/// `(tag, type, value)`; values longer than four bytes follow the IFD.
+fn tiff_header(entries: &[(u16, u16, &[u8])]) -> Vec<u8> {
+    let mut data = b"II*\0".to_vec();
+    data.extend_from_slice(&8u32.to_le_bytes());
+    data.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    let mut extra_at = 8 + 2 + entries.len() * 12 + 4;
+    let mut extra = Vec::new();
+    for (tag, typ, value) in entries {
+        data.extend_from_slice(&tag.to_le_bytes());
+        data.extend_from_slice(&typ.to_le_bytes());
+        data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        if value.len() <= 4 {
+            let mut inline = value.to_vec();
+            inline.resize(4, 0);
+            data.extend_from_slice(&inline);
+        } else {
+            data.extend_from_slice(&(extra_at as u32).to_le_bytes());
+            extra.extend_from_slice(value);
+            extra_at += value.len();
+        }
+    }
+    data.extend_from_slice(&0u32.to_le_bytes());
+    data.extend_from_slice(&extra);
+    data.resize(1024, 0);
+    data
+}
+
+const MAKE: u16 = 0x010F;
+const DNG_VERSION: u16 = 0xC612;
+
+/// The format the engine detects, read from the declared-format check's error.
+fn detected_format(data: Vec<u8>) -> String {
+    let reader: Box<dyn qe::domain::types::StreamReader> = Box::new(std::io::Cursor::new(data));
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Stream {
+        reader: std::cell::RefCell::new(reader),
+        content_type: Some("image/png".into()),
+    });
+    cfg.check_declared_format = true;
+    match qe::verify_c2pa(cfg) {
+        Err(qe::EngineError::Config(msg)) => msg.rsplit(' ').next().unwrap().to_string(),
+        other => panic!("expected a format mismatch, got {other:?}"),
+    }
+}
+
+fn sign_error(data: Vec<u8>) -> qe::EngineError {
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let cfg = qe::C2paConfig::secure_default(qe::AssetRef::Bytes { data }, signer, qe::SigAlg::Es256);
+    qe::sign_c2pa(cfg).expect_err("raw format should not sign")
+}
+
+#[test]
+fn tiff_based_raws_are_told_apart() {
+    let dng = tiff_header(&[(MAKE, 2, b"NIKON\0"), (DNG_VERSION, 1, &[1, 4, 0, 0])]);
+    assert_eq!(detected_format(dng), "image/x-adobe-dng");
+
+    let nef = tiff_header(&[(MAKE, 2, b"NIKON CORPORATION\0")]);
+    assert_eq!(detected_format(nef), "image/x-nikon-nef");
+
+    let arw = tiff_header(&[(MAKE, 2, b"SONY\0")]);
+    assert_eq!(detected_format(arw), "image/x-sony-arw");
+
+    let tiff = tiff_header(&[(MAKE, 2, b"Canon\0")]);
+    assert_eq!(detected_format(tiff), "image/tiff");
+}
+
+#[test]
+fn dng_declared_as_tiff_passes_the_format_check() {
+    let dng = tiff_header(&[(DNG_VERSION, 1, &[1, 4, 0, 0])]);
+    let reader: Box<dyn qe::domain::types::StreamReader> = Box::new(std::io::Cursor::new(dng));
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Stream {
+        reader: std::cell::RefCell::new(reader),
+        content_type: Some("image/tiff".into()),
+    });
+    cfg.check_declared_format = true;
+    if let Err(e) = qe::verify_c2pa(cfg) {
+        assert!(!e.to_string().contains("does not match"), "unexpected mismatch: {e}");
+    }
+}
+
+#[test]
+fn unsupported_raws_are_detected_and_rejected_for_signing() {
+    let mut cr2 = b"II*\0\x10\0\0\0CR\x02\0".to_vec();
+    cr2.resize(512, 0);
+    let mut cr3 = vec![0, 0, 0, 24];
+    cr3.extend_from_slice(b"ftypcrx \0\0\0\x01crx isom");
+    let mut orf = b"IIRO\x08\0\0\0".to_vec();
+    orf.resize(512, 0);
+    let mut rw2 = b"IIU\0\x08\0\0\0".to_vec();
+    rw2.resize(512, 0);
+    let mut raf = b"FUJIFILMCCD-RAW 0201FF383501".to_vec();
+    raf.resize(512, 0);
+
+    for (data, mime, name) in [
+        (cr2, "image/x-canon-cr2", "Canon CR2"),
+        (cr3, "image/x-canon-cr3", "Canon CR3"),
+        (orf, "image/x-olympus-orf", "Olympus ORF"),
+        (rw2, "image/x-panasonic-rw2", "Panasonic RW2"),
+        (raf, "image/x-fuji-raf", "Fujifilm RAF"),
+    ] {
+        assert_eq!(detected_format(data.clone()), mime);
+        match sign_error(data) {
+            qe::EngineError::UnsupportedFormat(msg) => assert!(msg.contains(name), "{msg}"),
+            other => panic!("expected UnsupportedFormat for {name}, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use que_engine as qe;
+
+fn signed_jpeg(chain: &common::TestChain) -> Option<Vec<u8>> {
+    let cfg = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .intermediates(chain.intermediate_pem.clone().into_bytes())
+    .build();
+    qe::sign_c2pa(cfg).ok().flatten()
+}
+
+fn verify(signed: &[u8], include_raw: bool) -> qe::VerificationResult {
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed.to_vec() });
+    cfg.include_raw_validation_results = include_raw;
+    qe::verify_c2pa(cfg).expect("verified")
+}
+
+#[test]
+fn raw_validation_results_are_opt_in() {
+    let chain = common::generate_es256_chain();
+    let Some(signed) = signed_jpeg(&chain) else {
+        return; // allow environments that cannot sign
+    };
+    let result = verify(&signed, false);
+    assert!(result.raw_validation_results.is_none());
+    assert!(result.to_json()["raw_validation_results"].is_null());
+}
+
+#[test]
+fn raw_validation_results_keep_c2pa_partitions() {
+    let chain = common::generate_es256_chain();
+    let Some(signed) = signed_jpeg(&chain) else {
+        return; // allow environments that cannot sign
+    };
+    let result = verify(&signed, true);
+    let raw = result.raw_validation_results.clone().expect("raw validation results");
+
+    let active = &raw["activeManifest"];
+    let success = active["success"].as_array().expect("success list");
+    assert!(!success.is_empty());
+    assert!(success.iter().any(|s| s["code"] == "claimSignature.validated"));
+    assert!(active["failure"].is_array());
+    assert_eq!(result.to_json()["raw_validation_results"], raw);
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use que_engine as qe;
+
+fn signer() -> qe::Signer {
+    let chain = common::generate_es256_chain();
+    qe::Signer::from_pem(
+        format!("{}{}", chain.leaf_pem, chain.intermediate_pem).as_bytes(),
+        chain.key_pem.as_bytes(),
+    )
+}
+
+fn sign_parent_with_exif() -> Vec<u8> {
+    let manifest = serde_json::json!({
+        "title": "parent with gps",
+        "format": "image/jpeg",
+        "assertions": [
+            {
+                "label": "c2pa.actions",
+                "data": { "actions": [ { "action": "c2pa.created" } ] }
+            },
+            {
+                "label": "stds.exif",
+                "data": {
+                    "@context": { "exif": "http://ns.adobe.com/exif/1.0/" },
+                    "exif:GPSLatitude": "39,21.102N",
+                    "exif:GPSLongitude": "74,26.5737W"
+                }
+            }
+        ]
+    }).to_string();
+
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(manifest);
+    qe::sign_c2pa(cfg).expect("signing the parent").expect("memory output")
+}
+
+#[test]
+fn redacted_exif_is_removed_and_manifest_still_verifies() {
+    let parent = sign_parent_with_exif();
+
+    let tmp = tempfile::tempdir().unwrap();
+    let parent_path = tmp.path().join("parent.jpg");
+    std::fs::write(&parent_path, &parent).unwrap();
+
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: parent.clone() },
+        signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    cfg.parent = Some(qe::AssetRef::Path(parent_path));
+    cfg.redactions = Some(vec!["stds.exif".into()]);
+
+    let signed = qe::sign_c2pa(cfg).expect("signing with redactions").expect("memory output");
+
+    let mut vcfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+    vcfg.mode = qe::VerifyMode::Detailed;
+    let res = qe::verify_c2pa(vcfg).expect("verify");
+    assert!(!res.report.contains("74,26.5737W"), "redacted GPS data should not appear");
+    // Redacted assertions must not surface as missing or mismatched assertions.
+    let statuses = res.status.unwrap_or_default();
+    assert!(
+        statuses.iter().all(|s| s.passed || !s.code.starts_with("assertion.")),
+        "unexpected assertion failures: {statuses:?}"
+    );
+}
+
+#[test]
+fn redactions_without_parent_are_rejected() {
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    cfg.redactions = Some(vec!["stds.exif".into()]);
+
+    assert!(matches!(qe::sign_c2pa(cfg), Err(qe::EngineError::Config(_))));
+}
@@ -0,0 +1,30 @@
+mod common;
+
+use que_engine as qe;
+
+fn verify_against(url: &str) -> qe::domain::error::EngineResult<qe::VerificationResult> {
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes {
+        data: common::make_test_jpeg_bytes(),
+    });
+    cfg.remote_manifest = Some(url.into());
+    qe::verify_c2pa(cfg)
+}
+
+#[test]
+fn remote_manifest_rejects_private_and_insecure_urls() {
+    for url in ["https://127.0.0.1/manifest.c2pa", "https://10.0.0.1/manifest.c2pa", "http://example.com/manifest.c2pa", "file:///etc/passwd"] {
+        match verify_against(url) {
+            Err(qe::EngineError::InvalidUrl(_)) => {}
+            // Without http_urls, plain HTTP is refused as a missing feature.
+            Err(qe::EngineError::Feature(_)) if url.starts_with("http:") => {}
+            other => panic!("{url}: expected InvalidUrl, got {other:?}"),
+        }
+    }
+}
+
+#[cfg(not(feature = "remote_manifests"))]
+#[test]
+fn remote_manifest_requires_feature() {
+    let res = verify_against("https://example.com/manifest.c2pa");
+    assert!(matches!(res, Err(qe::EngineError::Feature("remote_manifests"))));
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use que_engine as qe;
+
+#[test]
+fn remove_manifest_from_signed_jpeg() {
+    let chain = common::generate_es256_chain();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::Signer::from_pem(
+            format!("{}{}", chain.leaf_pem, chain.intermediate_pem).as_bytes(),
+            chain.key_pem.as_bytes(),
+        ),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    let signed = qe::sign_c2pa(cfg).expect("signing").expect("memory output");
+
+    let rcfg = qe::RemoveManifestConfig::secure_default(qe::AssetRef::Bytes { data: signed.clone() });
+    let stripped = qe::remove_manifest(rcfg).expect("remove").expect("memory output");
+    assert!(stripped.len() < signed.len());
+
+    // The stripped asset no longer carries a manifest store.
+    let err = qe::extract_manifest_store(qe::AssetRef::Bytes { data: stripped.clone() }, qe::default_limits())
+        .expect_err("manifest store should be gone");
+    assert!(matches!(err, qe::EngineError::Config(_)), "unexpected error: {err}");
+    let vcfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: stripped });
+    if let Ok(res) = qe::verify_c2pa(vcfg) {
+        assert!(res.status.unwrap_or_default().is_empty());
+    }
+}
+
+#[test]
+fn remove_manifest_unsigned_jpeg_is_unchanged() {
+    let bytes = common::make_test_jpeg_bytes();
+    let rcfg = qe::RemoveManifestConfig::secure_default(qe::AssetRef::Bytes { data: bytes.clone() });
+    let out = qe::remove_manifest(rcfg).expect("remove").expect("memory output");
+    assert_eq!(out, bytes);
+}
+
+#[test]
+fn remove_manifest_writes_to_path() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dest = tmp.path().join("stripped.jpg");
+    let mut rcfg = qe::RemoveManifestConfig::secure_default(qe::AssetRef::Bytes {
+        data: common::make_test_jpeg_bytes(),
+    });
+    rcfg.output = qe::OutputTarget::Path(dest.clone());
+    let out = qe::remove_manifest(rcfg).expect("remove");
+    assert!(out.is_none());
+    assert!(dest.exists());
+}
+
+#[test]
+fn remove_manifest_rejects_unsupported_format() {
+    let pdf = b"%PDF-1.7\n%%EOF\n".to_vec();
+    let rcfg = qe::RemoveManifestConfig::secure_default(qe::AssetRef::Bytes { data: pdf });
+    match qe::remove_manifest(rcfg) {
+        Err(qe::EngineError::UnsupportedFormat(msg)) => assert!(msg.contains("not supported")),
+        other => panic!("expected unsupported format error, got {other:?}"),
+    }
+}
@@ -20,3 +20,95 @@ fn engine_defaults_values() {
 
 
 
+
+#[test]
+fn raw_settings_must_be_an_object() {
+    let mut cfg = qe::C2paVerificationConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() }
+    );
+    cfg.raw_settings = Some(serde_json::json!(["verify"]));
+
+    let err = qe::verify_c2pa(cfg).expect_err("non-object raw settings must be rejected");
+    assert!(matches!(err, qe::EngineError::Config(_)), "unexpected error: {err:?}");
+}
+
+#[test]
+fn raw_settings_are_applied_to_verification() {
+    let mut cfg = qe::C2paVerificationConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() }
+    );
+    cfg.raw_settings = Some(serde_json::json!({ "verify": { "verify_trust": false } }));
+
+    // An unsigned asset fails with "no manifest" rather than a settings error.
+    if let Err(e) = qe::verify_c2pa(cfg) {
+        assert!(!e.to_string().contains("raw_settings"), "unexpected error: {e}");
+    }
+}
+
+fn verify_bytes_with_progress(
+    callback: impl Fn(qe::ProgressEvent) + Send + Sync + 'static,
+) -> qe::C2paVerificationConfig {
+    let mut cfg = qe::C2paVerificationConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() }
+    );
+    cfg.progress = Some(qe::ProgressCallback::new(callback));
+    cfg
+}
+
+#[test]
+fn calls_on_different_threads_run_concurrently() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    // Each call blocks inside its settings scope until the other has entered its own,
+    // which only happens if the two scopes do not exclude each other.
+    let entered = Arc::new(AtomicUsize::new(0));
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let entered = entered.clone();
+            std::thread::spawn(move || {
+                let overlapped = Arc::new(std::sync::atomic::AtomicBool::new(false));
+                let flag = overlapped.clone();
+                let first = std::sync::Once::new();
+                let cfg = verify_bytes_with_progress(move |_| {
+                    first.call_once(|| {
+                        entered.fetch_add(1, Ordering::SeqCst);
+                        let deadline = Instant::now() + Duration::from_secs(10);
+                        while entered.load(Ordering::SeqCst) < 2 && Instant::now() < deadline {
+                            std::thread::sleep(Duration::from_millis(5));
+                        }
+                        flag.store(entered.load(Ordering::SeqCst) == 2, Ordering::SeqCst);
+                    });
+                });
+                let _ = qe::verify_c2pa(cfg); // unsigned: the result does not matter here
+                overlapped.load(Ordering::SeqCst)
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        assert!(handle.join().unwrap(), "settings scopes on different threads were serialized");
+    }
+}
+
+#[test]
+fn nested_call_on_the_same_thread_is_rejected() {
+    use std::sync::{Arc, Mutex};
+
+    let nested = Arc::new(Mutex::new(None));
+    let sink = nested.clone();
+    let cfg = verify_bytes_with_progress(move |_| {
+        let mut slot = sink.lock().unwrap();
+        if slot.is_none() {
+            let inner = qe::C2paVerificationConfig::secure_default(
+                qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() }
+            );
+            *slot = Some(qe::verify_c2pa(inner).map(|_| ()));
+        }
+    });
+    let _ = qe::verify_c2pa(cfg);
+
+    let nested = nested.lock().unwrap().take().expect("progress callback ran");
+    assert!(matches!(nested, Err(qe::EngineError::Config(_))), "unexpected result: {nested:?}");
+}
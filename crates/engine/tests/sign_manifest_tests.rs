@@ -173,6 +173,32 @@ fn sign_with_remote_manifest_url_and_no_embed() {
     let _ = qe::sign_c2pa(cfg);
 }
 
+#[test]
+fn remote_url_reachability_is_checked_before_signing() {
+    let config = |url: &str| {
+        let mut cfg = qe::C2paConfig::secure_default(
+            qe::AssetRef::Bytes { data: jpeg_bytes() },
+            signer_env(),
+            qe::SigAlg::Es256,
+        );
+        cfg.embed = false;
+        cfg.remote_manifest_url = Some(url.to_string());
+        cfg.verify_remote_url_reachable = true;
+        cfg.manifest_definition = Some(manifest_with_actions_v2_created("image/jpeg", "qe"));
+        cfg
+    };
+
+    // The URL's structure and host are checked before any request is made.
+    let result = qe::sign_c2pa(config("https://127.0.0.1/manifest.c2pa"));
+    assert!(matches!(result, Err(qe::EngineError::InvalidUrl(_))), "{result:?}");
+
+    #[cfg(not(feature = "remote_manifests"))]
+    {
+        let result = qe::sign_c2pa(config("https://example.com/manifest.c2pa"));
+        assert!(matches!(result, Err(qe::EngineError::Feature("remote_manifests"))), "{result:?}");
+    }
+}
+
 #[test]
 fn sign_from_stream_input_to_path_output() {
     let signer = signer_env();
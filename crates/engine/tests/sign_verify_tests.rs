@@ -282,3 +282,36 @@ fn verify_tampered_manifest_fails() {
     }
 }
 
+
+#[test]
+fn sign_with_sha384_content_hash() {
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    cfg.hash_alg = Some(qe::HashAlg::Sha384);
+
+    if let Ok(Some(signed)) = qe::sign_c2pa(cfg) {
+        let mut vcfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+        vcfg.mode = qe::VerifyMode::Detailed;
+        if let Ok(res) = qe::verify_c2pa(vcfg) {
+            assert!(res.report.contains("sha384"), "report should record the sha384 digest");
+        }
+    }
+}
+
+#[test]
+fn hash_alg_conflicting_with_raw_settings_is_rejected() {
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.hash_alg = Some(qe::HashAlg::Sha512);
+    cfg.raw_settings = Some(serde_json::json!({ "core": { "hash_alg": "sha256" } }));
+
+    let err = qe::sign_c2pa(cfg).expect_err("conflicting hash algorithms must be rejected");
+    assert!(err.to_string().contains("hash_alg"), "unexpected error: {err}");
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use que_engine as qe;
+
+fn write_chain(dir: &std::path::Path, chain: &common::TestChain) -> qe::Signer {
+    let (cert, key) = (dir.join("chain.pem"), dir.join("key.pem"));
+    std::fs::write(&cert, format!("{}{}", chain.leaf_pem, chain.intermediate_pem)).unwrap();
+    std::fs::write(&key, &chain.key_pem).unwrap();
+    qe::Signer::from_files(cert, key)
+}
+
+fn signer_serial(signer: qe::Signer) -> String {
+    let cfg = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer,
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .build();
+    let signed = qe::sign_c2pa(cfg).expect("signing").expect("memory output");
+    let mut verify = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+    verify.include_certificates = Some(true);
+    let result = qe::verify_c2pa(verify).expect("verification");
+    let certificates = result.certificates.expect("certificates");
+    certificates[0].cert_serial_number.clone().expect("serial number")
+}
+
+// One test: the cache and its TTL are process-wide.
+#[test]
+fn signer_cache_reuses_keys_and_follows_rotation() {
+    let dir = tempfile::tempdir().unwrap();
+    let signer = write_chain(dir.path(), &common::generate_es256_chain());
+
+    qe::Signer::set_cache_ttl(None);
+    signer.reserve_size(qe::SigAlg::Es256).unwrap();
+    assert_eq!(qe::Signer::cached_signers(), 0);
+
+    qe::Signer::set_cache_ttl(Some(std::time::Duration::from_secs(60)));
+    let reserve = signer.reserve_size(qe::SigAlg::Es256).unwrap();
+    assert_eq!(signer.reserve_size(qe::SigAlg::Es256).unwrap(), reserve);
+    assert_eq!(qe::Signer::cached_signers(), 1);
+
+    // Pem signers are never cached.
+    let chain = common::generate_es256_chain();
+    qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes())
+        .reserve_size(qe::SigAlg::Es256)
+        .unwrap();
+    assert_eq!(qe::Signer::cached_signers(), 1);
+
+    // A rotated certificate and key replace the cached entry.
+    let first = signer_serial(signer.clone());
+    write_chain(dir.path(), &common::generate_es256_chain());
+    let second = signer_serial(signer.clone());
+    assert_ne!(first, second);
+    assert_eq!(qe::Signer::cached_signers(), 1);
+
+    qe::Signer::clear_cache();
+    assert_eq!(qe::Signer::cached_signers(), 0);
+    qe::Signer::set_cache_ttl(None);
+}
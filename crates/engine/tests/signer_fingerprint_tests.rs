@@ -0,0 +1,78 @@
+mod common;
+
+use que_engine as qe;
+
+fn signed_jpeg(chain: &common::TestChain) -> Option<Vec<u8>> {
+    let cfg = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .intermediates(chain.intermediate_pem.clone().into_bytes())
+    .build();
+    qe::sign_c2pa(cfg).ok().flatten()
+}
+
+fn verify(
+    signed: &[u8],
+    fingerprint: &str,
+    policy: Option<qe::TrustPolicyConfig>,
+) -> Result<qe::VerificationResult, qe::EngineError> {
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed.to_vec() });
+    cfg.policy = policy;
+    cfg.expected_signer_fingerprint = Some(fingerprint.to_string());
+    qe::verify_c2pa(cfg)
+}
+
+fn fingerprint_status(result: &qe::VerificationResult) -> qe::domain::verify::ValidationStatus {
+    result
+        .status
+        .as_ref()
+        .and_then(|ss| ss.iter().find(|s| s.code.starts_with("que.signerFingerprint.")))
+        .cloned()
+        .expect("fingerprint status")
+}
+
+#[test]
+fn fingerprint_mismatch_rejects_and_names_both_fingerprints() {
+    let chain = common::generate_es256_chain();
+    let Some(signed) = signed_jpeg(&chain) else {
+        return; // allow environments that cannot sign
+    };
+    let trusted = qe::TrustPolicyConfig { anchors: Some(chain.root_pem.into_bytes()), ..Default::default() };
+    let wrong = "00".repeat(32);
+
+    let result = verify(&signed, &wrong, Some(trusted.clone())).unwrap();
+    let status = fingerprint_status(&result);
+    assert_eq!(status.code_enum, qe::ValidationCode::SignerFingerprintMismatch);
+    assert!(!status.passed);
+    assert_eq!(result.verdict, Some(qe::Verdict::Rejected));
+    let explanation = status.explanation.expect("explanation");
+    assert!(explanation.contains(&wrong), "{explanation}");
+
+    // The explanation starts with the actual fingerprint; pinning it passes.
+    let actual = explanation
+        .split_whitespace()
+        .find(|w| w.len() == 64 && *w != wrong)
+        .expect("actual fingerprint")
+        .to_string();
+    let colon_form = actual
+        .to_ascii_uppercase()
+        .as_bytes()
+        .chunks(2)
+        .map(|c| std::str::from_utf8(c).unwrap())
+        .collect::<Vec<_>>()
+        .join(":");
+    let result = verify(&signed, &colon_form, Some(trusted)).unwrap();
+    let status = fingerprint_status(&result);
+    assert_eq!(status.code_enum, qe::ValidationCode::SignerFingerprintMatch);
+    assert!(status.passed);
+    assert_eq!(result.verdict, Some(qe::Verdict::Allowed));
+}
+
+#[test]
+fn malformed_fingerprint_is_a_config_error() {
+    let source = common::make_test_jpeg_bytes();
+    assert!(matches!(verify(&source, "not-a-fingerprint", None), Err(qe::EngineError::Config(_))));
+}
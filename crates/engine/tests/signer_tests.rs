@@ -23,6 +23,29 @@ fn parse_local_signer() {
     }
 }
 
+#[test]
+fn constructors_match_parsed_uris() {
+    assert!(matches!(
+        qe::Signer::from_files("cert.pem", "key.pem"),
+        qe::Signer::Local { cert_path, key_path } if cert_path.ends_with("cert.pem") && key_path.ends_with("key.pem")
+    ));
+    assert!(matches!(
+        qe::Signer::from_env("CERT", "KEY"),
+        qe::Signer::Env { cert_var, key_var } if cert_var == "CERT" && key_var == "KEY"
+    ));
+}
+
+#[test]
+fn pem_signer_resolves_without_env_or_files() {
+    let (cert_pem, key_pem) = common::generate_es256_pem_pair();
+    let signer = qe::Signer::from_pem(cert_pem.as_bytes(), key_pem.as_bytes());
+    assert!(!format!("{signer:?}").contains("BEGIN"), "PEM leaked into Debug output");
+    #[cfg(feature = "c2pa")]
+    {
+        let _ = signer.resolve(c2pa::SigningAlg::Es256).unwrap();
+    }
+}
+
 #[test]
 fn invalid_signer_scheme() {
     let err = "foo:bar".parse::<qe::Signer>().unwrap_err();
@@ -30,5 +53,41 @@ fn invalid_signer_scheme() {
     assert!(msg.contains("Invalid"));
 }
 
+#[cfg(feature = "c2pa")]
+#[test]
+fn reserve_size_estimate_covers_signature_and_chain() {
+    let (_dir, uri) = common::setup_local_signer_files();
+    let signer: qe::Signer = uri.parse().unwrap();
+    let estimate = signer.reserve_size(qe::SigAlg::Es256).unwrap();
+    let chain_len: usize = signer
+        .resolve(c2pa::SigningAlg::Es256)
+        .unwrap()
+        .certs()
+        .unwrap()
+        .iter()
+        .map(Vec::len)
+        .sum();
+    assert!(estimate > chain_len, "estimate {estimate} below chain length {chain_len}");
+}
 
+#[test]
+fn reserve_size_overrides_signer_estimate() {
+    let sign = |reserve_size: Option<usize>| {
+        let mut cfg = qe::C2paConfig::secure_default(
+            qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+            common::setup_env_signer_vars().parse().unwrap(),
+            qe::SigAlg::Es256,
+        );
+        cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+        cfg.output = qe::OutputTarget::Memory;
+        cfg.reserve_size = reserve_size;
+        qe::sign_c2pa(cfg)
+    };
+    if sign(None).is_err() {
+        return; // self-signed certs may be rejected
+    }
 
+    let err = sign(Some(64)).unwrap_err();
+    assert!(err.to_string().contains("too big for JUMBF box"), "unexpected error: {err}");
+    assert!(sign(Some(64 * 1024)).is_ok());
+}
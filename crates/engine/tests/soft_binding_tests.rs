@@ -0,0 +1,159 @@
+mod common;
+
+use que_engine as qe;
+
+fn signer() -> qe::Signer {
+    let chain = common::generate_es256_chain();
+    qe::Signer::from_pem(
+        format!("{}{}", chain.leaf_pem, chain.intermediate_pem).as_bytes(),
+        chain.key_pem.as_bytes(),
+    )
+}
+
+/// Test algorithm that records a fixed value without touching the asset.
+struct FixedBinding;
+
+impl qe::SoftBindingAlgorithm for FixedBinding {
+    fn alg(&self) -> &str {
+        "test.fixed"
+    }
+
+    fn embed(&self, asset: &[u8], _format: &str) -> qe::domain::error::EngineResult<qe::domain::soft_binding::SoftBinding> {
+        Ok(qe::domain::soft_binding::SoftBinding { asset: asset.to_vec(), value: b"fixed".to_vec() })
+    }
+
+    fn detect(&self, _asset: &[u8], _format: &str) -> qe::domain::error::EngineResult<Option<Vec<u8>>> {
+        Ok(Some(b"fixed".to_vec()))
+    }
+}
+
+#[test]
+fn soft_binding_assertion_is_reported_and_detected() {
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    cfg.soft_binding = Some(qe::SoftBindingConfig::new(FixedBinding));
+
+    let signed = qe::sign_c2pa(cfg).expect("signing with a soft binding").expect("memory output");
+
+    let mut vcfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+    vcfg.soft_binding = Some(qe::SoftBindingConfig::new(FixedBinding));
+    let res = qe::verify_c2pa(vcfg).expect("verify");
+    let bindings = res.soft_bindings.expect("soft bindings");
+    assert_eq!(bindings.len(), 1);
+    assert_eq!(bindings[0].alg, "test.fixed");
+    assert_eq!(bindings[0].value, "Zml4ZWQ=");
+    assert_eq!(bindings[0].detected, Some(true));
+}
+
+#[test]
+fn verification_without_soft_binding_reports_none() {
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+
+    let signed = qe::sign_c2pa(cfg).expect("signing").expect("memory output");
+    let vcfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+    let res = qe::verify_c2pa(vcfg).expect("verify");
+    assert!(res.soft_bindings.is_none());
+}
+
+#[cfg(feature = "watermark")]
+#[test]
+fn block_mean_watermark_roundtrip_survives_reencode() {
+    use qe::SoftBindingAlgorithm;
+
+    // Gentle gradient: paired blocks start out with near-identical luminance.
+    let img: image::RgbImage = image::ImageBuffer::from_fn(256, 256, |x, y| {
+        image::Rgb([100 + (x / 16) as u8, 120 + (y / 16) as u8, 140])
+    });
+    let mut png = std::io::Cursor::new(Vec::new());
+    image::DynamicImage::ImageRgb8(img)
+        .write_to(&mut png, image::ImageOutputFormat::Png)
+        .unwrap();
+
+    let wm = qe::BlockMeanWatermark::default();
+    let marked = wm.embed(png.get_ref(), "image/png").expect("embed");
+    assert_eq!(wm.detect(&marked.asset, "image/png").unwrap(), Some(marked.value.clone()));
+
+    // Re-encode as JPEG: the manifest would be lost, the watermark should not be.
+    let decoded = image::load_from_memory(&marked.asset).unwrap();
+    let mut jpeg = std::io::Cursor::new(Vec::new());
+    decoded.write_to(&mut jpeg, image::ImageOutputFormat::Jpeg(85)).unwrap();
+    assert_eq!(wm.detect(jpeg.get_ref(), "image/jpeg").unwrap(), Some(marked.value));
+
+    // Unmarked content is not reported as carrying a watermark.
+    assert_eq!(wm.detect(png.get_ref(), "image/png").unwrap(), None);
+}
+
+/// Test fingerprinter returning a fixed value.
+struct FixedFingerprint;
+
+impl qe::Fingerprinter for FixedFingerprint {
+    fn alg(&self) -> &str {
+        "test.fingerprint"
+    }
+
+    fn fingerprint(&self, _asset: &[u8], _format: &str) -> qe::domain::error::EngineResult<Vec<u8>> {
+        Ok(b"print".to_vec())
+    }
+}
+
+#[test]
+fn fingerprint_assertion_is_reported() {
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    cfg.fingerprint = Some(qe::FingerprintConfig::new(FixedFingerprint));
+
+    let signed = qe::sign_c2pa(cfg).expect("signing with a fingerprint").expect("memory output");
+
+    let vcfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+    let res = qe::verify_c2pa(vcfg).expect("verify");
+    let bindings = res.soft_bindings.expect("soft bindings");
+    assert_eq!(bindings.len(), 1);
+    assert_eq!(bindings[0].alg, "test.fingerprint");
+    assert_eq!(bindings[0].value, "cHJpbnQ=");
+    assert_eq!(bindings[0].detected, None);
+}
+
+#[cfg(feature = "phash")]
+#[test]
+fn phash_is_stable_across_reencode() {
+    use qe::Fingerprinter;
+
+    // Smooth gradients have near-zero low frequencies that flip on re-encode; give the
+    // image some large-scale structure instead.
+    let img: image::RgbImage = image::ImageBuffer::from_fn(128, 128, |x, y| {
+        let (fx, fy) = (x as f32 / 128.0, y as f32 / 128.0);
+        let l = 128.0 + 60.0 * (fx * 7.0).sin() + 50.0 * (fy * 5.0).cos()
+            - if (fx - 0.3).hypot(fy - 0.6) < 0.2 { 80.0 } else { 0.0 };
+        let l = l.clamp(0.0, 255.0) as u8;
+        image::Rgb([l, l / 2 + 40, 255 - l])
+    });
+    let mut png = std::io::Cursor::new(Vec::new());
+    image::DynamicImage::ImageRgb8(img.clone())
+        .write_to(&mut png, image::ImageOutputFormat::Png)
+        .unwrap();
+    let mut jpeg = std::io::Cursor::new(Vec::new());
+    image::DynamicImage::ImageRgb8(img)
+        .write_to(&mut jpeg, image::ImageOutputFormat::Jpeg(80))
+        .unwrap();
+
+    let ph = qe::PHashFingerprinter;
+    let a = u64::from_be_bytes(ph.fingerprint(png.get_ref(), "image/png").unwrap().try_into().unwrap());
+    let b = u64::from_be_bytes(ph.fingerprint(jpeg.get_ref(), "image/jpeg").unwrap().try_into().unwrap());
+    assert!((a ^ b).count_ones() <= 6, "hamming distance {}", (a ^ b).count_ones());
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use que_engine as qe;
+
+fn sign(chain: &common::TestChain, parent: Option<std::path::PathBuf>) -> Option<Vec<u8>> {
+    let mut builder = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .skip_post_sign_validation(true);
+    if let Some(parent) = parent {
+        builder = builder.parent(qe::AssetRef::Path(parent));
+    }
+    qe::sign_c2pa(builder.build()).ok().flatten()
+}
+
+fn active_label(signed: &[u8]) -> String {
+    let json = qe::read_manifest_json(qe::AssetRef::Bytes { data: signed.to_vec() }, qe::LimitsConfig::defaults())
+        .unwrap()
+        .unwrap();
+    let store: serde_json::Value = serde_json::from_str(&json).unwrap();
+    store["active_manifest"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn statuses_carry_the_label_of_their_manifest() {
+    let chain = common::generate_es256_chain();
+    let dir = tempfile::tempdir().unwrap();
+    let Some(parent) = sign(&chain, None) else {
+        return; // allow environments that cannot sign
+    };
+    let parent_path = dir.path().join("parent.jpg");
+    std::fs::write(&parent_path, &parent).unwrap();
+    let Some(child) = sign(&chain, Some(parent_path)) else {
+        return;
+    };
+
+    let (child_label, parent_label) = (active_label(&child), active_label(&parent));
+    let cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: child });
+    let statuses = qe::verify_c2pa(cfg).unwrap().status.unwrap();
+    let labelled = |label: &str| statuses.iter().filter(|s| s.manifest_label.as_deref() == Some(label)).count();
+    assert!(labelled(&child_label) > 0);
+    // The parent's validation shows up as an ingredient delta.
+    assert!(labelled(&parent_label) > 0);
+    assert_eq!(labelled(&child_label) + labelled(&parent_label), statuses.len());
+    for status in &statuses {
+        let label = status.manifest_label.as_deref().unwrap();
+        assert!(status.url.as_deref().unwrap_or_default().contains(label), "{}", status.code);
+    }
+}
+
+#[test]
+fn ingredient_report_statuses_carry_the_ingredient_label() {
+    let chain = common::generate_es256_chain();
+    let Some(signed) = sign(&chain, None) else {
+        return;
+    };
+    let label = active_label(&signed);
+
+    let report = qe::create_ingredient_report(qe::IngredientConfig::secure_default(qe::AssetRef::Bytes {
+        data: signed,
+    }))
+    .unwrap();
+    assert!(!report.validation_status.is_empty());
+    assert!(report.validation_status.iter().all(|s| s.manifest_label.as_deref() == Some(label.as_str())));
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use que_engine as qe;
+use que_engine::domain::verdict::status_severity;
+
+fn signed_jpeg(chain: &common::TestChain) -> Option<Vec<u8>> {
+    let cfg = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .intermediates(chain.intermediate_pem.clone().into_bytes())
+    .skip_post_sign_validation(true)
+    .build();
+    qe::sign_c2pa(cfg).ok().flatten()
+}
+
+fn verify(signed: &[u8], policy: Option<qe::TrustPolicyConfig>) -> qe::VerificationResult {
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed.to_vec() });
+    cfg.policy = policy;
+    qe::verify_c2pa(cfg).expect("verified")
+}
+
+#[test]
+fn summary_counts_every_status() {
+    let chain = common::generate_es256_chain();
+    let Some(signed) = signed_jpeg(&chain) else {
+        return; // allow environments that cannot sign
+    };
+    let result = verify(&signed, None);
+
+    let statuses = result.status.expect("statuses");
+    let summary = result.status_summary.expect("summary");
+    assert_eq!(summary.success + summary.informational + summary.failure, statuses.len());
+    assert!(summary.success > 0);
+    assert_eq!(summary.failure, statuses.iter().filter(|s| !s.passed).count());
+    for status in &statuses {
+        assert_eq!(status.severity, status_severity(status, qe::Severity::Fail), "{}", status.code);
+    }
+}
+
+#[test]
+fn trusted_signer_has_no_failures() {
+    let chain = common::generate_es256_chain();
+    let Some(signed) = signed_jpeg(&chain) else {
+        return;
+    };
+    let policy = qe::TrustPolicyConfig { anchors: Some(chain.root_pem.into_bytes()), ..Default::default() };
+    let result = verify(&signed, Some(policy));
+
+    assert_eq!(result.status_summary.expect("summary").failure, 0);
+    let statuses = result.status.expect("statuses");
+    assert!(statuses.iter().all(|s| s.severity != qe::Severity::Fail));
+}
+
+#[test]
+fn signer_name_status_is_counted() {
+    let chain = common::generate_es256_chain();
+    let Some(signed) = signed_jpeg(&chain) else {
+        return;
+    };
+    let policy = |subjects: &str| qe::TrustPolicyConfig {
+        anchors: Some(chain.root_pem.clone().into_bytes()),
+        allowed_subjects: Some(vec![subjects.into()]),
+        ..Default::default()
+    };
+
+    let allowed = verify(&signed, Some(policy("Test Signer")));
+    let rejected = verify(&signed, Some(policy("Someone Else")));
+    let (allowed, rejected) = (allowed.status_summary.unwrap(), rejected.status_summary.unwrap());
+    assert_eq!(rejected.failure, allowed.failure + 1);
+    assert_eq!(rejected.success + 1, allowed.success);
+}
@@ -18,17 +18,38 @@ mod tests {
             source: asset,
             output: OutputTarget::Memory,
             manifest_definition: None,
+            manifest_source: None,
+            validate_manifest: false,
+            check_declared_format: false,
+            default_format: None,
+            allowed_formats: None,
             parent: None,
             parent_base_dir: None,
             signer,
             signing_alg: SigAlg::Ps256,
+            co_signers: Vec::new(),
+            intermediates: None,
+            hash_alg: None,
             timestamper: None,
+            reserve_size: None,
             remote_manifest_url: None,
             embed: true,
+            verify_remote_url_reachable: false,
             trust_policy: None,
             skip_post_sign_validation: true,
+            post_sign: que_engine::PostSignMode::Skip,
             allow_insecure_remote_http: None,
             limits: LimitsConfig::defaults(),
+            temp_dir: None,
+            redactions: None,
+            soft_binding: None,
+            fingerprint: None,
+            auto_thumbnail: None,
+            claim_generator: None,
+            raw_settings: None,
+            progress: None,
+            cancel: None,
+            deterministic: None,
             #[cfg(feature = "cawg")] cawg_identity: None,
         };
 
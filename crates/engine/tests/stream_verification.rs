@@ -18,6 +18,17 @@ mod tests {
             allow_remote_manifests: false,
             include_certificates: None,
             limits: LimitsConfig::defaults(),
+            soft_binding: None,
+            raw_settings: None,
+            unknown_status_severity: que_engine::EngineDefaults::UNKNOWN_STATUS_SEVERITY,
+            progress: None,
+            cancel: None,
+            format_override: None,
+            check_declared_format: false,
+            allowed_formats: None,
+            remote_manifest: None,
+            include_raw_validation_results: false,
+            expected_signer_fingerprint: None,
             #[cfg(feature = "cawg")] cawg: None,
         };
 
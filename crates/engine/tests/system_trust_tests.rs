@@ -0,0 +1,53 @@
+mod common;
+
+use que_engine as qe;
+
+fn system_trust_policy() -> Option<qe::TrustPolicyConfig> {
+    Some(qe::TrustPolicyConfig { use_system_trust: Some(true), ..Default::default() })
+}
+
+// SSL_CERT_FILE is process-wide, so this file holds a single test.
+#[test]
+fn use_system_trust_reads_ssl_cert_file() {
+    let dir = tempfile::tempdir().unwrap();
+
+    // A bundle without certificates is an error, not an empty trust list.
+    let empty = dir.path().join("empty.crt");
+    std::fs::write(&empty, "# no certificates here\n").unwrap();
+    std::env::set_var("SSL_CERT_FILE", &empty);
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes {
+        data: common::make_test_jpeg_bytes(),
+    });
+    cfg.policy = system_trust_policy();
+    match qe::verify_c2pa(cfg) {
+        Err(qe::EngineError::Config(msg)) => assert!(msg.contains("no certificates"), "{msg}"),
+        other => panic!("expected Config error, got {other:?}"),
+    }
+
+    let signer = common::setup_env_signer_vars();
+    let cert_pem = std::env::var("QE_TEST_CERT_PEM").unwrap();
+    let bundle = dir.path().join("bundle.crt");
+    std::fs::write(&bundle, format!("# Test root\n{cert_pem}")).unwrap();
+    std::env::set_var("SSL_CERT_FILE", &bundle);
+
+    let mut sign = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer.parse().unwrap(),
+        qe::SigAlg::Es256,
+    );
+    sign.output = qe::OutputTarget::Memory;
+    sign.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    let signed = match qe::sign_c2pa(sign) {
+        Ok(Some(signed)) => signed,
+        Ok(None) => panic!("expected memory output"),
+        Err(_e) => return, // self-signed certs may be rejected
+    };
+
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+    cfg.policy = system_trust_policy();
+    let res = qe::verify_c2pa(cfg).expect("verify");
+    let trusted = res.status.as_deref().unwrap_or_default().iter().any(|s| {
+        s.passed && s.code_enum == qe::ValidationCode::SigningCredentialTrusted
+    });
+    assert_eq!(res.trust_anchor.is_some(), trusted);
+}
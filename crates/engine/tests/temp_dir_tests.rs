@@ -0,0 +1,75 @@
+mod common;
+
+use que_engine as qe;
+
+fn signer() -> qe::Signer {
+    let chain = common::generate_es256_chain();
+    qe::Signer::from_pem(
+        format!("{}{}", chain.leaf_pem, chain.intermediate_pem).as_bytes(),
+        chain.key_pem.as_bytes(),
+    )
+}
+
+fn sign_config(source: qe::AssetRef) -> qe::C2paConfig {
+    qe::C2paConfig::builder(source, signer(), qe::SigAlg::Es256)
+        .manifest(common::minimal_manifest_def("image/jpeg"))
+        .build()
+}
+
+#[test]
+fn signing_stages_under_the_configured_temp_dir() {
+    let root = tempfile::tempdir().unwrap();
+    let missing = root.path().join("missing");
+    let source = root.path().join("source.jpg");
+    std::fs::write(&source, common::make_test_jpeg_bytes()).unwrap();
+
+    // A path source signed into memory stages the output under the configured dir.
+    let mut cfg = sign_config(qe::AssetRef::Path(source.clone()));
+    cfg.temp_dir = Some(missing.clone());
+    match qe::sign_c2pa(cfg) {
+        Err(e @ qe::EngineError::TempDirUnavailable { .. }) => {
+            let message = e.to_string();
+            assert!(message.contains(&missing.display().to_string()), "{message}");
+            assert!(message.contains("temp_dir"), "{message}");
+        }
+        other => panic!("expected TempDirUnavailable, got {other:?}"),
+    }
+
+    // The directory belongs to that call only; others still use the system default.
+    let signed = qe::sign_c2pa(sign_config(qe::AssetRef::Path(source.clone()))).expect("signing");
+    assert!(signed.is_some_and(|bytes| !bytes.is_empty()));
+
+    let writable = root.path().join("staging");
+    std::fs::create_dir(&writable).unwrap();
+    let cfg = qe::C2paConfig::builder(qe::AssetRef::Path(source), signer(), qe::SigAlg::Es256)
+        .manifest(common::minimal_manifest_def("image/jpeg"))
+        .temp_dir(&writable)
+        .build();
+    assert!(qe::sign_c2pa(cfg).expect("signing").is_some());
+    // Staging directories are removed when the call returns.
+    assert_eq!(std::fs::read_dir(&writable).unwrap().count(), 0);
+}
+
+#[test]
+fn bytes_signed_to_memory_ignore_the_temp_dir() {
+    let mut cfg = sign_config(qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() });
+    cfg.temp_dir = Some(std::env::temp_dir().join("que-engine-missing-temp-dir"));
+    let signed = qe::sign_c2pa(cfg).expect("signing").expect("memory output");
+    assert!(!signed.is_empty());
+}
+
+#[test]
+fn remove_manifest_stages_under_the_configured_temp_dir() {
+    let root = tempfile::tempdir().unwrap();
+    let missing = root.path().join("missing");
+    let signed = qe::sign_c2pa(sign_config(qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() }))
+        .expect("signing")
+        .expect("memory output");
+
+    let mut cfg = qe::RemoveManifestConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+    cfg.temp_dir = Some(missing.clone());
+    match qe::remove_manifest(cfg) {
+        Err(qe::EngineError::TempDirUnavailable { path, .. }) => assert_eq!(path, missing),
+        other => panic!("expected TempDirUnavailable, got {other:?}"),
+    }
+}
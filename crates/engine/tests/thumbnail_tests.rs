@@ -0,0 +1,47 @@
+mod common;
+
+use que_engine as qe;
+
+fn jpeg_config() -> qe::C2paConfig {
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer,
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    cfg
+}
+
+#[cfg(feature = "thumbnail")]
+#[test]
+fn auto_thumbnail_is_added_for_raster_sources() {
+    let mut cfg = jpeg_config();
+    cfg.auto_thumbnail = Some(qe::ThumbnailConfig {
+        max_dimension: 64,
+        format: qe::ThumbnailFormat::Png,
+    });
+
+    let signed = match qe::sign_c2pa(cfg) {
+        Ok(Some(signed)) => signed,
+        Ok(None) => panic!("expected memory output"),
+        Err(_e) => return, // allow environments that cannot sign with self-signed cert
+    };
+
+    let mut vcfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+    vcfg.mode = qe::VerifyMode::Info;
+    let res = qe::verify_c2pa(vcfg).expect("verify");
+    assert!(res.report.contains("image/png"), "expected a PNG claim thumbnail");
+}
+
+#[cfg(not(feature = "thumbnail"))]
+#[test]
+fn auto_thumbnail_requires_feature() {
+    let mut cfg = jpeg_config();
+    cfg.auto_thumbnail = Some(qe::ThumbnailConfig::default());
+    assert!(matches!(
+        qe::sign_c2pa(cfg),
+        Err(qe::domain::error::EngineError::Feature("thumbnail"))
+    ));
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use que_engine as qe;
+
+fn signer() -> qe::Signer {
+    common::setup_env_signer_vars().parse().unwrap()
+}
+
+#[test]
+fn add_timestamp_rejects_unsigned_asset() {
+    let res = qe::add_timestamp(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+        qe::Timestamper::Digicert,
+        false,
+        qe::OutputTarget::Memory,
+    );
+    assert!(matches!(res, Err(qe::EngineError::Config(_))));
+}
+
+#[test]
+fn add_timestamp_rejects_http_tsa_url() {
+    let res = qe::add_timestamp(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+        qe::Timestamper::Custom("http://tsa.example.com".to_string()),
+        false,
+        qe::OutputTarget::Memory,
+    );
+    assert!(res.is_err());
+}
+
+#[test]
+fn signed_asset_without_tsa_reports_empty_timestamps() {
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+
+    let signed = match qe::sign_c2pa(cfg) {
+        Ok(Some(signed)) => signed,
+        Ok(None) => panic!("expected memory output"),
+        Err(_e) => return, // allow environments that cannot sign with self-signed cert
+    };
+
+    let res = qe::verify_c2pa(qe::C2paVerificationConfig::secure_default(
+        qe::AssetRef::Bytes { data: signed },
+    ))
+    .expect("verify");
+    assert_eq!(res.timestamps.as_ref().map(Vec::len), Some(0));
+    assert!(res.to_json()["timestamps"].is_array());
+}
+
+fn sign_with_tsa_headers(headers: &[(&str, &str)]) -> Result<Option<Vec<u8>>, qe::EngineError> {
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer(),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    cfg.timestamper = Some(qe::Timestamper::CustomWithHeaders {
+        url: "https://tsa.example.com".to_string(),
+        headers: headers.iter().map(|(n, v)| (n.to_string(), v.to_string())).collect(),
+    });
+    qe::sign_c2pa(cfg)
+}
+
+#[test]
+fn tsa_headers_are_validated_before_signing() {
+    for headers in [
+        &[("Bad Name", "x")][..],
+        &[("Authorization", "Bearer a\r\nX-Injected: 1")],
+        &[("content-type", "text/plain")],
+    ] {
+        let res = sign_with_tsa_headers(headers);
+        assert!(matches!(res, Err(qe::EngineError::Config(_))), "{headers:?}: {res:?}");
+    }
+}
+
+#[test]
+fn tsa_header_values_are_redacted_from_debug() {
+    let tsa = qe::Timestamper::CustomWithHeaders {
+        url: "https://tsa.example.com".to_string(),
+        headers: vec![("Authorization".to_string(), "Bearer secret-token".to_string())],
+    };
+    let debug = format!("{tsa:?}");
+    assert!(debug.contains("Authorization"));
+    assert!(!debug.contains("secret-token"));
+    assert_eq!(tsa.resolve().as_deref(), Some("https://tsa.example.com"));
+}
@@ -0,0 +1,131 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use que_engine as qe;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Span names and every recorded field (`name=value`), in emission order.
+#[derive(Default)]
+struct Recorded {
+    spans: Vec<&'static str>,
+    fields: Vec<String>,
+}
+
+#[derive(Clone, Default)]
+struct Recorder {
+    recorded: Arc<Mutex<Recorded>>,
+    next_id: Arc<AtomicU64>,
+}
+
+struct FieldVisitor<'a>(&'a mut Vec<String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.push(format!("{}={:?}", field.name(), value));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut recorded = self.recorded.lock().unwrap();
+        recorded.spans.push(span.metadata().name());
+        span.record(&mut FieldVisitor(&mut recorded.fields));
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _: &Id, values: &Record<'_>) {
+        values.record(&mut FieldVisitor(&mut self.recorded.lock().unwrap().fields));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        event.record(&mut FieldVisitor(&mut self.recorded.lock().unwrap().fields));
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+fn record<T>(f: impl FnOnce() -> T) -> (T, Recorded) {
+    let recorder = Recorder::default();
+    let out = tracing::subscriber::with_default(recorder.clone(), f);
+    let recorded = std::mem::take(&mut *recorder.recorded.lock().unwrap());
+    (out, recorded)
+}
+
+#[test]
+fn sign_emits_pipeline_spans_without_key_material() {
+    let signer = common::setup_env_signer_vars();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer.parse().unwrap(),
+        qe::SigAlg::Es256,
+    );
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    cfg.output = qe::OutputTarget::Memory;
+
+    let (_, recorded) = record(|| qe::sign_c2pa(cfg)); // self-signed certs may be rejected
+
+    for name in ["sign_c2pa", "prepare_manifest", "apply_settings", "resolve_signer"] {
+        assert!(recorded.spans.contains(&name), "missing span {name}: {:?}", recorded.spans);
+    }
+    assert!(recorded.fields.iter().any(|f| f == "signer=\"env\""));
+    assert!(recorded.fields.iter().any(|f| f.starts_with("alg=")));
+
+    for field in &recorded.fields {
+        assert!(!field.contains("BEGIN"), "PEM leaked into trace field: {field}");
+        assert!(!field.contains("QE_TEST_"), "signer config leaked into trace field: {field}");
+    }
+}
+
+#[test]
+fn verify_emits_read_span_and_source_kind() {
+    let cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes {
+        data: common::make_test_jpeg_bytes(),
+    });
+
+    let (_, recorded) = record(|| qe::verify_c2pa(cfg)); // unsigned: the result does not matter here
+
+    assert!(recorded.spans.contains(&"verify_c2pa"));
+    assert!(recorded.spans.contains(&"read_manifest"));
+    assert!(recorded.fields.iter().any(|f| f == "source=\"bytes\""));
+}
+
+#[test]
+fn sign_verifies_the_output_in_validate_report_mode() {
+    let chain = common::generate_es256_chain();
+    let config = |mode| {
+        qe::C2paConfig::builder(
+            qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+            qe::Signer::from_pem(
+                format!("{}{}", chain.leaf_pem, chain.intermediate_pem).as_bytes(),
+                chain.key_pem.as_bytes(),
+            ),
+            qe::SigAlg::Es256,
+        )
+        .manifest(common::minimal_manifest_def("image/jpeg"))
+        .post_sign(mode)
+        .build()
+    };
+
+    let (signed, recorded) = record(|| qe::sign_c2pa(config(qe::PostSignMode::ValidateReport)));
+    signed.expect("signing").expect("memory output");
+    assert!(recorded.spans.contains(&"verify_c2pa"), "{:?}", recorded.spans);
+
+    let (signed, recorded) = record(|| qe::sign_c2pa(config(qe::PostSignMode::Skip)));
+    signed.expect("signing").expect("memory output");
+    assert!(!recorded.spans.contains(&"verify_c2pa"), "{:?}", recorded.spans);
+}
@@ -28,6 +28,10 @@ fn configure_trust_policy_with_anchors() {
         allowed_list: None,
         allowed_ekus: Some(vec!["1.3.6.1.5.5.7.3.3".to_string()]),
         verify_identity_trust: Some(true),
+        allowed_subjects: None,
+        allowed_issuers: None,
+        intermediates: None,
+        use_system_trust: None,
     };
 
     let fixtures = common::c2pa_fixtures_dir();
@@ -41,6 +45,17 @@ fn configure_trust_policy_with_anchors() {
         allow_remote_manifests: false,
         include_certificates: None,
         limits: qe::LimitsConfig::defaults(),
+        soft_binding: None,
+        raw_settings: None,
+        unknown_status_severity: qe::EngineDefaults::UNKNOWN_STATUS_SEVERITY,
+        progress: None,
+        cancel: None,
+        format_override: None,
+        check_declared_format: false,
+        allowed_formats: None,
+        remote_manifest: None,
+        include_raw_validation_results: false,
+        expected_signer_fingerprint: None,
         #[cfg(feature = "cawg")]
         cawg: None,
     };
@@ -69,6 +84,10 @@ fn trust_policy_validation_differences() {
         allowed_list: None,
         allowed_ekus: None,
         verify_identity_trust: Some(true),
+        allowed_subjects: None,
+        allowed_issuers: None,
+        intermediates: None,
+        use_system_trust: None,
     };
 
     let cfg_with_trust = qe::C2paVerificationConfig {
@@ -78,6 +97,17 @@ fn trust_policy_validation_differences() {
         allow_remote_manifests: false,
         include_certificates: None,
         limits: qe::LimitsConfig::defaults(),
+        soft_binding: None,
+        raw_settings: None,
+        unknown_status_severity: qe::EngineDefaults::UNKNOWN_STATUS_SEVERITY,
+        progress: None,
+        cancel: None,
+        format_override: None,
+        check_declared_format: false,
+        allowed_formats: None,
+        remote_manifest: None,
+        include_raw_validation_results: false,
+        expected_signer_fingerprint: None,
         #[cfg(feature = "cawg")]
         cawg: None,
     };
@@ -93,4 +123,73 @@ fn trust_policy_validation_differences() {
         }
         _ => {} // Either may fail
     }
-}
\ No newline at end of file
+}
+#[test]
+fn trust_anchor_is_reported_only_for_trusted_signers() {
+    let signer = common::setup_env_signer_vars();
+    let cert_pem = std::env::var("QE_TEST_CERT_PEM").unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer.parse().unwrap(),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    let signed = match qe::sign_c2pa(cfg) {
+        Ok(Some(signed)) => signed,
+        Ok(None) => panic!("expected memory output"),
+        Err(_e) => return, // self-signed certs may be rejected
+    };
+
+    // No policy: trust is not evaluated, so no anchor is named.
+    let res = qe::verify_c2pa(qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes {
+        data: signed.clone(),
+    }))
+    .expect("verify");
+    assert!(res.trust_anchor.is_none());
+
+    let mut verify = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+    verify.policy = Some(qe::TrustPolicyConfig { anchors: Some(cert_pem.into_bytes()), ..Default::default() });
+    let res = qe::verify_c2pa(verify).expect("verify");
+    let trusted = res.status.as_deref().unwrap_or_default().iter().any(|s| {
+        s.passed && s.code_enum == qe::ValidationCode::SigningCredentialTrusted
+    });
+    assert_eq!(res.trust_anchor.is_some(), trusted);
+    assert!(res.to_json().get("trust_anchor").is_some());
+}
+
+#[test]
+fn allowed_subjects_contribute_to_verdict() {
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        common::setup_env_signer_vars().parse().unwrap(),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    let signed = match qe::sign_c2pa(cfg) {
+        Ok(Some(signed)) => signed,
+        Ok(None) => panic!("expected memory output"),
+        Err(_e) => return, // self-signed certs may be rejected
+    };
+
+    let verify = |subjects: &[&str]| {
+        let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed.clone() });
+        cfg.policy = Some(qe::TrustPolicyConfig {
+            allowed_subjects: Some(subjects.iter().map(|s| s.to_string()).collect()),
+            ..Default::default()
+        });
+        qe::verify_c2pa(cfg).expect("verify")
+    };
+    let has_code = |res: &qe::VerificationResult, code: qe::ValidationCode| {
+        res.status.as_deref().unwrap_or_default().iter().any(|s| s.code_enum == code)
+    };
+
+    // rcgen's default subject common name.
+    let allowed = verify(&["RCGEN SELF SIGNED CERT"]);
+    assert!(has_code(&allowed, qe::ValidationCode::SignerNameAllowed));
+
+    let refused = verify(&["Someone Else"]);
+    assert!(has_code(&refused, qe::ValidationCode::SignerNameNotAllowed));
+    assert_eq!(refused.verdict, Some(qe::Verdict::Rejected));
+}
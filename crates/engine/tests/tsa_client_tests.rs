@@ -0,0 +1,39 @@
+#![cfg(feature = "tsa_client")]
+
+mod common;
+
+use std::time::Duration;
+
+use que_engine as qe;
+
+#[test]
+fn tsa_client_starts_empty_and_clears() {
+    let client = qe::TsaClient::with_cache(Duration::from_secs(30), 8);
+    assert_eq!(client.cached_responses(), 0);
+    client.clear_cache();
+    assert_eq!(client.cached_responses(), 0);
+    // Clones share the same cache.
+    let shared = client.clone();
+    assert_eq!(shared.cached_responses(), client.cached_responses());
+}
+
+#[test]
+fn batch_without_timestamper_ignores_tsa_client() {
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut batch = match qe::BatchSigner::new(
+        Some(common::minimal_manifest_def("image/jpeg")),
+        signer,
+        qe::SigAlg::Es256,
+        None,
+    ) {
+        Ok(b) => b,
+        Err(_e) => return, // allow environments that cannot load the signer
+    };
+    let client = qe::TsaClient::new();
+    batch.tsa_client = Some(client.clone());
+
+    let sources = vec![qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() }];
+    let results = batch.sign_all(&sources).expect("batch settings");
+    assert_eq!(results.len(), 1);
+    assert_eq!(client.cached_responses(), 0);
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use que_engine as qe;
+
+#[test]
+fn copy_with_limits_copies_up_to_the_limit() {
+    let data = vec![7u8; 20_000];
+    let mut out = Vec::new();
+    let copied = qe::util::copy_with_limits(&mut data.as_slice(), &mut out, data.len()).unwrap();
+    assert_eq!(copied, data.len() as u64);
+    assert_eq!(out, data);
+}
+
+#[test]
+fn copy_with_limits_rejects_oversized_input() {
+    let data = vec![7u8; 20_000];
+    let mut out = Vec::new();
+    let result = qe::util::copy_with_limits(&mut data.as_slice(), &mut out, 10_000);
+    assert!(matches!(result, Err(qe::EngineError::AssetTooLarge { limit: 10_000, .. })));
+    assert!(out.len() <= 10_000);
+}
+
+#[test]
+fn write_bytes_to_temp_names_the_file_by_format() {
+    let jpeg = common::make_test_jpeg_bytes();
+    let (path, dir) = qe::util::write_bytes_to_temp(&jpeg, Some("image/png")).unwrap();
+    // Sniffed bytes win over the declared type.
+    assert_eq!(path.extension().unwrap(), "jpg");
+    assert_eq!(std::fs::read(&path).unwrap(), jpeg);
+
+    drop(dir);
+    assert!(!path.exists());
+}
+
+#[test]
+fn write_bytes_to_temp_falls_back_to_content_type() {
+    let (path, _dir) = qe::util::write_bytes_to_temp(b"%unknown", Some("application/pdf")).unwrap();
+    assert_eq!(path.extension().unwrap(), "pdf");
+
+    let (path, _dir) = qe::util::write_bytes_to_temp(b"%unknown", None).unwrap();
+    assert_eq!(path.file_name().unwrap(), "asset");
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use que_engine as qe;
+
+fn sign(chain: &common::TestChain) -> Option<Vec<u8>> {
+    let cfg = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .intermediates(chain.intermediate_pem.clone().into_bytes())
+    .build();
+    qe::sign_c2pa(cfg).ok().flatten()
+}
+
+fn full_verdict(signed: &[u8], policy: Option<qe::TrustPolicyConfig>) -> Option<qe::Verdict> {
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed.to_vec() });
+    cfg.mode = qe::VerifyMode::Summary;
+    cfg.policy = policy;
+    qe::verify_c2pa(cfg).unwrap().verdict
+}
+
+#[test]
+fn verdict_only_matches_full_verification() {
+    let chain = common::generate_es256_chain();
+    let Some(signed) = sign(&chain) else {
+        return; // allow environments that cannot sign
+    };
+    let trusted = qe::TrustPolicyConfig { anchors: Some(chain.root_pem.into_bytes()), ..Default::default() };
+
+    let verdict = qe::verify_verdict(qe::AssetRef::Bytes { data: signed.clone() }, Some(trusted.clone())).unwrap();
+    assert_eq!(verdict, qe::Verdict::Allowed);
+    assert_eq!(Some(verdict), full_verdict(&signed, Some(trusted)));
+
+    let untrusted = qe::verify_verdict(qe::AssetRef::Bytes { data: signed.clone() }, None).unwrap();
+    assert_eq!(Some(untrusted), full_verdict(&signed, None));
+}
+
+#[test]
+fn verdict_only_errors_without_a_manifest() {
+    let source = qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() };
+    assert!(qe::verify_verdict(source, None).is_err());
+}
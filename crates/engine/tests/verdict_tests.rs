@@ -0,0 +1,135 @@
+mod common;
+
+use que_engine as qe;
+use que_engine::domain::verdict::{code_severity, status_severity};
+use que_engine::domain::verify::ValidationStatus;
+
+fn status(code: &str, passed: bool) -> ValidationStatus {
+    ValidationStatus {
+        code: code.into(),
+        code_enum: qe::ValidationCode::parse(code),
+        url: None,
+        explanation: None,
+        ingredient_uri: None,
+        manifest_label: None,
+        passed,
+        severity: code_severity(code).unwrap_or(qe::Severity::Fail),
+    }
+}
+
+#[test]
+fn success_codes_pass() {
+    for code in ["claimSignature.validated", "assertion.dataHash.match", "signingCredential.trusted"] {
+        assert_eq!(code_severity(code), Some(qe::Severity::Pass), "{code}");
+    }
+}
+
+#[test]
+fn informational_codes_warn() {
+    for code in ["signingCredential.ocsp.skipped", "ingredient.unknownProvenance", "algorithm.deprecated"] {
+        assert_eq!(code_severity(code), Some(qe::Severity::Warn), "{code}");
+    }
+}
+
+#[test]
+fn failure_codes_fail() {
+    for code in ["assertion.dataHash.mismatch", "signingCredential.untrusted", "claimSignature.mismatch"] {
+        assert_eq!(code_severity(code), Some(qe::Severity::Fail), "{code}");
+    }
+}
+
+#[test]
+fn codes_are_not_classified_by_substring() {
+    // Would have been a "warning" under substring matching.
+    assert_eq!(code_severity("com.example.warning"), None);
+    assert_eq!(
+        status_severity(&status("com.example.warning", true), qe::Severity::Fail),
+        qe::Severity::Pass
+    );
+}
+
+#[test]
+fn informational_status_is_a_warning_even_when_not_passed() {
+    let statuses = [
+        status("claimSignature.validated", true),
+        status("signingCredential.ocsp.skipped", false),
+    ];
+    assert!(matches!(qe::compute_verdict(&statuses, qe::Severity::Fail), qe::domain::verify::Verdict::Warning));
+}
+
+#[test]
+fn any_failure_rejects() {
+    let statuses = [
+        status("claimSignature.validated", true),
+        status("ingredient.unknownProvenance", false),
+        status("assertion.dataHash.mismatch", false),
+    ];
+    assert!(matches!(qe::compute_verdict(&statuses, qe::Severity::Warn), qe::domain::verify::Verdict::Rejected));
+}
+
+#[test]
+fn unknown_failed_codes_use_configured_default() {
+    let statuses = [status("future.code.added", false)];
+    assert!(matches!(qe::compute_verdict(&statuses, qe::Severity::Fail), qe::domain::verify::Verdict::Rejected));
+    assert!(matches!(qe::compute_verdict(&statuses, qe::Severity::Warn), qe::domain::verify::Verdict::Warning));
+    assert!(matches!(qe::compute_verdict(&statuses, qe::Severity::Pass), qe::domain::verify::Verdict::Allowed));
+}
+
+#[test]
+fn no_statuses_is_allowed() {
+    assert!(matches!(qe::compute_verdict(&[], qe::Severity::Fail), qe::domain::verify::Verdict::Allowed));
+}
+
+#[test]
+fn validation_code_round_trips_known_codes() {
+    let code = qe::ValidationCode::parse("assertion.hashedURI.mismatch");
+    assert_eq!(code, qe::ValidationCode::AssertionHashedUriMismatch);
+    assert_eq!(code.as_str(), "assertion.hashedURI.mismatch");
+    assert_eq!(code.severity(), Some(qe::Severity::Fail));
+
+    let parsed: qe::ValidationCode = "claimSignature.validated".parse().unwrap();
+    assert_eq!(parsed, qe::ValidationCode::ClaimSignatureValidated);
+}
+
+#[test]
+fn signer_name_policy_codes_are_classified() {
+    assert_eq!(code_severity("que.signerName.allowed"), Some(qe::Severity::Pass));
+    assert_eq!(code_severity("que.signerName.notAllowed"), Some(qe::Severity::Fail));
+    assert_eq!(qe::ValidationCode::parse("que.signerName.notAllowed"), qe::ValidationCode::SignerNameNotAllowed);
+}
+
+#[test]
+fn validation_code_keeps_unknown_codes() {
+    let code = qe::ValidationCode::parse("com.example.custom");
+    assert_eq!(code, qe::ValidationCode::Other("com.example.custom".into()));
+    assert_eq!(code.to_string(), "com.example.custom");
+    assert_eq!(code.severity(), None);
+}
+
+#[test]
+fn validation_code_serializes_as_string() {
+    let json = serde_json::to_value(qe::ValidationCode::SigningCredentialExpired).unwrap();
+    assert_eq!(json, serde_json::json!("signingCredential.expired"));
+}
+
+#[test]
+fn verdict_exit_codes_and_display() {
+    use qe::Verdict;
+    assert_eq!(Verdict::Allowed.exit_code(), 0);
+    assert_eq!(Verdict::Warning.exit_code(), 0);
+    assert_eq!(Verdict::Rejected.exit_code(), 1);
+    assert_eq!(Verdict::Warning.exit_code_with(2), 2);
+    assert_eq!(Verdict::Rejected.to_string(), "rejected");
+}
+
+#[test]
+fn verification_result_to_json_has_stable_keys() {
+    let data = common::make_test_jpeg_bytes();
+    let cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data });
+    if let Ok(result) = qe::verify_c2pa(cfg) {
+        let json = result.to_json();
+        for key in ["report", "verdict", "status", "certificates", "is_embedded", "remote_url", "soft_bindings"] {
+            assert!(json.get(key).is_some(), "missing key {key}");
+        }
+    }
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use que_engine as qe;
+
+fn sign_jpeg() -> Option<Vec<u8>> {
+    let cfg = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        common::setup_env_signer_vars().parse().unwrap(),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .build();
+    qe::sign_c2pa(cfg).ok().flatten()
+}
+
+fn bytes_config(data: Vec<u8>) -> qe::C2paVerificationConfig {
+    qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data })
+}
+
+/// Unsigned JPEGs, unreadable bytes and invalid settings fail in distinct ways, so
+/// each result shows which config it came from.
+fn mixed_config(i: usize) -> qe::C2paVerificationConfig {
+    match i % 3 {
+        0 => bytes_config(common::make_test_jpeg_bytes()),
+        1 => bytes_config(b"not an image".to_vec()),
+        _ => {
+            let mut cfg = bytes_config(common::make_test_jpeg_bytes());
+            cfg.raw_settings = Some(serde_json::json!(["not", "an", "object"]));
+            cfg
+        }
+    }
+}
+
+#[test]
+fn batch_results_follow_input_order() {
+    let results = qe::verify_c2pa_batch((0..9).map(mixed_config).collect());
+    assert_eq!(results.len(), 9);
+    for (i, result) in results.iter().enumerate() {
+        let expected = qe::verify_c2pa(mixed_config(i)).unwrap_err().to_string();
+        assert_eq!(result.as_ref().unwrap_err().to_string(), expected, "entry {i}");
+    }
+    assert!(matches!(results[2], Err(qe::EngineError::Config(_))));
+}
+
+#[test]
+fn batch_matches_single_verification() {
+    let Some(signed) = sign_jpeg() else {
+        return;
+    };
+
+    let single = qe::verify_c2pa(bytes_config(signed.clone())).expect("verified");
+    let batch = qe::verify_c2pa_batch(vec![bytes_config(signed)]);
+    let batched = batch.into_iter().next().unwrap().expect("verified");
+    assert_eq!(batched.verdict, single.verdict);
+    assert_eq!(batched.is_embedded, single.is_embedded);
+}
+
+#[test]
+fn empty_batch_returns_no_results() {
+    assert!(qe::verify_c2pa_batch(Vec::new()).is_empty());
+}
@@ -42,3 +42,88 @@ fn include_certificates_false_omits_cert_info() {
     }
 }
 
+
+#[test]
+fn include_certificates_covers_every_manifest_active_first() {
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer.clone(),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    let signed = match qe::sign_c2pa(cfg) {
+        Ok(Some(signed)) => signed,
+        Ok(None) => panic!("expected memory output"),
+        Err(_e) => return, // allow environments that cannot sign with self-signed cert
+    };
+    let countersigned = match qe::countersign(
+        qe::AssetRef::Bytes { data: signed },
+        signer,
+        qe::SigAlg::Es256,
+        None,
+        qe::OutputTarget::Memory,
+    ) {
+        Ok(Some(out)) => out,
+        Ok(None) => panic!("expected memory output"),
+        Err(_e) => return,
+    };
+
+    let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: countersigned });
+    cfg.include_certificates = Some(true);
+    let res = qe::verify_c2pa(cfg).expect("verify");
+    let certs = res.certificates.expect("certificates");
+    assert_eq!(certs.len(), 2);
+
+    let report: serde_json::Value = serde_json::from_str(&res.report).expect("report json");
+    assert_eq!(certs[0].manifest_label.as_deref(), report["active_manifest"].as_str());
+    assert_ne!(certs[0].manifest_label, certs[1].manifest_label);
+}
+
+fn self_signed_pem(name: &str) -> String {
+    rcgen::generate_simple_self_signed(vec![name.to_string()]).unwrap().serialize_pem().unwrap()
+}
+
+#[test]
+fn write_chain_splits_pem_into_one_file_per_certificate() {
+    let chain = format!("{}{}", self_signed_pem("leaf.example"), self_signed_pem("ca.example"));
+    let info = qe::CertInfo { chain_pem: Some(chain), ..Default::default() };
+    let dir = tempfile::tempdir().unwrap();
+
+    let paths = info.write_chain(&dir.path().join("chain")).expect("write chain");
+    assert_eq!(paths.len(), 2);
+    assert!(paths[0].ends_with("cert_0.pem"));
+    assert!(paths[1].ends_with("cert_1.pem"));
+    for path in &paths {
+        let pem = std::fs::read_to_string(path).unwrap();
+        assert_eq!(pem.matches("-----BEGIN CERTIFICATE-----").count(), 1);
+    }
+}
+
+#[test]
+fn write_chain_rejects_malformed_pem() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let missing = qe::CertInfo::default();
+    assert!(matches!(missing.write_chain(dir.path()), Err(qe::EngineError::Config(_))));
+
+    let not_base64 = qe::CertInfo {
+        chain_pem: Some("-----BEGIN CERTIFICATE-----\n!!!!\n-----END CERTIFICATE-----\n".into()),
+        ..Default::default()
+    };
+    assert!(matches!(not_base64.write_chain(dir.path()), Err(qe::EngineError::Config(_))));
+
+    let not_a_cert = qe::CertInfo {
+        chain_pem: Some("-----BEGIN CERTIFICATE-----\nAAAA\n-----END CERTIFICATE-----\n".into()),
+        ..Default::default()
+    };
+    assert!(matches!(not_a_cert.write_chain(dir.path()), Err(qe::EngineError::Config(_))));
+
+    let truncated = qe::CertInfo {
+        chain_pem: Some(self_signed_pem("leaf.example").replace("-----END CERTIFICATE-----", "")),
+        ..Default::default()
+    };
+    assert!(matches!(truncated.write_chain(dir.path()), Err(qe::EngineError::Config(_))));
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+}
@@ -20,5 +20,34 @@ fn verify_modes_affect_report_format() {
     }
 }
 
+#[test]
+fn provenance_summary_lists_edit_actions() {
+    let manifest = qe::ManifestBuilder::new()
+        .title("edited")
+        .format("image/jpeg")
+        .add_action(qe::Action::new("c2pa.created").software_agent("que-engine-tests"))
+        .add_action(qe::Action::new("c2pa.resized"))
+        .add_action(qe::Action::new("c2pa.color_adjustments"))
+        .add_action(qe::Action::new("c2pa.resized"));
+
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer,
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_source = Some(manifest.into());
 
+    let signed = match qe::sign_c2pa(cfg) {
+        Ok(Some(signed)) => signed,
+        Ok(None) => panic!("expected memory output"),
+        Err(_e) => return, // allow environments that cannot sign with self-signed cert
+    };
 
+    let res = qe::verify_c2pa(qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed }))
+        .expect("verify");
+    let summary = res.provenance_summary.expect("provenance summary");
+    assert_eq!(summary.edit_actions, vec!["c2pa.resized", "c2pa.color_adjustments"]);
+    assert!(summary.generator.is_some());
+}
@@ -40,3 +40,92 @@ fn verdict_is_mapped_from_statuses() {
     }
 }
 
+
+#[test]
+fn remote_manifest_is_not_fetched_unless_allowed() {
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        common::setup_env_signer_vars().parse().unwrap(),
+        qe::SigAlg::Es256,
+    );
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    cfg.remote_manifest_url = Some("https://example.invalid/manifest.c2pa".into());
+    cfg.embed = false;
+
+    let signed = match qe::sign_c2pa(cfg) {
+        Ok(Some(signed)) => signed,
+        Ok(None) => panic!("expected memory output"),
+        Err(_e) => return,
+    };
+
+    // The asset only points at its manifest; with remote manifests off nothing is fetched.
+    let res = qe::verify_c2pa(qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed }));
+    let err = res.expect_err("remote manifest should not be fetched");
+    assert!(err.to_string().contains("example.invalid"), "unexpected error: {err}");
+}
+
+#[test]
+fn manifest_location_tells_embedded_from_remote_references() {
+    let chain = common::generate_es256_chain();
+    let sign = |remote_url: Option<&str>| {
+        let mut builder = qe::C2paConfig::builder(
+            qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+            qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+            qe::SigAlg::Es256,
+        )
+        .manifest(common::minimal_manifest_def("image/jpeg"))
+        .intermediates(chain.intermediate_pem.clone().into_bytes());
+        if let Some(url) = remote_url {
+            builder = builder.remote_manifest_url(url, true);
+        }
+        qe::sign_c2pa(builder.build()).ok().flatten()
+    };
+    let location = |signed: Vec<u8>| {
+        let cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: signed });
+        qe::verify_c2pa(cfg).unwrap().manifest_location
+    };
+
+    let Some(embedded) = sign(None) else {
+        return; // allow environments that cannot sign
+    };
+    assert_eq!(location(embedded), Some(qe::ManifestLocation::Embedded));
+
+    let url = "https://example.com/manifests/asset.c2pa";
+    let referenced = sign(Some(url)).expect("sign with a remote reference");
+    let streamed = qe::AssetRef::Stream {
+        reader: std::cell::RefCell::new(Box::new(std::io::Cursor::new(referenced.clone()))),
+        content_type: Some("image/jpeg".into()),
+    };
+    assert_eq!(location(referenced), Some(qe::ManifestLocation::EmbeddedWithRemoteRef(url.into())));
+    let result = qe::verify_c2pa(qe::C2paVerificationConfig::secure_default(streamed)).unwrap();
+    assert_eq!(result.manifest_location, Some(qe::ManifestLocation::EmbeddedWithRemoteRef(url.into())));
+    assert_eq!(result.is_embedded, Some(true));
+}
+
+#[test]
+fn extensionless_path_verifies_like_bytes() {
+    let chain = common::generate_es256_chain();
+    let cfg = qe::C2paConfig::builder(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        qe::Signer::from_pem(chain.leaf_pem.as_bytes(), chain.key_pem.as_bytes()),
+        qe::SigAlg::Es256,
+    )
+    .manifest(common::minimal_manifest_def("image/jpeg"))
+    .intermediates(chain.intermediate_pem.clone().into_bytes())
+    .build();
+    let Ok(Some(signed)) = qe::sign_c2pa(cfg) else {
+        return; // allow environments that cannot sign
+    };
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("asset");
+    std::fs::write(&path, &signed).unwrap();
+
+    let verify = |source| qe::verify_c2pa(qe::C2paVerificationConfig::secure_default(source)).unwrap();
+    let from_path = verify(qe::AssetRef::Path(path));
+    let from_bytes = verify(qe::AssetRef::Bytes { data: signed });
+    assert!(from_path.verdict.is_some());
+    assert_eq!(from_path.verdict, from_bytes.verdict);
+    assert_eq!(from_path.report, from_bytes.report);
+    assert_eq!(from_path.manifest_location, Some(qe::ManifestLocation::Embedded));
+}
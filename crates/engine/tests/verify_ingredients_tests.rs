@@ -57,3 +57,47 @@ fn ingredients_in_unsigned_asset_yield_no_status_but_report_prints() {
     }
 }
 
+
+/// Signs the test JPEG in memory, with `parent` (a signed asset on disk) as its parentOf ingredient.
+fn sign_with_parent(parent: Option<std::path::PathBuf>) -> Option<Vec<u8>> {
+    let signer: qe::Signer = common::setup_env_signer_vars().parse().unwrap();
+    let mut cfg = qe::C2paConfig::secure_default(
+        qe::AssetRef::Bytes { data: common::make_test_jpeg_bytes() },
+        signer,
+        qe::SigAlg::Es256,
+    );
+    cfg.manifest_definition = Some(common::minimal_manifest_def("image/jpeg"));
+    cfg.output = qe::OutputTarget::Memory;
+    cfg.parent = parent.map(qe::AssetRef::Path);
+    qe::sign_c2pa(cfg).ok().flatten() // self-signed certs may be rejected
+}
+
+#[test]
+fn ingredient_count_and_depth_limits_are_enforced() {
+    let dir = tempfile::tempdir().unwrap();
+    let Some(first) = sign_with_parent(None) else { return };
+    let first_path = dir.path().join("first.jpg");
+    std::fs::write(&first_path, first).unwrap();
+    let Some(second) = sign_with_parent(Some(first_path)) else { return };
+    let second_path = dir.path().join("second.jpg");
+    std::fs::write(&second_path, second).unwrap();
+    // third -> second -> first: two ingredients, nested two deep.
+    let Some(third) = sign_with_parent(Some(second_path)) else { return };
+
+    let verify = |max_ingredients, max_ingredient_depth| {
+        let mut cfg = qe::C2paVerificationConfig::secure_default(qe::AssetRef::Bytes { data: third.clone() });
+        cfg.limits.max_ingredients = max_ingredients;
+        cfg.limits.max_ingredient_depth = max_ingredient_depth;
+        qe::verify_c2pa(cfg)
+    };
+
+    assert!(verify(2, 2).is_ok());
+    match verify(1, 2) {
+        Err(qe::EngineError::Config(msg)) => assert!(msg.contains("max_ingredients"), "{msg}"),
+        other => panic!("expected ingredient count error, got {other:?}"),
+    }
+    match verify(2, 1) {
+        Err(qe::EngineError::Config(msg)) => assert!(msg.contains("max_ingredient_depth"), "{msg}"),
+        other => panic!("expected ingredient depth error, got {other:?}"),
+    }
+}
@@ -1,4 +1,8 @@
+use std::cell::RefCell;
+use std::io::{Read, Seek};
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use que_engine::crypto::signer::Signer;
 use que_engine::crypto::timestamper::Timestamper;
@@ -8,16 +12,60 @@ use que_engine::{sign_c2pa, verify_c2pa, create_ingredient};
 
 // (Keep VerifyOptions defined once later for deprecated helper)
 
+/// Errors returned across the FFI boundary, grouped by how a host app should react.
+/// `message` is the engine's English description, for logs rather than end users.
 #[derive(Debug, thiserror::Error, uniffi::Error)]
 pub enum FfiError {
+    /// The request itself is wrong: bad configuration, signer URI, JSON, or a missing file.
     #[error("{message}")]
-    Generic { message: String },
+    InvalidInput { message: String },
+    /// The format or operation is not supported, or needs a feature this build lacks.
+    #[error("{message}")]
+    Unsupported { message: String },
+    /// An input or result exceeded a `FfiLimitsConfig` bound.
+    #[error("{message}")]
+    TooLarge { size: u64, limit: u64, message: String },
+    /// Certificate or signature problems: expired, untrusted or malformed credentials,
+    /// or an asset that failed verification.
+    #[error("{message}")]
+    TrustFailure { message: String },
+    /// A URL was refused (scheme, private/loopback host) or a remote read timed out.
+    #[error("{message}")]
+    NetworkBlocked { message: String },
+    /// The call was cancelled before it finished.
+    #[error("{message}")]
+    Cancelled { message: String },
+    /// Anything else, including I/O failures and caught panics.
+    #[error("{message}")]
+    Internal { message: String },
+}
+
+impl FfiError {
+    fn invalid_input(message: impl Into<String>) -> Self {
+        FfiError::InvalidInput { message: message.into() }
+    }
 }
 
 impl From<EngineError> for FfiError {
     fn from(e: EngineError) -> Self {
-        FfiError::Generic {
-            message: e.to_string(),
+        let message = e.to_string();
+        if e.is_trust_failure() {
+            return FfiError::TrustFailure { message };
+        }
+        match e {
+            EngineError::AssetTooLarge { size, limit }
+            | EngineError::OutputTooLarge { size, limit }
+            | EngineError::ManifestTooLarge { size, limit } => {
+                FfiError::TooLarge { size, limit, message }
+            }
+            EngineError::UnsupportedFormat(_) | EngineError::Feature(_) => FfiError::Unsupported { message },
+            EngineError::InvalidUrl(_) | EngineError::StreamTimeout => FfiError::NetworkBlocked { message },
+            EngineError::Cancelled => FfiError::Cancelled { message },
+            EngineError::Config(_) | EngineError::Json(_) => FfiError::InvalidInput { message },
+            EngineError::Io(io) if matches!(io.kind(), std::io::ErrorKind::NotFound | std::io::ErrorKind::PermissionDenied) => {
+                FfiError::InvalidInput { message }
+            }
+            _ => FfiError::Internal { message },
         }
     }
 }
@@ -33,6 +81,21 @@ impl From<FfiSigAlg> for dt::SigAlg {
     }
 }
 
+impl From<dt::SigAlg> for FfiSigAlg {
+    fn from(v: dt::SigAlg) -> Self {
+        match v { dt::SigAlg::Es256 => FfiSigAlg::Es256, dt::SigAlg::Es384 => FfiSigAlg::Es384, dt::SigAlg::Ps256 => FfiSigAlg::Ps256, dt::SigAlg::Ed25519 => FfiSigAlg::Ed25519 }
+    }
+}
+
+#[derive(uniffi::Enum, Debug, Clone, Copy)]
+pub enum FfiHashAlg { Sha256, Sha384, Sha512 }
+
+impl From<FfiHashAlg> for dt::HashAlg {
+    fn from(v: FfiHashAlg) -> Self {
+        match v { FfiHashAlg::Sha256 => dt::HashAlg::Sha256, FfiHashAlg::Sha384 => dt::HashAlg::Sha384, FfiHashAlg::Sha512 => dt::HashAlg::Sha512 }
+    }
+}
+
 #[derive(uniffi::Enum, Debug, Clone, Copy)]
 pub enum FfiVerifyMode { Summary, Info, Detailed, Tree }
 
@@ -42,18 +105,102 @@ impl From<FfiVerifyMode> for dt::VerifyMode {
     }
 }
 
-#[derive(uniffi::Enum, Debug, Clone)]
-pub enum FfiAssetRef { Path(String), Bytes(Vec<u8>) }
+#[derive(uniffi::Enum, Clone)]
+pub enum FfiAssetRef {
+    Path(String),
+    Bytes(Vec<u8>),
+    Url(String),
+    /// Host-provided seekable stream, read on demand instead of copied into memory.
+    Stream { reader: Arc<dyn FfiStreamReader>, content_type: Option<String> },
+}
 
-impl From<FfiAssetRef> for dt::AssetRef {
-    fn from(v: FfiAssetRef) -> Self {
-        match v {
-            FfiAssetRef::Path(p) => dt::AssetRef::Path(PathBuf::from(p)),
-            FfiAssetRef::Bytes(b) => dt::AssetRef::Bytes { data: b },
+impl std::fmt::Debug for FfiAssetRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FfiAssetRef::Path(p) => f.debug_tuple("Path").field(p).finish(),
+            FfiAssetRef::Bytes(b) => f.debug_tuple("Bytes").field(&format_args!("{} bytes", b.len())).finish(),
+            FfiAssetRef::Url(u) => f.debug_tuple("Url").field(u).finish(),
+            FfiAssetRef::Stream { content_type, .. } => f.debug_struct("Stream").field("content_type", content_type).finish_non_exhaustive(),
         }
     }
 }
 
+/// Seek target for [`FfiStreamReader::seek`], mirroring `std::io::SeekFrom`.
+#[derive(uniffi::Enum, Debug, Clone, Copy)]
+pub enum FfiSeekFrom { Start(u64), Current(i64), End(i64) }
+
+impl From<std::io::SeekFrom> for FfiSeekFrom {
+    fn from(v: std::io::SeekFrom) -> Self {
+        match v { std::io::SeekFrom::Start(n) => FfiSeekFrom::Start(n), std::io::SeekFrom::Current(n) => FfiSeekFrom::Current(n), std::io::SeekFrom::End(n) => FfiSeekFrom::End(n) }
+    }
+}
+
+/// Seekable byte source implemented by the host (e.g. over a file handle or `PHAsset`),
+/// so large media can be signed or verified without loading it into memory.
+///
+/// Calls arrive on the engine's thread, one at a time.
+#[uniffi::export(with_foreign)]
+pub trait FfiStreamReader: Send + Sync {
+    /// Return up to `max` bytes from the current position; an empty result means end of stream.
+    fn read(&self, max: u64) -> Result<Vec<u8>, FfiError>;
+    /// Move the position and return the new offset from the start of the stream.
+    fn seek(&self, pos: FfiSeekFrom) -> Result<u64, FfiError>;
+}
+
+impl From<uniffi::UnexpectedUniFFICallbackError> for FfiError {
+    fn from(e: uniffi::UnexpectedUniFFICallbackError) -> Self {
+        FfiError::Internal { message: e.reason }
+    }
+}
+
+/// Adapts a host stream into the engine's `StreamReader`, applying the
+/// per-read timeout from `LimitsConfig::max_stream_read_timeout_secs`.
+struct HostStream {
+    host: Arc<dyn FfiStreamReader>,
+    read_timeout: Duration,
+}
+
+impl Read for HostStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let started = Instant::now();
+        let chunk = self.host.read(buf.len() as u64).map_err(std::io::Error::other)?;
+        if started.elapsed() > self.read_timeout {
+            return Err(std::io::Error::new(std::io::ErrorKind::TimedOut, "host stream read timed out"));
+        }
+        if chunk.len() > buf.len() {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "host stream returned more bytes than requested"));
+        }
+        buf[..chunk.len()].copy_from_slice(&chunk);
+        Ok(chunk.len())
+    }
+}
+
+impl Seek for HostStream {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.host.seek(pos.into()).map_err(std::io::Error::other)
+    }
+}
+
+/// Convert an FFI asset, bounding host streams by `limits.max_stream_copy_size`
+/// up front since the engine reads them directly rather than through a copy.
+fn asset_ref(v: FfiAssetRef, limits: dt::LimitsConfig) -> Result<dt::AssetRef, FfiError> {
+    Ok(match v {
+        FfiAssetRef::Path(p) => dt::AssetRef::Path(PathBuf::from(p)),
+        FfiAssetRef::Bytes(b) => dt::AssetRef::Bytes { data: b },
+        FfiAssetRef::Url(u) => dt::AssetRef::Url(u),
+        FfiAssetRef::Stream { reader, content_type } => {
+            let size = reader.seek(FfiSeekFrom::End(0))?;
+            reader.seek(FfiSeekFrom::Start(0))?;
+            let limit = limits.max_stream_copy_size as u64;
+            if size > limit {
+                return Err(EngineError::AssetTooLarge { size, limit }.into());
+            }
+            let stream = HostStream { host: reader, read_timeout: Duration::from_secs(limits.max_stream_read_timeout_secs) };
+            dt::AssetRef::Stream { reader: RefCell::new(Box::new(stream)), content_type }
+        }
+    })
+}
+
 #[derive(uniffi::Enum, Debug, Clone)]
 pub enum FfiOutputTarget { Path(String), Memory }
 
@@ -63,12 +210,33 @@ impl From<FfiOutputTarget> for dt::OutputTarget {
     }
 }
 
+/// An extra HTTP request header, such as `Authorization` for a TSA.
+#[derive(uniffi::Record, Clone)]
+pub struct FfiHttpHeader {
+    pub name: String,
+    pub value: String,
+}
+
+impl std::fmt::Debug for FfiHttpHeader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Header values are often credentials.
+        f.debug_struct("FfiHttpHeader").field("name", &self.name).finish_non_exhaustive()
+    }
+}
+
 #[derive(uniffi::Enum, Debug, Clone)]
-pub enum FfiTimestamper { Digicert, Custom(String) }
+pub enum FfiTimestamper { Digicert, Custom(String), CustomWithHeaders { url: String, headers: Vec<FfiHttpHeader> } }
 
 impl From<FfiTimestamper> for Timestamper {
     fn from(v: FfiTimestamper) -> Self {
-        match v { FfiTimestamper::Digicert => Timestamper::Digicert, FfiTimestamper::Custom(u) => Timestamper::Custom(u) }
+        match v {
+            FfiTimestamper::Digicert => Timestamper::Digicert,
+            FfiTimestamper::Custom(u) => Timestamper::Custom(u),
+            FfiTimestamper::CustomWithHeaders { url, headers } => Timestamper::CustomWithHeaders {
+                url,
+                headers: headers.into_iter().map(|h| (h.name, h.value)).collect(),
+            },
+        }
     }
 }
 
@@ -78,6 +246,9 @@ pub struct FfiLimitsConfig {
     pub max_in_memory_output_size: u64,
     pub max_stream_copy_size: u64,
     pub max_stream_read_timeout_secs: u64,
+    pub max_ingredients: u64,
+    pub max_ingredient_depth: u64,
+    pub max_manifest_size: u64,
 }
 
 impl From<FfiLimitsConfig> for dt::LimitsConfig {
@@ -87,18 +258,24 @@ impl From<FfiLimitsConfig> for dt::LimitsConfig {
             max_in_memory_output_size: v.max_in_memory_output_size as usize,
             max_stream_copy_size: v.max_stream_copy_size as usize,
             max_stream_read_timeout_secs: v.max_stream_read_timeout_secs,
+            max_ingredients: v.max_ingredients as usize,
+            max_ingredient_depth: v.max_ingredient_depth as usize,
+            max_manifest_size: v.max_manifest_size as usize,
         }
     }
 }
 
 impl FfiLimitsConfig {
     pub fn defaults() -> Self {
-        let d = dt::LimitsConfig::defaults();
+        let d = dt::default_limits();
         Self {
             max_in_memory_asset_size: d.max_in_memory_asset_size as u64,
             max_in_memory_output_size: d.max_in_memory_output_size as u64,
             max_stream_copy_size: d.max_stream_copy_size as u64,
             max_stream_read_timeout_secs: d.max_stream_read_timeout_secs,
+            max_ingredients: d.max_ingredients as u64,
+            max_ingredient_depth: d.max_ingredient_depth as u64,
+            max_manifest_size: d.max_manifest_size as u64,
         }
     }
 }
@@ -109,11 +286,15 @@ pub struct FfiTrustPolicyConfig {
     pub allowed_list: Option<Vec<u8>>,     // raw allowed-list
     pub allowed_ekus: Option<Vec<String>>, // EKU OIDs
     pub verify_identity_trust: Option<bool>,
+    pub allowed_subjects: Option<Vec<String>>, // signer CN, O or full DN
+    pub allowed_issuers: Option<Vec<String>>,  // signer's issuer CN, O or full DN
+    pub use_system_trust: Option<bool>,        // add OS root certificates to anchors
+    pub intermediates: Option<Vec<u8>>,        // PEM intermediates that chain to the anchors
 }
 
 impl From<FfiTrustPolicyConfig> for dt::TrustPolicyConfig {
     fn from(v: FfiTrustPolicyConfig) -> Self {
-        dt::TrustPolicyConfig { anchors: v.anchors, allowed_list: v.allowed_list, allowed_ekus: v.allowed_ekus, verify_identity_trust: v.verify_identity_trust }
+        dt::TrustPolicyConfig { anchors: v.anchors, allowed_list: v.allowed_list, allowed_ekus: v.allowed_ekus, verify_identity_trust: v.verify_identity_trust, allowed_subjects: v.allowed_subjects, allowed_issuers: v.allowed_issuers, intermediates: v.intermediates, use_system_trust: v.use_system_trust }
     }
 }
 
@@ -126,40 +307,153 @@ pub struct FfiC2paConfig {
     pub parent_base_dir: Option<String>,
     pub signer_uri: String,
     pub signing_alg: FfiSigAlg,
+    /// Additional signers applied in the same pass as CAWG X.509 identity
+    /// assertions (requires the `cawg` feature).
+    pub co_signers: Vec<FfiCoSigner>,
+    /// PEM intermediate certificates appended to the signer's chain.
+    pub intermediates: Option<Vec<u8>>,
+    pub hash_alg: Option<FfiHashAlg>,
     pub timestamper: Option<FfiTimestamper>,
+    /// Bytes reserved for the signature box; raise when signing fails with
+    /// "COSE Signature too big for JUMBF box". `None` keeps the signer's estimate.
+    pub reserve_size: Option<u64>,
     pub remote_manifest_url: Option<String>,
     pub embed: bool,
+    /// HEAD-check `remote_manifest_url` before signing (`remote_manifests` feature).
+    pub verify_remote_url_reachable: bool,
     pub trust_policy: Option<FfiTrustPolicyConfig>,
     pub skip_post_sign_validation: bool,
+    /// Validation after signing; `skip_post_sign_validation` overrides it.
+    pub post_sign: FfiPostSignMode,
     pub allow_insecure_remote_http: Option<bool>,
     pub limits: FfiLimitsConfig,
+    /// Directory for temporary files; `None` uses the system temp directory.
+    pub temp_dir: Option<String>,
+    pub redactions: Option<Vec<String>>,
+    pub auto_thumbnail: Option<FfiThumbnailConfig>,
+    pub claim_generator: Option<FfiClaimGeneratorInfo>,
+    pub validate_manifest: bool,
+    /// Reject a source whose declared content type conflicts with its bytes.
+    pub check_declared_format: bool,
+    /// Format to sign a stream source as when it has no content type and can't be sniffed.
+    pub default_format: Option<String>,
+    /// Formats (MIME types or extensions) to accept; others fail with `UnsupportedFormat`.
+    pub allowed_formats: Option<Vec<String>>,
+    /// Raw c2pa settings JSON object, applied after the engine's own settings.
+    pub raw_settings_json: Option<String>,
+    /// CAWG identity assertion to add while signing.
+    #[cfg(feature = "cawg")]
+    pub cawg_identity: Option<FfiCawgIdentity>,
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiCoSigner {
+    pub signer_uri: String,
+    pub signing_alg: FfiSigAlg,
+}
+
+impl TryFrom<FfiCoSigner> for (Signer, dt::SigAlg) {
+    type Error = FfiError;
+    fn try_from(v: FfiCoSigner) -> Result<Self, Self::Error> {
+        let signer: Signer = v.signer_uri.parse().map_err(|e| FfiError::invalid_input(format!("Invalid co-signer: {e}")))?;
+        Ok((signer, v.signing_alg.into()))
+    }
+}
+
+#[derive(uniffi::Enum, Debug, Clone)]
+pub enum FfiThumbnailFormat { Jpeg, Png }
+
+impl From<FfiThumbnailFormat> for dt::ThumbnailFormat {
+    fn from(v: FfiThumbnailFormat) -> Self {
+        match v { FfiThumbnailFormat::Jpeg => dt::ThumbnailFormat::Jpeg, FfiThumbnailFormat::Png => dt::ThumbnailFormat::Png }
+    }
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiThumbnailConfig {
+    pub max_dimension: u32,
+    pub format: FfiThumbnailFormat,
+}
+
+impl From<FfiThumbnailConfig> for dt::ThumbnailConfig {
+    fn from(v: FfiThumbnailConfig) -> Self {
+        dt::ThumbnailConfig { max_dimension: v.max_dimension, format: v.format.into() }
+    }
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiClaimGeneratorIcon {
+    /// MIME type of `data`, e.g. `image/png`.
+    pub format: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiClaimGeneratorInfo {
+    pub name: String,
+    pub version: Option<String>,
+    pub icon: Option<FfiClaimGeneratorIcon>,
+}
+
+impl From<FfiClaimGeneratorInfo> for dt::ClaimGeneratorInfo {
+    fn from(v: FfiClaimGeneratorInfo) -> Self {
+        dt::ClaimGeneratorInfo { name: v.name, version: v.version, icon: v.icon.map(|i| dt::ClaimGeneratorIcon { format: i.format, data: i.data }) }
+    }
 }
 
 impl TryFrom<FfiC2paConfig> for dt::C2paConfig {
     type Error = FfiError;
     fn try_from(v: FfiC2paConfig) -> Result<Self, Self::Error> {
-        let signer: Signer = v.signer_uri.parse().map_err(|e| FfiError::Generic { message: format!("Invalid signer: {e}") })?;
+        let signer: Signer = v.signer_uri.parse().map_err(|e| FfiError::invalid_input(format!("Invalid signer: {e}")))?;
+        let raw_settings = parse_raw_settings(v.raw_settings_json)?;
+        let limits: dt::LimitsConfig = v.limits.into();
         Ok(dt::C2paConfig {
-            source: v.source.into(),
+            source: asset_ref(v.source, limits)?,
             output: v.output.into(),
             manifest_definition: v.manifest_definition,
-            parent: v.parent.map(Into::into),
+            manifest_source: None,
+            validate_manifest: v.validate_manifest,
+            check_declared_format: v.check_declared_format,
+            default_format: v.default_format,
+            allowed_formats: v.allowed_formats,
+            parent: v.parent.map(|p| asset_ref(p, limits)).transpose()?,
             parent_base_dir: v.parent_base_dir.map(PathBuf::from),
             signer,
             signing_alg: v.signing_alg.into(),
+            co_signers: v.co_signers.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            intermediates: v.intermediates,
+            hash_alg: v.hash_alg.map(Into::into),
             timestamper: v.timestamper.map(Into::into),
+            reserve_size: v.reserve_size.map(|n| n as usize),
             remote_manifest_url: v.remote_manifest_url,
             embed: v.embed,
+            verify_remote_url_reachable: v.verify_remote_url_reachable,
             trust_policy: v.trust_policy.map(Into::into),
             skip_post_sign_validation: v.skip_post_sign_validation,
+            post_sign: v.post_sign.into(),
             allow_insecure_remote_http: v.allow_insecure_remote_http,
-            limits: v.limits.into(),
+            limits,
+            temp_dir: v.temp_dir.map(PathBuf::from),
+            redactions: v.redactions,
+            soft_binding: None,
+            fingerprint: None,
+            auto_thumbnail: v.auto_thumbnail.map(Into::into),
+            claim_generator: v.claim_generator.map(Into::into),
+            raw_settings,
+            progress: None,
+            cancel: None,
+            deterministic: None,
             #[cfg(feature = "cawg")]
-            cawg_identity: None,
+            cawg_identity: v.cawg_identity.map(TryInto::try_into).transpose()?,
         })
     }
 }
 
+fn parse_raw_settings(json: Option<String>) -> Result<Option<serde_json::Value>, FfiError> {
+    json.map(|s| serde_json::from_str(&s).map_err(|e| FfiError::invalid_input(format!("Invalid raw settings: {e}"))))
+        .transpose()
+}
+
 #[derive(uniffi::Record, Debug, Clone)]
 pub struct FfiC2paVerificationConfig {
     pub source: FfiAssetRef,
@@ -168,20 +462,76 @@ pub struct FfiC2paVerificationConfig {
     pub allow_remote_manifests: bool,
     pub include_certificates: Option<bool>,
     pub limits: FfiLimitsConfig,
+    /// Raw c2pa settings JSON object, applied after the engine's own settings.
+    pub raw_settings_json: Option<String>,
+    /// Severity for failed status codes the engine does not recognise.
+    pub unknown_status_severity: FfiSeverity,
+    /// MIME type to read the source as instead of sniffing it.
+    pub format_override: Option<String>,
+    /// Reject a source whose declared format conflicts with its bytes.
+    pub check_declared_format: bool,
+    /// Formats (MIME types or extensions) to accept; others fail with `UnsupportedFormat`.
+    pub allowed_formats: Option<Vec<String>>,
+    /// Manifest store URL to verify the source against (requires `remote_manifests`).
+    pub remote_manifest: Option<String>,
+    /// Return c2pa's validation results verbatim as `raw_validation_results_json`.
+    pub include_raw_validation_results: bool,
+    /// SHA-256 fingerprint of the certificate expected to sign the asset.
+    pub expected_signer_fingerprint: Option<String>,
+    /// CAWG identity validation; `None` skips it.
+    #[cfg(feature = "cawg")]
+    pub cawg: Option<FfiCawgVerifyOptions>,
+}
+
+#[derive(uniffi::Enum, Debug, Clone, Copy)]
+pub enum FfiSeverity { Pass, Warn, Fail }
+
+#[derive(uniffi::Enum, Debug, Clone, Copy)]
+pub enum FfiPostSignMode { Skip, ValidateStrict, ValidateReport }
+
+impl From<FfiPostSignMode> for dt::PostSignMode {
+    fn from(v: FfiPostSignMode) -> Self {
+        match v { FfiPostSignMode::Skip => dt::PostSignMode::Skip, FfiPostSignMode::ValidateStrict => dt::PostSignMode::ValidateStrict, FfiPostSignMode::ValidateReport => dt::PostSignMode::ValidateReport }
+    }
+}
+
+impl From<FfiSeverity> for que_engine::Severity {
+    fn from(v: FfiSeverity) -> Self {
+        match v { FfiSeverity::Pass => que_engine::Severity::Pass, FfiSeverity::Warn => que_engine::Severity::Warn, FfiSeverity::Fail => que_engine::Severity::Fail }
+    }
 }
 
-impl From<FfiC2paVerificationConfig> for dt::C2paVerificationConfig {
-    fn from(v: FfiC2paVerificationConfig) -> Self {
-        dt::C2paVerificationConfig {
-            source: v.source.into(),
+impl From<que_engine::Severity> for FfiSeverity {
+    fn from(v: que_engine::Severity) -> Self {
+        match v { que_engine::Severity::Pass => FfiSeverity::Pass, que_engine::Severity::Warn => FfiSeverity::Warn, que_engine::Severity::Fail => FfiSeverity::Fail }
+    }
+}
+
+impl TryFrom<FfiC2paVerificationConfig> for dt::C2paVerificationConfig {
+    type Error = FfiError;
+    fn try_from(v: FfiC2paVerificationConfig) -> Result<Self, Self::Error> {
+        let limits: dt::LimitsConfig = v.limits.into();
+        Ok(dt::C2paVerificationConfig {
+            source: asset_ref(v.source, limits)?,
             mode: v.mode.into(),
             policy: v.policy.map(Into::into),
             allow_remote_manifests: v.allow_remote_manifests,
             include_certificates: v.include_certificates,
-            limits: v.limits.into(),
+            limits,
+            soft_binding: None,
+            raw_settings: parse_raw_settings(v.raw_settings_json)?,
+            unknown_status_severity: v.unknown_status_severity.into(),
+            progress: None,
+            cancel: None,
+            format_override: v.format_override,
+            check_declared_format: v.check_declared_format,
+            allowed_formats: v.allowed_formats,
+            remote_manifest: v.remote_manifest,
+            include_raw_validation_results: v.include_raw_validation_results,
+            expected_signer_fingerprint: v.expected_signer_fingerprint,
             #[cfg(feature = "cawg")]
-            cawg: None,
-        }
+            cawg: v.cawg.map(Into::into),
+        })
     }
 }
 
@@ -190,11 +540,50 @@ pub struct FfiIngredientConfig {
     pub source: FfiAssetRef,
     pub output: FfiOutputTarget,
     pub limits: FfiLimitsConfig,
+    /// Directory for temporary files; `None` uses the system temp directory.
+    pub temp_dir: Option<String>,
+    pub title: Option<String>,
+    pub relationship: Option<FfiIngredientRelationship>,
+    /// JSON object merged into the ingredient's `metadata`.
+    pub metadata_json: Option<String>,
+    /// Produce JSON that can be passed back as `FfiC2paConfig.parent` bytes.
+    pub parent_ready: bool,
 }
 
-impl From<FfiIngredientConfig> for dt::IngredientConfig {
-    fn from(v: FfiIngredientConfig) -> Self {
-        dt::IngredientConfig { source: v.source.into(), output: v.output.into(), limits: v.limits.into() }
+impl TryFrom<FfiIngredientConfig> for dt::IngredientConfig {
+    type Error = FfiError;
+    fn try_from(v: FfiIngredientConfig) -> Result<Self, Self::Error> {
+        let metadata = v.metadata_json
+            .map(|json| serde_json::from_str(&json).map_err(|e| FfiError::invalid_input(format!("Invalid ingredient metadata: {e}"))))
+            .transpose()?;
+        let limits: dt::LimitsConfig = v.limits.into();
+        Ok(dt::IngredientConfig { source: asset_ref(v.source, limits)?, output: v.output.into(), limits, temp_dir: v.temp_dir.map(PathBuf::from), title: v.title, relationship: v.relationship.map(Into::into), metadata, parent_ready: v.parent_ready })
+    }
+}
+
+#[derive(uniffi::Enum, Debug, Clone, Copy)]
+pub enum FfiIngredientRelationship { ParentOf, ComponentOf, InputTo }
+
+impl From<FfiIngredientRelationship> for que_engine::IngredientRelationship {
+    fn from(v: FfiIngredientRelationship) -> Self {
+        match v { FfiIngredientRelationship::ParentOf => que_engine::IngredientRelationship::ParentOf, FfiIngredientRelationship::ComponentOf => que_engine::IngredientRelationship::ComponentOf, FfiIngredientRelationship::InputTo => que_engine::IngredientRelationship::InputTo }
+    }
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiRemoveManifestConfig {
+    pub source: FfiAssetRef,
+    pub output: FfiOutputTarget,
+    pub limits: FfiLimitsConfig,
+    /// Directory for temporary files; `None` uses the system temp directory.
+    pub temp_dir: Option<String>,
+}
+
+impl TryFrom<FfiRemoveManifestConfig> for dt::RemoveManifestConfig {
+    type Error = FfiError;
+    fn try_from(v: FfiRemoveManifestConfig) -> Result<Self, Self::Error> {
+        let limits: dt::LimitsConfig = v.limits.into();
+        Ok(dt::RemoveManifestConfig { source: asset_ref(v.source, limits)?, output: v.output.into(), limits, temp_dir: v.temp_dir.map(PathBuf::from) })
     }
 }
 
@@ -212,12 +601,53 @@ pub struct FfiFragmentedBmffConfig {
     pub skip_post_sign_validation: bool,
     pub allow_insecure_remote_http: Option<bool>,
     pub limits: FfiLimitsConfig,
+    pub parallelism: Option<u32>,
+    pub layout: FfiBmffLayout,
+}
+
+#[derive(uniffi::Enum, Debug, Clone)]
+pub enum FfiBmffLayout { Segmented, SingleFile }
+
+impl From<FfiBmffLayout> for dt::BmffLayout {
+    fn from(v: FfiBmffLayout) -> Self {
+        match v { FfiBmffLayout::Segmented => dt::BmffLayout::Segmented, FfiBmffLayout::SingleFile => dt::BmffLayout::SingleFile }
+    }
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiFragmentedBmffVerifyConfig {
+    pub init_glob: String,
+    pub fragments_glob: String,
+    pub mode: FfiVerifyMode,
+    pub policy: Option<FfiTrustPolicyConfig>,
+    pub include_certificates: Option<bool>,
+}
+
+impl From<FfiFragmentedBmffVerifyConfig> for dt::FragmentedBmffVerifyConfig {
+    fn from(v: FfiFragmentedBmffVerifyConfig) -> Self {
+        dt::FragmentedBmffVerifyConfig {
+            init_glob: PathBuf::from(v.init_glob),
+            fragments_glob: PathBuf::from(v.fragments_glob),
+            mode: v.mode.into(),
+            policy: v.policy.map(Into::into),
+            include_certificates: v.include_certificates,
+        }
+    }
+}
+
+/// Per-group outcome; exactly one of `result` and `error` is set.
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiFragmentedBmffVerification {
+    pub init_path: String,
+    pub fragments: Vec<String>,
+    pub result: Option<FfiVerificationResult>,
+    pub error: Option<String>,
 }
 
 impl TryFrom<FfiFragmentedBmffConfig> for dt::FragmentedBmffConfig {
     type Error = FfiError;
     fn try_from(v: FfiFragmentedBmffConfig) -> Result<Self, Self::Error> {
-        let signer: Signer = v.signer_uri.parse().map_err(|e| FfiError::Generic { message: format!("Invalid signer: {e}") })?;
+        let signer: Signer = v.signer_uri.parse().map_err(|e| FfiError::invalid_input(format!("Invalid signer: {e}")))?;
         Ok(dt::FragmentedBmffConfig {
             init_glob: PathBuf::from(v.init_glob),
             fragments_glob: PathBuf::from(v.fragments_glob),
@@ -231,14 +661,146 @@ impl TryFrom<FfiFragmentedBmffConfig> for dt::FragmentedBmffConfig {
             skip_post_sign_validation: v.skip_post_sign_validation,
             allow_insecure_remote_http: v.allow_insecure_remote_http,
             limits: v.limits.into(),
+            parallelism: v.parallelism.map(|n| n as usize),
+            layout: v.layout.into(),
+        })
+    }
+}
+
+// ===== CAWG identity =====
+
+/// Credentials for the CAWG identity signature, mirroring `CawgSigner`.
+#[cfg(feature = "cawg")]
+#[derive(uniffi::Enum, Debug, Clone)]
+pub enum FfiCawgSigner {
+    /// Reuse the main C2PA signer.
+    UseMainSigner,
+    /// A separate X.509 signer, given as a signer URI.
+    Separate { signer_uri: String },
+    /// A DID-issued verifiable credential; `credential_json` holds the identity claims.
+    Did { did: String, credential_json: String, signer_uri: String },
+}
+
+#[cfg(feature = "cawg")]
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiCawgIdentity {
+    pub signer: FfiCawgSigner,
+    pub signing_alg: FfiSigAlg,
+    pub referenced_assertions: Vec<String>,
+    pub timestamper: Option<FfiTimestamper>,
+}
+
+#[cfg(feature = "cawg")]
+impl TryFrom<FfiCawgIdentity> for que_engine::CawgIdentity {
+    type Error = FfiError;
+    fn try_from(v: FfiCawgIdentity) -> Result<Self, Self::Error> {
+        let parse = |uri: String| uri.parse::<Signer>().map_err(|e| FfiError::invalid_input(format!("Invalid CAWG signer: {e}")));
+        let signer = match v.signer {
+            FfiCawgSigner::UseMainSigner => que_engine::CawgSigner::UseMainSigner,
+            FfiCawgSigner::Separate { signer_uri } => que_engine::CawgSigner::Separate(parse(signer_uri)?),
+            FfiCawgSigner::Did { did, credential_json, signer_uri } => que_engine::CawgSigner::Did {
+                did,
+                credential: serde_json::from_str(&credential_json).map_err(|e| FfiError::invalid_input(format!("Invalid CAWG credential: {e}")))?,
+                signer: parse(signer_uri)?,
+            },
+        };
+        Ok(que_engine::CawgIdentity {
+            signer,
+            signing_alg: v.signing_alg.into(),
+            referenced_assertions: v.referenced_assertions,
+            timestamper: v.timestamper.map(Into::into),
         })
     }
 }
 
+#[cfg(feature = "cawg")]
+#[derive(uniffi::Record, Debug, Clone, Copy)]
+pub struct FfiCawgVerifyOptions {
+    pub validate: bool,
+    pub require_valid_identity: bool,
+}
+
+#[cfg(feature = "cawg")]
+impl From<FfiCawgVerifyOptions> for que_engine::CawgVerifyOptions {
+    fn from(v: FfiCawgVerifyOptions) -> Self {
+        que_engine::create_cawg_verify_options(v.validate, v.require_valid_identity)
+    }
+}
+
+/// CAWG identity outcome; the JSON fields carry the engine's `serde_json::Value`s.
+#[cfg(feature = "cawg")]
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiCawgVerification {
+    pub present: bool,
+    pub valid: bool,
+    pub signature_info_json: Option<String>,
+    pub identity_name: Option<String>,
+    pub claims_json: Option<String>,
+    pub did: Option<String>,
+}
+
+#[cfg(feature = "cawg")]
+impl From<que_engine::CawgVerification> for FfiCawgVerification {
+    fn from(v: que_engine::CawgVerification) -> Self {
+        FfiCawgVerification {
+            present: v.present,
+            valid: v.valid,
+            signature_info_json: v.signature_info.map(|j| j.to_string()),
+            identity_name: v.identity_name,
+            claims_json: v.claims.map(|j| j.to_string()),
+            did: v.did,
+        }
+    }
+}
+
+/// FFI wrapper for `create_cawg_x509_config`: a separate X.509 CAWG signer with the engine defaults.
+#[cfg(feature = "cawg")]
+#[uniffi::export]
+pub fn create_cawg_x509_config_ffi(signer_uri: String, referenced_assertions: Vec<String>) -> Result<FfiCawgIdentity, FfiError> {
+    let signer: Signer = signer_uri.parse().map_err(|e| FfiError::invalid_input(format!("Invalid CAWG signer: {e}")))?;
+    let identity = que_engine::create_cawg_x509_config(signer, referenced_assertions);
+    Ok(FfiCawgIdentity {
+        signer: FfiCawgSigner::Separate { signer_uri },
+        signing_alg: identity.signing_alg.into(),
+        referenced_assertions: identity.referenced_assertions,
+        timestamper: None,
+    })
+}
+
+/// FFI wrapper for `create_cawg_did_config`: a DID-issued identity with the engine defaults.
+#[cfg(feature = "cawg")]
+#[uniffi::export]
+pub fn create_cawg_did_config_ffi(
+    did: String,
+    credential_json: String,
+    signer_uri: String,
+    referenced_assertions: Vec<String>,
+) -> Result<FfiCawgIdentity, FfiError> {
+    let signer: Signer = signer_uri.parse().map_err(|e| FfiError::invalid_input(format!("Invalid CAWG signer: {e}")))?;
+    let credential = serde_json::from_str(&credential_json).map_err(|e| FfiError::invalid_input(format!("Invalid CAWG credential: {e}")))?;
+    let identity = que_engine::create_cawg_did_config(did.clone(), credential, signer, referenced_assertions);
+    Ok(FfiCawgIdentity {
+        signer: FfiCawgSigner::Did { did, credential_json, signer_uri },
+        signing_alg: identity.signing_alg.into(),
+        referenced_assertions: identity.referenced_assertions,
+        timestamper: None,
+    })
+}
+
+/// FFI wrapper for `create_cawg_verify_options`.
+#[cfg(feature = "cawg")]
+#[uniffi::export]
+pub fn create_cawg_verify_options_ffi(validate: bool, require_valid_identity: bool) -> FfiCawgVerifyOptions {
+    FfiCawgVerifyOptions { validate, require_valid_identity }
+}
+
 // ===== Verification result mappings =====
 
 #[derive(uniffi::Record, Debug, Clone)]
 pub struct FfiCertInfo {
+    pub manifest_label: Option<String>,
+    /// Identity assertion carrying this signature; `None` for the claim signature.
+    pub assertion_label: Option<String>,
     pub alg: Option<String>,
     pub issuer: Option<String>,
     pub cert_serial_number: Option<String>,
@@ -253,20 +815,96 @@ pub struct FfiValidationStatus {
     pub url: Option<String>,
     pub explanation: Option<String>,
     pub ingredient_uri: Option<String>,
+    pub manifest_label: Option<String>,
     pub passed: bool,
+    pub severity: FfiSeverity,
+}
+
+impl From<que_engine::domain::verify::ValidationStatus> for FfiValidationStatus {
+    fn from(s: que_engine::domain::verify::ValidationStatus) -> Self {
+        FfiValidationStatus { code: s.code, url: s.url, explanation: s.explanation, ingredient_uri: s.ingredient_uri, manifest_label: s.manifest_label, passed: s.passed, severity: s.severity.into() }
+    }
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiStatusSummary {
+    pub success: u64,
+    pub informational: u64,
+    pub failure: u64,
 }
 
 #[derive(uniffi::Enum, Debug, Clone, Copy)]
 pub enum FfiVerdict { Allowed, Warning, Rejected }
 
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiSoftBindingInfo {
+    pub alg: String,
+    pub value: String,
+    pub detected: Option<bool>,
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiTimestampInfo {
+    pub manifest_label: String,
+    pub time: String,
+    pub added_by: Option<String>,
+    pub valid: Option<bool>,
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiProvenanceSummary {
+    pub generator: Option<String>,
+    pub creation_source_type: Option<String>,
+    pub edit_actions: Vec<String>,
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiTrustAnchorInfo {
+    pub subject: String,
+    pub serial: String,
+}
+
+#[derive(uniffi::Enum, Debug, Clone)]
+pub enum FfiManifestLocation {
+    Embedded,
+    /// Fetched from this URL during verification.
+    Remote(String),
+    /// Embedded, with a remote reference to this URL that was not fetched.
+    EmbeddedWithRemoteRef(String),
+    Sidecar,
+}
+
+impl From<que_engine::domain::verify::ManifestLocation> for FfiManifestLocation {
+    fn from(v: que_engine::domain::verify::ManifestLocation) -> Self {
+        use que_engine::domain::verify::ManifestLocation as L;
+        match v {
+            L::Embedded => FfiManifestLocation::Embedded,
+            L::Remote(url) => FfiManifestLocation::Remote(url),
+            L::EmbeddedWithRemoteRef(url) => FfiManifestLocation::EmbeddedWithRemoteRef(url),
+            L::Sidecar => FfiManifestLocation::Sidecar,
+        }
+    }
+}
+
 #[derive(uniffi::Record, Debug, Clone)]
 pub struct FfiVerificationResult {
     pub report: String,
     pub certificates: Option<Vec<FfiCertInfo>>,
     pub status: Option<Vec<FfiValidationStatus>>,
+    pub status_summary: Option<FfiStatusSummary>,
     pub verdict: Option<FfiVerdict>,
     pub is_embedded: Option<bool>,
     pub remote_url: Option<String>,
+    pub manifest_location: Option<FfiManifestLocation>,
+    pub soft_bindings: Option<Vec<FfiSoftBindingInfo>>,
+    pub timestamps: Option<Vec<FfiTimestampInfo>>,
+    pub trust_anchor: Option<FfiTrustAnchorInfo>,
+    pub is_update_manifest: Option<bool>,
+    pub provenance_summary: Option<FfiProvenanceSummary>,
+    /// c2pa's validation results as a JSON string, when requested.
+    pub raw_validation_results_json: Option<String>,
+    #[cfg(feature = "cawg")]
+    pub cawg: Option<FfiCawgVerification>,
 }
 
 impl From<que_engine::domain::verify::VerificationResult> for FfiVerificationResult {
@@ -274,6 +912,8 @@ impl From<que_engine::domain::verify::VerificationResult> for FfiVerificationRes
         FfiVerificationResult {
             report: v.report,
             certificates: v.certificates.map(|cs| cs.into_iter().map(|c| FfiCertInfo {
+                manifest_label: c.manifest_label,
+                assertion_label: c.assertion_label,
                 alg: c.alg,
                 issuer: c.issuer,
                 cert_serial_number: c.cert_serial_number,
@@ -281,35 +921,376 @@ impl From<que_engine::domain::verify::VerificationResult> for FfiVerificationRes
                 revocation_status: c.revocation_status,
                 chain_pem: c.chain_pem,
             }).collect()),
-            status: v.status.map(|ss| ss.into_iter().map(|s| FfiValidationStatus { code: s.code, url: s.url, explanation: s.explanation, ingredient_uri: s.ingredient_uri, passed: s.passed }).collect()),
+            status: v.status.map(|ss| ss.into_iter().map(Into::into).collect()),
+            status_summary: v.status_summary.map(|s| FfiStatusSummary {
+                success: s.success as u64,
+                informational: s.informational as u64,
+                failure: s.failure as u64,
+            }),
             verdict: v.verdict.map(|vd| match vd { que_engine::domain::verify::Verdict::Allowed => FfiVerdict::Allowed, que_engine::domain::verify::Verdict::Warning => FfiVerdict::Warning, que_engine::domain::verify::Verdict::Rejected => FfiVerdict::Rejected }),
             is_embedded: v.is_embedded,
             remote_url: v.remote_url,
+            manifest_location: v.manifest_location.map(Into::into),
+            soft_bindings: v.soft_bindings.map(|sbs| sbs.into_iter().map(|sb| FfiSoftBindingInfo { alg: sb.alg, value: sb.value, detected: sb.detected }).collect()),
+            timestamps: v.timestamps.map(|ts| ts.into_iter().map(|t| FfiTimestampInfo { manifest_label: t.manifest_label, time: t.time, added_by: t.added_by, valid: t.valid }).collect()),
+            trust_anchor: v.trust_anchor.map(|a| FfiTrustAnchorInfo { subject: a.subject, serial: a.serial }),
+            is_update_manifest: v.is_update_manifest,
+            provenance_summary: v.provenance_summary.map(|p| FfiProvenanceSummary {
+                generator: p.generator,
+                creation_source_type: p.creation_source_type,
+                edit_actions: p.edit_actions,
+            }),
+            raw_validation_results_json: v.raw_validation_results.map(|r| r.to_string()),
+            #[cfg(feature = "cawg")]
+            cawg: v.cawg.map(Into::into),
+        }
+    }
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiIngredientReport {
+    pub json: Vec<u8>,
+    pub validation_status: Vec<FfiValidationStatus>,
+    pub is_signed: bool,
+}
+
+impl From<que_engine::IngredientReport> for FfiIngredientReport {
+    fn from(r: que_engine::IngredientReport) -> Self {
+        FfiIngredientReport {
+            json: r.json,
+            validation_status: r.validation_status.into_iter().map(Into::into).collect(),
+            is_signed: r.is_signed,
         }
     }
 }
 
 // ===== High-level API, mirroring Rust surface =====
 
+/// An asset and its detached manifest store, from `sign_c2pa_detached_ffi`.
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiDetachedManifest {
+    pub asset: Vec<u8>,
+    pub manifest: Vec<u8>,
+}
+
+impl From<dt::DetachedManifest> for FfiDetachedManifest {
+    fn from(d: dt::DetachedManifest) -> Self {
+        Self { asset: d.asset, manifest: d.manifest }
+    }
+}
+
+/// Signed asset and post-sign verification, from `sign_c2pa_with_report_ffi`.
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiSignOutcome {
+    pub artifact: Option<Vec<u8>>,
+    pub verification: Option<FfiVerificationResult>,
+    /// Why the post-sign verification could not run; the asset is signed regardless.
+    pub verification_error: Option<String>,
+}
+
+impl From<dt::SignOutcome> for FfiSignOutcome {
+    fn from(o: dt::SignOutcome) -> Self {
+        Self { artifact: o.artifact, verification: o.verification.map(Into::into), verification_error: o.verification_error }
+    }
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiEngineInfo {
+    pub engine_version: String,
+    pub c2pa_version: Option<String>,
+    pub supported_read_formats: Vec<String>,
+    pub supported_write_formats: Vec<String>,
+    pub features: Vec<String>,
+}
+
+impl From<que_engine::EngineInfo> for FfiEngineInfo {
+    fn from(info: que_engine::EngineInfo) -> Self {
+        FfiEngineInfo {
+            engine_version: info.engine_version,
+            c2pa_version: info.c2pa_version,
+            supported_read_formats: info.supported_read_formats,
+            supported_write_formats: info.supported_write_formats,
+            features: info.features,
+        }
+    }
+}
+
+#[uniffi::export]
+pub fn engine_info_ffi() -> FfiEngineInfo {
+    que_engine::engine_info().into()
+}
+
+#[uniffi::export]
+pub fn set_default_limits_ffi(limits: FfiLimitsConfig) {
+    que_engine::set_default_limits(limits.into());
+}
+
+#[uniffi::export]
+pub fn set_signer_cache_ttl_ffi(ttl_secs: Option<u64>) {
+    que_engine::Signer::set_cache_ttl(ttl_secs.map(std::time::Duration::from_secs));
+}
+
+#[uniffi::export]
+pub fn clear_signer_cache_ffi() {
+    que_engine::Signer::clear_cache();
+}
+
 #[uniffi::export]
 pub fn sign_c2pa_ffi(cfg: FfiC2paConfig) -> Result<Option<Vec<u8>>, FfiError> {
     let cfg: dt::C2paConfig = cfg.try_into()?;
     sign_c2pa(cfg).map_err(FfiError::from)
 }
 
+/// Like `sign_c2pa_ffi`, also returning the engine's verification of the signed asset
+/// unless post-sign validation is skipped.
+#[uniffi::export]
+pub fn sign_c2pa_with_report_ffi(cfg: FfiC2paConfig) -> Result<FfiSignOutcome, FfiError> {
+    let cfg: dt::C2paConfig = cfg.try_into()?;
+    que_engine::sign_c2pa_with_report(cfg).map(Into::into).map_err(FfiError::from)
+}
+
+/// The manifest JSON `sign_c2pa_ffi` would sign for `cfg`; the signer is not used.
+#[uniffi::export]
+pub fn preview_manifest_ffi(cfg: FfiC2paConfig) -> Result<String, FfiError> {
+    let cfg: dt::C2paConfig = cfg.try_into()?;
+    que_engine::preview_manifest(&cfg).map_err(FfiError::from)
+}
+
+/// Sign with a detached manifest, returning the asset and the manifest store bytes.
+/// `output` and `embed` are ignored.
+#[uniffi::export]
+pub fn sign_c2pa_detached_ffi(cfg: FfiC2paConfig) -> Result<FfiDetachedManifest, FfiError> {
+    let cfg: dt::C2paConfig = cfg.try_into()?;
+    que_engine::sign_c2pa_detached(cfg).map(Into::into).map_err(FfiError::from)
+}
+
+/// Async variant of [`sign_c2pa_ffi`] that Swift/Kotlin can `await` without blocking
+/// the calling thread.
+///
+/// Signing runs on a dedicated worker thread that drives `sign_c2pa_async` on its own
+/// current-thread runtime, so CAWG signing awaits its async signer instead of
+/// blocking. If the host cancels the task, the worker is cancelled too and stops at
+/// its next check; a `Path` output may be left partially written.
+#[cfg(not(target_arch = "wasm32"))]
+#[uniffi::export]
+pub async fn sign_c2pa_async_ffi(cfg: FfiC2paConfig) -> Result<Option<Vec<u8>>, FfiError> {
+    let cancel = que_engine::CancellationToken::new();
+    // Dropped with this future when the host cancels the task.
+    let guard = cancel.drop_guard();
+    let (tx, rx) = futures::channel::oneshot::channel();
+    std::thread::Builder::new()
+        .name("que-sign".into())
+        .spawn(move || {
+            let result = dt::C2paConfig::try_from(cfg).and_then(|mut cfg| {
+                cfg.cancel = Some(cancel);
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(|e| FfiError::Internal { message: format!("failed to start signing runtime: {e}") })?;
+                runtime.block_on(que_engine::sign_c2pa_async(cfg)).map_err(FfiError::from)
+            });
+            // The receiver is gone if the host cancelled; there is no one to report to.
+            let _ = tx.send(result);
+        })
+        .map_err(|e| FfiError::Internal { message: format!("failed to start signing thread: {e}") })?;
+    let result = rx.await.unwrap_or_else(|_| Err(FfiError::Internal { message: "signing worker panicked".into() }));
+    guard.disarm();
+    result
+}
+
 #[uniffi::export]
 pub fn verify_c2pa_ffi(cfg: FfiC2paVerificationConfig) -> Result<FfiVerificationResult, FfiError> {
-    let cfg: dt::C2paVerificationConfig = cfg.into();
+    let cfg: dt::C2paVerificationConfig = cfg.try_into()?;
     let res = verify_c2pa(cfg).map_err(FfiError::from)?;
     Ok(res.into())
 }
 
+/// Only the verdict for `source` under `policy`, skipping the report and certificate
+/// extraction `verify_c2pa_ffi` does. Uses the default limits.
+#[uniffi::export]
+pub fn verify_verdict_ffi(
+    source: FfiAssetRef,
+    policy: Option<FfiTrustPolicyConfig>,
+) -> Result<FfiVerdict, FfiError> {
+    let source = asset_ref(source, dt::default_limits())?;
+    let verdict = que_engine::verify_verdict(source, policy.map(Into::into)).map_err(FfiError::from)?;
+    Ok(match verdict {
+        que_engine::Verdict::Allowed => FfiVerdict::Allowed,
+        que_engine::Verdict::Warning => FfiVerdict::Warning,
+        que_engine::Verdict::Rejected => FfiVerdict::Rejected,
+    })
+}
+
+/// Per-asset outcome of [`verify_c2pa_batch_ffi`]; exactly one of `result` and `error` is set.
+#[derive(uniffi::Record, Debug)]
+pub struct FfiBatchResult {
+    pub result: Option<FfiVerificationResult>,
+    pub error: Option<FfiError>,
+}
+
+impl From<Result<FfiVerificationResult, FfiError>> for FfiBatchResult {
+    fn from(r: Result<FfiVerificationResult, FfiError>) -> Self {
+        match r {
+            Ok(result) => FfiBatchResult { result: Some(result), error: None },
+            Err(error) => FfiBatchResult { result: None, error: Some(error) },
+        }
+    }
+}
+
+/// Verify several assets in one FFI call, returning one result per config in input order.
+///
+/// Backed by `verify_c2pa_batch`: assets are verified on up to four worker threads. A
+/// failing asset only sets the `error` of its own entry; the call itself never fails.
+#[uniffi::export]
+pub fn verify_c2pa_batch_ffi(cfgs: Vec<FfiC2paVerificationConfig>) -> Vec<FfiBatchResult> {
+    let mut results: Vec<Option<FfiBatchResult>> = Vec::with_capacity(cfgs.len());
+    let mut indices = Vec::new();
+    let mut configs = Vec::new();
+    for cfg in cfgs {
+        match dt::C2paVerificationConfig::try_from(cfg) {
+            Ok(cfg) => {
+                indices.push(results.len());
+                configs.push(cfg);
+                results.push(None);
+            }
+            Err(e) => results.push(Some(Err(e).into())),
+        }
+    }
+    for (index, verified) in indices.into_iter().zip(que_engine::verify_c2pa_batch(configs)) {
+        results[index] = Some(verified.map(Into::into).map_err(FfiError::from).into());
+    }
+    results
+        .into_iter()
+        .map(|r| r.unwrap_or_else(|| Err(FfiError::Internal { message: "asset was not verified".into() }).into()))
+        .collect()
+}
+
 #[uniffi::export]
 pub fn create_ingredient_ffi(cfg: FfiIngredientConfig) -> Result<Option<Vec<u8>>, FfiError> {
-    let cfg: dt::IngredientConfig = cfg.into();
+    let cfg: dt::IngredientConfig = cfg.try_into()?;
     create_ingredient(cfg).map_err(FfiError::from)
 }
 
+#[uniffi::export]
+pub fn create_ingredient_report_ffi(cfg: FfiIngredientConfig) -> Result<FfiIngredientReport, FfiError> {
+    let cfg: dt::IngredientConfig = cfg.try_into()?;
+    let report = que_engine::create_ingredient_report(cfg).map_err(FfiError::from)?;
+    Ok(report.into())
+}
+
+#[uniffi::export]
+pub fn remove_manifest_ffi(cfg: FfiRemoveManifestConfig) -> Result<Option<Vec<u8>>, FfiError> {
+    let cfg: dt::RemoveManifestConfig = cfg.try_into()?;
+    que_engine::remove_manifest(cfg).map_err(FfiError::from)
+}
+
+#[uniffi::export]
+pub fn validate_manifest_definition_ffi(json: String) -> Result<Vec<String>, FfiError> {
+    que_engine::validate_manifest_definition(&json).map_err(FfiError::from)
+}
+
+#[cfg(feature = "c2pa")]
+#[uniffi::export]
+pub fn extract_manifest_store_ffi(source: FfiAssetRef, limits: FfiLimitsConfig) -> Result<Vec<u8>, FfiError> {
+    let limits: dt::LimitsConfig = limits.into();
+    que_engine::extract_manifest_store(asset_ref(source, limits)?, limits).map_err(FfiError::from)
+}
+
+/// The asset's manifest store JSON without validation or trust checks; `None` when
+/// the asset has no manifest. For display only.
+#[cfg(feature = "c2pa")]
+#[uniffi::export]
+pub fn read_manifest_json_ffi(source: FfiAssetRef, limits: FfiLimitsConfig) -> Result<Option<String>, FfiError> {
+    let limits: dt::LimitsConfig = limits.into();
+    que_engine::read_manifest_json(asset_ref(source, limits)?, limits).map_err(FfiError::from)
+}
+
+#[cfg(feature = "c2pa")]
+#[uniffi::export]
+pub fn countersign_ffi(
+    source: FfiAssetRef,
+    signer_uri: String,
+    signing_alg: FfiSigAlg,
+    timestamper: Option<FfiTimestamper>,
+    output: FfiOutputTarget,
+) -> Result<Option<Vec<u8>>, FfiError> {
+    let signer: Signer = signer_uri.parse().map_err(|e| FfiError::invalid_input(format!("Invalid signer: {e}")))?;
+    que_engine::countersign(asset_ref(source, dt::default_limits())?, signer, signing_alg.into(), timestamper.map(Into::into), output.into()).map_err(FfiError::from)
+}
+
+#[cfg(feature = "c2pa")]
+#[uniffi::export]
+pub fn add_timestamp_ffi(
+    source: FfiAssetRef,
+    signer_uri: String,
+    signing_alg: FfiSigAlg,
+    timestamper: FfiTimestamper,
+    force: bool,
+    output: FfiOutputTarget,
+) -> Result<Option<Vec<u8>>, FfiError> {
+    let signer: Signer = signer_uri.parse().map_err(|e| FfiError::invalid_input(format!("Invalid signer: {e}")))?;
+    que_engine::add_timestamp(asset_ref(source, dt::default_limits())?, signer, signing_alg.into(), timestamper.into(), force, output.into()).map_err(FfiError::from)
+}
+
+#[derive(uniffi::Enum, Debug, Clone, Copy)]
+pub enum FfiChangeKind { Added, Removed, Changed }
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiManifestChange {
+    pub key: String,
+    pub kind: FfiChangeKind,
+    /// JSON of the entry in the first manifest
+    pub before_json: Option<String>,
+    /// JSON of the entry in the second manifest
+    pub after_json: Option<String>,
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct FfiManifestDiff {
+    pub assertions: Vec<FfiManifestChange>,
+    pub ingredients: Vec<FfiManifestChange>,
+    pub signer: Vec<FfiManifestChange>,
+}
+
+impl From<que_engine::ManifestChange> for FfiManifestChange {
+    fn from(c: que_engine::ManifestChange) -> Self {
+        FfiManifestChange {
+            key: c.key,
+            kind: match c.kind { que_engine::ChangeKind::Added => FfiChangeKind::Added, que_engine::ChangeKind::Removed => FfiChangeKind::Removed, que_engine::ChangeKind::Changed => FfiChangeKind::Changed },
+            before_json: c.before.map(|v| v.to_string()),
+            after_json: c.after.map(|v| v.to_string()),
+        }
+    }
+}
+
+impl From<que_engine::ManifestDiff> for FfiManifestDiff {
+    fn from(d: que_engine::ManifestDiff) -> Self {
+        FfiManifestDiff {
+            assertions: d.assertions.into_iter().map(Into::into).collect(),
+            ingredients: d.ingredients.into_iter().map(Into::into).collect(),
+            signer: d.signer.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[cfg(feature = "c2pa")]
+#[uniffi::export]
+pub fn diff_manifests_ffi(a: FfiAssetRef, b: FfiAssetRef) -> Result<FfiManifestDiff, FfiError> {
+    let limits = dt::default_limits();
+    que_engine::diff_manifests(asset_ref(a, limits)?, asset_ref(b, limits)?).map(Into::into).map_err(FfiError::from)
+}
+
+#[cfg(feature = "c2pa")]
+#[uniffi::export]
+pub fn apply_sidecar_manifest_ffi(
+    asset: FfiAssetRef,
+    manifest_bytes: Vec<u8>,
+    output: FfiOutputTarget,
+    embed: bool,
+) -> Result<Option<Vec<u8>>, FfiError> {
+    que_engine::apply_sidecar_manifest(asset_ref(asset, dt::default_limits())?, manifest_bytes, output.into(), embed).map_err(FfiError::from)
+}
+
 #[cfg(all(feature = "c2pa", feature = "bmff"))]
 #[uniffi::export]
 pub fn generate_fragmented_bmff_ffi(cfg: FfiFragmentedBmffConfig) -> Result<(), FfiError> {
@@ -317,6 +1298,27 @@ pub fn generate_fragmented_bmff_ffi(cfg: FfiFragmentedBmffConfig) -> Result<(),
     que_engine::generate_fragmented_bmff(cfg).map_err(FfiError::from)
 }
 
+#[cfg(all(feature = "c2pa", feature = "bmff"))]
+#[uniffi::export]
+pub fn verify_fragmented_bmff_ffi(cfg: FfiFragmentedBmffVerifyConfig) -> Result<Vec<FfiFragmentedBmffVerification>, FfiError> {
+    let groups = que_engine::verify_fragmented_bmff(cfg.into()).map_err(FfiError::from)?;
+    Ok(groups
+        .into_iter()
+        .map(|g| {
+            let (result, error) = match g.result {
+                Ok(r) => (Some(r.into()), None),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            FfiFragmentedBmffVerification {
+                init_path: g.init_path.to_string_lossy().into_owned(),
+                fragments: g.fragments.iter().map(|f| f.to_string_lossy().into_owned()).collect(),
+                result,
+                error,
+            }
+        })
+        .collect())
+}
+
 // ===== Backward-compatible simple helpers (deprecated) =====
 
 #[uniffi::export]
@@ -331,24 +1333,45 @@ pub fn sign_file_c2pa(
     remote_manifest_url: Option<String>,
     embed: bool,
 ) -> Result<(), FfiError> {
-    let signer: Signer = signer_spec.parse().map_err(|e| FfiError::Generic { message: format!("Invalid signer: {e}") })?;
-    let alg = match alg.to_ascii_uppercase().as_str() { "ES256" => dt::SigAlg::Es256, "ES384" => dt::SigAlg::Es384, "PS256" => dt::SigAlg::Ps256, "ED25519" => dt::SigAlg::Ed25519, _ => { return Err(FfiError::Generic { message: format!("Unsupported alg: {alg}") }) } };
-    let tsa = match timestamper { None => None, Some(v) if v == "digicert" => Some(Timestamper::Digicert), Some(v) if v.starts_with("custom:") => Some(Timestamper::Custom(v.trim_start_matches("custom:").to_string())), Some(v) => { return Err(FfiError::Generic { message: format!("Invalid timestamper: {v}") }) } };
+    let signer: Signer = signer_spec.parse().map_err(|e| FfiError::invalid_input(format!("Invalid signer: {e}")))?;
+    let alg = match alg.to_ascii_uppercase().as_str() { "ES256" => dt::SigAlg::Es256, "ES384" => dt::SigAlg::Es384, "PS256" => dt::SigAlg::Ps256, "ED25519" => dt::SigAlg::Ed25519, a if a.starts_with("ML-DSA") || a.starts_with("MLDSA") => { return Err(FfiError::Unsupported { message: format!("Unsupported alg: {alg} (ML-DSA is not available in the c2pa SDK this engine is built on)") }) } _ => { return Err(FfiError::invalid_input(format!("Unsupported alg: {alg}"))) } };
+    let tsa = match timestamper { None => None, Some(v) if v == "digicert" => Some(Timestamper::Digicert), Some(v) if v.starts_with("custom:") => Some(Timestamper::Custom(v.trim_start_matches("custom:").to_string())), Some(v) => { return Err(FfiError::invalid_input(format!("Invalid timestamper: {v}"))) } };
     let cfg = dt::C2paConfig {
         source: dt::AssetRef::Path(PathBuf::from(source_path)),
         output: dt::OutputTarget::Path(PathBuf::from(dest_path)),
         manifest_definition: manifest_json,
+        manifest_source: None,
+        validate_manifest: false,
+        check_declared_format: dt::EngineDefaults::CHECK_DECLARED_FORMAT,
+        default_format: dt::EngineDefaults::HAS_DEFAULT_FORMAT,
+        allowed_formats: dt::EngineDefaults::HAS_ALLOWED_FORMATS,
         parent: parent_path.map(|p| dt::AssetRef::Path(PathBuf::from(p))),
         parent_base_dir: None,
         signer,
         signing_alg: alg,
+        co_signers: Vec::new(),
+        intermediates: None,
+        hash_alg: None,
         timestamper: tsa,
+        reserve_size: None,
         remote_manifest_url,
         embed,
+        verify_remote_url_reachable: dt::EngineDefaults::VERIFY_REMOTE_URL_REACHABLE,
         trust_policy: None,
         skip_post_sign_validation: false,
+        post_sign: dt::EngineDefaults::POST_SIGN_MODE,
         allow_insecure_remote_http: None,
-        limits: dt::LimitsConfig::defaults(),
+        limits: dt::default_limits(),
+        temp_dir: None,
+        redactions: None,
+        soft_binding: None,
+        fingerprint: None,
+        auto_thumbnail: None,
+        claim_generator: None,
+        raw_settings: None,
+        progress: None,
+        cancel: None,
+        deterministic: None,
         #[cfg(feature = "cawg")]
         cawg_identity: None,
     };
@@ -361,7 +1384,7 @@ pub struct VerifyOptions { pub detailed: bool, pub info: bool, pub tree: bool }
 #[uniffi::export]
 pub fn verify_file_c2pa(source_path: String, opts: VerifyOptions) -> Result<String, FfiError> {
     let mode = if opts.detailed { dt::VerifyMode::Detailed } else if opts.info { dt::VerifyMode::Info } else if opts.tree { dt::VerifyMode::Tree } else { dt::VerifyMode::Summary };
-    let cfg = dt::C2paVerificationConfig { source: dt::AssetRef::Path(PathBuf::from(source_path)), mode, policy: None, allow_remote_manifests: false, include_certificates: None, limits: dt::LimitsConfig::defaults(), #[cfg(feature = "cawg")] cawg: None };
+    let cfg = dt::C2paVerificationConfig { source: dt::AssetRef::Path(PathBuf::from(source_path)), mode, policy: None, allow_remote_manifests: false, include_certificates: None, limits: dt::default_limits(), soft_binding: None, raw_settings: None, unknown_status_severity: dt::EngineDefaults::UNKNOWN_STATUS_SEVERITY, progress: None, cancel: None, format_override: None, check_declared_format: dt::EngineDefaults::CHECK_DECLARED_FORMAT, allowed_formats: dt::EngineDefaults::HAS_ALLOWED_FORMATS, remote_manifest: None, include_raw_validation_results: dt::EngineDefaults::INCLUDE_RAW_VALIDATION_RESULTS, expected_signer_fingerprint: None, #[cfg(feature = "cawg")] cawg: None };
     let report = verify_c2pa(cfg).map_err(FfiError::from)?;
     Ok(report.report)
 }